@@ -0,0 +1,13 @@
+-- Captcha challenges that are shared between multiple instances
+CREATE TABLE captcha (
+    rowid      INTEGER PRIMARY KEY,
+    --
+    nonce      TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    kind       TINYINT, -- NULL if the challenge has not been generated yet
+    answer     TEXT,    -- NULL if the challenge has not been generated yet
+    --
+    UNIQUE (nonce)
+);
+
+CREATE INDEX captcha_idx_created_at ON captcha(created_at);
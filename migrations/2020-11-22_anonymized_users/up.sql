@@ -0,0 +1,13 @@
+CREATE TABLE anonymized_users (
+    rowid                 INTEGER PRIMARY KEY,
+    pseudonym             TEXT NOT NULL,
+    anonymized_at         INTEGER NOT NULL,
+    place_revisions       INTEGER NOT NULL,
+    place_reviews         INTEGER NOT NULL,
+    place_ratings         INTEGER NOT NULL,
+    place_rating_comments INTEGER NOT NULL,
+    events                INTEGER NOT NULL,
+    UNIQUE (pseudonym)
+);
+
+CREATE INDEX anonymized_users_idx_anonymized_at ON anonymized_users (anonymized_at);
@@ -0,0 +1,11 @@
+CREATE TABLE user_regions (
+    rowid           INTEGER PRIMARY KEY,
+    user_id         INTEGER NOT NULL,
+    south_west_lat  FLOAT NOT NULL,
+    south_west_lng  FLOAT NOT NULL,
+    north_east_lat  FLOAT NOT NULL,
+    north_east_lng  FLOAT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+CREATE INDEX user_regions_idx_user_id ON user_regions (user_id);
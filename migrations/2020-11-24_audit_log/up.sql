@@ -0,0 +1,16 @@
+CREATE TABLE audit_log (
+    rowid       INTEGER PRIMARY KEY,
+    at          INTEGER NOT NULL,
+    actor       INTEGER,
+    action      TEXT NOT NULL,
+    entity_kind TEXT NOT NULL,
+    entity_id   TEXT NOT NULL,
+    details     TEXT,
+    context     TEXT,
+    comment     TEXT,
+    FOREIGN KEY (actor) REFERENCES users(id)
+);
+
+CREATE INDEX audit_log_idx_at ON audit_log (at);
+CREATE INDEX audit_log_idx_actor ON audit_log (actor);
+CREATE INDEX audit_log_idx_entity ON audit_log (entity_id, entity_kind);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE place_rating_flag;
+DROP INDEX place_rating_idx_author_session;
@@ -0,0 +1,15 @@
+ALTER TABLE place_rating ADD COLUMN author_session TEXT;
+
+CREATE INDEX place_rating_idx_author_session ON place_rating (author_session);
+
+CREATE TABLE place_rating_flag (
+    rowid        INTEGER PRIMARY KEY,
+    parent_rowid INTEGER NOT NULL,
+    created_at   INTEGER NOT NULL,
+    created_by   INTEGER,
+    comment      TEXT,
+    FOREIGN KEY (parent_rowid) REFERENCES place_rating(rowid),
+    FOREIGN KEY (created_by) REFERENCES users(id)
+);
+
+CREATE INDEX place_rating_flag_idx_parent_rowid ON place_rating_flag (parent_rowid);
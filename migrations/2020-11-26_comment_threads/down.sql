@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX place_rating_comment_idx_reply_to;
@@ -0,0 +1,5 @@
+ALTER TABLE place_rating_comment ADD COLUMN reply_to INTEGER REFERENCES place_rating_comment(rowid);
+ALTER TABLE place_rating_comment ADD COLUMN created_by_org INTEGER REFERENCES organization(rowid);
+ALTER TABLE place_rating_comment ADD COLUMN response INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX place_rating_comment_idx_reply_to ON place_rating_comment (reply_to);
@@ -0,0 +1,16 @@
+-- Pending authorization/approval of events by organizations
+CREATE TABLE organization_event_clearance (
+    rowid       INTEGER PRIMARY KEY,
+    --
+    org_rowid   INTEGER NOT NULL,
+    event_rowid INTEGER NOT NULL,
+    --
+    created_at         INTEGER NOT NULL,
+    last_cleared_state TEXT, -- JSON snapshot of the last cleared event or NULL if the event is new and has not been cleared yet
+    --
+    UNIQUE (org_rowid, event_rowid),
+    FOREIGN KEY (org_rowid) REFERENCES organization(rowid),
+    FOREIGN KEY (event_rowid) REFERENCES events(id)
+);
+
+CREATE INDEX organization_event_clearance_idx_event_rowid ON organization_event_clearance(event_rowid);
@@ -0,0 +1,3 @@
+-- The time when an organization decided to permanently keep
+-- the last cleared revision instead of the pending changes
+ALTER TABLE organization_place_clearance ADD COLUMN reverted_at INTEGER;
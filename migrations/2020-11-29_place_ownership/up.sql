@@ -0,0 +1,13 @@
+ALTER TABLE user_tokens ADD COLUMN place_id TEXT;
+
+CREATE TABLE place_owner (
+    place_rowid     INTEGER PRIMARY KEY,
+    user_id         INTEGER NOT NULL,
+    created_at      INTEGER NOT NULL,
+    -- comma-separated list of field names
+    locked_fields   TEXT NOT NULL DEFAULT '',
+    FOREIGN KEY (place_rowid) REFERENCES place(rowid),
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+CREATE INDEX place_owner_idx_user_id ON place_owner (user_id);
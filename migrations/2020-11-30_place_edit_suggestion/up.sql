@@ -0,0 +1,22 @@
+CREATE TABLE place_edit_suggestion (
+    rowid            INTEGER PRIMARY KEY,
+    id               TEXT NOT NULL,
+    place_rowid      INTEGER NOT NULL,
+    base_rev         INTEGER NOT NULL,
+    created_at       INTEGER NOT NULL,
+    created_by       INTEGER,
+    -- JSON snapshot of the suggested place revision
+    suggested_state  TEXT NOT NULL,
+    status           TEXT NOT NULL,
+    reviewed_at      INTEGER,
+    reviewed_by      INTEGER,
+    review_context   TEXT,
+    review_comment   TEXT,
+    UNIQUE (id),
+    FOREIGN KEY (place_rowid) REFERENCES place(rowid),
+    FOREIGN KEY (created_by) REFERENCES users(id),
+    FOREIGN KEY (reviewed_by) REFERENCES users(id)
+);
+
+CREATE INDEX place_edit_suggestion_idx_place_rowid ON place_edit_suggestion (place_rowid);
+CREATE INDEX place_edit_suggestion_idx_status ON place_edit_suggestion (status);
@@ -0,0 +1,13 @@
+CREATE TABLE image (
+    rowid        INTEGER PRIMARY KEY,
+    id           TEXT NOT NULL,
+    format       TEXT NOT NULL,
+    width        INTEGER NOT NULL,
+    height       INTEGER NOT NULL,
+    license      TEXT NOT NULL,
+    attribution  TEXT,
+    created_at   INTEGER NOT NULL,
+    created_by   INTEGER,
+    UNIQUE (id),
+    FOREIGN KEY (created_by) REFERENCES users(id)
+);
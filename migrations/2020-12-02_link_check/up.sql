@@ -0,0 +1,14 @@
+CREATE TABLE link_check (
+    rowid         INTEGER PRIMARY KEY,
+    -- place or event
+    owner_kind    TEXT NOT NULL,
+    owner_id      TEXT NOT NULL,
+    url           TEXT NOT NULL,
+    status        TEXT NOT NULL,
+    http_status   INTEGER,
+    redirect_url  TEXT,
+    checked_at    INTEGER NOT NULL,
+    UNIQUE (owner_kind, owner_id, url)
+);
+
+CREATE INDEX link_check_idx_status ON link_check (status);
@@ -0,0 +1,2 @@
+-- Unknown for users that registered before
+ALTER TABLE users ADD COLUMN created_at INTEGER;
@@ -0,0 +1,23 @@
+CREATE TABLE saved_search (
+    rowid           INTEGER PRIMARY KEY,
+    id              TEXT NOT NULL,
+    user_id         INTEGER NOT NULL,
+    name            TEXT NOT NULL,
+    south_west_lat  REAL NOT NULL,
+    south_west_lng  REAL NOT NULL,
+    north_east_lat  REAL NOT NULL,
+    north_east_lng  REAL NOT NULL,
+    -- comma-separated list of category ids
+    categories      TEXT NOT NULL DEFAULT '',
+    -- comma-separated list of tags
+    hash_tags       TEXT NOT NULL DEFAULT '',
+    text            TEXT,
+    org_tag         TEXT,
+    alert           BOOLEAN NOT NULL DEFAULT 0,
+    created_at      INTEGER NOT NULL,
+    UNIQUE (id),
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+CREATE INDEX saved_search_idx_user_id ON saved_search (user_id);
+CREATE INDEX saved_search_idx_alert ON saved_search (alert);
@@ -0,0 +1,11 @@
+CREATE TABLE tag_synonym (
+    synonym TEXT PRIMARY KEY NOT NULL,
+    tag     TEXT NOT NULL
+);
+
+CREATE INDEX tag_synonym_idx_tag ON tag_synonym (tag);
+
+CREATE TABLE tag_description (
+    tag         TEXT PRIMARY KEY NOT NULL,
+    description TEXT NOT NULL
+);
@@ -0,0 +1,6 @@
+CREATE TABLE tag_taxonomy (
+    tag    TEXT PRIMARY KEY NOT NULL,
+    parent TEXT
+);
+
+CREATE INDEX tag_taxonomy_idx_parent ON tag_taxonomy (parent);
@@ -0,0 +1,30 @@
+CREATE TABLE category (
+    rowid INTEGER PRIMARY KEY,
+    id    TEXT NOT NULL,
+    tag   TEXT NOT NULL,
+    icon  TEXT,
+    color TEXT,
+    UNIQUE (id),
+    UNIQUE (tag)
+);
+
+CREATE TABLE category_name (
+    category_rowid INTEGER NOT NULL,
+    lang           TEXT NOT NULL,
+    name           TEXT NOT NULL,
+    PRIMARY KEY (category_rowid, lang),
+    FOREIGN KEY (category_rowid) REFERENCES category(rowid)
+);
+
+-- The built-in categories keep their ids and tags
+INSERT INTO category (id, tag) VALUES
+    ('2cd00bebec0c48ba9db761da48678134', 'non-profit'),
+    ('77b3c33a92554bcf8e8c2c86cedd6f6f', 'commercial'),
+    ('c2dc278a2d6a4b9b8a50cb606fc017ed', 'event');
+
+INSERT INTO category_name (category_rowid, lang, name)
+SELECT rowid, 'en', 'Non-profit' FROM category WHERE tag='non-profit';
+INSERT INTO category_name (category_rowid, lang, name)
+SELECT rowid, 'en', 'Commercial' FROM category WHERE tag='commercial';
+INSERT INTO category_name (category_rowid, lang, name)
+SELECT rowid, 'en', 'Event' FROM category WHERE tag='event';
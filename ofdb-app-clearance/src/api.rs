@@ -1,8 +1,54 @@
-use ofdb_boundary::PendingClearanceForPlace;
+use ofdb_boundary::{PendingClearanceForPlace, RejectionForPlace, ResultCount};
 use ofdb_entities::place::{PlaceHistory, PlaceRevision};
+use seed::prelude::*;
 
 pub const API_ROOT: &str = "/api";
 
+pub async fn get_places_clearance_count(api_token: &str) -> fetch::Result<ResultCount> {
+    let url = format!("{}/places/clearance/count", API_ROOT);
+    Request::new(url)
+        .header(Header::bearer(api_token))
+        .fetch()
+        .await?
+        .check_status()?
+        .json()
+        .await
+}
+
+pub async fn get_places_clearance_page(
+    api_token: &str,
+    offset: u64,
+    limit: u64,
+) -> fetch::Result<Vec<PendingClearanceForPlace>> {
+    let url = format!(
+        "{}/places/clearance?offset={}&limit={}",
+        API_ROOT, offset, limit
+    );
+    Request::new(url)
+        .header(Header::bearer(api_token))
+        .fetch()
+        .await?
+        .check_status()?
+        .json()
+        .await
+}
+
+pub async fn post_places_clearance_reject(
+    api_token: &str,
+    rejections: &[RejectionForPlace],
+) -> fetch::Result<ResultCount> {
+    let url = format!("{}/places/clearance/reject", API_ROOT);
+    Request::new(url)
+        .method(Method::Post)
+        .header(Header::bearer(api_token))
+        .json(rejections)?
+        .fetch()
+        .await?
+        .check_status()?
+        .json()
+        .await
+}
+
 #[derive(Clone, Debug)]
 pub struct PlaceClearance {
     pub pending: PendingClearanceForPlace,
@@ -1,24 +1,43 @@
 use crate::api;
-use difference::{Changeset, Difference};
-use ofdb_boundary::ClearanceForPlace;
-use ofdb_entities::{place::PlaceHistory, place::PlaceRevision};
+use ofdb_boundary::{ClearanceForPlace, RejectionForPlace};
+use ofdb_entities::place::PlaceHistory;
 use ofdb_seed::Api;
 use seed::{prelude::*, *};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+mod compare;
+
+const PAGE_SIZE: u64 = 50;
 
 #[derive(Debug)]
 pub struct Mdl {
     token: String,
     place_clearances: Vec<api::PlaceClearance>,
     expanded: HashMap<String, bool>,
+    selected: HashSet<String>,
+    offset: u64,
+    total_count: Option<u64>,
+    reject_reason: String,
+    reject_revert: bool,
+    busy: bool,
 }
 
 #[derive(Clone)]
 pub enum Msg {
     GetPendingClearancesFull,
     GotPendingClearancesFull(Vec<api::PlaceClearance>),
+    GotPendingClearancesCount(u64),
     Toggle(String),
+    Select(String),
+    SelectAll,
     Accept(String, u64),
+    AcceptSelected,
+    RejectSelected,
+    RejectReasonInput(String),
+    ToggleRejectRevert,
+    ClearanceUpdated,
+    PreviousPage,
+    NextPage,
     Logout,
     ConsoleLog(String),
 }
@@ -26,32 +45,106 @@ pub enum Msg {
 pub fn update(msg: Msg, mdl: &mut Mdl, orders: &mut impl Orders<Msg>) {
     match msg {
         Msg::GetPendingClearancesFull => {
-            orders.perform_cmd(get_pending_clearances_full(mdl.token.clone()));
+            mdl.busy = true;
+            orders.perform_cmd(get_pending_clearances_count(mdl.token.clone()));
+            orders.perform_cmd(get_pending_clearances_full(mdl.token.clone(), mdl.offset));
         }
         Msg::GotPendingClearancesFull(pcs) => {
+            mdl.busy = false;
+            mdl.selected
+                .retain(|id| pcs.iter().any(|pc| &pc.pending.place_id == id));
             mdl.place_clearances = pcs;
         }
+        Msg::GotPendingClearancesCount(count) => {
+            mdl.total_count = Some(count);
+            if mdl.offset > 0 && mdl.offset >= count {
+                // The last page has been cleared completely
+                mdl.offset = count.saturating_sub(1) / PAGE_SIZE * PAGE_SIZE;
+                orders.send_msg(Msg::GetPendingClearancesFull);
+            }
+        }
         Msg::Toggle(id) => {
             mdl.expanded
                 .entry(id)
                 .and_modify(|e| *e = !*e)
                 .or_insert(true);
         }
+        Msg::Select(id) => {
+            if !mdl.selected.remove(&id) {
+                mdl.selected.insert(id);
+            }
+        }
+        Msg::SelectAll => {
+            if mdl.selected.len() == mdl.place_clearances.len() {
+                mdl.selected.clear();
+            } else {
+                mdl.selected = mdl
+                    .place_clearances
+                    .iter()
+                    .map(|pc| pc.pending.place_id.clone())
+                    .collect();
+            }
+        }
         Msg::Accept(id, rev_nr) => {
             let c = ClearanceForPlace {
                 place_id: id,
                 cleared_revision: Some(rev_nr),
             };
-            let clearances = vec![c];
+            mdl.busy = true;
+            orders.perform_cmd(post_clearances(mdl.token.clone(), vec![c]));
+        }
+        Msg::AcceptSelected => {
+            let clearances: Vec<_> = selected_clearances(mdl)
+                .map(|pc| ClearanceForPlace {
+                    place_id: pc.pending.place_id.clone(),
+                    cleared_revision: Some(pc.current_rev_nr()),
+                })
+                .collect();
+            if clearances.is_empty() {
+                return;
+            }
+            mdl.busy = true;
+            orders.perform_cmd(post_clearances(mdl.token.clone(), clearances));
+        }
+        Msg::RejectSelected => {
+            let reason = mdl.reject_reason.trim();
+            if reason.is_empty() {
+                return;
+            }
+            let rejections: Vec<_> = selected_clearances(mdl)
+                .map(|pc| RejectionForPlace {
+                    place_id: pc.pending.place_id.clone(),
+                    rejected_revision: Some(pc.current_rev_nr()),
+                    reason: reason.to_owned(),
+                    revert: mdl.reject_revert,
+                })
+                .collect();
+            if rejections.is_empty() {
+                return;
+            }
+            mdl.busy = true;
             let token = mdl.token.to_owned();
             orders.perform_cmd(async move {
-                let api = Api::new(api::API_ROOT.into());
-                let _res = api
-                    .post_places_clearance_with_api_token(&token, clearances)
-                    .await;
-                Url::reload();
+                if let Err(err) = api::post_places_clearance_reject(&token, &rejections).await {
+                    error!(err);
+                }
+                Msg::ClearanceUpdated
             });
         }
+        Msg::RejectReasonInput(reason) => mdl.reject_reason = reason,
+        Msg::ToggleRejectRevert => mdl.reject_revert = !mdl.reject_revert,
+        Msg::ClearanceUpdated => {
+            mdl.selected.clear();
+            orders.send_msg(Msg::GetPendingClearancesFull);
+        }
+        Msg::PreviousPage => {
+            mdl.offset = mdl.offset.saturating_sub(PAGE_SIZE);
+            orders.send_msg(Msg::GetPendingClearancesFull);
+        }
+        Msg::NextPage => {
+            mdl.offset += PAGE_SIZE;
+            orders.send_msg(Msg::GetPendingClearancesFull);
+        }
         Msg::Logout => {
             if let Err(err) = SessionStorage::remove(crate::TOKEN_KEY) {
                 error!(err);
@@ -62,6 +155,12 @@ pub fn update(msg: Msg, mdl: &mut Mdl, orders: &mut impl Orders<Msg>) {
     }
 }
 
+fn selected_clearances(mdl: &Mdl) -> impl Iterator<Item = &api::PlaceClearance> {
+    mdl.place_clearances
+        .iter()
+        .filter(move |pc| mdl.selected.contains(&pc.pending.place_id))
+}
+
 pub fn init(_url: Url, orders: &mut impl Orders<Msg>) -> Option<Mdl> {
     let token = match SessionStorage::get(crate::TOKEN_KEY) {
         Ok(token) => {
@@ -86,6 +185,12 @@ pub fn init(_url: Url, orders: &mut impl Orders<Msg>) -> Option<Mdl> {
         token,
         place_clearances: Vec::new(),
         expanded: HashMap::new(),
+        selected: HashSet::new(),
+        offset: 0,
+        total_count: None,
+        reject_reason: String::new(),
+        reject_revert: false,
+        busy: false,
     })
 }
 
@@ -94,98 +199,21 @@ pub fn view(mdl: &Mdl) -> Node<Msg> {
         let id = &pc.pending.place_id;
         let lastrev = pc.last_cleared_rev();
         let currrev = pc.current_rev();
-        let title_cs = changeset(lastrev, currrev, |r| r.title.clone());
-        let desc_cs = changeset(lastrev, currrev, |r| r.description.clone());
-        let location_cs = changeset(lastrev, currrev, |r| {
-            let pos = r.location.pos;
-            let addr = &r.location.address;
-            format!(
-                r#"
-                Lat {lat}, Lon {lon}<br>
-                {street}<br>
-                {zip}<br>
-                {city}<br>
-                {country}<br>
-                {state}
-                "#,
-                lat = pos.lat(),
-                lon = pos.lng(),
-                street = addr.clone().map(|a| a.street).flatten().unwrap_or_default(),
-                zip = addr.clone().map(|a| a.zip).flatten().unwrap_or_default(),
-                city = addr.clone().map(|a| a.city).flatten().unwrap_or_default(),
-                country = addr
-                    .clone()
-                    .map(|a| a.country)
-                    .flatten()
-                    .unwrap_or_default(),
-                state = addr.clone().map(|a| a.state).flatten().unwrap_or_default(),
-            )
-        });
-        let contact_cs = changeset(lastrev, currrev, |r| {
-            let c = &r.contact;
-            format!(
-                r#"
-                {email}<br>
-                {phone}
-                "#,
-                email = c
-                    .clone()
-                    .map(|c| c.email.map(String::from))
-                    .flatten()
-                    .unwrap_or_default(),
-                phone = c
-                    .clone()
-                    .map(|c| c.phone.map(String::from))
-                    .flatten()
-                    .unwrap_or_default(),
-            )
-        });
-        let opening_cs = changeset(lastrev, currrev, |r| {
-            format!(
-                r#"
-                {hours}
-                "#,
-                hours = &r
-                    .opening_hours
-                    .clone()
-                    .map(String::from)
-                    .unwrap_or_default(),
-            )
-        });
-        let links_cs = changeset(lastrev, currrev, |r| {
-            let l = &r.links;
-            format!(
-                r#"
-                {homepage}<br>
-                {image}<br>
-                {imagehref}
-                "#,
-                homepage = l
-                    .clone()
-                    .map(|l| l.homepage)
-                    .flatten()
-                    .map(|h| h.into_string())
-                    .unwrap_or_default(),
-                image = l
-                    .clone()
-                    .map(|l| l.image)
-                    .flatten()
-                    .map(|h| h.into_string())
-                    .unwrap_or_default(),
-                imagehref = l
-                    .clone()
-                    .map(|l| l.image_href)
-                    .flatten()
-                    .map(|h| h.into_string())
-                    .unwrap_or_default(),
-            )
-        });
-        let tags_cs = changeset_split(lastrev, currrev, "\n", |r| r.tags.join("<br>\n"));
         let expanded = *mdl.expanded.get(id).unwrap_or(&false);
+        let selected = mdl.selected.contains(id);
         let toggle_msg = Msg::Toggle(id.clone());
+        let select_msg = Msg::Select(id.clone());
         let accept_msg = Msg::Accept(id.clone(), pc.current_rev_nr());
 
         li![
+            input![
+                attrs! {
+                    At::Type => "checkbox",
+                    At::Checked => selected.as_at_value(),
+                },
+                ev(Ev::Change, |_| select_msg),
+            ],
+            " ",
             pc.overview_title(),
             " ",
             button![
@@ -234,16 +262,14 @@ pub fn view(mdl: &Mdl) -> Node<Msg> {
                                 format!("(rev {})", pc.current_rev_nr())
                             ],
                             " ",
-                            button!["Accept", ev(Ev::Click, |_| accept_msg)],
+                            button![
+                                attrs! { At::Disabled => mdl.busy.as_at_value() },
+                                "Accept",
+                                ev(Ev::Click, |_| accept_msg)
+                            ],
                         ]
                     ],
-                    table_row_always("Title", &title_cs),
-                    table_row_always("Description", &desc_cs),
-                    table_row_always("Location", &location_cs),
-                    table_row("Contact", &contact_cs),
-                    table_row("Opening hours", &opening_cs),
-                    table_row("Links", &links_cs),
-                    table_row("Tags", &tags_cs),
+                    compare::view(lastrev, currrev),
                 ]]
             } else {
                 empty![]
@@ -258,69 +284,119 @@ pub fn view(mdl: &Mdl) -> Node<Msg> {
             button![ev(Ev::Click, |_| Msg::Logout), "Logout",],
         ],
         h1![crate::TITLE],
-        h2!["Overview"],
+        h2![
+            "Overview",
+            mdl.total_count
+                .map(|count| format!(" ({} pending)", count))
+                .unwrap_or_default()
+        ],
         if li.clone().count() == 0 {
             p!["There is nothing to clear :)"]
         } else {
-            ul![li]
+            div![bulk_actions(mdl), ul![li], pagination(mdl)]
         }
     ]
 }
 
-pub fn changeset<F>(last: Option<&PlaceRevision>, curr: &PlaceRevision, f: F) -> Changeset
-where
-    F: Fn(&PlaceRevision) -> String,
-{
-    changeset_split(last, curr, "", f)
-}
-
-pub fn changeset_split<F>(
-    last: Option<&PlaceRevision>,
-    curr: &PlaceRevision,
-    split: &str,
-    f: F,
-) -> Changeset
-where
-    F: Fn(&PlaceRevision) -> String,
-{
-    let slast = last.map_or(String::from(""), &f);
-    let scurr = f(curr);
-    Changeset::new(&slast, &scurr, split)
+fn bulk_actions(mdl: &Mdl) -> Node<Msg> {
+    let selected_count = mdl.selected.len();
+    let all_selected = selected_count == mdl.place_clearances.len();
+    let nothing_selected = mdl.busy || selected_count == 0;
+    div![
+        C!["bulk-actions"],
+        label![
+            input![
+                attrs! {
+                    At::Type => "checkbox",
+                    At::Checked => all_selected.as_at_value(),
+                },
+                ev(Ev::Change, |_| Msg::SelectAll),
+            ],
+            " Select all on this page",
+        ],
+        " ",
+        button![
+            attrs! { At::Disabled => nothing_selected.as_at_value() },
+            format!("Accept selected ({})", selected_count),
+            ev(Ev::Click, |_| Msg::AcceptSelected),
+        ],
+        " ",
+        input![
+            attrs! {
+                At::Type => "text",
+                At::Placeholder => "Reason for rejection",
+                At::Value => &mdl.reject_reason,
+            },
+            input_ev(Ev::Input, Msg::RejectReasonInput),
+        ],
+        " ",
+        label![
+            input![
+                attrs! {
+                    At::Type => "checkbox",
+                    At::Checked => mdl.reject_revert.as_at_value(),
+                },
+                ev(Ev::Change, |_| Msg::ToggleRejectRevert),
+            ],
+            " Keep last checked revision",
+        ],
+        " ",
+        button![
+            attrs! {
+                At::Disabled => (nothing_selected || mdl.reject_reason.trim().is_empty()).as_at_value()
+            },
+            format!("Reject selected ({})", selected_count),
+            ev(Ev::Click, |_| Msg::RejectSelected),
+        ],
+    ]
 }
 
-pub fn table_row_always<Ms>(title: &str, cs: &Changeset) -> Node<Ms> {
-    tr![td![title], td![diffy_last(cs)], td![diffy_current(cs)],]
+fn pagination(mdl: &Mdl) -> Node<Msg> {
+    let total_count = mdl.total_count.unwrap_or_default();
+    let page_count = (total_count + PAGE_SIZE - 1) / PAGE_SIZE;
+    let page = mdl.offset / PAGE_SIZE + 1;
+    let has_previous = mdl.offset > 0;
+    let has_next = mdl.offset + PAGE_SIZE < total_count;
+    div![
+        C!["pagination"],
+        button![
+            attrs! { At::Disabled => (mdl.busy || !has_previous).as_at_value() },
+            "Previous",
+            ev(Ev::Click, |_| Msg::PreviousPage),
+        ],
+        format!(" Page {} of {} ", page, page_count.max(1)),
+        button![
+            attrs! { At::Disabled => (mdl.busy || !has_next).as_at_value() },
+            "Next",
+            ev(Ev::Click, |_| Msg::NextPage),
+        ],
+    ]
 }
 
-pub fn table_row<Ms>(title: &str, cs: &Changeset) -> Node<Ms> {
-    if cs.distance == 0 {
-        empty![]
-    } else {
-        table_row_always(title, cs)
+async fn post_clearances(api_token: String, clearances: Vec<ClearanceForPlace>) -> Msg {
+    let api = Api::new(api::API_ROOT.into());
+    if let Err(err) = api
+        .post_places_clearance_with_api_token(&api_token, clearances)
+        .await
+    {
+        error!(err);
     }
+    Msg::ClearanceUpdated
 }
 
-pub fn diffy_current<Ms>(cs: &Changeset) -> Node<Ms> {
-    let csm = cs.diffs.iter().map(|d| match d {
-        Difference::Same(s) => span![raw![s]],
-        Difference::Add(s) => span![C!["diffadd"], raw![s]],
-        _ => empty![],
-    });
-    span![csm]
-}
-
-pub fn diffy_last<Ms>(cs: &Changeset) -> Node<Ms> {
-    let csm = cs.diffs.iter().map(|d| match d {
-        Difference::Same(s) => span![raw![s]],
-        Difference::Rem(s) => span![C!["diffrem"], raw![s]],
-        _ => empty![],
-    });
-    span![csm]
+async fn get_pending_clearances_count(api_token: String) -> Option<Msg> {
+    match api::get_places_clearance_count(&api_token).await {
+        Ok(count) => Some(Msg::GotPendingClearancesCount(count.count)),
+        Err(err) => {
+            error!(err);
+            None
+        }
+    }
 }
 
-pub async fn get_pending_clearances_full(api_token: String) -> Option<Msg> {
+pub async fn get_pending_clearances_full(api_token: String, offset: u64) -> Option<Msg> {
     let api = Api::new(api::API_ROOT.into());
-    match api.get_places_clearance_with_api_token(&api_token).await {
+    match api::get_places_clearance_page(&api_token, offset, PAGE_SIZE).await {
         Ok(pend) => {
             let mut rezz = Vec::new();
             for i in pend {
@@ -0,0 +1,206 @@
+//! Field-by-field comparison of the last cleared and the current revision
+
+use difference::{Changeset, Difference};
+use ofdb_entities::{geo::MapPoint, place::PlaceRevision};
+use seed::{prelude::*, *};
+
+/// Half of the width/height of the map preview in degrees
+const MAP_PREVIEW_DELTA_DEG: f64 = 0.005;
+
+/// Position changes below this distance are not highlighted
+const POSITION_TOLERANCE_METERS: f64 = 1.0;
+
+pub fn view<Ms>(last: Option<&PlaceRevision>, curr: &PlaceRevision) -> Vec<Node<Ms>> {
+    vec![
+        text_row(true, "Title", last, curr, |r| Some(r.title.clone())),
+        description_row(last, curr),
+        position_row(last, curr),
+        text_row(false, "Street", last, curr, |r| {
+            r.location.address.as_ref().and_then(|a| a.street.clone())
+        }),
+        text_row(false, "ZIP", last, curr, |r| {
+            r.location.address.as_ref().and_then(|a| a.zip.clone())
+        }),
+        text_row(false, "City", last, curr, |r| {
+            r.location.address.as_ref().and_then(|a| a.city.clone())
+        }),
+        text_row(false, "Country", last, curr, |r| {
+            r.location.address.as_ref().and_then(|a| a.country.clone())
+        }),
+        text_row(false, "State", last, curr, |r| {
+            r.location.address.as_ref().and_then(|a| a.state.clone())
+        }),
+        text_row(false, "Contact name", last, curr, |r| {
+            r.contact.as_ref().and_then(|c| c.name.clone())
+        }),
+        text_row(false, "Email", last, curr, |r| {
+            r.contact
+                .as_ref()
+                .and_then(|c| c.email.clone())
+                .map(String::from)
+        }),
+        text_row(false, "Phone", last, curr, |r| {
+            r.contact
+                .as_ref()
+                .and_then(|c| c.phone.clone())
+                .map(String::from)
+        }),
+        text_row(false, "Opening hours", last, curr, |r| {
+            r.opening_hours.clone().map(String::from)
+        }),
+        text_row(false, "Founded on", last, curr, |r| {
+            r.founded_on.map(|d| d.to_string())
+        }),
+        text_row(false, "Homepage", last, curr, |r| {
+            r.links
+                .as_ref()
+                .and_then(|l| l.homepage.clone())
+                .map(|u| u.into_string())
+        }),
+        text_row(false, "Image", last, curr, |r| {
+            r.links
+                .as_ref()
+                .and_then(|l| l.image.clone())
+                .map(|u| u.into_string())
+        }),
+        text_row(false, "Image link", last, curr, |r| {
+            r.links
+                .as_ref()
+                .and_then(|l| l.image_href.clone())
+                .map(|u| u.into_string())
+        }),
+        list_row("Custom links", last, curr, |r| {
+            r.links
+                .as_ref()
+                .map(|l| {
+                    l.custom
+                        .iter()
+                        .map(|c| match &c.title {
+                            Some(title) => format!("{}: {}", title, c.url.as_str()),
+                            None => c.url.as_str().to_owned(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        }),
+        list_row("Tags", last, curr, |r| r.tags.clone()),
+    ]
+}
+
+fn text_row<Ms, F>(
+    always: bool,
+    title: &str,
+    last: Option<&PlaceRevision>,
+    curr: &PlaceRevision,
+    f: F,
+) -> Node<Ms>
+where
+    F: Fn(&PlaceRevision) -> Option<String>,
+{
+    let last_val = last.and_then(&f).unwrap_or_default();
+    let curr_val = f(curr).unwrap_or_default();
+    let changed = last_val != curr_val;
+    if !always && !changed {
+        return empty![];
+    }
+    tr![
+        IF!(changed => C!["changed"]),
+        td![title],
+        td![IF!(changed => C!["diffrem"]), last_val],
+        td![IF!(changed => C!["diffadd"]), curr_val],
+    ]
+}
+
+fn description_row<Ms>(last: Option<&PlaceRevision>, curr: &PlaceRevision) -> Node<Ms> {
+    let last_val = last.map(|r| r.description.as_str()).unwrap_or_default();
+    let cs = Changeset::new(last_val, &curr.description, " ");
+    let removed = cs.diffs.iter().map(|d| match d {
+        Difference::Same(s) => span![s.as_str(), " "],
+        Difference::Rem(s) => span![C!["diffrem"], s.as_str(), " "],
+        Difference::Add(_) => empty![],
+    });
+    let added = cs.diffs.iter().map(|d| match d {
+        Difference::Same(s) => span![s.as_str(), " "],
+        Difference::Add(s) => span![C!["diffadd"], s.as_str(), " "],
+        Difference::Rem(_) => empty![],
+    });
+    tr![
+        IF!(cs.distance > 0 => C!["changed"]),
+        td!["Description"],
+        td![removed],
+        td![added],
+    ]
+}
+
+fn position_row<Ms>(last: Option<&PlaceRevision>, curr: &PlaceRevision) -> Node<Ms> {
+    let curr_pos = curr.location.pos;
+    let last_pos = last.map(|r| r.location.pos);
+    let distance = last_pos
+        .and_then(|pos| MapPoint::distance(pos, curr_pos))
+        .map(|d| d.to_meters());
+    let changed = distance.map_or(true, |d| d >= POSITION_TOLERANCE_METERS);
+    tr![
+        IF!(changed => C!["changed"]),
+        td!["Position"],
+        td![last_pos.map_or(empty![], |pos| position_preview(pos))],
+        td![
+            position_preview(curr_pos),
+            match distance {
+                Some(d) if changed => div![C!["diffadd"], format!("moved by {:.0} m", d)],
+                _ => empty![],
+            }
+        ],
+    ]
+}
+
+fn position_preview<Ms>(pos: MapPoint) -> Node<Ms> {
+    let (lat, lng) = pos.to_lat_lng_deg();
+    let src = format!(
+        "https://www.openstreetmap.org/export/embed.html?bbox={},{},{},{}&layer=mapnik&marker={},{}",
+        lng - MAP_PREVIEW_DELTA_DEG,
+        lat - MAP_PREVIEW_DELTA_DEG,
+        lng + MAP_PREVIEW_DELTA_DEG,
+        lat + MAP_PREVIEW_DELTA_DEG,
+        lat,
+        lng
+    );
+    div![
+        div![format!("Lat {:.6}, Lon {:.6}", lat, lng)],
+        iframe![
+            C!["map-preview"],
+            attrs! {
+                At::Src => src,
+                At::Title => "Map preview",
+            }
+        ]
+    ]
+}
+
+fn list_row<Ms, F>(
+    title: &str,
+    last: Option<&PlaceRevision>,
+    curr: &PlaceRevision,
+    f: F,
+) -> Node<Ms>
+where
+    F: Fn(&PlaceRevision) -> Vec<String>,
+{
+    let last_items = last.map(&f).unwrap_or_default();
+    let curr_items = f(curr);
+    if last_items == curr_items {
+        return empty![];
+    }
+    let removed = last_items.iter().map(|item| {
+        div![
+            IF!(!curr_items.contains(item) => C!["diffrem"]),
+            item.as_str()
+        ]
+    });
+    let added = curr_items.iter().map(|item| {
+        div![
+            IF!(!last_items.contains(item) => C!["diffadd"]),
+            item.as_str()
+        ]
+    });
+    tr![C!["changed"], td![title], td![removed], td![added]]
+}
@@ -37,9 +37,20 @@ impl From<CustomLink> for e::links::CustomLink {
 impl From<e::category::Category> for Category {
     fn from(from: e::category::Category) -> Self {
         let name = from.name();
+        let e::category::Category {
+            id,
+            tag,
+            names,
+            icon,
+            color,
+        } = from;
         Self {
-            id: from.id.into(),
+            id: id.into(),
             name,
+            tag,
+            names,
+            icon,
+            color,
         }
     }
 }
@@ -84,6 +95,137 @@ impl From<e::user::User> for User {
     }
 }
 
+impl From<e::nonce::UserToken> for UserToken {
+    fn from(from: e::nonce::UserToken) -> Self {
+        // The secret nonce must not be revealed
+        let e::nonce::UserToken {
+            email_nonce: _email_nonce,
+            expires_at,
+            new_email,
+            place_id,
+        } = from;
+        Self {
+            expires_at: expires_at.into_inner(),
+            new_email,
+            place_id,
+        }
+    }
+}
+
+impl From<e::user::AnonymizedUser> for AnonymizedUser {
+    fn from(from: e::user::AnonymizedUser) -> Self {
+        let e::user::AnonymizedUser {
+            pseudonym,
+            anonymized_at,
+            place_revisions,
+            place_reviews,
+            place_ratings,
+            place_rating_comments,
+            events,
+        } = from;
+        Self {
+            pseudonym,
+            anonymized_at: anonymized_at.into_inner(),
+            place_revisions,
+            place_reviews,
+            place_ratings,
+            place_rating_comments,
+            events,
+        }
+    }
+}
+
+impl From<e::audit::AuditLogEntry> for AuditLogEntry {
+    fn from(from: e::audit::AuditLogEntry) -> Self {
+        let e::audit::AuditLogEntry {
+            log:
+                e::activity::ActivityLog {
+                    activity: e::activity::Activity { at, by },
+                    context: ctx,
+                    comment,
+                },
+            action,
+            entity,
+            details,
+        } = from;
+        Self {
+            at: at.into_inner(),
+            by: by.map(Into::into),
+            action: action.to_string(),
+            entity_kind: entity.kind.to_string(),
+            entity_id: entity.id,
+            details,
+            ctx,
+            comment,
+        }
+    }
+}
+
+impl From<e::link_check::LinkCheck> for LinkCheck {
+    fn from(from: e::link_check::LinkCheck) -> Self {
+        let e::link_check::LinkCheck {
+            owner,
+            url,
+            outcome,
+            checked_at,
+        } = from;
+        Self {
+            owner_kind: owner.kind.to_string(),
+            owner_id: owner.id.into(),
+            url: url.into_string(),
+            status: outcome.status.to_string(),
+            http_status: outcome.http_status,
+            redirect_url: outcome.redirect_url,
+            checked_at: checked_at.into_inner(),
+        }
+    }
+}
+
+impl From<e::subscription::BboxSubscription> for BboxSubscription {
+    fn from(from: e::subscription::BboxSubscription) -> Self {
+        let e::subscription::BboxSubscription {
+            id,
+            user_email: _user_email,
+            bbox,
+        } = from;
+        Self {
+            id: id.into(),
+            south_west_lat: bbox.southwest().lat().to_deg(),
+            south_west_lng: bbox.southwest().lng().to_deg(),
+            north_east_lat: bbox.northeast().lat().to_deg(),
+            north_east_lng: bbox.northeast().lng().to_deg(),
+        }
+    }
+}
+
+impl From<e::subscription::SavedSearch> for SavedSearch {
+    fn from(from: e::subscription::SavedSearch) -> Self {
+        let e::subscription::SavedSearch {
+            id,
+            user_email: _user_email,
+            name,
+            bbox,
+            categories,
+            hash_tags,
+            text,
+            org_tag,
+            alert,
+            created_at,
+        } = from;
+        Self {
+            id: id.into(),
+            name,
+            bbox: bbox.into(),
+            categories,
+            tags: hash_tags,
+            text,
+            org_tag,
+            alert,
+            created_at: created_at.into_inner(),
+        }
+    }
+}
+
 impl From<e::user::Role> for UserRole {
     fn from(from: e::user::Role) -> Self {
         use e::user::Role::*;
@@ -279,6 +421,84 @@ impl From<ClearanceForPlace> for e::clearance::ClearanceForPlace {
     }
 }
 
+impl From<RejectionForPlace> for e::clearance::RejectionForPlace {
+    fn from(from: RejectionForPlace) -> Self {
+        let RejectionForPlace {
+            place_id,
+            rejected_revision,
+            reason,
+            revert,
+        } = from;
+        Self {
+            place_id: place_id.into(),
+            rejected_revision: rejected_revision.map(Into::into),
+            reason,
+            revert,
+        }
+    }
+}
+
+impl From<e::ownership::PlaceOwnership> for PlaceOwnership {
+    fn from(from: e::ownership::PlaceOwnership) -> Self {
+        let e::ownership::PlaceOwnership {
+            place_id,
+            owner: _,
+            created_at,
+            locked_fields,
+        } = from;
+        Self {
+            place_id: place_id.into(),
+            created_at: created_at.into_inner(),
+            locked_fields: locked_fields.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+impl From<e::suggestion::PlaceEditSuggestion> for PlaceEditSuggestion {
+    fn from(from: e::suggestion::PlaceEditSuggestion) -> Self {
+        let e::suggestion::PlaceEditSuggestion {
+            id,
+            base_revision,
+            suggested,
+            status,
+            reviewed: _,
+        } = from;
+        let place_id = suggested.id.to_string();
+        let (_, suggested): (_, e::place::PlaceRevision) = suggested.into();
+        Self {
+            id: id.into(),
+            place_id,
+            base_revision: base_revision.into(),
+            status: status.to_string(),
+            suggested: suggested.into(),
+        }
+    }
+}
+
+impl From<e::clearance::PendingClearanceForEvent> for PendingClearanceForEvent {
+    fn from(from: e::clearance::PendingClearanceForEvent) -> Self {
+        let e::clearance::PendingClearanceForEvent {
+            event_id,
+            created_at,
+            last_cleared_event,
+        } = from;
+        Self {
+            event_id: event_id.into(),
+            created_at: created_at.into_inner(),
+            last_cleared_event: last_cleared_event.map(Into::into),
+        }
+    }
+}
+
+impl From<ClearanceForEvent> for e::clearance::ClearanceForEvent {
+    fn from(from: ClearanceForEvent) -> Self {
+        let ClearanceForEvent { event_id } = from;
+        Self {
+            event_id: event_id.into(),
+        }
+    }
+}
+
 impl From<e::geo::MapPoint> for LatLonDegrees {
     fn from(from: e::geo::MapPoint) -> Self {
         Self(from.lat().to_deg(), from.lng().to_deg())
@@ -311,6 +531,25 @@ impl From<e::geo::MapBbox> for MapBbox {
     }
 }
 
+impl TryFrom<MapPoint> for e::geo::MapPoint {
+    type Error = e::geo::CoordRangeError;
+
+    fn try_from(from: MapPoint) -> Result<Self, Self::Error> {
+        e::geo::MapPoint::try_from_lat_lng_deg(from.lat, from.lng)
+    }
+}
+
+impl TryFrom<MapBbox> for e::geo::MapBbox {
+    type Error = e::geo::CoordRangeError;
+
+    fn try_from(from: MapBbox) -> Result<Self, Self::Error> {
+        Ok(e::geo::MapBbox::new(
+            from.sw.try_into()?,
+            from.ne.try_into()?,
+        ))
+    }
+}
+
 impl From<e::address::Address> for Address {
     fn from(from: e::address::Address) -> Self {
         let e::address::Address {
@@ -560,6 +799,26 @@ impl From<PlaceHistory> for e::place::PlaceHistory {
     }
 }
 
+impl From<e::comment::Comment> for Comment {
+    fn from(from: e::comment::Comment) -> Self {
+        let e::comment::Comment {
+            id,
+            created_at,
+            text,
+            reply_to,
+            is_response,
+            ..
+        } = from;
+        Self {
+            id: id.into(),
+            created: created_at.into_seconds(),
+            text,
+            reply_to: reply_to.map(Into::into),
+            response: is_response,
+        }
+    }
+}
+
 impl From<e::activity::ActivityLog> for ActivityLog {
     fn from(from: e::activity::ActivityLog) -> Self {
         let e::activity::ActivityLog {
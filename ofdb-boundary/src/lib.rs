@@ -11,6 +11,7 @@
 
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[cfg(feature = "entity-conversions")]
 mod conv;
@@ -233,13 +234,40 @@ pub struct Comment {
     pub id: String,
     pub created: i64,
     pub text: String,
+    /// The id of the comment this comment replies to
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub reply_to: Option<String>,
+    /// An official response of the place owner
+    #[serde(default)]
+    pub response: bool,
 }
 
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
 pub struct Category {
     pub id: String,
+    /// The hash tag of the category, e.g. "#non-profit"
     pub name: String,
+    pub tag: String,
+    /// Translated names by language code
+    #[serde(default)]
+    pub names: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct NewCategory {
+    pub tag: String,
+    /// Translated names by language code
+    #[serde(default)]
+    pub names: BTreeMap<String, String>,
+    pub icon: Option<String>,
+    /// Hex color code, e.g. "#ff0000"
+    pub color: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -274,6 +302,65 @@ pub enum ReviewStatus {
 pub struct SearchResponse {
     pub visible: Vec<PlaceSearchResult>,
     pub invisible: Vec<PlaceSearchResult>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub facets: Option<SearchFacets>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct StatusCount {
+    pub status: ReviewStatus,
+    pub count: u64,
+}
+
+/// The number of places with a total rating
+/// from `rating` (inclusive) to `rating + 1` (exclusive)
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct RatingCount {
+    pub rating: i64,
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct SearchFacets {
+    pub total_count: u64,
+    pub categories: Vec<PlaceCount>,
+    pub tags: Vec<PlaceCount>,
+    pub status: Vec<StatusCount>,
+    pub ratings: Vec<RatingCount>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct PlaceCluster {
+    pub count: u64,
+    pub lat: f64,
+    pub lng: f64,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub category: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "extra-derive",
+    derive(Debug, Clone, Copy, PartialEq, Eq, Hash)
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SuggestionKind {
+    Title,
+    Tag,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct Suggestion {
+    pub text: String,
+    pub kind: SuggestionKind,
+    pub count: u64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -299,6 +386,88 @@ pub struct BboxSubscription {
     pub north_east_lng: f64,
 }
 
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct TagDetails {
+    pub id: String,
+    pub description: Option<String>,
+    pub synonyms: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct UpdateTag {
+    pub description: Option<String>,
+    /// Alternative spellings that are found when searching for the tag
+    #[serde(default)]
+    pub synonyms: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct MergeTags {
+    /// The tags that will be replaced
+    pub tags: Vec<String>,
+    /// The remaining tag
+    pub into: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct MergedTags {
+    /// Ids of the places with a new revision
+    pub places: Vec<String>,
+    /// Ids of the updated events
+    pub events: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct TaxonomyNode {
+    pub tag: String,
+    pub description: Option<String>,
+    pub children: Vec<TaxonomyNode>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct NewTaxonomyNode {
+    pub tag: String,
+    #[serde(default)]
+    pub children: Vec<NewTaxonomyNode>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct NewSavedSearch {
+    pub name: String,
+    pub bbox: MapBbox,
+    /// Category ids
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub text: Option<String>,
+    pub org_tag: Option<String>,
+    /// Notify by e-mail when places or events start matching
+    #[serde(default)]
+    pub alert: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct SavedSearch {
+    pub id: String,
+    pub name: String,
+    pub bbox: MapBbox,
+    pub categories: Vec<String>,
+    pub tags: Vec<String>,
+    pub text: Option<String>,
+    pub org_tag: Option<String>,
+    pub alert: bool,
+    pub created_at: i64,
+}
+
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
 pub struct MapBbox {
@@ -326,6 +495,217 @@ pub struct ResetPassword {
     pub new_password: String,
 }
 
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct ChangePassword {
+    pub old_password: String,
+    pub new_password: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct RequestEmailChange {
+    pub new_email: String,
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct ConfirmEmailChange {
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct ConfirmPlaceOwnership {
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct PlaceOwnership {
+    pub place_id: String,
+    pub created_at: i64,
+    /// Fields that could only be modified by the owner
+    pub locked_fields: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug))]
+pub struct PlaceEditSuggestion {
+    pub id: String,
+    pub place_id: String,
+    /// The revision of the place that has been modified
+    pub base_revision: RevisionValue,
+    pub status: String,
+    pub suggested: PlaceRevision,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct DeclineSuggestion {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// An uploaded image
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct Image {
+    pub id: String,
+    /// Could be used as `image_url` of places and events
+    pub url: String,
+    pub thumbnail_url: String,
+    pub width: u32,
+    pub height: u32,
+    pub license: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attribution: Option<String>,
+    pub created_at: i64,
+}
+
+/// The number of items within a week or month
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct PeriodCount {
+    /// The first day of the week (Monday) or month
+    pub period: NaiveDate,
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct PendingClearanceCount {
+    pub org_id: String,
+    pub org_name: String,
+    pub places: u64,
+    pub events: u64,
+}
+
+/// The number of places with a category, tag or region
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct PlaceCount {
+    pub key: String,
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct Statistics {
+    pub places_created_per_week: Vec<PeriodCount>,
+    pub places_updated_per_week: Vec<PeriodCount>,
+    pub events_per_month: Vec<PeriodCount>,
+    pub users_registered_per_week: Vec<PeriodCount>,
+    pub ratings_per_week: Vec<PeriodCount>,
+    pub pending_clearances: Vec<PendingClearanceCount>,
+    pub places_by_category: Vec<PlaceCount>,
+    pub places_by_tag: Vec<PlaceCount>,
+    pub places_by_country: Vec<PlaceCount>,
+    pub places_by_state: Vec<PlaceCount>,
+}
+
+/// Names of the permissions that are granted to each role,
+/// e.g. `{ "scout": ["place.review", "place.archive"] }`
+#[derive(Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct RolePermissions {
+    #[serde(default)]
+    pub guest: Vec<String>,
+    #[serde(default)]
+    pub user: Vec<String>,
+    #[serde(default)]
+    pub scout: Vec<String>,
+    #[serde(default)]
+    pub admin: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct UserToken {
+    pub expires_at: i64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_email: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub place_id: Option<String>,
+}
+
+/// All data that is linked to a user account
+/// Report about a deleted user whose authored
+/// activities refer to a pseudonym
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct AnonymizedUser {
+    pub pseudonym: String,
+    pub anonymized_at: i64,
+    pub place_revisions: u64,
+    pub place_reviews: u64,
+    pub place_ratings: u64,
+    pub place_rating_comments: u64,
+    pub events: u64,
+}
+
+/// A recorded moderation action
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct AuditLogEntry {
+    pub at: i64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub by: Option<String>,
+
+    pub action: String,
+
+    pub entity_kind: String,
+
+    pub entity_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ctx: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// The last check of a link of a place or event
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct LinkCheck {
+    /// Either `place` or `event`
+    pub owner_kind: String,
+
+    pub owner_id: String,
+
+    pub url: String,
+
+    /// One of `ok`, `redirect`, `broken` or `unreachable`
+    pub status: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect_url: Option<String>,
+
+    pub checked_at: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug))]
+pub struct UserExport {
+    pub user: User,
+    pub place_revisions: Vec<(PlaceRoot, PlaceRevision, ReviewStatus)>,
+    /// Pairs of (place id, rating)
+    pub ratings: Vec<(String, Rating)>,
+    pub bbox_subscriptions: Vec<BboxSubscription>,
+    pub user_tokens: Vec<UserToken>,
+}
+
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
 pub struct Credentials {
@@ -349,6 +729,32 @@ pub struct Rating {
     pub source: String,
 }
 
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct NewRatingFlag {
+    /// The reason for reporting the rating
+    pub comment: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct NewRatingComment {
+    pub text: String,
+    /// The id of a comment of the same rating
+    pub reply_to: Option<String>,
+    /// Only permitted for the owners of the place
+    #[serde(default)]
+    pub response: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug))]
+pub struct FlaggedRating {
+    pub rating: Rating,
+    /// Most recent first
+    pub flags: Vec<ActivityLog>,
+}
+
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
 pub struct PendingClearanceForPlace {
@@ -364,6 +770,34 @@ pub struct ClearanceForPlace {
     pub cleared_revision: Option<RevisionValue>,
 }
 
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct RejectionForPlace {
+    pub place_id: String,
+    /// The rejected revision or None for the current revision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejected_revision: Option<RevisionValue>,
+    pub reason: String,
+    /// Permanently keep the last cleared revision
+    #[serde(default)]
+    pub revert: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone))]
+pub struct PendingClearanceForEvent {
+    pub event_id: String,
+    pub created_at: i64,
+    /// The last cleared state or None if the event is new
+    pub last_cleared_event: Option<Event>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
+pub struct ClearanceForEvent {
+    pub event_id: String,
+}
+
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(feature = "extra-derive", derive(Debug, Clone, PartialEq, Eq))]
 pub struct ResultCount {
@@ -26,6 +26,7 @@ fn create_ratings_of_entry(place_id: &str, n: usize) -> Vec<Rating> {
             value: 2.into(),
             context: RatingContext::Diversity,
             source: None,
+            author: None,
         })
         .collect()
 }
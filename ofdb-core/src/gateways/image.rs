@@ -0,0 +1,20 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ImageStorageError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Image storage failed: {0}")]
+    Other(String),
+}
+
+/// Binary storage for uploaded images, e.g. a local
+/// directory or an S3-compatible bucket.
+pub trait ImageStorageGateway {
+    fn store(&self, key: &str, content_type: &str, data: &[u8]) -> Result<(), ImageStorageError>;
+    /// Returns `None` if nothing has been stored under this key.
+    fn load(&self, key: &str) -> Result<Option<Vec<u8>>, ImageStorageError>;
+    /// Deleting a key that does not exist is not an error.
+    fn delete(&self, key: &str) -> Result<(), ImageStorageError>;
+}
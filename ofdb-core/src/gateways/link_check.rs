@@ -0,0 +1,6 @@
+use ofdb_entities::{link_check::LinkCheckOutcome, url::Url};
+
+pub trait LinkCheckGateway {
+    /// Request the link without following redirects.
+    fn check_link(&self, url: &Url) -> LinkCheckOutcome;
+}
@@ -12,8 +12,25 @@ pub trait NotificationGateway {
     );
     fn event_created(&self, email_addresses: &[String], event: &Event);
     fn event_updated(&self, email_addresses: &[String], event: &Event);
+    fn place_matches_saved_search(
+        &self,
+        email: &str,
+        search_name: &str,
+        place: &Place,
+        all_categories: Vec<Category>,
+    );
+    fn event_matches_saved_search(&self, email: &str, search_name: &str, event: &Event);
     fn user_registered_kvm(&self, user: &User);
     fn user_registered_ofdb(&self, user: &User);
     fn user_registered(&self, user: &User, url: &str);
     fn user_reset_password_requested(&self, email_nonce: &EmailNonce);
+    fn user_email_change_requested(&self, email_nonce: &EmailNonce, new_email: &str);
+    fn user_email_changed(&self, old_email: &str, new_email: &str);
+    fn place_clearance_rejected(&self, email: &str, place: &Place, org_name: &str, reason: &str);
+    fn place_ownership_requested(
+        &self,
+        email_nonce: &EmailNonce,
+        contact_email: &str,
+        place: &Place,
+    );
 }
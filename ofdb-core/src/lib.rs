@@ -3,4 +3,5 @@ pub mod gateways;
 pub mod rating;
 pub mod tag;
 pub mod text;
+pub mod tile;
 pub mod user;
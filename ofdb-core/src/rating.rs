@@ -39,6 +39,7 @@ pub mod tests {
             value: value.into(),
             context,
             source: Some("blabla".into()),
+            author: None,
         }
     }
 
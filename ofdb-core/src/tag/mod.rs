@@ -1,4 +1,5 @@
 use crate::text;
+use ofdb_entities::tag::{TagSynonym, TaxonomyTag};
 
 pub mod moderated;
 
@@ -8,3 +9,123 @@ pub fn split_text_into_tags(text: &str) -> Vec<String> {
         .map(str::to_lowercase)
         .collect()
 }
+
+/// The tag of a synonym or otherwise the tag itself.
+pub fn canonical_tag<'a>(tag: &'a str, synonyms: &'a [TagSynonym]) -> &'a str {
+    synonyms
+        .iter()
+        .find(|s| s.synonym == tag)
+        .map(|s| s.tag.as_str())
+        .unwrap_or(tag)
+}
+
+/// Adds the tags that are synonymous to any of the given tags,
+/// i.e. the synonyms of a tag and the tag of a synonym including
+/// all of its other synonyms.
+pub fn expand_synonyms(mut tags: Vec<String>, synonyms: &[TagSynonym]) -> Vec<String> {
+    let canonical_tags: Vec<_> = tags
+        .iter()
+        .map(|tag| canonical_tag(tag, synonyms).to_owned())
+        .collect();
+    for canonical in canonical_tags {
+        tags.extend(
+            synonyms
+                .iter()
+                .filter(|s| s.tag == canonical)
+                .map(|s| s.synonym.clone()),
+        );
+        tags.push(canonical);
+    }
+    let mut unique_tags = Vec::with_capacity(tags.len());
+    for tag in tags {
+        if !unique_tags.contains(&tag) {
+            unique_tags.push(tag);
+        }
+    }
+    unique_tags
+}
+
+/// All direct and indirect children of the tag in the taxonomy.
+pub fn descendants(tag: &str, taxonomy: &[TaxonomyTag]) -> Vec<String> {
+    let mut descendants: Vec<String> = vec![];
+    let mut parents = vec![tag];
+    while let Some(parent) = parents.pop() {
+        for child in taxonomy
+            .iter()
+            .filter(|t| t.parent.as_deref() == Some(parent))
+        {
+            // Guard against cycles
+            if child.tag != tag && !descendants.contains(&child.tag) {
+                descendants.push(child.tag.clone());
+                parents.push(&child.tag);
+            }
+        }
+    }
+    descendants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synonym(synonym: &str, tag: &str) -> TagSynonym {
+        TagSynonym {
+            synonym: synonym.into(),
+            tag: tag.into(),
+        }
+    }
+
+    #[test]
+    fn expand_tags_with_synonyms() {
+        let synonyms = vec![
+            synonym("repair-cafe", "repaircafe"),
+            synonym("reparaturcafe", "repaircafe"),
+            synonym("bio", "organic"),
+        ];
+        assert_eq!(
+            vec!["repaircafe", "repair-cafe", "reparaturcafe"],
+            expand_synonyms(vec!["repaircafe".into()], &synonyms)
+        );
+        assert_eq!(
+            vec!["reparaturcafe", "fair", "repair-cafe", "repaircafe"],
+            expand_synonyms(vec!["reparaturcafe".into(), "fair".into()], &synonyms)
+        );
+        assert_eq!(
+            vec!["fair"],
+            expand_synonyms(vec!["fair".into()], &synonyms)
+        );
+    }
+
+    #[test]
+    fn canonical_tags_of_synonyms() {
+        let synonyms = vec![synonym("repair-cafe", "repaircafe")];
+        assert_eq!("repaircafe", canonical_tag("repair-cafe", &synonyms));
+        assert_eq!("repaircafe", canonical_tag("repaircafe", &synonyms));
+        assert_eq!("fair", canonical_tag("fair", &synonyms));
+    }
+
+    fn taxonomy_tag(tag: &str, parent: Option<&str>) -> TaxonomyTag {
+        TaxonomyTag {
+            tag: tag.into(),
+            parent: parent.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn descendants_of_taxonomy_tags() {
+        let taxonomy = vec![
+            taxonomy_tag("food", None),
+            taxonomy_tag("organic-shop", Some("food")),
+            taxonomy_tag("food-coop", Some("food")),
+            taxonomy_tag("unpacked", Some("organic-shop")),
+            taxonomy_tag("mobility", None),
+        ];
+        assert_eq!(
+            vec!["organic-shop", "food-coop", "unpacked"],
+            descendants("food", &taxonomy)
+        );
+        assert_eq!(vec!["unpacked"], descendants("organic-shop", &taxonomy));
+        assert!(descendants("mobility", &taxonomy).is_empty());
+        assert!(descendants("unknown", &taxonomy).is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+//! Tiles of a web map in the Web Mercator projection.
+//!
+//! See also: <https://wiki.openstreetmap.org/wiki/Slippy_map_tilenames>
+
+use ofdb_entities::geo::*;
+
+use std::f64::consts::PI;
+
+pub const MAX_ZOOM: u8 = 22;
+
+/// The buffer around each tile relative to its size to
+/// avoid that symbols are clipped at the edges of a tile.
+pub const BUFFER: f64 = 1.0 / 64.0;
+
+// Web Mercator does not cover the poles
+const MAX_LAT_DEG: f64 = 85.051_128_779_806_59;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TileCoord {
+    pub z: u8,
+    pub x: u32,
+    pub y: u32,
+}
+
+fn tile_count(z: u8) -> f64 {
+    f64::from(1u32 << z)
+}
+
+// The position in units of tiles on the given zoom level
+fn project(pos: MapPoint, z: u8) -> (f64, f64) {
+    let n = tile_count(z);
+    let lat_rad = pos
+        .lat()
+        .to_deg()
+        .max(-MAX_LAT_DEG)
+        .min(MAX_LAT_DEG)
+        .to_radians();
+    let x = (pos.lng().to_deg() + 180.0) / 360.0 * n;
+    let y = (1.0 - (lat_rad.tan() + 1.0 / lat_rad.cos()).ln() / PI) / 2.0 * n;
+    (x, y)
+}
+
+fn unproject(x: f64, y: f64, z: u8) -> MapPoint {
+    let n = tile_count(z);
+    let x = x.max(0.0).min(n);
+    let y = y.max(0.0).min(n);
+    let lng_deg = x / n * 360.0 - 180.0;
+    let lat_deg = (PI * (1.0 - 2.0 * y / n)).sinh().atan().to_degrees();
+    MapPoint::from_lat_lng_deg(lat_deg, lng_deg)
+}
+
+impl TileCoord {
+    pub fn new(z: u8, x: u32, y: u32) -> Option<Self> {
+        if z > MAX_ZOOM || x >= 1 << z || y >= 1 << z {
+            return None;
+        }
+        Some(Self { z, x, y })
+    }
+
+    /// The area of the tile including a buffer
+    /// relative to the size of the tile.
+    pub fn bbox(self, buffer: f64) -> MapBbox {
+        let x = f64::from(self.x);
+        let y = f64::from(self.y);
+        MapBbox::new(
+            unproject(x - buffer, y + 1.0 + buffer, self.z),
+            unproject(x + 1.0 + buffer, y - buffer, self.z),
+        )
+    }
+
+    /// The position relative to the north-west corner of
+    /// the tile, i.e. (0, 0) to (1, 1) within the tile.
+    pub fn position(self, pos: MapPoint) -> (f64, f64) {
+        let (x, y) = project(pos, self.z);
+        (x - f64::from(self.x), y - f64::from(self.y))
+    }
+
+    /// All tiles on the given zoom level that contain
+    /// the position within their buffer.
+    pub fn covering(pos: MapPoint, z: u8, buffer: f64) -> Vec<Self> {
+        debug_assert!(z <= MAX_ZOOM);
+        let max = (1u32 << z) - 1;
+        let (x, y) = project(pos, z);
+        let range = |v: f64| {
+            let min = (v - buffer).floor().max(0.0) as u32;
+            let max = ((v + buffer).floor().max(0.0) as u32).min(max);
+            min..=max
+        };
+        let mut tiles = Vec::with_capacity(4);
+        for x in range(x) {
+            for y in range(y) {
+                tiles.push(Self { z, x, y });
+            }
+        }
+        tiles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_tile_coord() {
+        assert!(TileCoord::new(0, 0, 0).is_some());
+        assert!(TileCoord::new(0, 1, 0).is_none());
+        assert!(TileCoord::new(2, 3, 3).is_some());
+        assert!(TileCoord::new(2, 3, 4).is_none());
+        assert!(TileCoord::new(MAX_ZOOM + 1, 0, 0).is_none());
+    }
+
+    #[test]
+    fn tile_bbox() {
+        let bbox = TileCoord::new(1, 1, 0).unwrap().bbox(0.0);
+        assert!((bbox.southwest().lat().to_deg() - 0.0).abs() < 1e-6);
+        assert!((bbox.southwest().lng().to_deg() - 0.0).abs() < 1e-6);
+        assert!((bbox.northeast().lat().to_deg() - MAX_LAT_DEG).abs() < 1e-6);
+        assert!((bbox.northeast().lng().to_deg() - 180.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn position_within_tile() {
+        let tile = TileCoord::new(1, 1, 0).unwrap();
+        let (x, y) = tile.position(MapPoint::from_lat_lng_deg(0.0, 90.0));
+        assert!((x - 0.5).abs() < 1e-6);
+        assert!((y - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn tiles_covering_position() {
+        let pos = MapPoint::from_lat_lng_deg(48.5, 9.0);
+        let tiles = TileCoord::covering(pos, 10, 0.0);
+        assert_eq!(1, tiles.len());
+        let (x, y) = tiles[0].position(pos);
+        assert!(x >= 0.0 && x < 1.0);
+        assert!(y >= 0.0 && y < 1.0);
+        assert_eq!(
+            vec![TileCoord::new(0, 0, 0).unwrap()],
+            TileCoord::covering(pos, 0, BUFFER)
+        );
+        // On the edge between 4 tiles
+        let pos = MapPoint::from_lat_lng_deg(0.0, 0.0);
+        assert_eq!(4, TileCoord::covering(pos, 1, BUFFER).len());
+    }
+}
@@ -1,19 +1,192 @@
-use ofdb_entities::user::{Role, User};
+use ofdb_entities::{
+    geo::{MapBbox, MapPoint},
+    user::{Permission, Role, User},
+};
 
-use std::result::Result as StdResult;
+use std::{
+    collections::{HashMap, HashSet},
+    result::Result as StdResult,
+};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("unauthorized role")]
-    UnauthorizedRole,
+    #[error("missing permission: {0}")]
+    MissingPermission(Permission),
+    #[error("outside of permitted regions")]
+    OutsideOfRegion,
 }
 
 pub type Result<T> = StdResult<T, Error>;
 
-pub fn authorize_role(user: &User, min_required_role: Role) -> Result<()> {
-    if user.role < min_required_role {
-        return Err(Error::UnauthorizedRole);
+/// Maps roles to the permissions that are granted to them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RolePermissions(HashMap<Role, HashSet<Permission>>);
+
+impl RolePermissions {
+    /// No permissions are granted at all.
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn grant(&mut self, role: Role, permission: Permission) {
+        self.0.entry(role).or_default().insert(permission);
+    }
+
+    pub fn is_granted(&self, role: Role, permission: Permission) -> bool {
+        self.0
+            .get(&role)
+            .map(|permissions| permissions.contains(&permission))
+            .unwrap_or(false)
+    }
+
+    /// A role outranks another role if it is granted all
+    /// permissions of the other role and at least one more.
+    pub fn outranks(&self, role: Role, other: Role) -> bool {
+        let empty = HashSet::new();
+        let permissions = self.0.get(&role).unwrap_or(&empty);
+        let other_permissions = self.0.get(&other).unwrap_or(&empty);
+        permissions.len() > other_permissions.len() && permissions.is_superset(other_permissions)
+    }
+}
+
+impl Default for RolePermissions {
+    /// Equivalent to the former fixed role ordering:
+    /// Scouts may moderate and export all contents and read
+    /// the history of places. Admins may additionally manage
+    /// and anonymize users, manage tags and categories, read
+    /// the authors of all contents, the audit log, the report
+    /// of broken links and the statistics.
+    fn default() -> Self {
+        use Permission::*;
+        let mut permissions = Self::empty();
+        for role in &[Role::Scout, Role::Admin] {
+            for permission in &[
+                PlaceReview,
+                PlaceArchive,
+                PlaceReadHistory,
+                PlaceExport,
+                EventArchive,
+                EventExport,
+                RatingArchive,
+                CommentArchive,
+            ] {
+                permissions.grant(*role, *permission);
+            }
+        }
+        permissions.grant(Role::Admin, UserChangeRole);
+        permissions.grant(Role::Admin, UserAnonymize);
+        permissions.grant(Role::Admin, AuditRead);
+        permissions.grant(Role::Admin, LinkReport);
+        permissions.grant(Role::Admin, StatsRead);
+        permissions.grant(Role::Admin, TagManage);
+        permissions.grant(Role::Admin, CategoryManage);
+        permissions.grant(Role::Admin, AuthorRead);
+        permissions
+    }
+}
+
+pub fn authorize_permission(
+    permissions: &RolePermissions,
+    user: &User,
+    permission: Permission,
+) -> Result<()> {
+    if !permissions.is_granted(user.role, permission) {
+        return Err(Error::MissingPermission(permission));
     }
     Ok(())
 }
+
+/// Checks if a position is inside of the regions of a user.
+///
+/// Users without any regions are not restricted. Contents
+/// without a position are only accessible for unrestricted
+/// users.
+pub fn authorize_region(regions: &[MapBbox], pos: Option<MapPoint>) -> Result<()> {
+    if regions.is_empty() {
+        return Ok(());
+    }
+    match pos {
+        Some(pos) if regions.iter().any(|bbox| bbox.contains_point(pos)) => Ok(()),
+        _ => Err(Error::OutsideOfRegion),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(role: Role) -> User {
+        User {
+            email: "user@example.com".into(),
+            email_confirmed: true,
+            password: "secret".to_string().into(),
+            role,
+        }
+    }
+
+    #[test]
+    fn default_role_permissions() {
+        let permissions = RolePermissions::default();
+        assert!(
+            authorize_permission(&permissions, &user(Role::User), Permission::PlaceArchive)
+                .is_err()
+        );
+        assert!(
+            authorize_permission(&permissions, &user(Role::Scout), Permission::PlaceArchive)
+                .is_ok()
+        );
+        assert!(
+            authorize_permission(&permissions, &user(Role::Scout), Permission::UserChangeRole)
+                .is_err()
+        );
+        assert!(
+            authorize_permission(&permissions, &user(Role::Admin), Permission::UserChangeRole)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn permissions_are_not_ordered_by_role() {
+        let mut permissions = RolePermissions::empty();
+        permissions.grant(Role::Scout, Permission::EventArchive);
+        assert!(
+            authorize_permission(&permissions, &user(Role::Scout), Permission::EventArchive)
+                .is_ok()
+        );
+        assert!(
+            authorize_permission(&permissions, &user(Role::Admin), Permission::EventArchive)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn outrank_roles_by_permissions() {
+        let permissions = RolePermissions::default();
+        assert!(permissions.outranks(Role::Admin, Role::Scout));
+        assert!(permissions.outranks(Role::Scout, Role::User));
+        assert!(!permissions.outranks(Role::Admin, Role::Admin));
+        assert!(!permissions.outranks(Role::Scout, Role::Admin));
+        assert!(!permissions.outranks(Role::User, Role::Guest));
+
+        let mut permissions = RolePermissions::empty();
+        permissions.grant(Role::Scout, Permission::EventArchive);
+        permissions.grant(Role::Admin, Permission::UserChangeRole);
+        assert!(!permissions.outranks(Role::Admin, Role::Scout));
+    }
+
+    #[test]
+    fn restrict_by_region() {
+        let region = MapBbox::new(
+            MapPoint::from_lat_lng_deg(0.0, 0.0),
+            MapPoint::from_lat_lng_deg(10.0, 10.0),
+        );
+        let inside = MapPoint::from_lat_lng_deg(5.0, 5.0);
+        let outside = MapPoint::from_lat_lng_deg(-5.0, 5.0);
+        assert!(authorize_region(&[], Some(outside)).is_ok());
+        assert!(authorize_region(&[], None).is_ok());
+        assert!(authorize_region(&[region], Some(inside)).is_ok());
+        assert!(authorize_region(&[region], Some(outside)).is_err());
+        assert!(authorize_region(&[region], None).is_err());
+    }
+}
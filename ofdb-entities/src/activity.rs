@@ -25,3 +25,13 @@ pub struct ActivityLog {
     pub context: Option<String>,
     pub comment: Option<String>,
 }
+
+impl From<Activity> for ActivityLog {
+    fn from(activity: Activity) -> Self {
+        Self {
+            activity,
+            context: None,
+            comment: None,
+        }
+    }
+}
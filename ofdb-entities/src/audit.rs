@@ -0,0 +1,68 @@
+use crate::activity::*;
+use strum_macros::{Display, EnumString};
+
+/// Moderation actions that are recorded in the audit log.
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum AuditAction {
+    #[strum(serialize = "place.review")]
+    PlaceReview,
+    #[strum(serialize = "place.reject_clearance")]
+    PlaceRejectClearance,
+    #[strum(serialize = "place.accept_suggestion")]
+    PlaceAcceptSuggestion,
+    #[strum(serialize = "place.decline_suggestion")]
+    PlaceDeclineSuggestion,
+    #[strum(serialize = "event.archive")]
+    EventArchive,
+    #[strum(serialize = "rating.archive")]
+    RatingArchive,
+    #[strum(serialize = "comment.archive")]
+    CommentArchive,
+    #[strum(serialize = "user.change_role")]
+    UserChangeRole,
+    #[strum(serialize = "user.change_regions")]
+    UserChangeRegions,
+    #[strum(serialize = "user.anonymize")]
+    UserAnonymize,
+    #[strum(serialize = "tag.merge")]
+    TagMerge,
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum AuditEntityKind {
+    #[strum(serialize = "place")]
+    Place,
+    #[strum(serialize = "event")]
+    Event,
+    #[strum(serialize = "rating")]
+    Rating,
+    #[strum(serialize = "comment")]
+    Comment,
+    #[strum(serialize = "user")]
+    User,
+}
+
+/// The entity that has been affected by an action.
+///
+/// Users are identified by their e-mail address and all
+/// other entities by their id.
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntity {
+    pub kind : AuditEntityKind,
+    pub id   : String,
+}
+
+/// Who did what, when, why and to which entity.
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditLogEntry {
+    pub log     : ActivityLog,
+    pub action  : AuditAction,
+    pub entity  : AuditEntity,
+    /// Additional information about the action,
+    /// e.g. the new review status or role
+    pub details : Option<String>,
+}
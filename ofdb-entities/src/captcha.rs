@@ -0,0 +1,70 @@
+use crate::time::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptchaKind {
+    /// Distorted characters rendered as a PNG image
+    Image,
+    /// A simple arithmetic question as plain text that
+    /// is also accessible for screen readers
+    Arithmetic,
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captcha {
+    pub created_at : Timestamp,
+    // The kind and the expected answer are only
+    // available after the challenge has been generated
+    pub challenge  : Option<(CaptchaKind, String)>,
+}
+
+impl Captcha {
+    pub fn prepare() -> Self {
+        Self {
+            created_at: Timestamp::now(),
+            challenge: None,
+        }
+    }
+
+    pub fn is_prepared(&self) -> bool {
+        self.challenge.is_none()
+    }
+
+    pub fn is_expired(&self, created_before: Timestamp) -> bool {
+        self.created_at < created_before
+    }
+
+    pub fn is_valid_answer(&self, answer: &str) -> bool {
+        self.challenge
+            .as_ref()
+            .map(|(_, expected)| expected == answer.trim())
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_answer_only_after_challenge_has_been_generated() {
+        let mut captcha = Captcha::prepare();
+        assert!(captcha.is_prepared());
+        assert!(!captcha.is_valid_answer(""));
+        captcha.challenge = Some((CaptchaKind::Arithmetic, "7".into()));
+        assert!(!captcha.is_prepared());
+        assert!(captcha.is_valid_answer("7"));
+        assert!(captcha.is_valid_answer(" 7\n"));
+        assert!(!captcha.is_valid_answer("8"));
+    }
+
+    #[test]
+    fn expire_captcha() {
+        let captcha = Captcha {
+            created_at: Timestamp::from_seconds(100),
+            challenge: None,
+        };
+        assert!(!captcha.is_expired(Timestamp::from_seconds(100)));
+        assert!(captcha.is_expired(Timestamp::from_seconds(101)));
+    }
+}
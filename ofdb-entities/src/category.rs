@@ -1,9 +1,16 @@
 use crate::id::Id;
 
+use std::collections::BTreeMap;
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Category {
     pub id: Id,
     pub tag: String,
+    /// Translated names by language code, e.g. "en" or "de"
+    pub names: BTreeMap<String, String>,
+    pub icon: Option<String>,
+    /// Hex color code, e.g. "#ff0000"
+    pub color: Option<String>,
 }
 
 impl Category {
@@ -21,58 +28,81 @@ impl Category {
     pub const TAG_COMMERCIAL: &'static str = "commercial";
     pub const TAG_EVENT: &'static str = "event";
 
-    pub fn new_non_profit() -> Self {
+    fn new_built_in(id: &str, tag: &str, name: &str) -> Self {
+        let mut names = BTreeMap::new();
+        names.insert("en".to_string(), name.to_string());
         Self {
-            id: Self::ID_NON_PROFIT.into(),
-            tag: Self::TAG_NON_PROFIT.into(),
+            id: id.into(),
+            tag: tag.into(),
+            names,
+            icon: None,
+            color: None,
         }
     }
 
+    pub fn new_non_profit() -> Self {
+        Self::new_built_in(Self::ID_NON_PROFIT, Self::TAG_NON_PROFIT, "Non-profit")
+    }
+
     pub fn new_commercial() -> Self {
-        Self {
-            id: Self::ID_COMMERCIAL.into(),
-            tag: Self::TAG_COMMERCIAL.into(),
-        }
+        Self::new_built_in(Self::ID_COMMERCIAL, Self::TAG_COMMERCIAL, "Commercial")
     }
 
     pub fn new_event() -> Self {
-        Self {
-            id: Self::ID_EVENT.into(),
-            tag: Self::TAG_EVENT.into(),
-        }
+        Self::new_built_in(Self::ID_EVENT, Self::TAG_EVENT, "Event")
+    }
+
+    /// The categories that have existed before categories
+    /// became configurable. Their ids and tags never change.
+    pub fn built_in() -> Vec<Self> {
+        vec![
+            Self::new_non_profit(),
+            Self::new_commercial(),
+            Self::new_event(),
+        ]
     }
 
+    pub fn is_built_in(id: &str) -> bool {
+        id == Self::ID_NON_PROFIT || id == Self::ID_COMMERCIAL || id == Self::ID_EVENT
+    }
+
+    /// Only recognizes the built-in categories.
     pub fn split_from_tags(tags: Vec<String>) -> (Vec<String>, Vec<Category>) {
+        Self::split_from_tags_with(&Self::built_in(), tags)
+    }
+
+    pub fn split_from_tags_with(
+        all_categories: &[Category],
+        tags: Vec<String>,
+    ) -> (Vec<String>, Vec<Category>) {
         let mut categories = Vec::with_capacity(3);
         let tags = tags
             .into_iter()
-            .filter(|t| match t.as_str() {
-                Self::TAG_NON_PROFIT => {
-                    categories.push(Self::new_non_profit());
-                    false
-                }
-                Self::TAG_COMMERCIAL => {
-                    categories.push(Self::new_commercial());
-                    false
-                }
-                Self::TAG_EVENT => {
-                    categories.push(Self::new_event());
+            .filter(|t| match all_categories.iter().find(|c| &c.tag == t) {
+                Some(category) => {
+                    categories.push(category.clone());
                     false
                 }
-                _ => true,
+                None => true,
             })
             .collect();
         (tags, categories)
     }
 
-    pub fn merge_ids_into_tags(ids: &[Id], mut tags: Vec<String>) -> Vec<String> {
+    /// Only recognizes the built-in categories.
+    pub fn merge_ids_into_tags(ids: &[Id], tags: Vec<String>) -> Vec<String> {
+        Self::merge_ids_into_tags_with(&Self::built_in(), ids, tags)
+    }
+
+    pub fn merge_ids_into_tags_with(
+        all_categories: &[Category],
+        ids: &[Id],
+        mut tags: Vec<String>,
+    ) -> Vec<String> {
         tags.reserve(ids.len());
         tags = ids.iter().fold(tags, |mut tags, id| {
-            match id.as_ref() {
-                Self::ID_NON_PROFIT => tags.push(Self::TAG_NON_PROFIT.into()),
-                Self::ID_COMMERCIAL => tags.push(Self::TAG_COMMERCIAL.into()),
-                Self::ID_EVENT => tags.push(Self::TAG_EVENT.into()),
-                _ => (),
+            if let Some(category) = all_categories.iter().find(|c| &c.id == id) {
+                tags.push(category.tag.clone());
             }
             tags
         });
@@ -1,4 +1,4 @@
-use crate::{id::Id, revision::Revision, time::TimestampMs};
+use crate::{event::Event, id::Id, revision::Revision, time::TimestampMs};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PendingClearanceForPlace {
@@ -12,3 +12,33 @@ pub struct ClearanceForPlace {
     pub place_id: Id,
     pub cleared_revision: Option<Revision>,
 }
+
+/// Events are not versioned. Instead the last cleared state
+/// of an event is stored together with the pending clearance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingClearanceForEvent {
+    pub event_id: Id,
+    pub created_at: TimestampMs,
+    pub last_cleared_event: Option<Event>,
+}
+
+/// Clearing an event accepts its current state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClearanceForEvent {
+    pub event_id: Id,
+}
+
+/// Rejecting a pending clearance records the reason and
+/// notifies the editor of the rejected revision.
+///
+/// If `revert` is set the organization permanently keeps the
+/// last cleared revision, i.e. the pending clearance is resolved
+/// without ever accepting the rejected revision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectionForPlace {
+    pub place_id: Id,
+    /// The rejected revision or None for the current revision
+    pub rejected_revision: Option<Revision>,
+    pub reason: String,
+    pub revert: bool,
+}
@@ -1,4 +1,13 @@
-use crate::{id::*, time::*};
+use crate::{email::*, id::*, time::*};
+
+/// Who wrote a comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommentAuthor {
+    /// A registered user
+    User(Email),
+    /// An organization identified by its API token
+    Organization(Id),
+}
 
 #[rustfmt::skip]
 #[derive(Debug, Clone, PartialEq)]
@@ -9,4 +18,10 @@ pub struct Comment {
     pub created_at  : Timestamp,
     pub archived_at : Option<Timestamp>,
     pub text        : String,
+    // The author of older or anonymous comments is unknown
+    pub author      : Option<CommentAuthor>,
+    // The comment this comment replies to (same rating)
+    pub reply_to    : Option<Id>,
+    // An official response by the owner of the place
+    pub is_response : bool,
 }
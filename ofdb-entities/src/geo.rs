@@ -531,6 +531,21 @@ impl MapBbox {
         self.ne
     }
 
+    pub fn center(&self) -> MapPoint {
+        let lat_deg = (self.sw.lat().to_deg() + self.ne.lat().to_deg()) / 2.0;
+        let sw_lng_deg = self.sw.lng().to_deg();
+        let mut ne_lng_deg = self.ne.lng().to_deg();
+        if ne_lng_deg < sw_lng_deg {
+            // inverse, i.e. crossing the antimeridian
+            ne_lng_deg += 360.0;
+        }
+        let mut lng_deg = (sw_lng_deg + ne_lng_deg) / 2.0;
+        if lng_deg > 180.0 {
+            lng_deg -= 360.0;
+        }
+        MapPoint::from_lat_lng_deg(lat_deg, lng_deg)
+    }
+
     pub fn is_valid(&self) -> bool {
         self.sw.is_valid() && self.ne.is_valid() && self.sw.lat() <= self.ne.lat()
     }
@@ -720,6 +735,24 @@ mod tests {
         assert!(MapPoint::distance(p1, p2).unwrap().to_meters() >= 0.0);
     }
 
+    #[test]
+    fn bbox_center() {
+        let bbox = MapBbox::new(
+            MapPoint::from_lat_lng_deg(-25.0, -20.0),
+            MapPoint::from_lat_lng_deg(25.0, 30.0),
+        );
+        let center = bbox.center();
+        assert!((center.lat().to_deg() - 0.0).abs() < 1e-6);
+        assert!((center.lng().to_deg() - 5.0).abs() < 1e-6);
+        let bbox = MapBbox::new(
+            MapPoint::from_lat_lng_deg(10.0, 170.0),
+            MapPoint::from_lat_lng_deg(20.0, -160.0),
+        );
+        let center = bbox.center();
+        assert!((center.lat().to_deg() - 15.0).abs() < 1e-6);
+        assert!((center.lng().to_deg() - -175.0).abs() < 1e-6);
+    }
+
     #[test]
     fn bbox_contains_point() {
         let sw = MapPoint::from_lat_lng_deg(-25.0, -20.0);
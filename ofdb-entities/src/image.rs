@@ -0,0 +1,92 @@
+use crate::{activity::*, id::*};
+use strum_macros::{Display, EnumString};
+
+/// The encoding of stored images.
+///
+/// Uploaded images are always re-encoded into one
+/// of these formats.
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum ImageFormat {
+    #[strum(serialize = "jpeg")]
+    Jpeg,
+    #[strum(serialize = "png")]
+    Png,
+}
+
+impl ImageFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+        }
+    }
+
+    pub fn file_extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+        }
+    }
+}
+
+/// The stored sizes of an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageVariant {
+    /// The uploaded image, scaled down if it exceeds
+    /// the maximum size
+    Original,
+    Thumbnail,
+}
+
+/// An image that has been uploaded and is
+/// hosted by OpenFairDB.
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub id          : Id,
+    pub format      : ImageFormat,
+    /// Width of the original variant in pixels
+    pub width       : u32,
+    /// Height of the original variant in pixels
+    pub height      : u32,
+    pub license     : String,
+    /// Author or source of the image as it should
+    /// be credited when displayed
+    pub attribution : Option<String>,
+    pub uploaded    : Activity,
+}
+
+impl Image {
+    /// The key of a variant in the image storage.
+    pub fn storage_key(&self, variant: ImageVariant) -> String {
+        let suffix = match variant {
+            ImageVariant::Original => "",
+            ImageVariant::Thumbnail => "_thumbnail",
+        };
+        format!("{}{}.{}", self.id, suffix, self.format.file_extension())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_keys_of_variants() {
+        let image = Image {
+            id: "foo".into(),
+            format: ImageFormat::Png,
+            width: 1,
+            height: 1,
+            license: "CC0-1.0".into(),
+            attribution: None,
+            uploaded: Activity::now(None),
+        };
+        assert_eq!("foo.png", image.storage_key(ImageVariant::Original));
+        assert_eq!(
+            "foo_thumbnail.png",
+            image.storage_key(ImageVariant::Thumbnail)
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Languages of texts that are analyzed specifically
+/// by the full-text search, e.g. with stemming.
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, EnumIter, Display)]
+pub enum Language {
+    #[strum(serialize = "de")]
+    German,
+    #[strum(serialize = "en")]
+    English,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_language() {
+        assert_eq!(Ok(Language::German), "de".parse());
+        assert_eq!("en", Language::English.to_string());
+        assert!("fr".parse::<Language>().is_err());
+    }
+}
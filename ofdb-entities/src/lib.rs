@@ -11,6 +11,8 @@
 
 pub mod activity;
 pub mod address;
+pub mod audit;
+pub mod captcha;
 pub mod category;
 pub mod clearance;
 pub mod comment;
@@ -19,16 +21,21 @@ pub mod email;
 pub mod event;
 pub mod geo;
 pub mod id;
+pub mod image;
+pub mod language;
+pub mod link_check;
 pub mod links;
 pub mod location;
 pub mod nonce;
 pub mod organization;
+pub mod ownership;
 pub mod password;
 pub mod place;
 pub mod rating;
 pub mod review;
 pub mod revision;
 pub mod subscription;
+pub mod suggestion;
 pub mod tag;
 pub mod time;
 pub mod user;
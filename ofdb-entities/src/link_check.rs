@@ -0,0 +1,77 @@
+use crate::{id::*, time::*, url::Url};
+use strum_macros::{Display, EnumString};
+
+/// The result of requesting a link.
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum LinkStatus {
+    #[strum(serialize = "ok")]
+    Ok,
+    /// The link still works but should be replaced
+    /// by the redirect target
+    #[strum(serialize = "redirect")]
+    Redirect,
+    /// The server responded with an error status
+    #[strum(serialize = "broken")]
+    Broken,
+    /// No response at all, e.g. an unknown host or a timeout
+    #[strum(serialize = "unreachable")]
+    Unreachable,
+}
+
+impl LinkStatus {
+    pub fn is_dead(self) -> bool {
+        matches!(self, Self::Broken | Self::Unreachable)
+    }
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+pub enum LinkOwnerKind {
+    #[strum(serialize = "place")]
+    Place,
+    #[strum(serialize = "event")]
+    Event,
+}
+
+/// The place or event that contains a link.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LinkOwner {
+    pub kind: LinkOwnerKind,
+    pub id: Id,
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkCheckOutcome {
+    pub status       : LinkStatus,
+    /// Missing if the server could not be reached
+    pub http_status  : Option<u16>,
+    /// The `Location` header of a redirect response
+    pub redirect_url : Option<String>,
+}
+
+/// The last check of a link that is contained
+/// in a place or event.
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkCheck {
+    pub owner      : LinkOwner,
+    pub url        : Url,
+    pub outcome    : LinkCheckOutcome,
+    pub checked_at : TimestampMs,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_link_status() {
+        assert_eq!(Ok(LinkStatus::Unreachable), "unreachable".parse());
+        assert_eq!("redirect", LinkStatus::Redirect.to_string());
+        assert!("dead".parse::<LinkStatus>().is_err());
+        assert!(LinkStatus::Broken.is_dead());
+        assert!(!LinkStatus::Redirect.is_dead());
+    }
+}
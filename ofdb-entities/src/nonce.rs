@@ -2,7 +2,7 @@ use crate::time::*;
 use std::{fmt, ops::Deref, str::FromStr};
 use uuid::Uuid;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Nonce(Uuid);
 
 impl Nonce {
@@ -116,6 +116,12 @@ pub struct UserToken {
     pub email_nonce: EmailNonce,
     // TODO: Convert time stamps from second to millisecond precision?
     pub expires_at: Timestamp,
+    // Only set for tokens that confirm a pending change
+    // of the user's e-mail address
+    pub new_email: Option<String>,
+    // Only set for tokens that confirm the claimed
+    // ownership of a place
+    pub place_id: Option<String>,
 }
 
 #[cfg(test)]
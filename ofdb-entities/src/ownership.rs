@@ -0,0 +1,63 @@
+use crate::{email::*, id::*, place::*, time::*};
+use strum_macros::{Display, EnumString};
+
+/// Fields of a place that could be locked by its owner.
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum PlaceField {
+    #[strum(serialize = "title")]
+    Title,
+    #[strum(serialize = "description")]
+    Description,
+    #[strum(serialize = "location")]
+    Location,
+    #[strum(serialize = "contact")]
+    Contact,
+    #[strum(serialize = "opening_hours")]
+    OpeningHours,
+    #[strum(serialize = "founded_on")]
+    FoundedOn,
+    #[strum(serialize = "links")]
+    Links,
+    #[strum(serialize = "tags")]
+    Tags,
+}
+
+impl PlaceField {
+    pub fn is_modified(self, old: &Place, new: &Place) -> bool {
+        match self {
+            Self::Title => old.title != new.title,
+            Self::Description => old.description != new.description,
+            Self::Location => old.location != new.location,
+            Self::Contact => old.contact != new.contact,
+            Self::OpeningHours => old.opening_hours != new.opening_hours,
+            Self::FoundedOn => old.founded_on != new.founded_on,
+            Self::Links => old.links != new.links,
+            Self::Tags => old.tags != new.tags,
+        }
+    }
+}
+
+/// A user who has verified to own a place by confirming
+/// a link that has been sent to the contact of the place.
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceOwnership {
+    pub place_id      : Id,
+    pub owner         : Email,
+    pub created_at    : TimestampMs,
+    /// Only the owner is allowed to modify these fields
+    pub locked_fields : Vec<PlaceField>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_place_field() {
+        assert_eq!(Ok(PlaceField::OpeningHours), "opening_hours".parse());
+        assert_eq!("founded_on", PlaceField::FoundedOn.to_string());
+        assert!("unknown".parse::<PlaceField>().is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{id::*, time::*};
+use crate::{activity::*, email::*, id::*, time::*};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum RatingContext {
@@ -221,6 +221,15 @@ impl std::ops::AddAssign<(RatingContext, RatingValue)> for AvgRatingsBuilder {
     }
 }
 
+/// Who created a rating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RatingAuthor {
+    /// A registered user
+    User(Email),
+    /// An anonymous visitor who has solved a captcha
+    AnonymousSession(String),
+}
+
 #[rustfmt::skip]
 #[derive(Debug, Clone, PartialEq)]
 pub struct Rating {
@@ -233,4 +242,15 @@ pub struct Rating {
     pub value       : RatingValue,
     pub context     : RatingContext,
     pub source      : Option<String>,
+    // The author of older ratings is unknown
+    pub author      : Option<RatingAuthor>,
+}
+
+/// A rating that has been reported for review by scouts.
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlaggedRating {
+    pub rating : Rating,
+    // The comment of each flag contains the reason, most recent first
+    pub flags  : Vec<ActivityLog>,
 }
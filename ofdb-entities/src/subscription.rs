@@ -1,4 +1,4 @@
-use crate::{geo::*, id::*};
+use crate::{geo::*, id::*, time::*};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct BboxSubscription {
@@ -6,3 +6,20 @@ pub struct BboxSubscription {
     pub user_email: String,
     pub bbox: MapBbox,
 }
+
+/// A search that has been saved by a user under a name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedSearch {
+    pub id: Id,
+    pub user_email: String,
+    pub name: String,
+    pub bbox: MapBbox,
+    // Category ids
+    pub categories: Vec<String>,
+    pub hash_tags: Vec<String>,
+    pub text: Option<String>,
+    pub org_tag: Option<String>,
+    // Notify the user when places or events start matching
+    pub alert: bool,
+    pub created_at: TimestampMs,
+}
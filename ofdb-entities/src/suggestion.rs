@@ -0,0 +1,46 @@
+use crate::{activity::*, id::*, place::*, revision::*};
+use strum_macros::{Display, EnumString};
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum SuggestionStatus {
+    #[strum(serialize = "pending")]
+    Pending,
+    #[strum(serialize = "accepted")]
+    Accepted,
+    #[strum(serialize = "declined")]
+    Declined,
+}
+
+/// A modification of a place that has been filed by a user
+/// who is not permitted to edit the place directly, e.g.
+/// because of moderated tags.
+///
+/// The suggestion becomes a new revision of the place after
+/// it has been accepted.
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceEditSuggestion {
+    pub id            : Id,
+    /// The current revision of the place when the
+    /// suggestion has been filed
+    pub base_revision : Revision,
+    /// The suggested content of the place including
+    /// the submitter and the time of submission
+    pub suggested     : Place,
+    pub status        : SuggestionStatus,
+    /// Who accepted or declined the suggestion and why
+    pub reviewed      : Option<ActivityLog>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_suggestion_status() {
+        assert_eq!(Ok(SuggestionStatus::Declined), "declined".parse());
+        assert_eq!("pending", SuggestionStatus::Pending.to_string());
+        assert!("unknown".parse::<SuggestionStatus>().is_err());
+    }
+}
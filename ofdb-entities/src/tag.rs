@@ -7,3 +7,20 @@ pub type TagCount = u64;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TagFrequency(pub String, pub TagCount);
+
+/// An alternative label of a tag, e.g. a different spelling
+/// or translation. Searching for either of them finds both.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TagSynonym {
+    pub synonym: String,
+    pub tag: String,
+}
+
+/// A curated tag of the taxonomy. Tags without a parent
+/// are the top-level themes, e.g. "food" with the children
+/// "organic-shop" and "food-coop".
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TaxonomyTag {
+    pub tag: String,
+    pub parent: Option<String>,
+}
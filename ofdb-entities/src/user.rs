@@ -1,5 +1,6 @@
-use crate::password::Password;
+use crate::{password::Password, time::TimestampMs};
 use num_derive::{FromPrimitive, ToPrimitive};
+use strum_macros::{Display, EnumIter, EnumString};
 
 #[rustfmt::skip]
 #[derive(Debug, Clone, PartialEq)]
@@ -11,7 +12,7 @@ pub struct User {
 }
 
 #[rustfmt::skip]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, ToPrimitive)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, FromPrimitive, ToPrimitive)]
 pub enum Role {
     Guest = 0,
     User  = 1,
@@ -24,3 +25,55 @@ impl Default for Role {
         Role::Guest
     }
 }
+
+/// Named permissions that are granted to roles.
+#[rustfmt::skip]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter, EnumString, Display)]
+pub enum Permission {
+    #[strum(serialize = "place.review")]
+    PlaceReview,
+    #[strum(serialize = "place.archive")]
+    PlaceArchive,
+    #[strum(serialize = "place.read_history")]
+    PlaceReadHistory,
+    #[strum(serialize = "place.export")]
+    PlaceExport,
+    #[strum(serialize = "event.archive")]
+    EventArchive,
+    #[strum(serialize = "event.export")]
+    EventExport,
+    #[strum(serialize = "rating.archive")]
+    RatingArchive,
+    #[strum(serialize = "comment.archive")]
+    CommentArchive,
+    #[strum(serialize = "user.change_role")]
+    UserChangeRole,
+    #[strum(serialize = "user.anonymize")]
+    UserAnonymize,
+    #[strum(serialize = "audit.read")]
+    AuditRead,
+    #[strum(serialize = "link.report")]
+    LinkReport,
+    #[strum(serialize = "stats.read")]
+    StatsRead,
+    #[strum(serialize = "tag.manage")]
+    TagManage,
+    #[strum(serialize = "category.manage")]
+    CategoryManage,
+    #[strum(serialize = "author.read")]
+    AuthorRead,
+}
+
+/// Report about a deleted user whose authored
+/// activities have been kept under a pseudonym.
+#[rustfmt::skip]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnonymizedUser {
+    pub pseudonym             : String,
+    pub anonymized_at         : TimestampMs,
+    pub place_revisions       : u64,
+    pub place_reviews         : u64,
+    pub place_ratings         : u64,
+    pub place_rating_comments : u64,
+    pub events                : u64,
+}
@@ -0,0 +1,71 @@
+use ofdb_core::gateways::image::{ImageStorageError, ImageStorageGateway};
+use std::{fs, io, path::PathBuf};
+
+/// Stores images as files in a local directory.
+#[derive(Debug, Clone)]
+pub struct FileImageStorage {
+    dir: PathBuf,
+}
+
+impl FileImageStorage {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, ImageStorageError> {
+        // Keys must never escape the storage directory
+        if key.is_empty() || key.contains(|c| c == '/' || c == '\\') || key.starts_with('.') {
+            return Err(ImageStorageError::Other(format!("Invalid key '{}'", key)));
+        }
+        Ok(self.dir.join(key))
+    }
+}
+
+impl ImageStorageGateway for FileImageStorage {
+    fn store(&self, key: &str, _content_type: &str, data: &[u8]) -> Result<(), ImageStorageError> {
+        let path = self.path(key)?;
+        fs::create_dir_all(&self.dir)?;
+        // Write into a temporary file first to prevent
+        // that partially written images are served
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, data)?;
+        fs::rename(&tmp_path, &path)?;
+        debug!("Stored image {}", path.display());
+        Ok(())
+    }
+
+    fn load(&self, key: &str) -> Result<Option<Vec<u8>>, ImageStorageError> {
+        match fs::read(self.path(key)?) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn delete(&self, key: &str) -> Result<(), ImageStorageError> {
+        match fs::remove_file(self.path(key)?) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_and_load_image() {
+        let dir = std::env::temp_dir().join(format!("ofdb-images-{}", std::process::id()));
+        let storage = FileImageStorage::new(&dir);
+        assert!(storage.load("foo.png").unwrap().is_none());
+        storage.store("foo.png", "image/png", &[1, 2, 3]).unwrap();
+        assert_eq!(Some(vec![1, 2, 3]), storage.load("foo.png").unwrap());
+        assert!(storage.store("../foo.png", "image/png", &[1]).is_err());
+        storage.delete("foo.png").unwrap();
+        assert!(storage.load("foo.png").unwrap().is_none());
+        assert!(storage.delete("foo.png").is_ok());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
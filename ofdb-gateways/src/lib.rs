@@ -1,8 +1,11 @@
 #[macro_use]
 extern crate log;
 
+pub mod file_storage;
+pub mod link_checker;
 pub mod mailgun;
 pub mod notify;
 pub mod opencage;
+pub mod s3;
 pub mod sendmail;
 pub mod user_communication;
@@ -0,0 +1,352 @@
+use ofdb_core::gateways::link_check::LinkCheckGateway;
+use ofdb_entities::{link_check::*, url::Url};
+use reqwest::{
+    blocking::{Client, ClientBuilder, Response},
+    header::LOCATION,
+    redirect::Policy,
+    StatusCode,
+};
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+const USER_AGENT: &str = "OpenFairDB link checker";
+
+/// Checks links by sending HTTP requests.
+///
+/// Links to hosts that don't resolve to public addresses
+/// are not requested to prevent probing internal services.
+/// The requests are sent to the checked address without
+/// resolving the host again, i.e. the host cannot switch
+/// to a private address in the meantime (DNS rebinding).
+/// Redirects are reported but never followed.
+#[derive(Debug, Clone)]
+pub struct HttpLinkChecker {
+    timeout: Duration,
+    allow_private_hosts: bool,
+}
+
+impl HttpLinkChecker {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            allow_private_hosts: false,
+        }
+    }
+
+    fn client_builder(&self) -> ClientBuilder {
+        Client::builder()
+            .timeout(self.timeout)
+            .redirect(Policy::none())
+            .user_agent(USER_AGENT)
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // 0.0.0.0/8
+        || a == 0
+        // Shared address space 100.64.0.0/10
+        || (a == 100 && (b & 0b1100_0000) == 64)
+        // IETF protocol assignments 192.0.0.0/24
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking 198.18.0.0/15
+        || (a == 198 && (b & 0xfe) == 18)
+        // Reserved 240.0.0.0/4
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(ipv4) = ip.to_ipv4() {
+        // IPv4-mapped and IPv4-compatible addresses
+        // including the loopback address ::1
+        return is_public_ipv4(ipv4);
+    }
+    let segments = ip.segments();
+    !(ip.is_multicast()
+        // Unique local fc00::/7
+        || (segments[0] & 0xfe00) == 0xfc00
+        // Link-local fe80::/10
+        || (segments[0] & 0xffc0) == 0xfe80
+        // Documentation 2001:db8::/32
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8))
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+// Resolves the host of the URL to the address that should be
+// connected to. All resolved addresses must be public.
+fn resolve_public_addr(url: &reqwest::Url) -> Option<SocketAddr> {
+    match url.socket_addrs(|| None) {
+        Ok(addrs) => {
+            if addrs.iter().all(|addr| is_public_ip(addr.ip())) {
+                addrs.first().copied()
+            } else {
+                None
+            }
+        }
+        Err(err) => {
+            debug!("Failed to resolve the host of {}: {}", url, err);
+            None
+        }
+    }
+}
+
+fn unreachable_outcome() -> LinkCheckOutcome {
+    LinkCheckOutcome {
+        status: LinkStatus::Unreachable,
+        http_status: None,
+        redirect_url: None,
+    }
+}
+
+fn outcome_of_response(res: &Response) -> LinkCheckOutcome {
+    let status = res.status();
+    let http_status = Some(status.as_u16());
+    if status.is_redirection() {
+        let redirect_url = res
+            .headers()
+            .get(LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .map(|location| {
+                // Resolve relative redirect targets
+                res.url()
+                    .join(location)
+                    .map(|url| url.into_string())
+                    .unwrap_or_else(|_| location.to_owned())
+            });
+        return LinkCheckOutcome {
+            status: LinkStatus::Redirect,
+            http_status,
+            redirect_url,
+        };
+    }
+    LinkCheckOutcome {
+        status: if status.is_success() {
+            LinkStatus::Ok
+        } else {
+            LinkStatus::Broken
+        },
+        http_status,
+        redirect_url: None,
+    }
+}
+
+impl LinkCheckGateway for HttpLinkChecker {
+    fn check_link(&self, url: &Url) -> LinkCheckOutcome {
+        let url = match url.as_str().parse::<reqwest::Url>() {
+            Ok(url) => url,
+            Err(err) => {
+                debug!("Invalid URL {}: {}", url.as_str(), err);
+                return unreachable_outcome();
+            }
+        };
+        let mut client_builder = self.client_builder();
+        if !self.allow_private_hosts {
+            let addr = match resolve_public_addr(&url) {
+                Some(addr) => addr,
+                None => {
+                    debug!("Refused to check a link to a non-public host: {}", url);
+                    return unreachable_outcome();
+                }
+            };
+            // Connect to the checked address instead of
+            // resolving the host again when sending requests
+            if let Some(domain) = url.domain() {
+                client_builder = client_builder.resolve(domain, addr);
+            }
+        }
+        let client = match client_builder.build() {
+            Ok(client) => client,
+            Err(err) => {
+                warn!("Failed to create HTTP client: {}", err);
+                return unreachable_outcome();
+            }
+        };
+        // Try a cheap HEAD request first and fall back to GET
+        // for servers that don't support HEAD requests
+        let res = client
+            .head(url.clone())
+            .send()
+            .and_then(|res| match res.status() {
+                StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED => {
+                    client.get(url.clone()).send()
+                }
+                _ => Ok(res),
+            });
+        match res {
+            Ok(res) => outcome_of_response(&res),
+            Err(err) => {
+                debug!("Failed to request {}: {}", url, err);
+                unreachable_outcome()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        collections::HashMap,
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// A minimal HTTP server that responds to requests
+    /// with the configured status line and headers of the
+    /// requested path. Unknown paths are answered with 404.
+    fn serve(responses: HashMap<&'static str, &'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                // Skip the request headers
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                let mut parts = request_line.split_whitespace();
+                let method = parts.next().unwrap_or_default();
+                let path = parts.next().unwrap_or_default();
+                let response = responses
+                    .get(format!("{} {}", method, path).as_str())
+                    .or_else(|| responses.get(path))
+                    .copied()
+                    .unwrap_or("404 Not Found");
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    response
+                )
+                .unwrap();
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    fn local_checker() -> HttpLinkChecker {
+        HttpLinkChecker {
+            allow_private_hosts: true,
+            ..HttpLinkChecker::new(Duration::from_secs(5))
+        }
+    }
+
+    fn check(checker: &HttpLinkChecker, url: String) -> LinkCheckOutcome {
+        checker.check_link(&url.parse().unwrap())
+    }
+
+    #[test]
+    fn check_links_of_local_server() {
+        let mut responses = HashMap::new();
+        responses.insert("/ok", "200 OK");
+        responses.insert("/moved", "301 Moved Permanently\r\nLocation: /ok");
+        responses.insert("/error", "500 Internal Server Error");
+        responses.insert("HEAD /get-only", "405 Method Not Allowed");
+        responses.insert("GET /get-only", "200 OK");
+        let base_url = serve(responses);
+        let checker = local_checker();
+
+        let outcome = check(&checker, format!("{}/ok", base_url));
+        assert_eq!(LinkStatus::Ok, outcome.status);
+        assert_eq!(Some(200), outcome.http_status);
+
+        let outcome = check(&checker, format!("{}/moved", base_url));
+        assert_eq!(LinkStatus::Redirect, outcome.status);
+        assert_eq!(Some(301), outcome.http_status);
+        assert_eq!(Some(format!("{}/ok", base_url)), outcome.redirect_url);
+
+        let outcome = check(&checker, format!("{}/missing", base_url));
+        assert_eq!(LinkStatus::Broken, outcome.status);
+        assert_eq!(Some(404), outcome.http_status);
+
+        let outcome = check(&checker, format!("{}/error", base_url));
+        assert_eq!(LinkStatus::Broken, outcome.status);
+
+        let outcome = check(&checker, format!("{}/get-only", base_url));
+        assert_eq!(LinkStatus::Ok, outcome.status);
+    }
+
+    #[test]
+    fn check_unreachable_link() {
+        // Bind and immediately release a port to get
+        // an address without a listening server
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let checker = local_checker();
+        let outcome = check(&checker, format!("http://{}/", addr));
+        assert_eq!(LinkStatus::Unreachable, outcome.status);
+        assert_eq!(None, outcome.http_status);
+    }
+
+    #[test]
+    fn refuse_to_check_links_to_private_hosts() {
+        let mut responses = HashMap::new();
+        responses.insert("/ok", "200 OK");
+        let base_url = serve(responses);
+        let checker = HttpLinkChecker::new(Duration::from_secs(5));
+        let outcome = check(&checker, format!("{}/ok", base_url));
+        assert_eq!(LinkStatus::Unreachable, outcome.status);
+        assert_eq!(None, outcome.http_status);
+        let outcome = check(&checker, "http://localhost/".to_string());
+        assert_eq!(LinkStatus::Unreachable, outcome.status);
+    }
+
+    #[test]
+    fn resolve_only_public_addresses() {
+        let resolve = |url: &str| resolve_public_addr(&url.parse().unwrap());
+        assert_eq!(
+            Some("1.1.1.1:80".parse().unwrap()),
+            resolve("http://1.1.1.1/")
+        );
+        assert_eq!(
+            Some("[2606:4700:4700::1111]:8443".parse().unwrap()),
+            resolve("https://[2606:4700:4700::1111]:8443/")
+        );
+        assert_eq!(None, resolve("http://127.0.0.1/"));
+        assert_eq!(None, resolve("http://localhost/"));
+    }
+
+    #[test]
+    fn public_ip_addresses() {
+        for ip in &["1.1.1.1", "93.184.216.34", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in &[
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "::ffff:127.0.0.1",
+            "fd00::1",
+            "fe80::1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+}
@@ -25,13 +25,9 @@ impl NotificationGateway for Notify {
         all_categories: Vec<Category>,
     ) {
         let mut place = place.clone();
-        let (tags, categories) = Category::split_from_tags(place.tags);
+        let (tags, categories) = Category::split_from_tags_with(&all_categories, place.tags);
         place.tags = tags;
-        let category_names: Vec<String> = all_categories
-            .into_iter()
-            .filter(|c1| categories.iter().any(|c2| c1.id == c2.id))
-            .map(|c| c.name())
-            .collect();
+        let category_names: Vec<String> = categories.into_iter().map(|c| c.name()).collect();
         let content = user_communication::place_created_email(&place, &category_names);
 
         {
@@ -55,13 +51,9 @@ impl NotificationGateway for Notify {
         all_categories: Vec<Category>,
     ) {
         let mut place = place.clone();
-        let (tags, categories) = Category::split_from_tags(place.tags);
+        let (tags, categories) = Category::split_from_tags_with(&all_categories, place.tags);
         place.tags = tags;
-        let category_names: Vec<String> = all_categories
-            .into_iter()
-            .filter(|c1| categories.iter().any(|c2| c1.id == c2.id))
-            .map(|c| c.name())
-            .collect();
+        let category_names: Vec<String> = categories.into_iter().map(|c| c.name()).collect();
         let content = user_communication::place_updated_email(&place, &category_names);
 
         {
@@ -112,6 +104,52 @@ impl NotificationGateway for Notify {
             );
         }
     }
+    fn place_matches_saved_search(
+        &self,
+        email: &str,
+        search_name: &str,
+        place: &Place,
+        all_categories: Vec<Category>,
+    ) {
+        let mut place = place.clone();
+        let (tags, categories) = Category::split_from_tags_with(&all_categories, place.tags);
+        place.tags = tags;
+        let category_names: Vec<String> = categories.into_iter().map(|c| c.name()).collect();
+        let content = user_communication::place_matches_saved_search_email(
+            search_name,
+            &place,
+            &category_names,
+        );
+
+        {
+            info!(
+                "Sending e-mail to {} after place {} matches saved search '{}'",
+                email, place.id, search_name
+            );
+            compose_and_send_emails(
+                &*self.email_gw,
+                &[email.to_owned()],
+                &content.subject,
+                &content.body,
+            );
+        }
+    }
+    fn event_matches_saved_search(&self, email: &str, search_name: &str, event: &Event) {
+        let content = user_communication::event_matches_saved_search_email(search_name, event);
+
+        {
+            info!(
+                "Sending e-mail to {} after event {} matches saved search '{}'",
+                email, event.id, search_name
+            );
+            compose_and_send_emails(
+                &*self.email_gw,
+                &[email.to_owned()],
+                &content.subject,
+                &content.body,
+            );
+        }
+    }
     fn user_registered_kvm(&self, user: &User) {
         let token = EmailNonce {
             email: user.email.clone(),
@@ -163,6 +201,84 @@ impl NotificationGateway for Notify {
             );
         }
     }
+    fn user_email_change_requested(&self, email_nonce: &EmailNonce, new_email: &str) {
+        let url = format!(
+            "https://openfairdb.org/change-email/confirm/{}",
+            email_nonce.encode_to_string()
+        );
+        let content = user_communication::user_change_email_email(&url);
+
+        {
+            info!(
+                "Sending e-mail to {} after change of e-mail requested by {}",
+                new_email, email_nonce.email
+            );
+            compose_and_send_emails(
+                &*self.email_gw,
+                &[new_email.to_owned()],
+                &content.subject,
+                &content.body,
+            );
+        }
+    }
+    fn user_email_changed(&self, old_email: &str, new_email: &str) {
+        let content = user_communication::user_email_changed_email(new_email);
+
+        {
+            info!(
+                "Sending e-mail to {} after e-mail changed into {}",
+                old_email, new_email
+            );
+            compose_and_send_emails(
+                &*self.email_gw,
+                &[old_email.to_owned()],
+                &content.subject,
+                &content.body,
+            );
+        }
+    }
+    fn place_clearance_rejected(&self, email: &str, place: &Place, org_name: &str, reason: &str) {
+        let content = user_communication::place_clearance_rejected_email(place, org_name, reason);
+
+        {
+            info!(
+                "Sending e-mail to {} after clearance of place {} has been rejected by '{}'",
+                email, place.id, org_name
+            );
+            compose_and_send_emails(
+                &*self.email_gw,
+                &[email.to_owned()],
+                &content.subject,
+                &content.body,
+            );
+        }
+    }
+    fn place_ownership_requested(
+        &self,
+        email_nonce: &EmailNonce,
+        contact_email: &str,
+        place: &Place,
+    ) {
+        let url = format!(
+            "https://openfairdb.org/places/claim/confirm/{}",
+            email_nonce.encode_to_string()
+        );
+        let content =
+            user_communication::place_ownership_requested_email(&url, place, &email_nonce.email);
+
+        {
+            info!(
+                "Sending e-mail to {} after ownership of place {} has been requested by {}",
+                contact_email, place.id, email_nonce.email
+            );
+            compose_and_send_emails(
+                &*self.email_gw,
+                &[contact_email.to_owned()],
+                &content.subject,
+                &content.body,
+            );
+        }
+    }
 }
 
 fn compose_and_send_emails(
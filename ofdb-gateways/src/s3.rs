@@ -0,0 +1,145 @@
+use ofdb_core::gateways::image::{ImageStorageError, ImageStorageGateway};
+use s3::{bucket::Bucket, creds::Credentials, region::Region};
+
+/// Stores images in a bucket of an S3-compatible
+/// object storage, e.g. AWS S3 or MinIO.
+#[derive(Debug, Clone)]
+pub struct S3ImageStorage {
+    bucket: Bucket,
+}
+
+#[derive(Debug, Clone)]
+pub struct S3Config {
+    /// The URL of the storage service, e.g. `http://localhost:9000`
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+impl S3ImageStorage {
+    pub fn new(config: S3Config) -> Result<Self, ImageStorageError> {
+        let S3Config {
+            endpoint,
+            region,
+            bucket,
+            access_key,
+            secret_key,
+        } = config;
+        if !(endpoint.starts_with("http://") || endpoint.starts_with("https://")) {
+            return Err(ImageStorageError::Other(format!(
+                "Invalid S3 endpoint '{}'",
+                endpoint
+            )));
+        }
+        if bucket.trim().is_empty() {
+            return Err(ImageStorageError::Other("Missing S3 bucket name".into()));
+        }
+        let credentials = Credentials::new(Some(&access_key), Some(&secret_key), None, None, None)
+            .map_err(other_err)?;
+        let region = Region::Custom { region, endpoint };
+        // Path style URLs are required by MinIO and work with AWS as well
+        let bucket =
+            Bucket::new_with_path_style(&bucket, region, credentials).map_err(other_err)?;
+        Ok(Self { bucket })
+    }
+}
+
+fn other_err<E: ToString>(err: E) -> ImageStorageError {
+    ImageStorageError::Other(err.to_string())
+}
+
+impl ImageStorageGateway for S3ImageStorage {
+    fn store(&self, key: &str, content_type: &str, data: &[u8]) -> Result<(), ImageStorageError> {
+        let (_, status) = self
+            .bucket
+            .put_object_with_content_type(key, data, content_type)
+            .map_err(other_err)?;
+        if status != 200 {
+            return Err(ImageStorageError::Other(format!(
+                "Could not store image '{}': response status: {}",
+                key, status
+            )));
+        }
+        debug!("Stored image {} in bucket {}", key, self.bucket.name);
+        Ok(())
+    }
+
+    fn load(&self, key: &str) -> Result<Option<Vec<u8>>, ImageStorageError> {
+        let (data, status) = self.bucket.get_object(key).map_err(other_err)?;
+        match status {
+            200 => Ok(Some(data)),
+            404 => Ok(None),
+            _ => Err(ImageStorageError::Other(format!(
+                "Could not load image '{}': response status: {}",
+                key, status
+            ))),
+        }
+    }
+
+    fn delete(&self, key: &str) -> Result<(), ImageStorageError> {
+        let (_, status) = self.bucket.delete_object(key).map_err(other_err)?;
+        match status {
+            200 | 204 | 404 => Ok(()),
+            _ => Err(ImageStorageError::Other(format!(
+                "Could not delete image '{}': response status: {}",
+                key, status
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn config() -> S3Config {
+        S3Config {
+            endpoint: "http://localhost:9000".into(),
+            region: "us-east-1".into(),
+            bucket: "images".into(),
+            access_key: "minio".into(),
+            secret_key: "minio123".into(),
+        }
+    }
+
+    #[test]
+    fn reject_invalid_config() {
+        assert!(S3ImageStorage::new(config()).is_ok());
+        assert!(S3ImageStorage::new(S3Config {
+            endpoint: "localhost:9000".into(),
+            ..config()
+        })
+        .is_err());
+        assert!(S3ImageStorage::new(S3Config {
+            bucket: " ".into(),
+            ..config()
+        })
+        .is_err());
+    }
+
+    /// Requires a running MinIO instance with an existing bucket, e.g.
+    ///
+    /// ```sh
+    /// docker run -p 9000:9000 -e MINIO_ACCESS_KEY=minio -e MINIO_SECRET_KEY=minio123 minio/minio server /data
+    /// ```
+    #[test]
+    #[ignore]
+    fn store_and_load_image_in_minio() {
+        let storage = S3ImageStorage::new(S3Config {
+            endpoint: env::var("S3_ENDPOINT").unwrap_or_else(|_| "http://localhost:9000".into()),
+            region: "us-east-1".into(),
+            bucket: env::var("S3_BUCKET").unwrap_or_else(|_| "images".into()),
+            access_key: env::var("S3_ACCESS_KEY").unwrap_or_else(|_| "minio".into()),
+            secret_key: env::var("S3_SECRET_KEY").unwrap_or_else(|_| "minio123".into()),
+        })
+        .unwrap();
+        assert!(storage.load("missing.png").unwrap().is_none());
+        storage.store("test.png", "image/png", &[1, 2, 3]).unwrap();
+        assert_eq!(Some(vec![1, 2, 3]), storage.load("test.png").unwrap());
+        storage.delete("test.png").unwrap();
+        assert!(storage.load("test.png").unwrap().is_none());
+    }
+}
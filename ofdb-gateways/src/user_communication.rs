@@ -11,6 +11,13 @@ const INTRO_ENTRY_CREATED: &str = "ein neuer Eintrag auf der Karte von morgen wu
 
 const INTRO_ENTRY_UPDATED: &str = "folgender Eintrag auf der Karte von morgen wurde verändert";
 
+const UNSUBSCRIBE_BBOX_HINT: &str = "Du kannst dein Abonnement des Kartenbereichs abbestellen,
+indem du dich auf https://kartevonmorgen.org einloggst.";
+
+const UNSUBSCRIBE_SAVED_SEARCH_HINT: &str =
+    "Du kannst die Benachrichtigungen für deine gespeicherte Suche abbestellen,
+indem du dich auf https://kartevonmorgen.org einloggst.";
+
 const OUTRO_HINT: &str = "Weitere Hinweise und Tipps zur Nutzung, z.B. wie du interaktive Karten
 per <iframe> auf deiner Webseite einbettest oder Papierkarten erstellst,
 findest du hier: https://blog.vonmorgen.org";
@@ -23,6 +30,20 @@ fn subject_entry_updated(entry_title: &str) -> String {
     format!("Kvm - Eintrag verändert: {}", entry_title)
 }
 
+fn subject_saved_search_matched(search_name: &str, entry_title: &str) -> String {
+    format!(
+        "Kvm - neuer Treffer für \"{}\": {}",
+        search_name, entry_title
+    )
+}
+
+fn intro_saved_search_matched(search_name: &str) -> String {
+    format!(
+        "folgender Eintrag auf der Karte von morgen passt zu deiner gespeicherten Suche \"{}\"",
+        search_name
+    )
+}
+
 fn address_line(address: Option<&Address>) -> String {
     if let Some(address) = address {
         let Address {
@@ -74,20 +95,101 @@ das Karte von morgen-Team",
     EmailContent { subject, body }
 }
 
+pub fn user_change_email_email(url: &str) -> EmailContent {
+    let subject = "Karte von morgen: Bitte bestätige deine neue Email-Adresse".into();
+    let body = format!(
+        "Na du Weltverbesserer*,\n
+hast du uns kürzlich gebeten deine Email-Adresse zu ändern?\n\n
+Bitte bestätige deine neue Email-Adresse hier:\n
+{url}\n\n
+Falls du das nicht warst, kannst du diese Nachricht einfach ignorieren.\n\n
+euphorische Grüße,\n
+das Karte von morgen-Team",
+        url = url,
+    );
+    EmailContent { subject, body }
+}
+
+pub fn user_email_changed_email(new_email: &str) -> EmailContent {
+    let subject = "Karte von morgen: Deine Email-Adresse wurde geändert".into();
+    let body = format!(
+        "Na du Weltverbesserer*,\n
+die Email-Adresse deines Kontos wurde soeben geändert in:\n
+{new_email}\n\n
+Falls du das nicht warst, melde dich bitte umgehend bei uns.\n\n
+euphorische Grüße,\n
+das Karte von morgen-Team",
+        new_email = new_email,
+    );
+    EmailContent { subject, body }
+}
+
+pub fn place_clearance_rejected_email(place: &Place, org_name: &str, reason: &str) -> EmailContent {
+    let subject = format!("Kvm - Änderung nicht freigegeben: {}", place.title);
+    let body = format!(
+        "Hallo,\n
+deine Änderung am Eintrag \"{title}\" wurde von {org_name} nicht freigegeben.\n
+Begründung:
+{reason}\n
+Du kannst den Eintrag hier überarbeiten:
+https://kartevonmorgen.org/#/?entry={id}\n
+euphorische Grüße,\n
+das Karte von morgen-Team",
+        title = place.title,
+        org_name = org_name,
+        reason = reason,
+        id = place.id,
+    );
+    EmailContent { subject, body }
+}
+
+pub fn place_ownership_requested_email(url: &str, place: &Place, user_email: &str) -> EmailContent {
+    let subject = format!("Kvm - Bestätigung als Inhaber*in: {}", place.title);
+    let body = format!(
+        "Hallo,\n
+{user_email} möchte als Inhaber*in des Eintrags \"{title}\" eingetragen werden.
+Als Inhaber*in können Felder des Eintrags gegen Änderungen durch andere gesperrt werden.\n
+Bitte bestätige die Anfrage hier:\n
+{url}\n\n
+Falls du diese Person nicht kennst, kannst du diese Nachricht einfach ignorieren.\n\n
+euphorische Grüße,\n
+das Karte von morgen-Team",
+        user_email = user_email,
+        title = place.title,
+        url = url,
+    );
+    EmailContent { subject, body }
+}
+
 pub fn place_created_email(place: &Place, category_names: &[String]) -> EmailContent {
     let subject = subject_entry_created(&place.title);
-    let body = place_email(place, category_names, INTRO_ENTRY_CREATED);
+    let body = place_email(
+        place,
+        category_names,
+        INTRO_ENTRY_CREATED,
+        UNSUBSCRIBE_BBOX_HINT,
+    );
     EmailContent { subject, body }
 }
 
 //TODO: calc diff
 pub fn place_updated_email(place: &Place, category_names: &[String]) -> EmailContent {
     let subject = subject_entry_updated(&place.title);
-    let body = place_email(place, category_names, INTRO_ENTRY_UPDATED);
+    let body = place_email(
+        place,
+        category_names,
+        INTRO_ENTRY_UPDATED,
+        UNSUBSCRIBE_BBOX_HINT,
+    );
     EmailContent { subject, body }
 }
 
-fn place_email(place: &Place, category_names: &[String], intro_sentence: &str) -> String {
+fn place_email(
+    place: &Place,
+    category_names: &[String],
+    intro_sentence: &str,
+    unsubscribe_hint: &str,
+) -> String {
     let category = if !category_names.is_empty() {
         category_names[0].clone()
     } else {
@@ -116,12 +218,12 @@ fn place_email(place: &Place, category_names: &[String], intro_sentence: &str) -
     Telefon: {phone}\n
 Eintrag anschauen oder bearbeiten:
 https://kartevonmorgen.org/#/?entry={id}\n
-Du kannst dein Abonnement des Kartenbereichs abbestellen,
-indem du dich auf https://kartevonmorgen.org einloggst.\n
+{unsubscribe_hint}\n
 euphorische Grüße,\n
 das Karte von morgen-Team\n
 {outro_text}",
         intro_sentence = intro_sentence,
+        unsubscribe_hint = unsubscribe_hint,
         outro_text = OUTRO_HINT,
         id = &place.id,
         title = &place.title,
@@ -142,18 +244,43 @@ das Karte von morgen-Team\n
 
 pub fn event_created_email(event: &Event) -> EmailContent {
     let subject = subject_entry_created(&event.title);
-    let body = event_email(event, INTRO_ENTRY_CREATED);
+    let body = event_email(event, INTRO_ENTRY_CREATED, UNSUBSCRIBE_BBOX_HINT);
     EmailContent { subject, body }
 }
 
 //TODO: calc diff
 pub fn event_updated_email(event: &Event) -> EmailContent {
     let subject = subject_entry_updated(&event.title);
-    let body = event_email(event, INTRO_ENTRY_UPDATED);
+    let body = event_email(event, INTRO_ENTRY_UPDATED, UNSUBSCRIBE_BBOX_HINT);
+    EmailContent { subject, body }
+}
+
+pub fn place_matches_saved_search_email(
+    search_name: &str,
+    place: &Place,
+    category_names: &[String],
+) -> EmailContent {
+    let subject = subject_saved_search_matched(search_name, &place.title);
+    let body = place_email(
+        place,
+        category_names,
+        &intro_saved_search_matched(search_name),
+        UNSUBSCRIBE_SAVED_SEARCH_HINT,
+    );
     EmailContent { subject, body }
 }
 
-fn event_email(event: &Event, intro_sentence: &str) -> String {
+pub fn event_matches_saved_search_email(search_name: &str, event: &Event) -> EmailContent {
+    let subject = subject_saved_search_matched(search_name, &event.title);
+    let body = event_email(
+        event,
+        &intro_saved_search_matched(search_name),
+        UNSUBSCRIBE_SAVED_SEARCH_HINT,
+    );
+    EmailContent { subject, body }
+}
+
+fn event_email(event: &Event, intro_sentence: &str, unsubscribe_hint: &str) -> String {
     let Contact {
         name: _,
         email,
@@ -179,12 +306,12 @@ fn event_email(event: &Event, intro_sentence: &str) -> String {
     Telefon: {phone}\n
 Eintrag anschauen oder bearbeiten:
 https://kartevonmorgen.org/#/?entry={id}\n
-Du kannst dein Abonnement des Kartenbereichs abbestellen,
-indem du dich auf https://kartevonmorgen.org einloggst.\n
+{unsubscribe_hint}\n
 euphorische Grüße,\n
 das Karte von morgen-Team\n
 {outro_text}",
         intro_sentence = intro_sentence,
+        unsubscribe_hint = unsubscribe_hint,
         outro_text = OUTRO_HINT,
         category = "Event",
         id = &event.id,
@@ -315,6 +442,21 @@ mod tests {
         print_email(&email);
     }
 
+    #[test]
+    fn print_user_change_email_email() {
+        let url = "https://openfairdb.org/change-email/confirm/";
+        let email = user_change_email_email(url);
+        assert!(email.body.contains(url));
+        print_email(&email);
+    }
+
+    #[test]
+    fn print_user_email_changed_email() {
+        let email = user_email_changed_email("new@example.com");
+        assert!(email.body.contains("new@example.com"));
+        print_email(&email);
+    }
+
     #[test]
     fn print_place_created_email() {
         let place = new_place();
@@ -326,6 +468,27 @@ mod tests {
         print_email(&email);
     }
 
+    #[test]
+    fn print_place_clearance_rejected_email() {
+        let place = new_place();
+        let email = place_clearance_rejected_email(&place, "<organization>", "<reason>");
+        assert!(email.body.contains("<organization>"));
+        assert!(email.body.contains("<reason>"));
+        assert!(email.body.contains(place.id.as_str()));
+        assert!(email.body.contains(&place.title));
+        print_email(&email);
+    }
+
+    #[test]
+    fn print_place_ownership_requested_email() {
+        let place = new_place();
+        let email = place_ownership_requested_email("<url>", &place, "<user_email>");
+        assert!(email.body.contains("<url>"));
+        assert!(email.body.contains("<user_email>"));
+        assert!(email.body.contains(&place.title));
+        print_email(&email);
+    }
+
     #[test]
     fn print_place_updated_email() {
         let place = new_place();
@@ -358,4 +521,26 @@ mod tests {
         assert!(email.body.contains(&event.title));
         print_email(&email);
     }
+
+    #[test]
+    fn print_place_matches_saved_search_email() {
+        let place = new_place();
+        let email = place_matches_saved_search_email("<search>", &place, &["<category>".into()]);
+        assert!(email.subject.contains("<search>"));
+        assert!(email.body.contains(UNSUBSCRIBE_SAVED_SEARCH_HINT));
+        assert!(email.body.contains(place.id.as_str()));
+        assert!(email.body.contains(&place.title));
+        print_email(&email);
+    }
+
+    #[test]
+    fn print_event_matches_saved_search_email() {
+        let event = new_event();
+        let email = event_matches_saved_search_email("<search>", &event);
+        assert!(email.subject.contains("<search>"));
+        assert!(email.body.contains(UNSUBSCRIBE_SAVED_SEARCH_HINT));
+        assert!(email.body.contains(event.id.as_str()));
+        assert!(email.body.contains(&event.title));
+        print_email(&email);
+    }
 }
@@ -9,55 +9,56 @@ impl From<Credentials> for usecases::Login {
     }
 }
 
-impl From<IndexedPlace> for PlaceSearchResult {
-    fn from(from: IndexedPlace) -> Self {
-        let IndexedPlace {
-            id,
-            status,
-            title,
-            description,
-            tags,
-            pos,
-            ratings,
-            ..
-        } = from;
-        // The status should never be undefined! It is optional only
-        // for technical reasons.
-        debug_assert!(status.is_some());
-        let status = status.map(Into::into);
-        let (tags, categories) = e::Category::split_from_tags(tags);
-        let categories = categories.into_iter().map(|c| c.id.to_string()).collect();
-        let lat = pos.lat().to_deg();
-        let lng = pos.lng().to_deg();
-        let e::AvgRatings {
-            diversity,
-            fairness,
-            humanity,
-            renewable,
-            solidarity,
-            transparency,
-        } = ratings;
-        let total = ratings.total().into();
-        let ratings = EntrySearchRatings {
-            total,
-            diversity: diversity.into(),
-            fairness: fairness.into(),
-            humanity: humanity.into(),
-            renewable: renewable.into(),
-            solidarity: solidarity.into(),
-            transparency: transparency.into(),
-        };
-        Self {
-            id,
-            status,
-            lat,
-            lng,
-            title,
-            description,
-            categories,
-            tags,
-            ratings,
-        }
+pub fn place_search_result(
+    from: IndexedPlace,
+    all_categories: &[e::Category],
+) -> PlaceSearchResult {
+    let IndexedPlace {
+        id,
+        status,
+        title,
+        description,
+        tags,
+        pos,
+        ratings,
+        ..
+    } = from;
+    // The status should never be undefined! It is optional only
+    // for technical reasons.
+    debug_assert!(status.is_some());
+    let status = status.map(Into::into);
+    let (tags, categories) = e::Category::split_from_tags_with(all_categories, tags);
+    let categories = categories.into_iter().map(|c| c.id.to_string()).collect();
+    let lat = pos.lat().to_deg();
+    let lng = pos.lng().to_deg();
+    let e::AvgRatings {
+        diversity,
+        fairness,
+        humanity,
+        renewable,
+        solidarity,
+        transparency,
+    } = ratings;
+    let total = ratings.total().into();
+    let ratings = EntrySearchRatings {
+        total,
+        diversity: diversity.into(),
+        fairness: fairness.into(),
+        humanity: humanity.into(),
+        renewable: renewable.into(),
+        solidarity: solidarity.into(),
+        transparency: transparency.into(),
+    };
+    PlaceSearchResult {
+        id,
+        status,
+        lat,
+        lng,
+        title,
+        description,
+        categories,
+        tags,
+        ratings,
     }
 }
 
@@ -180,7 +181,11 @@ impl From<UpdatePlace> for usecases::UpdatePlace {
 
 // Entity -> JSON
 
-pub fn entry_from_place_with_ratings(place: e::Place, ratings: Vec<e::Rating>) -> Entry {
+pub fn entry_from_place_with_ratings(
+    place: e::Place,
+    ratings: Vec<e::Rating>,
+    all_categories: &[e::Category],
+) -> Entry {
     let e::Place {
         id,
         license,
@@ -224,7 +229,7 @@ pub fn entry_from_place_with_ratings(place: e::Place, ratings: Vec<e::Rating>) -
         )
         .unwrap_or_default();
 
-    let (tags, categories) = e::Category::split_from_tags(tags);
+    let (tags, categories) = e::Category::split_from_tags_with(all_categories, tags);
 
     Entry {
         id: id.into(),
@@ -255,6 +260,230 @@ pub fn entry_from_place_with_ratings(place: e::Place, ratings: Vec<e::Rating>) -
     }
 }
 
+pub fn rating_without_comments(rating: e::Rating) -> Rating {
+    let e::Rating {
+        id,
+        created_at,
+        title,
+        value,
+        context,
+        source,
+        ..
+    } = rating;
+    Rating {
+        id: id.into(),
+        created: created_at.into_seconds(),
+        title,
+        value: value.into(),
+        context: context.into(),
+        source: source.unwrap_or_default(),
+        comments: vec![],
+    }
+}
+
+pub fn flagged_rating(from: e::FlaggedRating) -> FlaggedRating {
+    let e::FlaggedRating { rating, flags } = from;
+    FlaggedRating {
+        rating: rating_without_comments(rating),
+        flags: flags.into_iter().map(Into::into).collect(),
+    }
+}
+
+/// The URLs of the image are relative to `base_url`.
+pub fn image(from: e::Image, base_url: &str) -> Image {
+    let e::Image {
+        id,
+        format: _,
+        width,
+        height,
+        license,
+        attribution,
+        uploaded,
+    } = from;
+    let base_url = base_url.trim_end_matches('/');
+    Image {
+        url: format!("{}/{}", base_url, id),
+        thumbnail_url: format!("{}/{}/thumbnail", base_url, id),
+        id: id.into(),
+        width,
+        height,
+        license,
+        attribution,
+        created_at: uploaded.at.into_inner(),
+    }
+}
+
+impl From<usecases::UserExport> for UserExport {
+    fn from(from: usecases::UserExport) -> Self {
+        let usecases::UserExport {
+            user,
+            place_revisions,
+            ratings,
+            bbox_subscriptions,
+            user_tokens,
+        } = from;
+        Self {
+            user: user.into(),
+            place_revisions: place_revisions
+                .into_iter()
+                .map(|(place, status)| {
+                    let (root, revision): (e::PlaceRoot, e::PlaceRevision) = place.into();
+                    (root.into(), revision.into(), status.into())
+                })
+                .collect(),
+            ratings: ratings
+                .into_iter()
+                .map(|r| (r.place_id.to_string(), rating_without_comments(r)))
+                .collect(),
+            bbox_subscriptions: bbox_subscriptions.into_iter().map(Into::into).collect(),
+            user_tokens: user_tokens.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<usecases::Statistics> for Statistics {
+    fn from(from: usecases::Statistics) -> Self {
+        let usecases::Statistics {
+            places_created_per_week,
+            places_updated_per_week,
+            events_per_month,
+            users_registered_per_week,
+            ratings_per_week,
+            pending_clearances,
+            places_by_category,
+            places_by_tag,
+            places_by_country,
+            places_by_state,
+        } = from;
+        let periods = |counts: Vec<usecases::PeriodCount>| {
+            counts
+                .into_iter()
+                .map(|c| PeriodCount {
+                    period: c.period_start,
+                    count: c.count,
+                })
+                .collect::<Vec<_>>()
+        };
+        let places = |counts: Vec<usecases::PlaceCount>| {
+            counts
+                .into_iter()
+                .map(|c| PlaceCount {
+                    key: c.key,
+                    count: c.count,
+                })
+                .collect::<Vec<_>>()
+        };
+        Self {
+            places_created_per_week: periods(places_created_per_week),
+            places_updated_per_week: periods(places_updated_per_week),
+            events_per_month: periods(events_per_month),
+            users_registered_per_week: periods(users_registered_per_week),
+            ratings_per_week: periods(ratings_per_week),
+            pending_clearances: pending_clearances
+                .into_iter()
+                .map(|c| PendingClearanceCount {
+                    org_id: c.org_id.into(),
+                    org_name: c.org_name,
+                    places: c.places,
+                    events: c.events,
+                })
+                .collect(),
+            places_by_category: places(places_by_category),
+            places_by_tag: places(places_by_tag),
+            places_by_country: places(places_by_country),
+            places_by_state: places(places_by_state),
+        }
+    }
+}
+
+impl From<usecases::SearchFacets> for SearchFacets {
+    fn from(from: usecases::SearchFacets) -> Self {
+        let usecases::SearchFacets {
+            total_count,
+            categories,
+            tags,
+            status,
+            ratings,
+        } = from;
+        let places = |counts: Vec<usecases::PlaceCount>| {
+            counts
+                .into_iter()
+                .map(|c| PlaceCount {
+                    key: c.key,
+                    count: c.count,
+                })
+                .collect::<Vec<_>>()
+        };
+        Self {
+            total_count,
+            categories: places(categories),
+            tags: places(tags),
+            status: status
+                .into_iter()
+                .map(|(status, count)| StatusCount {
+                    status: status.into(),
+                    count,
+                })
+                .collect(),
+            ratings: ratings
+                .into_iter()
+                .map(|(rating, count)| RatingCount { rating, count })
+                .collect(),
+        }
+    }
+}
+
+impl From<usecases::PlaceCluster> for PlaceCluster {
+    fn from(from: usecases::PlaceCluster) -> Self {
+        let usecases::PlaceCluster {
+            count,
+            centroid,
+            category,
+        } = from;
+        Self {
+            count,
+            lat: centroid.lat().to_deg(),
+            lng: centroid.lng().to_deg(),
+            category,
+        }
+    }
+}
+
+impl From<usecases::Suggestion> for Suggestion {
+    fn from(from: usecases::Suggestion) -> Self {
+        let usecases::Suggestion { text, kind, count } = from;
+        let kind = match kind {
+            usecases::SuggestionKind::Title => SuggestionKind::Title,
+            usecases::SuggestionKind::Tag => SuggestionKind::Tag,
+        };
+        Self { text, kind, count }
+    }
+}
+
+pub fn role_permissions(from: RolePermissions) -> anyhow::Result<ofdb_core::user::RolePermissions> {
+    let RolePermissions {
+        guest,
+        user,
+        scout,
+        admin,
+    } = from;
+    let mut permissions = ofdb_core::user::RolePermissions::empty();
+    for (role, names) in vec![
+        (e::Role::Guest, guest),
+        (e::Role::User, user),
+        (e::Role::Scout, scout),
+        (e::Role::Admin, admin),
+    ] {
+        for name in names {
+            let permission = name
+                .parse::<e::Permission>()
+                .map_err(|_| anyhow::anyhow!("Invalid permission '{}'", name))?;
+            permissions.grant(role, permission);
+        }
+    }
+    Ok(permissions)
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Review {
     pub status: ReviewStatus,
@@ -0,0 +1,206 @@
+//! Encoding of Mapbox Vector Tiles (MVT).
+//!
+//! See also: <https://github.com/mapbox/vector-tile-spec/tree/master/2.1>
+
+use crate::core::{db::IndexedPlace, entities::Category};
+use ofdb_core::tile::TileCoord;
+
+use std::collections::HashMap;
+
+pub const CONTENT_TYPE: (&str, &str) = ("application", "vnd.mapbox-vector-tile");
+
+pub const PLACES_LAYER_NAME: &str = "places";
+
+const VERSION: u32 = 2;
+const EXTENT: u32 = 4096;
+
+const GEOM_TYPE_POINT: u32 = 1;
+const CMD_MOVE_TO: u32 = 1;
+
+const WIRE_TYPE_VARINT: u32 = 0;
+const WIRE_TYPE_64BIT: u32 = 1;
+const WIRE_TYPE_LEN: u32 = 2;
+
+const KEY_ID: u32 = 0;
+const KEY_TITLE: u32 = 1;
+const KEY_CATEGORY: u32 = 2;
+const KEY_RATING: u32 = 3;
+const KEYS: &[&str] = &["id", "title", "category", "rating"];
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_key(buf: &mut Vec<u8>, field: u32, wire_type: u32) {
+    write_varint(buf, u64::from((field << 3) | wire_type));
+}
+
+fn write_uint32(buf: &mut Vec<u8>, field: u32, value: u32) {
+    write_key(buf, field, WIRE_TYPE_VARINT);
+    write_varint(buf, value.into());
+}
+
+fn write_bytes(buf: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+    write_key(buf, field, WIRE_TYPE_LEN);
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn write_packed(buf: &mut Vec<u8>, field: u32, values: &[u32]) {
+    let mut packed = Vec::with_capacity(values.len() * 2);
+    for value in values {
+        write_varint(&mut packed, (*value).into());
+    }
+    write_bytes(buf, field, &packed);
+}
+
+fn zigzag(value: i32) -> u32 {
+    ((value << 1) ^ (value >> 31)) as u32
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Value {
+    String(String),
+    // The bits of an f64
+    Double(u64),
+}
+
+impl Value {
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        match self {
+            Self::String(s) => write_bytes(&mut buf, 1, s.as_bytes()),
+            Self::Double(bits) => {
+                write_key(&mut buf, 3, WIRE_TYPE_64BIT);
+                buf.extend_from_slice(&bits.to_le_bytes());
+            }
+        }
+        buf
+    }
+}
+
+#[derive(Default)]
+struct Values {
+    values: Vec<Value>,
+    indexes: HashMap<Value, u32>,
+}
+
+impl Values {
+    fn index_of(&mut self, value: Value) -> u32 {
+        if let Some(index) = self.indexes.get(&value) {
+            return *index;
+        }
+        let index = self.values.len() as u32;
+        self.values.push(value.clone());
+        self.indexes.insert(value, index);
+        index
+    }
+}
+
+/// Encode a tile with a single layer of points.
+///
+/// Places in the buffer around the tile have coordinates
+/// outside of the extent.
+pub fn encode_places_tile(
+    tile: TileCoord,
+    places: &[IndexedPlace],
+    all_categories: &[Category],
+) -> Vec<u8> {
+    let mut values = Values::default();
+    let mut layer = vec![];
+    write_uint32(&mut layer, 15, VERSION);
+    write_bytes(&mut layer, 1, PLACES_LAYER_NAME.as_bytes());
+    for place in places {
+        let (x, y) = tile.position(place.pos);
+        let x = (x * f64::from(EXTENT)).round() as i32;
+        let y = (y * f64::from(EXTENT)).round() as i32;
+        let mut tags = vec![
+            KEY_ID,
+            values.index_of(Value::String(place.id.clone())),
+            KEY_TITLE,
+            values.index_of(Value::String(place.title.clone())),
+        ];
+        let (_, categories) = Category::split_from_tags_with(all_categories, place.tags.clone());
+        if let Some(category) = categories.first() {
+            tags.push(KEY_CATEGORY);
+            tags.push(values.index_of(Value::String(category.id.to_string())));
+        }
+        tags.push(KEY_RATING);
+        tags.push(values.index_of(Value::Double(f64::from(place.ratings.total()).to_bits())));
+        let mut feature = vec![];
+        write_packed(&mut feature, 2, &tags);
+        write_uint32(&mut feature, 3, GEOM_TYPE_POINT);
+        write_packed(
+            &mut feature,
+            4,
+            &[CMD_MOVE_TO | (1 << 3), zigzag(x), zigzag(y)],
+        );
+        write_bytes(&mut layer, 2, &feature);
+    }
+    for key in KEYS {
+        write_bytes(&mut layer, 3, key.as_bytes());
+    }
+    for value in &values.values {
+        write_bytes(&mut layer, 4, &value.encode());
+    }
+    write_uint32(&mut layer, 5, EXTENT);
+    let mut buf = vec![];
+    write_bytes(&mut buf, 3, &layer);
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::util::geo::MapPoint;
+
+    #[test]
+    fn encode_varint_and_zigzag() {
+        let mut buf = vec![];
+        write_varint(&mut buf, 300);
+        assert_eq!(vec![0b1010_1100, 0b0000_0010], buf);
+        assert_eq!(0, zigzag(0));
+        assert_eq!(1, zigzag(-1));
+        assert_eq!(2, zigzag(1));
+        assert_eq!(4095, zigzag(-2048));
+    }
+
+    #[test]
+    fn encode_empty_tile() {
+        let tile = TileCoord::new(0, 0, 0).unwrap();
+        let buf = encode_places_tile(tile, &[], &[]);
+        let mut layer = vec![0x78, 0x02, 0x0a, 0x06];
+        layer.extend_from_slice(b"places");
+        for key in KEYS {
+            layer.push(0x1a);
+            layer.push(key.len() as u8);
+            layer.extend_from_slice(key.as_bytes());
+        }
+        layer.extend_from_slice(&[0x28, 0x80, 0x20]);
+        let mut expected = vec![0x1a, layer.len() as u8];
+        expected.extend(layer);
+        assert_eq!(expected, buf);
+    }
+
+    #[test]
+    fn encode_place_in_center_of_tile() {
+        let tile = TileCoord::new(0, 0, 0).unwrap();
+        let place = IndexedPlace {
+            id: "foo".into(),
+            title: "Bar".into(),
+            pos: MapPoint::from_lat_lng_deg(0.0, 0.0),
+            tags: vec![Category::TAG_NON_PROFIT.into()],
+            ..Default::default()
+        };
+        let buf = encode_places_tile(tile, &[place], &Category::built_in());
+        // Geometry: MoveTo(2048, 2048)
+        let geometry = [0x22, 0x05, 0x09, 0x80, 0x20, 0x80, 0x20];
+        assert!(buf.windows(geometry.len()).any(|w| w == geometry));
+        let category = Category::ID_NON_PROFIT.as_bytes();
+        assert!(buf.windows(category.len()).any(|w| w == category));
+    }
+}
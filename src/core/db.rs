@@ -9,6 +9,10 @@ use super::{
 };
 
 use anyhow::Result as Fallible;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
 
 type Result<T> = std::result::Result<T, RepoError>;
 
@@ -55,6 +59,12 @@ pub trait PlaceRepo {
     fn get_place_history(&self, id: &str, revision: Option<Revision>) -> Result<PlaceHistory>;
 
     fn load_place_revision(&self, id: &str, rev: Revision) -> Result<(Place, ReviewStatus)>;
+
+    // All revisions that have been created by the given user
+    fn load_place_revisions_created_by(
+        &self,
+        user_email: &str,
+    ) -> Result<Vec<(Place, ReviewStatus)>>;
 }
 
 pub trait EventGateway {
@@ -82,8 +92,24 @@ pub trait EventGateway {
 pub trait UserGateway {
     fn create_user(&self, user: &User) -> Result<()>;
     fn update_user(&self, user: &User) -> Result<()>;
+    fn update_user_email(&self, email: &str, new_email: &str) -> Result<()>;
     fn delete_user_by_email(&self, email: &str) -> Result<()>;
 
+    // Replaces all personal data of the user by a pseudonym while
+    // keeping all references from authored activities intact.
+    fn anonymize_user_by_email(
+        &self,
+        email: &str,
+        pseudonym: &str,
+        anonymized_at: TimestampMs,
+    ) -> Result<AnonymizedUser>;
+    fn all_anonymized_users(&self) -> Result<Vec<AnonymizedUser>>;
+
+    // Regions that restrict the moderation permissions of a user.
+    // An empty list means that the user is not restricted.
+    fn replace_user_regions_by_email(&self, email: &str, regions: &[MapBbox]) -> Result<()>;
+    fn user_regions_by_email(&self, email: &str) -> Result<Vec<MapBbox>>;
+
     fn all_users(&self) -> Result<Vec<User>>;
     fn count_users(&self) -> Result<usize>;
 
@@ -94,6 +120,7 @@ pub trait UserGateway {
 pub trait OrganizationRepo {
     fn create_org(&mut self, _: Organization) -> Result<()>;
     fn get_org_by_api_token(&self, token: &str) -> Result<Organization>;
+    fn all_orgs(&self) -> Result<Vec<Organization>>;
     fn map_tag_to_clearance_org_id(&self, tag: &str) -> Result<Option<Id>>;
     fn get_moderated_tags_by_org(
         &self,
@@ -124,6 +151,40 @@ pub trait PlaceClearanceRepo {
         clearances: &[ClearanceForPlace],
     ) -> Result<usize>;
     fn cleanup_pending_clearances_for_places(&self, org_id: &Id) -> Result<u64>;
+    // Reverted clearances are neither counted nor listed as pending
+    // until the place is modified again.
+    fn revert_pending_clearances_for_places(
+        &self,
+        org_id: &Id,
+        place_ids: &[&str],
+    ) -> Result<usize>;
+}
+
+pub trait EventClearanceRepo {
+    // Existing pending clearances are preserved, i.e. the last
+    // cleared state of an event is only recorded once.
+    fn add_pending_clearance_for_events(
+        &self,
+        org_ids: &[Id],
+        pending_clearance: &PendingClearanceForEvent,
+    ) -> Result<usize>;
+    fn count_pending_clearances_for_events(&self, org_id: &Id) -> Result<u64>;
+    fn list_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<PendingClearanceForEvent>>;
+    fn load_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        event_ids: &[&str],
+    ) -> Result<Vec<PendingClearanceForEvent>>;
+    // Clearing an event removes the corresponding pending clearance
+    fn update_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        clearances: &[ClearanceForEvent],
+    ) -> Result<usize>;
 }
 
 //TODO:
@@ -145,16 +206,19 @@ pub trait Db:
     + RatingRepository
     + UserTokenRepo
     + PlaceClearanceRepo
+    + EventClearanceRepo
+    + AuditLogRepo
+    + PlaceOwnershipRepo
+    + PlaceEditSuggestionRepo
+    + ImageRepo
+    + LinkCheckRepo
+    + StatisticsRepo
+    + SavedSearchRepo
+    + TagRepo
+    + CategoryRepo
 {
     fn create_tag_if_it_does_not_exist(&self, _: &Tag) -> Result<()>;
 
-    fn all_categories(&self) -> Result<Vec<Category>> {
-        Ok(vec![
-            Category::new_non_profit(),
-            Category::new_commercial(),
-            Category::new_event(),
-        ])
-    }
     fn all_tags(&self) -> Result<Vec<Tag>>;
     fn count_tags(&self) -> Result<usize>;
 
@@ -182,10 +246,20 @@ pub struct IndexQuery<'a, 'b> {
     pub include_bbox: Option<MapBbox>,
     pub exclude_bbox: Option<MapBbox>,
     pub categories: Vec<&'a str>,
+    // all_categories = empty vector: Only the built-in categories are known
+    pub all_categories: Vec<Category>,
     pub ids: Vec<&'b str>,
     pub hash_tags: Vec<String>,
+    // The children of hash tags in the taxonomy and the synonyms,
+    // i.e. a hash tag also matches if any of its alternatives matches
+    pub hash_tag_alternatives: HashMap<String, Vec<String>>,
     pub text_tags: Vec<String>,
     pub text: Option<String>,
+    // lang = None: Analyze the text for all supported languages
+    pub lang: Option<Language>,
+    // Match titles or tags that start with this prefix, e.g. for
+    // completing the text while it is typed
+    pub prefix: Option<String>,
     pub ts_min_lb: Option<Timestamp>, // lower bound (inclusive)
     pub ts_min_ub: Option<Timestamp>, // upper bound (inclusive)
     pub ts_max_lb: Option<Timestamp>, // lower bound (inclusive)
@@ -218,10 +292,94 @@ pub struct IndexedPlace {
     pub description: String,
     pub tags: Vec<String>,
     pub ratings: AvgRatings,
+    // The time of the current revision
+    pub changed_at: Option<TimestampMs>,
+    // The value by which the query results have been ordered
+    pub score: f64,
+}
+
+// The number of places that match a query, independent of any limit
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IndexedPlaceFacets {
+    pub total_count: u64,
+    // Including the tags of categories
+    pub tags: HashMap<String, u64>,
+    pub status: BTreeMap<ReviewStatus, u64>,
+    // The total rating rounded down
+    pub ratings: BTreeMap<i64, u64>,
+}
+
+// Places that are aggregated into a single cell of a regular grid
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct IndexedPlaceCluster {
+    pub count: u64,
+    // The mean position of all places in the cell
+    pub centroid: MapPoint,
+    // The number of places per category id
+    pub categories: HashMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum PlaceSortOrder {
+    // Highest total rating first
+    Rating,
+    // Highest score first, i.e. boosted by the rating
+    // if the query contains search terms or tags
+    Relevance,
+    // Nearest to the given point first
+    Distance(MapPoint),
+    // Most recently changed first
+    RecentlyChanged,
+    // By the lowercase title
+    Title,
+}
+
+// Numbers and texts are both sorted in ascending order, i.e.
+// numbers are negated for sorting in descending order.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum PlaceSortKey {
+    Number(f64),
+    Text(String),
+}
+
+// The position of a place in sorted results that is
+// independent of any other places
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaceSortPosition {
+    pub key: PlaceSortKey,
+    pub id: String,
+}
+
+impl PlaceSortPosition {
+    pub fn cmp(&self, other: &Self) -> Ordering {
+        self.key
+            .partial_cmp(&other.key)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.id.cmp(&other.id))
+    }
 }
 
 pub trait PlaceIndex {
     fn query_places(&self, query: &IndexQuery, limit: usize) -> Fallible<Vec<IndexedPlace>>;
+
+    // Only places that are positioned after the given
+    // position are returned
+    fn query_places_sorted(
+        &self,
+        query: &IndexQuery,
+        order: PlaceSortOrder,
+        after: Option<&PlaceSortPosition>,
+        limit: usize,
+    ) -> Fallible<Vec<(PlaceSortPosition, IndexedPlace)>>;
+
+    fn query_place_facets(&self, query: &IndexQuery) -> Fallible<IndexedPlaceFacets>;
+
+    // The width and height of the grid cells are given in degrees
+    fn query_place_clusters(
+        &self,
+        query: &IndexQuery,
+        cell_size: f64,
+    ) -> Fallible<Vec<IndexedPlaceCluster>>;
 }
 
 pub trait PlaceIndexer: IdIndexer + PlaceIndex {
@@ -1,3 +1,4 @@
+use ofdb_entities::ownership::PlaceField;
 use std::io;
 use thiserror::Error;
 
@@ -31,6 +32,10 @@ pub enum ParameterError {
     RatingValue,
     #[error("Invalid rating context")]
     RatingContext(String),
+    #[error("The place has already been rated in this context")]
+    RatingExists,
+    #[error("The comment to reply to does not belong to the rating")]
+    ReplyToComment,
     #[error("Invalid credentials")]
     Credentials,
     #[error("Email not confirmed")]
@@ -61,6 +66,60 @@ pub enum ParameterError {
     InvalidNonce,
     #[error("Missing id list")]
     EmptyIdList,
+    #[error("Invalid audit action")]
+    AuditAction,
+    #[error("Missing reason")]
+    EmptyReason,
+    #[error("The revision has already been cleared")]
+    ClearedRevision,
+    #[error("The place has no contact e-mail address")]
+    MissingContactEmail,
+    #[error("Invalid place field")]
+    PlaceField,
+    #[error("The field '{0}' is locked by the owner of the place")]
+    LockedField(PlaceField),
+    #[error("The suggestion has already been accepted or declined")]
+    SuggestionNotPending,
+    #[error("The place has been modified after the suggestion has been made")]
+    OutdatedSuggestion,
+    #[error("Unsupported image format")]
+    ImageFormat,
+    #[error("The image is too large")]
+    ImageTooLarge,
+    #[error("The referenced image does not exist")]
+    Image,
+    #[error("The license requires an attribution")]
+    MissingAttribution,
+    #[error("Invalid link status")]
+    LinkStatus,
+    #[error("Invalid kind of link owner")]
+    LinkOwnerKind,
+    #[error("Unsupported language")]
+    Language,
+    #[error("Invalid sort order")]
+    SearchSort,
+    #[error("Invalid cursor")]
+    Cursor,
+    #[error("Invalid zoom level")]
+    Zoom,
+    #[error("Invalid tile coordinates")]
+    Tile,
+    #[error("Missing name of the search")]
+    SearchName,
+    #[error("Invalid tag")]
+    Tag,
+    #[error("Synonyms must not have synonyms themselves")]
+    TagSynonym,
+    #[error("Each tag may only appear once in the taxonomy")]
+    Taxonomy,
+    #[error("Tags of the taxonomy cannot be merged or become synonyms")]
+    CuratedTag,
+    #[error("The tag already belongs to another category")]
+    CategoryTag,
+    #[error("Built-in categories cannot be deleted or change their tag")]
+    BuiltInCategory,
+    #[error("Invalid color")]
+    Color,
 }
 
 #[derive(Debug, Error)]
@@ -88,6 +147,8 @@ pub enum Error {
     Repo(#[from] RepoError),
     #[error(transparent)]
     Pwhash(#[from] pwhash::error::Error),
+    #[error(transparent)]
+    ImageStorage(#[from] ofdb_core::gateways::image::ImageStorageError),
     #[error("Internal error: {0}")]
     Internal(String),
 }
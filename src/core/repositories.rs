@@ -4,7 +4,7 @@
 // by their id and never modified or loaded by another
 // repository.
 
-use super::{entities::*, error::RepoError, util::time::Timestamp};
+use super::{db::Pagination, entities::*, error::RepoError, util::time::Timestamp};
 
 type Result<T> = std::result::Result<T, RepoError>;
 
@@ -46,11 +46,18 @@ pub trait RatingRepository {
     fn load_rating(&self, id: &str) -> Result<Rating>;
     fn load_ratings(&self, ids: &[&str]) -> Result<Vec<Rating>>;
     fn load_ratings_of_place(&self, place_id: &str) -> Result<Vec<Rating>>;
+    // Including archived ratings, but also without comments
+    fn load_ratings_created_by(&self, user_email: &str) -> Result<Vec<Rating>>;
 
     fn archive_ratings(&self, ids: &[&str], activity: &Activity) -> Result<usize>;
     fn archive_ratings_of_places(&self, place_ids: &[&str], activity: &Activity) -> Result<usize>;
 
     fn load_place_ids_of_ratings(&self, ids: &[&str]) -> Result<Vec<String>>;
+
+    // Report a rating for review by scouts
+    fn flag_rating(&self, id: &str, activity: &Activity, comment: Option<&str>) -> Result<()>;
+    // Only unarchived ratings, most recently flagged first
+    fn load_flagged_ratings(&self, pagination: &Pagination) -> Result<Vec<FlaggedRating>>;
 }
 
 pub trait UserTokenRepo {
@@ -62,3 +69,163 @@ pub trait UserTokenRepo {
 
     fn get_user_token_by_email(&self, email: &str) -> Result<UserToken>;
 }
+
+pub trait CaptchaRepo {
+    fn create_captcha(&self, nonce: &Nonce, captcha: &Captcha) -> Result<()>;
+
+    fn update_captcha(&self, nonce: &Nonce, captcha: &Captcha) -> Result<()>;
+
+    fn load_captcha(&self, nonce: &Nonce) -> Result<Captcha>;
+
+    // Load and delete the captcha so that each challenge could
+    // only be answered once
+    fn consume_captcha(&self, nonce: &Nonce) -> Result<Captcha>;
+
+    fn delete_expired_captchas(&self, created_before: Timestamp) -> Result<usize>;
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AuditLogQuery<'a> {
+    pub since: Option<TimestampMs>,
+    pub actor_email: Option<&'a str>,
+    pub entity_id: Option<&'a str>,
+    pub action: Option<AuditAction>,
+}
+
+pub trait AuditLogRepo {
+    fn add_audit_log_entries(&self, entries: &[AuditLogEntry]) -> Result<()>;
+
+    // Ordered by time, newest entries first
+    fn query_audit_log(
+        &self,
+        query: &AuditLogQuery,
+        pagination: &Pagination,
+    ) -> Result<Vec<AuditLogEntry>>;
+}
+
+pub trait PlaceOwnershipRepo {
+    // Replaces any previous owner of the place
+    fn set_place_owner(&self, place_id: &str, owner_email: &str) -> Result<PlaceOwnership>;
+
+    fn get_place_ownership(&self, place_id: &str) -> Result<Option<PlaceOwnership>>;
+
+    fn update_locked_place_fields(&self, place_id: &str, fields: &[PlaceField]) -> Result<()>;
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PlaceEditSuggestionQuery<'a> {
+    pub place_id: Option<&'a str>,
+    pub status: Option<SuggestionStatus>,
+    // Only suggestions for places that are currently
+    // tagged with any of these tags if not empty
+    pub tags: &'a [String],
+}
+
+pub trait PlaceEditSuggestionRepo {
+    fn add_place_edit_suggestion(&self, suggestion: &PlaceEditSuggestion) -> Result<()>;
+
+    fn get_place_edit_suggestion(&self, id: &str) -> Result<PlaceEditSuggestion>;
+
+    // Ordered by time, oldest suggestions first
+    fn query_place_edit_suggestions(
+        &self,
+        query: &PlaceEditSuggestionQuery,
+        pagination: &Pagination,
+    ) -> Result<Vec<PlaceEditSuggestion>>;
+
+    // Only pending suggestions could be reviewed
+    fn review_place_edit_suggestion(
+        &self,
+        id: &str,
+        status: SuggestionStatus,
+        log: &ActivityLog,
+    ) -> Result<()>;
+}
+
+pub trait ImageRepo {
+    fn add_image(&self, image: &Image) -> Result<()>;
+
+    fn get_image(&self, id: &str) -> Result<Image>;
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LinkCheckQuery {
+    pub owner_kind: Option<LinkOwnerKind>,
+    pub status: Option<LinkStatus>,
+}
+
+pub trait LinkCheckRepo {
+    // Replaces previous checks of the same link and owner
+    fn save_link_checks(&self, checks: &[LinkCheck]) -> Result<()>;
+
+    fn delete_link_checks(&self, checks: &[LinkCheck]) -> Result<()>;
+
+    // Ordered by owner and URL
+    fn query_link_checks(
+        &self,
+        query: &LinkCheckQuery,
+        pagination: &Pagination,
+    ) -> Result<Vec<LinkCheck>>;
+}
+
+pub trait StatisticsRepo {
+    // Creation times of all place revisions, the initial
+    // revision marks the creation of the place
+    fn place_revision_times(&self) -> Result<Vec<(Revision, TimestampMs)>>;
+
+    // Start times of all events that have not been archived
+    fn event_start_times(&self) -> Result<Vec<Timestamp>>;
+
+    // Only known for users that registered after
+    // the registration time has been recorded
+    fn user_registration_times(&self) -> Result<Vec<TimestampMs>>;
+
+    // Including archived ratings
+    fn rating_creation_times(&self) -> Result<Vec<Timestamp>>;
+}
+
+pub trait SavedSearchRepo {
+    fn create_saved_search(&self, saved_search: &SavedSearch) -> Result<()>;
+
+    // Replaces all fields except the owner and the creation time
+    fn update_saved_search(&self, saved_search: &SavedSearch) -> Result<()>;
+
+    fn delete_saved_search(&self, id: &str) -> Result<()>;
+
+    fn get_saved_search(&self, id: &str) -> Result<SavedSearch>;
+
+    // Ordered by creation time
+    fn saved_searches_by_email(&self, user_email: &str) -> Result<Vec<SavedSearch>>;
+
+    fn all_saved_searches_with_alert(&self) -> Result<Vec<SavedSearch>>;
+}
+
+pub trait CategoryRepo {
+    fn all_categories(&self) -> Result<Vec<Category>>;
+
+    fn create_category(&self, category: &Category) -> Result<()>;
+
+    // Replaces all names of the category
+    fn update_category(&self, category: &Category) -> Result<()>;
+
+    fn delete_category(&self, id: &str) -> Result<()>;
+}
+
+pub trait TagRepo {
+    fn all_tag_synonyms(&self) -> Result<Vec<TagSynonym>>;
+
+    // Replaces all synonyms of the tag. Synonyms that have
+    // been assigned to another tag are reassigned.
+    fn replace_tag_synonyms(&self, tag: &str, synonyms: &[String]) -> Result<()>;
+
+    fn get_tag_description(&self, tag: &str) -> Result<Option<String>>;
+
+    fn set_tag_description(&self, tag: &str, description: Option<&str>) -> Result<()>;
+
+    // Only removes the tags from the list of all tags
+    fn delete_tags(&self, tags: &[&str]) -> Result<usize>;
+
+    fn all_taxonomy_tags(&self) -> Result<Vec<TaxonomyTag>>;
+
+    fn replace_taxonomy(&self, taxonomy: &[TaxonomyTag]) -> Result<()>;
+}
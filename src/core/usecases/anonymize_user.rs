@@ -0,0 +1,71 @@
+use super::{authorize_user_by_permission, record_audit_log};
+use crate::core::prelude::*;
+use ofdb_core::user::RolePermissions;
+
+const PSEUDONYM_DOMAIN: &str = "deleted.invalid";
+
+/// Delete a user account while keeping all authored activities.
+///
+/// All references are kept and point to a pseudonymous placeholder
+/// instead. Users might delete their own account, while users with
+/// the corresponding permission are allowed to delete any account.
+pub fn anonymize_user(
+    db: &dyn Db,
+    permissions: &RolePermissions,
+    login_email: &str,
+    email: &str,
+) -> Result<AnonymizedUser> {
+    if login_email != email {
+        authorize_user_by_permission(db, permissions, login_email, Permission::UserAnonymize)
+            .map_err(|_| Error::Parameter(ParameterError::Forbidden))?;
+    }
+    let pseudonym = format!("anonymous-{}@{}", Nonce::new(), PSEUDONYM_DOMAIN);
+    // Recorded in advance, because the actor might be the
+    // anonymized user who could not be resolved afterwards.
+    // The entity id is replaced by the pseudonym.
+    record_audit_log(
+        db,
+        &Activity::now(Some(login_email.into())).into(),
+        AuditAction::UserAnonymize,
+        AuditEntityKind::User,
+        &[email],
+        None,
+    )?;
+    let anonymized_user = db.anonymize_user_by_email(email, &pseudonym, TimestampMs::now())?;
+    info!("Anonymized user ({}) as {}", email, pseudonym);
+    Ok(anonymized_user)
+}
+
+/// Report about all anonymized users.
+pub fn anonymized_users(
+    db: &dyn Db,
+    permissions: &RolePermissions,
+    login_email: &str,
+) -> Result<Vec<AnonymizedUser>> {
+    authorize_user_by_permission(db, permissions, login_email, Permission::UserAnonymize)?;
+    Ok(db.all_anonymized_users()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{create_user, MockDb};
+    use super::*;
+
+    #[test]
+    fn only_admins_may_anonymize_other_users() {
+        let db = MockDb::default();
+        let permissions = RolePermissions::default();
+        create_user(&db, "user@foo.bar", Role::User);
+        create_user(&db, "scout@foo.bar", Role::Scout);
+        match anonymize_user(&db, &permissions, "scout@foo.bar", "user@foo.bar")
+            .err()
+            .unwrap()
+        {
+            Error::Parameter(ParameterError::Forbidden) => {
+                // ok
+            }
+            _ => panic!("invalid error"),
+        }
+        assert!(anonymized_users(&db, &permissions, "scout@foo.bar").is_err());
+    }
+}
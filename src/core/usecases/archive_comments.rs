@@ -1,15 +1,34 @@
+use super::{authorize_user_by_permission, authorize_user_regions_for_ratings, record_audit_log};
 use crate::core::prelude::*;
+use ofdb_core::user::RolePermissions;
 
-pub fn archive_comments<D: Db>(db: &D, user_email: &str, ids: &[&str]) -> Result<usize> {
+pub fn archive_comments<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    user_email: &str,
+    ids: &[&str],
+) -> Result<usize> {
     info!("Archiving {} comments", ids.len());
     // TODO: Pass an authentication token with user id and role to
     // check if the user is authorized to perform this use case
-    let user = db.try_get_user_by_email(user_email)?;
-    if let Some(user) = user {
-        if user.role >= Role::Scout {
-            let archived = Activity::now(Some(user_email.into()));
-            return Ok(db.archive_comments(ids, &archived)?);
-        }
-    }
-    Err(ParameterError::Forbidden.into())
+    authorize_user_by_permission(db, permissions, user_email, Permission::CommentArchive)
+        .map_err(|_| ParameterError::Forbidden)?;
+    let rating_ids: Vec<_> = db
+        .load_comments(ids)?
+        .into_iter()
+        .map(|comment| comment.rating_id)
+        .collect();
+    let rating_ids: Vec<_> = rating_ids.iter().map(Id::as_str).collect();
+    authorize_user_regions_for_ratings(db, user_email, &rating_ids)?;
+    let archived = Activity::now(Some(user_email.into()));
+    let count = db.archive_comments(ids, &archived)?;
+    record_audit_log(
+        db,
+        &archived.into(),
+        AuditAction::CommentArchive,
+        AuditEntityKind::Comment,
+        ids,
+        None,
+    )?;
+    Ok(count)
 }
@@ -1,7 +1,17 @@
+use super::record_audit_log;
 use crate::core::prelude::*;
 
-pub fn archive_events<D: Db>(db: &D, ids: &[&str]) -> Result<usize> {
+pub fn archive_events<D: Db>(db: &D, ids: &[&str], archived_by_email: &str) -> Result<usize> {
     debug!("Archiving events {:?}", ids);
     let archived = Timestamp::now();
-    Ok(db.archive_events(ids, archived)?)
+    let count = db.archive_events(ids, archived)?;
+    record_audit_log(
+        db,
+        &Activity::now(Some(archived_by_email.into())).into(),
+        AuditAction::EventArchive,
+        AuditEntityKind::Event,
+        ids,
+        None,
+    )?;
+    Ok(count)
 }
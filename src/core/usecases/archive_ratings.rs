@@ -1,16 +1,29 @@
+use super::{authorize_user_by_permission, authorize_user_regions_for_ratings, record_audit_log};
 use crate::core::prelude::*;
+use ofdb_core::user::RolePermissions;
 
-pub fn archive_ratings<D: Db>(db: &D, user_email: &str, ids: &[&str]) -> Result<usize> {
+pub fn archive_ratings<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    user_email: &str,
+    ids: &[&str],
+) -> Result<usize> {
     debug!("Archiving ratings {:?}", ids);
     // TODO: Pass an authentication token with user id and role to
     // check if the user is authorized to perform this use case
-    let user = db.try_get_user_by_email(user_email)?;
-    if let Some(user) = user {
-        if user.role >= Role::Scout {
-            let archived = Activity::now(Some(user_email.into()));
-            db.archive_comments_of_ratings(ids, &archived)?;
-            return Ok(db.archive_ratings(ids, &archived)?);
-        }
-    }
-    Err(ParameterError::Forbidden.into())
+    authorize_user_by_permission(db, permissions, user_email, Permission::RatingArchive)
+        .map_err(|_| ParameterError::Forbidden)?;
+    authorize_user_regions_for_ratings(db, user_email, ids)?;
+    let archived = Activity::now(Some(user_email.into()));
+    db.archive_comments_of_ratings(ids, &archived)?;
+    let count = db.archive_ratings(ids, &archived)?;
+    record_audit_log(
+        db,
+        &archived.into(),
+        AuditAction::RatingArchive,
+        AuditEntityKind::Rating,
+        ids,
+        None,
+    )?;
+    Ok(count)
 }
@@ -0,0 +1,128 @@
+use super::authorize_user_by_permission;
+use crate::core::prelude::*;
+use ofdb_core::user::RolePermissions;
+
+/// Record the same action for multiple entities of the same kind.
+pub fn record_audit_log<D: Db + ?Sized>(
+    db: &D,
+    log: &ActivityLog,
+    action: AuditAction,
+    kind: AuditEntityKind,
+    ids: &[&str],
+    details: Option<String>,
+) -> Result<()> {
+    let entries: Vec<_> = ids
+        .iter()
+        .map(|id| AuditLogEntry {
+            log: log.clone(),
+            action,
+            entity: AuditEntity {
+                kind,
+                id: (*id).to_owned(),
+            },
+            details: details.clone(),
+        })
+        .collect();
+    debug!(
+        "Recording {} audit log entries for {}",
+        entries.len(),
+        action
+    );
+    Ok(db.add_audit_log_entries(&entries)?)
+}
+
+/// Query the audit log, newest entries first.
+pub fn audit_log(
+    db: &dyn Db,
+    permissions: &RolePermissions,
+    login_email: &str,
+    query: &AuditLogQuery,
+    pagination: &Pagination,
+) -> Result<Vec<AuditLogEntry>> {
+    authorize_user_by_permission(db, permissions, login_email, Permission::AuditRead)?;
+    Ok(db.query_audit_log(query, pagination)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        archive_ratings, change_user_role,
+        tests::{create_user, MockDb},
+    };
+    use super::*;
+
+    #[test]
+    fn record_moderation_actions() {
+        let db = MockDb::default();
+        let permissions = RolePermissions::default();
+        create_user(&db, "admin@foo.bar", Role::Admin);
+        create_user(&db, "scout@foo.bar", Role::Scout);
+        create_user(&db, "user@foo.bar", Role::User);
+        db.entries
+            .borrow_mut()
+            .push((Place::build().id("p1").finish(), ReviewStatus::Created));
+        db.ratings.borrow_mut().push(Rating {
+            id: "r1".into(),
+            place_id: "p1".into(),
+            created_at: Timestamp::now(),
+            archived_at: None,
+            title: "title".into(),
+            value: RatingValue::from(1i8),
+            context: RatingContext::Diversity,
+            source: None,
+            author: None,
+        });
+        archive_ratings(&db, &permissions, "scout@foo.bar", &["r1"]).unwrap();
+        change_user_role(
+            &db,
+            &permissions,
+            "admin@foo.bar",
+            "user@foo.bar",
+            Role::Scout,
+        )
+        .unwrap();
+
+        assert!(audit_log(
+            &db,
+            &permissions,
+            "scout@foo.bar",
+            &Default::default(),
+            &Default::default()
+        )
+        .is_err());
+
+        let entries = audit_log(
+            &db,
+            &permissions,
+            "admin@foo.bar",
+            &Default::default(),
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(2, entries.len());
+        assert_eq!(AuditAction::UserChangeRole, entries[0].action);
+        assert_eq!("user@foo.bar", entries[0].entity.id);
+        assert_eq!(Some("scout".into()), entries[0].details);
+        assert_eq!(AuditAction::RatingArchive, entries[1].action);
+        assert_eq!("r1", entries[1].entity.id);
+        assert_eq!(
+            Some("scout@foo.bar"),
+            entries[1].log.activity.by.as_ref().map(|by| by.as_str())
+        );
+
+        let query = AuditLogQuery {
+            actor_email: Some("scout@foo.bar"),
+            ..Default::default()
+        };
+        let entries = audit_log(
+            &db,
+            &permissions,
+            "admin@foo.bar",
+            &query,
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(1, entries.len());
+        assert_eq!(AuditAction::RatingArchive, entries[0].action);
+    }
+}
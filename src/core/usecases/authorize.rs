@@ -1,4 +1,5 @@
 use crate::core::prelude::*;
+use ofdb_core::user::RolePermissions;
 
 pub fn authorize_organization_by_possible_api_tokens<D: OrganizationRepo>(
     db: &D,
@@ -14,15 +15,97 @@ pub fn authorize_organization_by_possible_api_tokens<D: OrganizationRepo>(
     Err(Error::Parameter(ParameterError::Unauthorized))
 }
 
-pub fn authorize_user_by_email(db: &dyn Db, email: &str, min_required_role: Role) -> Result<User> {
+pub fn authorize_user_by_permission(
+    db: &dyn Db,
+    permissions: &RolePermissions,
+    email: &str,
+    permission: Permission,
+) -> Result<User> {
     if let Some(user) = db.try_get_user_by_email(email)? {
-        return ofdb_core::user::authorize_role(&user, min_required_role)
+        return ofdb_core::user::authorize_permission(permissions, &user, permission)
             .map(|()| user)
             .map_err(|_| Error::Parameter(ParameterError::Unauthorized));
     }
     Err(Error::Parameter(ParameterError::Unauthorized))
 }
 
+// Checks if all positions are located within the regions
+// of the user. Users without regions are not restricted.
+fn authorize_user_regions(
+    db: &dyn Db,
+    email: &str,
+    positions: impl IntoIterator<Item = Option<MapPoint>>,
+) -> Result<()> {
+    let regions = db.user_regions_by_email(email)?;
+    if regions.is_empty() {
+        return Ok(());
+    }
+    for pos in positions {
+        ofdb_core::user::authorize_region(&regions, pos)
+            .map_err(|_| Error::Parameter(ParameterError::Forbidden))?;
+    }
+    Ok(())
+}
+
+pub fn authorize_user_regions_for_places(db: &dyn Db, email: &str, ids: &[&str]) -> Result<()> {
+    let places = db.get_places(ids)?;
+    authorize_user_regions(
+        db,
+        email,
+        places
+            .into_iter()
+            .map(|(place, _)| Some(place.location.pos)),
+    )
+}
+
+pub fn authorize_user_regions_for_events(db: &dyn Db, email: &str, ids: &[&str]) -> Result<()> {
+    let events = db.get_events_chronologically(ids)?;
+    authorize_user_regions(
+        db,
+        email,
+        events
+            .into_iter()
+            .map(|event| event.location.map(|location| location.pos)),
+    )
+}
+
+pub fn authorize_user_regions_for_ratings(db: &dyn Db, email: &str, ids: &[&str]) -> Result<()> {
+    let place_ids = db.load_place_ids_of_ratings(ids)?;
+    let place_ids: Vec<_> = place_ids.iter().map(String::as_str).collect();
+    authorize_user_regions_for_places(db, email, &place_ids)
+}
+
+/// Checks if the user is permitted to change the review
+/// status of all places.
+pub fn authorize_user_for_reviewing_places(
+    db: &dyn Db,
+    permissions: &RolePermissions,
+    email: &str,
+    ids: &[&str],
+    status: ReviewStatus,
+) -> Result<User> {
+    let permission = if status == ReviewStatus::Archived {
+        Permission::PlaceArchive
+    } else {
+        Permission::PlaceReview
+    };
+    let user = authorize_user_by_permission(db, permissions, email, permission)?;
+    authorize_user_regions_for_places(db, email, ids)?;
+    Ok(user)
+}
+
+/// Checks if the user is permitted to archive all events.
+pub fn authorize_user_for_archiving_events(
+    db: &dyn Db,
+    permissions: &RolePermissions,
+    email: &str,
+    ids: &[&str],
+) -> Result<User> {
+    let user = authorize_user_by_permission(db, permissions, email, Permission::EventArchive)?;
+    authorize_user_regions_for_events(db, email, ids)?;
+    Ok(user)
+}
+
 // Checks if the addition and removal of tags is permitted.
 //
 // Returns a list with the ids of other organizations that require
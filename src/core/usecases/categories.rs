@@ -0,0 +1,216 @@
+use super::{authorize_user_by_permission, tags::prepare_tag};
+use crate::core::prelude::*;
+use ofdb_core::user::RolePermissions;
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone)]
+pub struct NewCategory {
+    pub tag: String,
+    /// Translated names by language code
+    pub names: BTreeMap<String, String>,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+}
+
+// Hex color codes like "#00ff7f"
+fn is_valid_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn prepare_category<D: Db>(db: &D, id: Id, new: NewCategory) -> Result<Category> {
+    let NewCategory {
+        tag,
+        names,
+        icon,
+        color,
+    } = new;
+    let tag = prepare_tag(&tag)?;
+    if db
+        .all_categories()?
+        .iter()
+        .any(|c| c.tag == tag && c.id != id)
+    {
+        return Err(ParameterError::CategoryTag.into());
+    }
+    let names = names
+        .into_iter()
+        .map(|(lang, name)| (lang.trim().to_lowercase(), name.trim().to_owned()))
+        .filter(|(lang, name)| !lang.is_empty() && !name.is_empty())
+        .collect();
+    let icon = icon.map(|i| i.trim().to_owned()).filter(|i| !i.is_empty());
+    let color = color
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty());
+    if let Some(ref color) = color {
+        if !is_valid_color(color) {
+            return Err(ParameterError::Color.into());
+        }
+    }
+    Ok(Category {
+        id,
+        tag,
+        names,
+        icon,
+        color,
+    })
+}
+
+/// Places are assigned to categories by their tag. Existing
+/// places that are tagged accordingly become part of the new
+/// category.
+pub fn create_category<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    account_email: &str,
+    new: NewCategory,
+) -> Result<Category> {
+    authorize_user_by_permission(db, permissions, account_email, Permission::CategoryManage)?;
+    let category = prepare_category(db, Id::new(), new)?;
+    info!("Creating category #{}", category.tag);
+    db.create_tag_if_it_does_not_exist(&Tag {
+        id: category.tag.clone(),
+    })?;
+    db.create_category(&category)?;
+    Ok(category)
+}
+
+/// The tags of the built-in categories are fixed for
+/// backwards compatibility. Changing the tag of any other
+/// category does not modify the tags of existing places.
+pub fn update_category<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    account_email: &str,
+    id: &str,
+    update: NewCategory,
+) -> Result<Category> {
+    authorize_user_by_permission(db, permissions, account_email, Permission::CategoryManage)?;
+    let old_category = db
+        .all_categories()?
+        .into_iter()
+        .find(|c| c.id.as_str() == id)
+        .ok_or(RepoError::NotFound)?;
+    let category = prepare_category(db, old_category.id, update)?;
+    if Category::is_built_in(id) && category.tag != old_category.tag {
+        return Err(ParameterError::BuiltInCategory.into());
+    }
+    info!("Updating category #{}", category.tag);
+    db.create_tag_if_it_does_not_exist(&Tag {
+        id: category.tag.clone(),
+    })?;
+    db.update_category(&category)?;
+    Ok(category)
+}
+
+/// The tag of the deleted category remains an ordinary
+/// tag of all places.
+pub fn delete_category<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    account_email: &str,
+    id: &str,
+) -> Result<()> {
+    authorize_user_by_permission(db, permissions, account_email, Permission::CategoryManage)?;
+    if Category::is_built_in(id) {
+        return Err(ParameterError::BuiltInCategory.into());
+    }
+    info!("Deleting category {}", id);
+    Ok(db.delete_category(id)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{create_user, MockDb};
+    use super::*;
+
+    fn new_category(tag: &str) -> NewCategory {
+        let mut names = BTreeMap::new();
+        names.insert("en".into(), " Repair ".into());
+        names.insert("DE".into(), "Reparatur".into());
+        names.insert("fr".into(), " ".into());
+        NewCategory {
+            tag: tag.into(),
+            names,
+            icon: Some("wrench".into()),
+            color: Some("#FF8800".into()),
+        }
+    }
+
+    #[test]
+    fn create_update_and_delete_category() {
+        let db = MockDb::default();
+        db.categories.replace(Category::built_in());
+        let permissions = RolePermissions::default();
+        create_user(&db, "admin@foo.bar", Role::Admin);
+        let category =
+            create_category(&db, &permissions, "admin@foo.bar", new_category("#Repair")).unwrap();
+        assert_eq!("repair", category.tag);
+        assert_eq!(2, category.names.len());
+        assert_eq!("Repair", category.names["en"]);
+        assert_eq!("Reparatur", category.names["de"]);
+        assert_eq!(Some("#ff8800"), category.color.as_deref());
+        assert!(db.all_tags().unwrap().iter().any(|t| t.id == "repair"));
+        assert_eq!(4, db.all_categories().unwrap().len());
+
+        let updated = update_category(
+            &db,
+            &permissions,
+            "admin@foo.bar",
+            category.id.as_str(),
+            NewCategory {
+                icon: None,
+                ..new_category("repair-cafe")
+            },
+        )
+        .unwrap();
+        assert_eq!(category.id, updated.id);
+        assert_eq!("repair-cafe", updated.tag);
+        assert!(updated.icon.is_none());
+
+        assert!(delete_category(&db, &permissions, "admin@foo.bar", category.id.as_str()).is_ok());
+        assert_eq!(Category::built_in(), db.all_categories().unwrap());
+    }
+
+    #[test]
+    fn reject_invalid_categories() {
+        let db = MockDb::default();
+        db.categories.replace(Category::built_in());
+        let permissions = RolePermissions::default();
+        create_user(&db, "admin@foo.bar", Role::Admin);
+        assert!(
+            create_category(&db, &permissions, "user@foo.bar", new_category("repair")).is_err()
+        );
+        assert!(
+            create_category(&db, &permissions, "admin@foo.bar", new_category("event")).is_err()
+        );
+        let invalid_color = NewCategory {
+            color: Some("red".into()),
+            ..new_category("repair")
+        };
+        assert!(create_category(&db, &permissions, "admin@foo.bar", invalid_color).is_err());
+        assert!(update_category(
+            &db,
+            &permissions,
+            "admin@foo.bar",
+            Category::ID_EVENT,
+            new_category("meetup")
+        )
+        .is_err());
+        assert!(
+            delete_category(&db, &permissions, "admin@foo.bar", Category::ID_COMMERCIAL).is_err()
+        );
+
+        // Built-in categories may be renamed
+        let event = update_category(
+            &db,
+            &permissions,
+            "admin@foo.bar",
+            Category::ID_EVENT,
+            new_category("event"),
+        )
+        .unwrap();
+        assert_eq!("Repair", event.names["en"]);
+        assert_eq!(3, db.all_categories().unwrap().len());
+    }
+}
@@ -0,0 +1,120 @@
+use super::{super::util::validate, refresh_user_token_for_new_email};
+use crate::core::prelude::*;
+
+/// Request to change the e-mail address of an existing user.
+///
+/// The returned token must be sent to the new address. The
+/// change only takes effect after the token has been confirmed.
+pub fn request_email_change<D: Db>(
+    db: &D,
+    email: &str,
+    password: &str,
+    new_email: &str,
+) -> Result<EmailNonce> {
+    let user = db.get_user_by_email(email)?;
+    if !user.password.verify(password) {
+        return Err(Error::Parameter(ParameterError::Credentials));
+    }
+    validate::email(new_email)?;
+    if db.try_get_user_by_email(new_email)?.is_some() {
+        return Err(ParameterError::UserExists.into());
+    }
+    info!(
+        "Requesting to change e-mail of user ({}) into {}",
+        email, new_email
+    );
+    refresh_user_token_for_new_email(db, user.email, new_email.to_owned())
+}
+
+/// Apply the change of the e-mail address after the
+/// corresponding user token has been consumed.
+pub fn change_email_with_user_token<D: Db>(db: &D, token: UserToken) -> Result<User> {
+    let UserToken {
+        email_nonce,
+        new_email,
+        ..
+    } = token;
+    let new_email = new_email.ok_or(ParameterError::TokenInvalid)?;
+    // Another user might have registered with the same
+    // address in the meantime
+    if db.try_get_user_by_email(&new_email)?.is_some() {
+        return Err(ParameterError::UserExists.into());
+    }
+    info!(
+        "Changing e-mail of user ({}) into {}",
+        email_nonce.email, new_email
+    );
+    db.update_user_email(&email_nonce.email, &new_email)?;
+    let mut user = db.get_user_by_email(&new_email)?;
+    // The new address has been confirmed by the token
+    if !user.email_confirmed {
+        user.email_confirmed = true;
+        if user.role == Role::Guest {
+            user.role = Role::User;
+        }
+        db.update_user(&user)?;
+    }
+    Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        consume_user_token, refresh_user_token,
+        tests::{create_user, MockDb},
+    };
+    use super::*;
+
+    #[test]
+    fn change_email_after_confirmation() {
+        let db = MockDb::default();
+        create_user(&db, "old@foo.bar", Role::User);
+        let email_nonce =
+            request_email_change(&db, "old@foo.bar", "secret", "new@foo.bar").unwrap();
+        assert_eq!("old@foo.bar", email_nonce.email);
+        // Nothing changed yet
+        assert!(db.try_get_user_by_email("old@foo.bar").unwrap().is_some());
+        assert!(db.try_get_user_by_email("new@foo.bar").unwrap().is_none());
+
+        let token = consume_user_token(&db, &email_nonce).unwrap();
+        let user = change_email_with_user_token(&db, token).unwrap();
+        assert_eq!("new@foo.bar", user.email);
+        assert!(user.email_confirmed);
+        assert!(db.try_get_user_by_email("old@foo.bar").unwrap().is_none());
+        assert!(db.get_user_by_email("new@foo.bar").is_ok());
+    }
+
+    #[test]
+    fn reject_email_change_with_wrong_password() {
+        let db = MockDb::default();
+        create_user(&db, "old@foo.bar", Role::User);
+        assert!(request_email_change(&db, "old@foo.bar", "wrong", "new@foo.bar").is_err());
+        assert!(db.token.borrow().is_empty());
+    }
+
+    #[test]
+    fn reject_email_change_to_existing_user() {
+        let db = MockDb::default();
+        create_user(&db, "old@foo.bar", Role::User);
+        create_user(&db, "new@foo.bar", Role::User);
+        match request_email_change(&db, "old@foo.bar", "secret", "new@foo.bar")
+            .err()
+            .unwrap()
+        {
+            Error::Parameter(ParameterError::UserExists) => {
+                // ok
+            }
+            _ => panic!("invalid error"),
+        }
+    }
+
+    #[test]
+    fn reject_email_change_with_password_reset_token() {
+        let db = MockDb::default();
+        create_user(&db, "old@foo.bar", Role::User);
+        let email_nonce = refresh_user_token(&db, "old@foo.bar".into()).unwrap();
+        let token = consume_user_token(&db, &email_nonce).unwrap();
+        assert!(change_email_with_user_token(&db, token).is_err());
+        assert!(db.get_user_by_email("old@foo.bar").is_ok());
+    }
+}
@@ -0,0 +1,72 @@
+use crate::core::prelude::*;
+
+pub fn change_password<D: Db>(
+    db: &D,
+    email: &str,
+    old_password: &str,
+    new_password: Password,
+) -> Result<()> {
+    let mut user = db.get_user_by_email(email)?;
+    if !user.password.verify(old_password) {
+        return Err(Error::Parameter(ParameterError::Credentials));
+    }
+    info!("Changing password for user ({})", email);
+    user.password = new_password;
+    db.update_user(&user)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::MockDb;
+    use super::*;
+
+    fn new_user(email: &str, password: &str) -> User {
+        User {
+            email: email.into(),
+            email_confirmed: true,
+            password: password.parse::<Password>().unwrap(),
+            role: Role::User,
+        }
+    }
+
+    #[test]
+    fn change_password_with_old_password() {
+        let db = MockDb::default();
+        db.users
+            .borrow_mut()
+            .push(new_user("a@foo.bar", "old secret"));
+        assert!(change_password(
+            &db,
+            "a@foo.bar",
+            "old secret",
+            "new secret".parse::<Password>().unwrap()
+        )
+        .is_ok());
+        assert!(db.users.borrow()[0].password.verify("new secret"));
+        assert!(!db.users.borrow()[0].password.verify("old secret"));
+    }
+
+    #[test]
+    fn reject_password_change_with_wrong_old_password() {
+        let db = MockDb::default();
+        db.users
+            .borrow_mut()
+            .push(new_user("a@foo.bar", "old secret"));
+        match change_password(
+            &db,
+            "a@foo.bar",
+            "wrong secret",
+            "new secret".parse::<Password>().unwrap(),
+        )
+        .err()
+        .unwrap()
+        {
+            Error::Parameter(ParameterError::Credentials) => {
+                // ok
+            }
+            _ => panic!("invalid error"),
+        }
+        assert!(db.users.borrow()[0].password.verify("old secret"));
+    }
+}
@@ -1,7 +1,9 @@
 use crate::core::prelude::*;
+use ofdb_core::user::{authorize_permission, RolePermissions};
 
 pub fn change_user_role<D: Db>(
     db: &D,
+    permissions: &RolePermissions,
     account_email: &str,
     user_email: &str,
     role: Role,
@@ -16,9 +18,22 @@ pub fn change_user_role<D: Db>(
     let mut user = db
         .try_get_user_by_email(user_email)?
         .ok_or(ParameterError::UserDoesNotExist)?;
-    if account.role > user.role && role < account.role {
+    authorize_permission(permissions, &account, Permission::UserChangeRole)
+        .map_err(|_| ParameterError::Forbidden)?;
+    // Nobody is allowed to change the role of users that are not
+    // outranked by the own role or to grant roles that are not
+    // outranked by the own role
+    if permissions.outranks(account.role, user.role) && permissions.outranks(account.role, role) {
         user.role = role;
         db.update_user(&user)?;
+        super::record_audit_log(
+            db,
+            &Activity::now(Some(account_email.into())).into(),
+            AuditAction::UserChangeRole,
+            AuditEntityKind::User,
+            &[user_email],
+            Some(format!("{:?}", role).to_lowercase()),
+        )?;
         Ok(())
     } else {
         Err(ParameterError::Forbidden.into())
@@ -0,0 +1,239 @@
+use super::authorize_user_by_permission;
+use crate::core::prelude::*;
+use ofdb_core::user::RolePermissions;
+use std::collections::HashMap;
+
+/// Links of visible places and events that need to be checked.
+#[derive(Debug, Default)]
+pub struct LinkCheckPlan {
+    /// Links that have never been checked or not recently
+    /// together with all places and events that contain them
+    pub due: Vec<(Url, Vec<LinkOwner>)>,
+    /// Recent results of links that have been checked
+    /// for other places or events
+    pub reused: Vec<LinkCheck>,
+    /// Checks of links that are no longer contained in
+    /// their place or event
+    pub obsolete: Vec<LinkCheck>,
+}
+
+pub fn links_of_place(place: &Place) -> Vec<&Url> {
+    let mut links = vec![];
+    if let Some(ref l) = place.links {
+        links.extend(l.homepage.iter());
+        links.extend(l.image.iter());
+        links.extend(l.image_href.iter());
+        links.extend(l.custom.iter().map(|c| &c.url));
+    }
+    links
+}
+
+pub fn links_of_event(event: &Event) -> Vec<&Url> {
+    event
+        .homepage
+        .iter()
+        .chain(event.image_url.iter())
+        .chain(event.image_link_url.iter())
+        .collect()
+}
+
+/// Find all links that have not been checked since `checked_before`.
+pub fn plan_link_checks<D: Db>(db: &D, checked_before: TimestampMs) -> Result<LinkCheckPlan> {
+    // All links and their owners in a deterministic order
+    let mut current: Vec<(Url, Vec<LinkOwner>)> = vec![];
+    let mut current_index: HashMap<String, usize> = HashMap::new();
+    let mut add_link = |url: &Url, owner: LinkOwner| {
+        let idx = *current_index
+            .entry(url.as_str().to_owned())
+            .or_insert_with(|| {
+                current.push((url.clone(), vec![]));
+                current.len() - 1
+            });
+        let owners = &mut current[idx].1;
+        if !owners.contains(&owner) {
+            owners.push(owner);
+        }
+    };
+    for (place, status) in db.all_places()? {
+        if !status.exists() {
+            continue;
+        }
+        for url in links_of_place(&place) {
+            add_link(
+                url,
+                LinkOwner {
+                    kind: LinkOwnerKind::Place,
+                    id: place.id.clone(),
+                },
+            );
+        }
+    }
+    for event in db.all_events_chronologically()? {
+        if event.archived.is_some() {
+            continue;
+        }
+        for url in links_of_event(&event) {
+            add_link(
+                url,
+                LinkOwner {
+                    kind: LinkOwnerKind::Event,
+                    id: event.id.clone(),
+                },
+            );
+        }
+    }
+
+    let existing = db.query_link_checks(&Default::default(), &Default::default())?;
+    let mut latest: HashMap<&str, &LinkCheck> = HashMap::new();
+    for check in &existing {
+        let entry = latest.entry(check.url.as_str()).or_insert(check);
+        if check.checked_at > entry.checked_at {
+            *entry = check;
+        }
+    }
+
+    let mut plan = LinkCheckPlan::default();
+    for check in &existing {
+        let is_current = current_index
+            .get(check.url.as_str())
+            .map_or(false, |idx| current[*idx].1.contains(&check.owner));
+        if !is_current {
+            plan.obsolete.push(check.clone());
+        }
+    }
+    for (url, owners) in current {
+        match latest.get(url.as_str()) {
+            Some(latest) if latest.checked_at >= checked_before => {
+                for owner in owners {
+                    let up_to_date = existing.iter().any(|c| {
+                        c.owner == owner && c.url == url && c.checked_at >= latest.checked_at
+                    });
+                    if !up_to_date {
+                        plan.reused.push(LinkCheck {
+                            owner,
+                            url: url.clone(),
+                            outcome: latest.outcome.clone(),
+                            checked_at: latest.checked_at,
+                        });
+                    }
+                }
+            }
+            _ => plan.due.push((url, owners)),
+        }
+    }
+    Ok(plan)
+}
+
+/// Ids of places that contain at least one link with the given status.
+pub fn place_ids_with_link_status<D: Db>(db: &D, status: LinkStatus) -> Result<Vec<Id>> {
+    let query = LinkCheckQuery {
+        owner_kind: Some(LinkOwnerKind::Place),
+        status: Some(status),
+    };
+    let mut ids: Vec<_> = db
+        .query_link_checks(&query, &Default::default())?
+        .into_iter()
+        .map(|c| c.owner.id)
+        .collect();
+    ids.dedup();
+    Ok(ids)
+}
+
+/// Report the results of link checks, ordered by place or event.
+pub fn link_check_report(
+    db: &dyn Db,
+    permissions: &RolePermissions,
+    login_email: &str,
+    query: &LinkCheckQuery,
+    pagination: &Pagination,
+) -> Result<Vec<LinkCheck>> {
+    authorize_user_by_permission(db, permissions, login_email, Permission::LinkReport)?;
+    Ok(db.query_link_checks(query, pagination)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::MockDb;
+    use super::*;
+
+    fn outcome(status: LinkStatus) -> LinkCheckOutcome {
+        LinkCheckOutcome {
+            status,
+            http_status: None,
+            redirect_url: None,
+        }
+    }
+
+    fn owner(id: &str) -> LinkOwner {
+        LinkOwner {
+            kind: LinkOwnerKind::Place,
+            id: id.into(),
+        }
+    }
+
+    #[test]
+    fn plan_checks_of_new_outdated_and_removed_links() {
+        let db = MockDb::default();
+        let mut p1 = Place::build().id("p1").finish();
+        p1.links = Some(Links {
+            homepage: Some("https://foo.org/".parse().unwrap()),
+            image: Some("https://img.org/a.png".parse().unwrap()),
+            ..Default::default()
+        });
+        let mut p2 = Place::build().id("p2").finish();
+        p2.links = Some(Links {
+            homepage: Some("https://foo.org/".parse().unwrap()),
+            ..Default::default()
+        });
+        let mut archived = Place::build().id("p3").finish();
+        archived.links = p1.links.clone();
+        db.entries.borrow_mut().push((p1, ReviewStatus::Created));
+        db.entries.borrow_mut().push((p2, ReviewStatus::Confirmed));
+        db.entries
+            .borrow_mut()
+            .push((archived, ReviewStatus::Archived));
+        let now = TimestampMs::now();
+        let recently = TimestampMs::from_inner(now.into_inner() - 1_000);
+        let long_ago = TimestampMs::from_inner(now.into_inner() - 1_000_000);
+        db.save_link_checks(&[
+            // Recently checked for p1 but not yet for p2
+            LinkCheck {
+                owner: owner("p1"),
+                url: "https://foo.org/".parse().unwrap(),
+                outcome: outcome(LinkStatus::Broken),
+                checked_at: recently,
+            },
+            LinkCheck {
+                owner: owner("p1"),
+                url: "https://img.org/a.png".parse().unwrap(),
+                outcome: outcome(LinkStatus::Ok),
+                checked_at: long_ago,
+            },
+            // The link has been removed from p2
+            LinkCheck {
+                owner: owner("p2"),
+                url: "https://bar.org/".parse().unwrap(),
+                outcome: outcome(LinkStatus::Ok),
+                checked_at: recently,
+            },
+        ])
+        .unwrap();
+
+        let checked_before = TimestampMs::from_inner(now.into_inner() - 10_000);
+        let plan = plan_link_checks(&db, checked_before).unwrap();
+        assert_eq!(1, plan.due.len());
+        assert_eq!("https://img.org/a.png", plan.due[0].0.as_str());
+        assert_eq!(vec![owner("p1")], plan.due[0].1);
+        assert_eq!(1, plan.reused.len());
+        assert_eq!(owner("p2"), plan.reused[0].owner);
+        assert_eq!(LinkStatus::Broken, plan.reused[0].outcome.status);
+        assert_eq!(1, plan.obsolete.len());
+        assert_eq!("https://bar.org/", plan.obsolete[0].url.as_str());
+
+        db.save_link_checks(&plan.reused).unwrap();
+        assert_eq!(
+            vec![Id::from("p1"), Id::from("p2")],
+            place_ids_with_link_status(&db, LinkStatus::Broken).unwrap()
+        );
+    }
+}
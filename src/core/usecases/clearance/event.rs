@@ -0,0 +1,82 @@
+use crate::core::prelude::*;
+
+use std::collections::HashMap;
+
+pub(crate) fn add_pending_clearance<R: EventClearanceRepo>(
+    repo: &R,
+    org_ids: &[Id],
+    pending_clearance: &PendingClearanceForEvent,
+) -> Result<usize> {
+    Ok(repo.add_pending_clearance_for_events(org_ids, pending_clearance)?)
+}
+
+pub fn count_pending_clearances<R: OrganizationRepo + EventClearanceRepo>(
+    repo: &R,
+    org: &Organization,
+) -> Result<u64> {
+    Ok(repo.count_pending_clearances_for_events(&org.id)?)
+}
+
+pub fn list_pending_clearances<R: OrganizationRepo + EventClearanceRepo>(
+    repo: &R,
+    org: &Organization,
+    pagination: &Pagination,
+) -> Result<Vec<PendingClearanceForEvent>> {
+    Ok(repo.list_pending_clearances_for_events(&org.id, pagination)?)
+}
+
+pub fn update_pending_clearances<R: OrganizationRepo + EventClearanceRepo>(
+    repo: &R,
+    org: &Organization,
+    clearances: &[ClearanceForEvent],
+) -> Result<usize> {
+    let count = repo.update_pending_clearances_for_events(&org.id, clearances)?;
+    log::info!(
+        "Updated {} of {} pending clearance(s) for events on behalf of organization '{}'",
+        count,
+        clearances.len(),
+        org.name
+    );
+    Ok(count)
+}
+
+pub fn clear_repo_results<R: EventClearanceRepo>(
+    repo: &R,
+    org_id: &Id,
+    org_tag: &str,
+    results: Vec<Event>,
+) -> Result<Vec<Event>> {
+    let event_ids: Vec<_> = results.iter().map(|e| e.id.as_str()).collect();
+    let pending_clearances = repo.load_pending_clearances_for_events(org_id, &event_ids)?;
+    if pending_clearances.is_empty() {
+        // No filtering required
+        return Ok(results);
+    }
+    let mut pending_clearances: HashMap<_, _> = pending_clearances
+        .into_iter()
+        .map(|p| (p.event_id.to_string(), p))
+        .collect();
+    let mut cleared_results = Vec::with_capacity(results.len());
+    for event in results.into_iter() {
+        let pending_clearance = pending_clearances.remove(event.id.as_str());
+        if let Some(pending_clearance) = pending_clearance {
+            if let Some(last_cleared_event) = pending_clearance.last_cleared_event {
+                if !last_cleared_event
+                    .tags
+                    .iter()
+                    .map(String::as_str)
+                    .any(|tag| tag == org_tag)
+                {
+                    // Remove previously untagged events from the result
+                    continue;
+                }
+                // Replace the actual/current event with the last cleared state
+                cleared_results.push(last_cleared_event);
+            }
+            // Skip newly created but not yet cleared events
+            continue;
+        }
+        cleared_results.push(event);
+    }
+    Ok(cleared_results)
+}
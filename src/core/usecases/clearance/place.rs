@@ -41,6 +41,90 @@ pub fn update_pending_clearances<R: OrganizationRepo + PlaceClearanceRepo>(
     Ok(count)
 }
 
+/// A rejected place revision. The editor is recorded in `place.created`.
+#[derive(Debug, Clone)]
+pub struct RejectedPlace {
+    pub place: Place,
+    pub reason: String,
+}
+
+pub fn reject_pending_clearances<D: Db>(
+    db: &D,
+    org: &Organization,
+    rejections: &[RejectionForPlace],
+) -> Result<Vec<RejectedPlace>> {
+    if rejections.iter().any(|r| r.reason.trim().is_empty()) {
+        return Err(ParameterError::EmptyReason.into());
+    }
+    let place_ids: Vec<_> = rejections.iter().map(|r| r.place_id.as_str()).collect();
+    let pending_clearances: HashMap<_, _> = db
+        .load_pending_clearances_for_places(&org.id, &place_ids)?
+        .into_iter()
+        .map(|p| (p.place_id.to_string(), p))
+        .collect();
+    let mut rejected_places = Vec::with_capacity(rejections.len());
+    let mut reverted_place_ids = Vec::with_capacity(rejections.len());
+    for rejection in rejections {
+        let RejectionForPlace {
+            place_id,
+            rejected_revision,
+            reason,
+            revert,
+        } = rejection;
+        let pending_clearance = match pending_clearances.get(place_id.as_str()) {
+            Some(pending_clearance) => pending_clearance,
+            None => {
+                log::debug!("No pending clearance for place {}", place_id);
+                continue;
+            }
+        };
+        let (place, _) = if let Some(rejected_revision) = rejected_revision {
+            db.load_place_revision(place_id.as_str(), *rejected_revision)?
+        } else {
+            db.get_place(place_id.as_str())?
+        };
+        if let Some(last_cleared_revision) = pending_clearance.last_cleared_revision {
+            if place.revision <= last_cleared_revision {
+                return Err(ParameterError::ClearedRevision.into());
+            }
+        }
+        let log = ActivityLog {
+            activity: Activity::now(None),
+            context: Some(org.id.to_string()),
+            comment: None,
+        };
+        let details = format!(
+            "revision {}: {}",
+            RevisionValue::from(place.revision),
+            reason
+        );
+        super::super::record_audit_log(
+            db,
+            &log,
+            AuditAction::PlaceRejectClearance,
+            AuditEntityKind::Place,
+            &[place_id.as_str()],
+            Some(details),
+        )?;
+        if *revert {
+            reverted_place_ids.push(place_id.as_str());
+        }
+        rejected_places.push(RejectedPlace {
+            place,
+            reason: reason.clone(),
+        });
+    }
+    let revert_count = db.revert_pending_clearances_for_places(&org.id, &reverted_place_ids)?;
+    log::info!(
+        "Rejected {} of {} pending clearance(s) for places and kept the last cleared revision of {} place(s) on behalf of organization '{}'",
+        rejected_places.len(),
+        rejections.len(),
+        revert_count,
+        org.name
+    );
+    Ok(rejected_places)
+}
+
 pub fn clear_repo_results<R: PlaceRepo + PlaceClearanceRepo>(
     repo: &R,
     org_id: &Id,
@@ -0,0 +1,233 @@
+use crate::core::prelude::*;
+
+#[rustfmt::skip]
+#[derive(Debug, Clone)]
+pub struct NewRatingComment {
+    pub text        : String,
+    pub reply_to    : Option<String>,
+    pub is_response : bool,
+}
+
+/// Add a comment to an existing rating.
+///
+/// Only the owners of a place are permitted to respond, i.e.
+/// the creator of the place, the user who has verified the
+/// ownership of the place or an organization that moderates
+/// one of its tags.
+pub fn comment_rating<D: Db>(
+    db: &D,
+    rating_id: &str,
+    author: CommentAuthor,
+    c: NewRatingComment,
+) -> Result<Comment> {
+    let NewRatingComment {
+        text,
+        reply_to,
+        is_response,
+    } = c;
+    let text = text.trim().to_owned();
+    if text.is_empty() {
+        return Err(Error::Parameter(ParameterError::EmptyComment));
+    }
+    // Only unarchived ratings could be commented
+    let rating = db.load_rating(rating_id)?;
+    let reply_to = if let Some(reply_to) = reply_to {
+        let parent = db.load_comment(&reply_to).map_err(|err| match err {
+            RepoError::NotFound => Error::Parameter(ParameterError::ReplyToComment),
+            err => err.into(),
+        })?;
+        if parent.rating_id != rating.id {
+            return Err(Error::Parameter(ParameterError::ReplyToComment));
+        }
+        Some(parent.id)
+    } else {
+        None
+    };
+    if is_response && !is_place_owner(db, rating.place_id.as_ref(), &author)? {
+        return Err(Error::Parameter(ParameterError::Forbidden));
+    }
+    let comment = Comment {
+        id: Id::new(),
+        rating_id: rating.id,
+        created_at: Timestamp::now(),
+        archived_at: None,
+        text,
+        author: Some(author),
+        reply_to,
+        is_response,
+    };
+    db.create_comment(comment.clone())?;
+    Ok(comment)
+}
+
+fn is_place_owner<D: Db>(db: &D, place_id: &str, author: &CommentAuthor) -> Result<bool> {
+    match author {
+        CommentAuthor::User(email) => {
+            if let Some(ownership) = db.get_place_ownership(place_id)? {
+                if &ownership.owner == email {
+                    return Ok(true);
+                }
+            }
+            let (place, _) = db.load_place_revision(place_id, Revision::initial())?;
+            Ok(place.created.by.as_ref() == Some(email))
+        }
+        CommentAuthor::Organization(org_id) => {
+            let (place, _) = db.get_place(place_id)?;
+            Ok(db
+                .get_moderated_tags_by_org(None)?
+                .into_iter()
+                .any(|(id, moderated_tag)| {
+                    &id == org_id && place.tags.iter().any(|tag| tag == &moderated_tag.label)
+                }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::MockDb;
+    use super::*;
+
+    fn setup() -> MockDb {
+        let mut db = MockDb::default();
+        let mut place = Place::build().id("p1").tags(vec!["foo", "bar"]).finish();
+        place.created.by = Some("creator@foo.bar".into());
+        db.entries.borrow_mut().push((place, ReviewStatus::Created));
+        db.orgs = vec![
+            Organization {
+                id: "moderator".into(),
+                name: "Moderator".into(),
+                api_token: "moderator".into(),
+                moderated_tags: vec![ModeratedTag {
+                    label: "bar".into(),
+                    allow_add: true,
+                    allow_remove: true,
+                    require_clearance: false,
+                }],
+            },
+            Organization {
+                id: "other".into(),
+                name: "Other".into(),
+                api_token: "other".into(),
+                moderated_tags: vec![ModeratedTag {
+                    label: "baz".into(),
+                    allow_add: true,
+                    allow_remove: true,
+                    require_clearance: false,
+                }],
+            },
+        ];
+        db.ratings.borrow_mut().push(Rating {
+            id: "r1".into(),
+            place_id: "p1".into(),
+            created_at: Timestamp::now(),
+            archived_at: None,
+            title: "title".into(),
+            value: RatingValue::from(1i8),
+            context: RatingContext::Diversity,
+            source: None,
+            author: None,
+        });
+        db.ratings.borrow_mut().push(Rating {
+            id: "r2".into(),
+            place_id: "p1".into(),
+            created_at: Timestamp::now(),
+            archived_at: None,
+            title: "title".into(),
+            value: RatingValue::from(1i8),
+            context: RatingContext::Fairness,
+            source: None,
+            author: None,
+        });
+        db
+    }
+
+    fn new_comment(text: &str, reply_to: Option<&str>, is_response: bool) -> NewRatingComment {
+        NewRatingComment {
+            text: text.into(),
+            reply_to: reply_to.map(Into::into),
+            is_response,
+        }
+    }
+
+    #[test]
+    fn reply_to_comment_of_the_same_rating() {
+        let db = setup();
+        let user = CommentAuthor::User("user@foo.bar".into());
+        assert!(comment_rating(&db, "r1", user.clone(), new_comment(" ", None, false)).is_err());
+        let first =
+            comment_rating(&db, "r1", user.clone(), new_comment("first", None, false)).unwrap();
+        let reply = comment_rating(
+            &db,
+            "r1",
+            user.clone(),
+            new_comment("reply", Some(first.id.as_str()), false),
+        )
+        .unwrap();
+        assert_eq!(Some(first.id.clone()), reply.reply_to);
+        assert_eq!(Some(user.clone()), reply.author);
+        assert!(comment_rating(
+            &db,
+            "r2",
+            user,
+            new_comment("reply", Some(first.id.as_str()), false)
+        )
+        .is_err());
+        assert_eq!(2, db.load_comments_of_rating("r1").unwrap().len());
+    }
+
+    #[test]
+    fn only_owners_are_permitted_to_respond() {
+        let db = setup();
+        for author in &[
+            CommentAuthor::User("user@foo.bar".into()),
+            CommentAuthor::Organization("other".into()),
+        ] {
+            assert!(comment_rating(
+                &db,
+                "r1",
+                author.clone(),
+                new_comment("response", None, true)
+            )
+            .is_err());
+        }
+        for author in &[
+            CommentAuthor::User("creator@foo.bar".into()),
+            CommentAuthor::Organization("moderator".into()),
+        ] {
+            let comment = comment_rating(
+                &db,
+                "r1",
+                author.clone(),
+                new_comment("response", None, true),
+            )
+            .unwrap();
+            assert!(comment.is_response);
+        }
+    }
+
+    #[test]
+    fn verified_owners_are_permitted_to_respond() {
+        let db = setup();
+        let owner = CommentAuthor::User("owner@foo.bar".into());
+        assert!(comment_rating(
+            &db,
+            "r1",
+            owner.clone(),
+            new_comment("response", None, true)
+        )
+        .is_err());
+        db.set_place_owner("p1", "owner@foo.bar").unwrap();
+        let comment =
+            comment_rating(&db, "r1", owner, new_comment("response", None, true)).unwrap();
+        assert!(comment.is_response);
+        // The creator is still permitted to respond
+        assert!(comment_rating(
+            &db,
+            "r1",
+            CommentAuthor::User("creator@foo.bar".into()),
+            new_comment("response", None, true)
+        )
+        .is_ok());
+    }
+}
@@ -74,7 +74,7 @@ pub fn prepare_new_place<D: Db>(
     let categories: Vec<_> = categories.into_iter().map(Id::from).collect();
     let old_tags = vec![];
     let new_tags = super::prepare_tag_list(
-        Category::merge_ids_into_tags(&categories, tags)
+        Category::merge_ids_into_tags_with(&db.all_categories()?, &categories, tags)
             .iter()
             .map(String::as_str),
     );
@@ -1,18 +1,19 @@
 use crate::core::prelude::*;
+use ofdb_core::user::RolePermissions;
 
 pub fn export_event<'a>(
     event: Event,
+    permissions: &RolePermissions,
     role: Role,
     moderated_tags: impl IntoIterator<Item = &'a str>,
 ) -> Event {
-    if role < Role::Admin {
-        let event = super::filter_event(event, moderated_tags);
-        if role < Role::Scout {
-            event.strip_contact_details()
-        } else {
-            event
-        }
-    } else {
+    if permissions.is_granted(role, Permission::AuthorRead) {
+        return event;
+    }
+    let event = super::filter_event(event, moderated_tags);
+    if permissions.is_granted(role, Permission::EventExport) {
         event
+    } else {
+        event.strip_contact_details()
     }
 }
@@ -1,18 +1,19 @@
 use crate::core::prelude::*;
+use ofdb_core::user::RolePermissions;
 
 pub fn export_place<'a>(
     place: Place,
+    permissions: &RolePermissions,
     role: Role,
     moderated_tags: impl IntoIterator<Item = &'a str>,
 ) -> Place {
-    if role < Role::Admin {
-        let place = super::filter_place(place, moderated_tags);
-        if role < Role::Scout {
-            place.strip_contact_details()
-        } else {
-            place
-        }
-    } else {
+    if permissions.is_granted(role, Permission::AuthorRead) {
+        return place;
+    }
+    let place = super::filter_place(place, moderated_tags);
+    if permissions.is_granted(role, Permission::PlaceExport) {
         place
+    } else {
+        place.strip_contact_details()
     }
 }
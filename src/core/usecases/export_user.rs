@@ -0,0 +1,30 @@
+use crate::core::prelude::*;
+
+/// All data that is linked to a user account
+#[derive(Debug, Clone)]
+pub struct UserExport {
+    pub user: User,
+    pub place_revisions: Vec<(Place, ReviewStatus)>,
+    pub ratings: Vec<Rating>,
+    pub bbox_subscriptions: Vec<BboxSubscription>,
+    pub user_tokens: Vec<UserToken>,
+}
+
+pub fn export_user<D: Db>(db: &D, email: &str) -> Result<UserExport> {
+    let user = db.get_user_by_email(email)?;
+    let place_revisions = db.load_place_revisions_created_by(email)?;
+    let ratings = db.load_ratings_created_by(email)?;
+    let bbox_subscriptions = db.all_bbox_subscriptions_by_email(email)?;
+    let user_tokens = match db.get_user_token_by_email(email) {
+        Ok(token) => vec![token],
+        Err(RepoError::NotFound) => vec![],
+        Err(err) => return Err(err.into()),
+    };
+    Ok(UserExport {
+        user,
+        place_revisions,
+        ratings,
+        bbox_subscriptions,
+        user_tokens,
+    })
+}
@@ -0,0 +1,78 @@
+use super::authorize_user_by_permission;
+use crate::core::prelude::*;
+use ofdb_core::user::RolePermissions;
+
+/// Report a rating for review by scouts.
+pub fn flag_rating<D: Db>(
+    db: &D,
+    id: &str,
+    flagged_by: Option<&str>,
+    comment: Option<String>,
+) -> Result<()> {
+    // Only unarchived ratings could be flagged
+    let rating = db.load_rating(id)?;
+    let comment = comment
+        .map(|comment| comment.trim().to_owned())
+        .filter(|comment| !comment.is_empty());
+    info!("Flagging rating {}", rating.id);
+    let activity = Activity::now(flagged_by.map(Into::into));
+    Ok(db.flag_rating(id, &activity, comment.as_deref())?)
+}
+
+/// All flagged ratings that await a review by scouts.
+pub fn flagged_ratings(
+    db: &dyn Db,
+    permissions: &RolePermissions,
+    email: &str,
+    pagination: &Pagination,
+) -> Result<Vec<FlaggedRating>> {
+    authorize_user_by_permission(db, permissions, email, Permission::RatingArchive)?;
+    Ok(db.load_flagged_ratings(pagination)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        archive_ratings,
+        tests::{create_user, MockDb},
+    };
+    use super::*;
+
+    #[test]
+    fn archived_ratings_are_no_longer_flagged() {
+        let db = MockDb::default();
+        let permissions = RolePermissions::default();
+        create_user(&db, "scout@foo.bar", Role::Scout);
+        db.entries
+            .borrow_mut()
+            .push((Place::build().id("p1").finish(), ReviewStatus::Created));
+        db.ratings.borrow_mut().push(Rating {
+            id: "r1".into(),
+            place_id: "p1".into(),
+            created_at: Timestamp::now(),
+            archived_at: None,
+            title: "title".into(),
+            value: RatingValue::from(1i8),
+            context: RatingContext::Diversity,
+            source: None,
+            author: None,
+        });
+        assert!(flag_rating(&db, "r2", None, None).is_err());
+        flag_rating(&db, "r1", None, Some(" spam ".into())).unwrap();
+        flag_rating(&db, "r1", Some("scout@foo.bar"), Some("".into())).unwrap();
+
+        let flagged =
+            flagged_ratings(&db, &permissions, "scout@foo.bar", &Default::default()).unwrap();
+        assert_eq!(1, flagged.len());
+        assert_eq!(2, flagged[0].flags.len());
+        assert_eq!(None, flagged[0].flags[0].comment);
+        assert_eq!(Some("spam".into()), flagged[0].flags[1].comment);
+
+        archive_ratings(&db, &permissions, "scout@foo.bar", &["r1"]).unwrap();
+        assert!(
+            flagged_ratings(&db, &permissions, "scout@foo.bar", &Default::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use crate::core::{
+    prelude::*,
+    util::{image::process_uploaded_image, validate},
+};
+use ofdb_core::gateways::image::ImageStorageGateway;
+
+#[rustfmt::skip]
+#[derive(Debug, Clone)]
+pub struct NewImage {
+    pub license     : String,
+    pub attribution : Option<String>,
+}
+
+/// Process an uploaded image and write both the scaled image
+/// and its thumbnail to the storage.
+///
+/// Processing is expensive and doesn't need any database
+/// access. The image must be recorded afterwards with
+/// [`add_stored_image`].
+pub fn store_image(
+    storage: &dyn ImageStorageGateway,
+    data: &[u8],
+    new_image: NewImage,
+    created_by_email: Option<&str>,
+) -> Result<Image> {
+    let NewImage {
+        license,
+        attribution,
+    } = new_image;
+    let attribution = attribution
+        .map(|a| a.trim().to_owned())
+        .filter(|a| !a.is_empty());
+    if validate::image_license(&license)? && attribution.is_none() {
+        return Err(ParameterError::MissingAttribution.into());
+    }
+    let processed = process_uploaded_image(data)?;
+    let image = Image {
+        id: Id::new(),
+        format: processed.format,
+        width: processed.width,
+        height: processed.height,
+        license,
+        attribution,
+        uploaded: Activity::now(created_by_email.map(Into::into)),
+    };
+    let content_type = image.format.content_type();
+    storage.store(
+        &image.storage_key(ImageVariant::Original),
+        content_type,
+        &processed.data,
+    )?;
+    if let Err(err) = storage.store(
+        &image.storage_key(ImageVariant::Thumbnail),
+        content_type,
+        &processed.thumbnail,
+    ) {
+        delete_image_files(storage, &image);
+        return Err(err.into());
+    }
+    Ok(image)
+}
+
+/// Record the metadata of a stored image.
+///
+/// The stored files are deleted if the image could not be
+/// recorded to prevent orphaned files.
+pub fn add_stored_image<D: Db>(
+    db: &D,
+    storage: &dyn ImageStorageGateway,
+    image: &Image,
+) -> Result<()> {
+    if let Err(err) = db.add_image(image) {
+        delete_image_files(storage, image);
+        return Err(err.into());
+    }
+    info!(
+        "Uploaded image {} ({}x{})",
+        image.id, image.width, image.height
+    );
+    Ok(())
+}
+
+fn delete_image_files(storage: &dyn ImageStorageGateway, image: &Image) {
+    for variant in &[ImageVariant::Original, ImageVariant::Thumbnail] {
+        let key = image.storage_key(*variant);
+        if let Err(err) = storage.delete(&key) {
+            warn!("Failed to delete stored image file {}: {}", key, err);
+        }
+    }
+}
+
+/// Uploaded images are referenced by their URL below the
+/// base URL of the images API, e.g. `{base}/{id}` or
+/// `{base}/{id}/thumbnail`. References to images that don't
+/// exist are rejected. Other URLs are still accepted.
+pub fn check_image_url<D: Db>(db: &D, image_base_url: &str, image_url: &str) -> Result<()> {
+    let prefix = format!("{}/", image_base_url.trim_end_matches('/'));
+    if !image_url.starts_with(&prefix) {
+        return Ok(());
+    }
+    let id = image_url[prefix.len()..]
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    match db.get_image(id) {
+        Ok(_) => Ok(()),
+        Err(RepoError::NotFound) => Err(ParameterError::Image.into()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Load the metadata and the content of a stored image.
+pub fn load_image<D: Db>(
+    db: &D,
+    storage: &dyn ImageStorageGateway,
+    id: &str,
+    variant: ImageVariant,
+) -> Result<(Image, Vec<u8>)> {
+    let image = db.get_image(id)?;
+    let data = storage
+        .load(&image.storage_key(variant))?
+        .ok_or(RepoError::NotFound)?;
+    Ok((image, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::MockDb;
+    use super::*;
+    use ofdb_core::gateways::image::ImageStorageError;
+    use std::{cell::RefCell, collections::HashMap};
+
+    #[derive(Default)]
+    struct MockStorage(RefCell<HashMap<String, Vec<u8>>>);
+
+    impl ImageStorageGateway for MockStorage {
+        fn store(
+            &self,
+            key: &str,
+            _: &str,
+            data: &[u8],
+        ) -> std::result::Result<(), ImageStorageError> {
+            self.0.borrow_mut().insert(key.into(), data.to_vec());
+            Ok(())
+        }
+
+        fn load(&self, key: &str) -> std::result::Result<Option<Vec<u8>>, ImageStorageError> {
+            Ok(self.0.borrow().get(key).cloned())
+        }
+
+        fn delete(&self, key: &str) -> std::result::Result<(), ImageStorageError> {
+            self.0.borrow_mut().remove(key);
+            Ok(())
+        }
+    }
+
+    fn gif() -> Vec<u8> {
+        // 1x1 transparent GIF
+        vec![
+            0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0xff, 0xff, 0xff, 0x21, 0xf9, 0x04, 0x01, 0x00, 0x00, 0x00, 0x00, 0x2c,
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x02, 0x02, 0x44, 0x01, 0x00,
+            0x3b,
+        ]
+    }
+
+    #[test]
+    fn upload_and_load_image() {
+        let db = MockDb::default();
+        let storage = MockStorage::default();
+        let image = store_image(
+            &storage,
+            &gif(),
+            NewImage {
+                license: "CC0-1.0".into(),
+                attribution: Some(" ".into()),
+            },
+            None,
+        )
+        .unwrap();
+        assert_eq!(ImageFormat::Png, image.format);
+        assert_eq!((1, 1), (image.width, image.height));
+        assert!(image.attribution.is_none());
+        assert_eq!(2, storage.0.borrow().len());
+        add_stored_image(&db, &storage, &image).unwrap();
+        let (loaded, data) =
+            load_image(&db, &storage, image.id.as_str(), ImageVariant::Thumbnail).unwrap();
+        assert_eq!(image, loaded);
+        assert!(data.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn require_attribution_for_cc_by_licenses() {
+        let storage = MockStorage::default();
+        let new_image = NewImage {
+            license: "CC-BY-SA-4.0".into(),
+            attribution: None,
+        };
+        assert!(matches!(
+            store_image(&storage, &gif(), new_image.clone(), None),
+            Err(Error::Parameter(ParameterError::MissingAttribution))
+        ));
+        assert!(matches!(
+            store_image(
+                &storage,
+                &gif(),
+                NewImage {
+                    license: "proprietary".into(),
+                    ..new_image
+                },
+                None
+            ),
+            Err(Error::Parameter(ParameterError::License))
+        ));
+        assert!(storage.0.borrow().is_empty());
+    }
+
+    #[test]
+    fn delete_stored_files_if_the_image_could_not_be_recorded() {
+        let db = MockDb::default();
+        let storage = MockStorage::default();
+        let new_image = NewImage {
+            license: "CC0-1.0".into(),
+            attribution: None,
+        };
+        let image = store_image(&storage, &gif(), new_image, None).unwrap();
+        add_stored_image(&db, &storage, &image).unwrap();
+        // Recording the same image twice fails
+        assert!(add_stored_image(&db, &storage, &image).is_err());
+        assert!(storage.0.borrow().is_empty());
+    }
+
+    #[test]
+    fn check_references_to_uploaded_images() {
+        let db = MockDb::default();
+        let storage = MockStorage::default();
+        let new_image = NewImage {
+            license: "CC0-1.0".into(),
+            attribution: None,
+        };
+        let image = store_image(&storage, &gif(), new_image, None).unwrap();
+        add_stored_image(&db, &storage, &image).unwrap();
+        let base_url = "https://example.com/v0/images/";
+        let url = format!("https://example.com/v0/images/{}", image.id);
+        assert!(check_image_url(&db, base_url, &url).is_ok());
+        assert!(check_image_url(&db, base_url, &format!("{}/thumbnail", url)).is_ok());
+        assert!(check_image_url(&db, base_url, "https://elsewhere.org/image.png").is_ok());
+        assert!(matches!(
+            check_image_url(&db, base_url, "https://example.com/v0/images/unknown"),
+            Err(Error::Parameter(ParameterError::Image))
+        ));
+    }
+}
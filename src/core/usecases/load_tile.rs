@@ -0,0 +1,20 @@
+use crate::core::prelude::*;
+use ofdb_core::tile::{self, TileCoord};
+
+/// The maximum number of places per tile. The places
+/// with the highest rating are preferred.
+const MAX_TILE_PLACES: usize = 5_000;
+
+/// Load all visible places within a map tile and its buffer.
+pub fn load_tile_places(index: &dyn PlaceIndex, tile: TileCoord) -> Result<Vec<IndexedPlace>> {
+    let query = IndexQuery {
+        include_bbox: Some(tile.bbox(tile::BUFFER)),
+        // Only visible places
+        status: Some(vec![]),
+        ..Default::default()
+    };
+    let places = index
+        .query_places(&query, MAX_TILE_PLACES)
+        .map_err(RepoError::Other)?;
+    Ok(places)
+}
@@ -8,12 +8,19 @@ use crate::core::{
     },
 };
 
+mod anonymize_user;
 mod archive_comments;
 mod archive_events;
 mod archive_ratings;
+mod audit_log;
 mod authorize;
+mod categories;
+mod change_email;
+mod change_password;
 mod change_user_role;
+mod check_links;
 pub mod clearance;
+mod comment_rating;
 mod confirm_email;
 mod confirm_email_and_reset_password;
 mod create_new_place;
@@ -21,30 +28,46 @@ mod create_new_user;
 mod delete_event;
 mod export_event;
 mod export_place;
+mod export_user;
 mod filter_event;
 mod filter_place;
 mod find_duplicates;
+mod flag_rating;
+mod images;
 mod indexing;
 mod load_places;
+mod load_tile;
 mod login;
+mod place_edit_suggestions;
+mod place_ownership;
 mod query_events;
 mod rate_place;
 mod register;
 mod review_places;
+mod saved_searches;
 mod search;
+mod statistics;
 mod store_event;
+mod suggest;
+mod tags;
+mod taxonomy;
 mod update_place;
+mod user_regions;
 mod user_tokens;
 
 #[cfg(test)]
 pub mod tests;
 
 pub use self::{
-    archive_comments::*, archive_events::*, archive_ratings::*, authorize::*, change_user_role::*,
-    confirm_email::*, confirm_email_and_reset_password::*, create_new_place::*, create_new_user::*,
-    delete_event::*, export_event::*, export_place::*, filter_event::*, filter_place::*,
-    find_duplicates::*, indexing::*, load_places::*, login::*, query_events::*, rate_place::*,
-    register::*, review_places::*, search::*, store_event::*, update_place::*, user_tokens::*,
+    anonymize_user::*, archive_comments::*, archive_events::*, archive_ratings::*, audit_log::*,
+    authorize::*, categories::*, change_email::*, change_password::*, change_user_role::*,
+    check_links::*, comment_rating::*, confirm_email::*, confirm_email_and_reset_password::*,
+    create_new_place::*, create_new_user::*, delete_event::*, export_event::*, export_place::*,
+    export_user::*, filter_event::*, filter_place::*, find_duplicates::*, flag_rating::*,
+    images::*, indexing::*, load_places::*, load_tile::*, login::*, place_edit_suggestions::*,
+    place_ownership::*, query_events::*, rate_place::*, register::*, review_places::*,
+    saved_searches::*, search::*, statistics::*, store_event::*, suggest::*, tags::*, taxonomy::*,
+    update_place::*, user_regions::*, user_tokens::*,
 };
 
 //TODO: move usecases into separate files
@@ -77,6 +100,9 @@ pub struct EventQuery {
     pub start_max: Option<Timestamp>,
     pub tags: Option<Vec<String>>,
     pub text: Option<String>,
+    // Only show events that have been cleared by the organization
+    // that owns this tag
+    pub org_tag: Option<String>,
 
     pub limit: Option<usize>,
 }
@@ -90,6 +116,7 @@ impl EventQuery {
             ref start_max,
             ref tags,
             ref text,
+            ref org_tag,
             ref limit,
         } = self;
         bbox.is_none()
@@ -98,6 +125,7 @@ impl EventQuery {
             && start_max.is_none()
             && tags.is_none()
             && text.is_none()
+            && org_tag.is_none()
             && limit.is_none()
     }
 }
@@ -0,0 +1,372 @@
+use super::{update_place::prepare_place_revision, Storable, UpdatePlace};
+use crate::core::prelude::*;
+use ofdb_core::user::RolePermissions;
+
+/// Who is reviewing suggestions for places.
+#[derive(Debug, Clone, Copy)]
+pub enum SuggestionReviewer<'a> {
+    /// The owner of the place or a user with the
+    /// permission to review places
+    User(&'a str),
+    /// An organization that moderates tags of the place
+    Organization(&'a Organization),
+}
+
+/// File a modification of a place that is reviewed before
+/// it becomes a new revision.
+///
+/// Unlike a direct update neither moderated tags nor
+/// locked fields prevent the suggestion.
+pub fn suggest_place_edit<D: Db>(
+    db: &D,
+    place_id: Id,
+    update: UpdatePlace,
+    created_by_email: Option<&str>,
+) -> Result<PlaceEditSuggestion> {
+    let (old_place, suggested) = prepare_place_revision(db, place_id, update, created_by_email)?;
+    let suggestion = PlaceEditSuggestion {
+        id: Id::new(),
+        base_revision: old_place.revision,
+        suggested,
+        status: SuggestionStatus::Pending,
+        reviewed: None,
+    };
+    db.add_place_edit_suggestion(&suggestion)?;
+    info!(
+        "Added suggestion {} for place {}",
+        suggestion.id, suggestion.suggested.id
+    );
+    Ok(suggestion)
+}
+
+/// Load pending suggestions.
+///
+/// Without a place users need the permission to review places
+/// and organizations only get suggestions for places that are
+/// tagged with one of their moderated tags.
+pub fn load_pending_place_edit_suggestions<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    reviewer: SuggestionReviewer,
+    place_id: Option<&str>,
+    pagination: &Pagination,
+) -> Result<Vec<PlaceEditSuggestion>> {
+    let moderated_tags;
+    let mut query = PlaceEditSuggestionQuery {
+        place_id,
+        status: Some(SuggestionStatus::Pending),
+        ..Default::default()
+    };
+    if let Some(place_id) = place_id {
+        let (place, _) = db.get_place(place_id)?;
+        authorize_reviewer(db, permissions, reviewer, &place, None)?;
+    } else {
+        match reviewer {
+            SuggestionReviewer::User(email) => {
+                super::authorize_user_by_permission(
+                    db,
+                    permissions,
+                    email,
+                    Permission::PlaceReview,
+                )?;
+            }
+            SuggestionReviewer::Organization(org) => {
+                moderated_tags = org
+                    .moderated_tags
+                    .iter()
+                    .map(|t| t.label.clone())
+                    .collect::<Vec<_>>();
+                if moderated_tags.is_empty() {
+                    return Ok(vec![]);
+                }
+                query.tags = &moderated_tags;
+            }
+        }
+    }
+    Ok(db.query_place_edit_suggestions(&query, pagination)?)
+}
+
+/// Accept a pending suggestion.
+///
+/// The returned place revision still needs to be stored.
+pub fn accept_place_edit_suggestion<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    reviewer: SuggestionReviewer,
+    id: &str,
+) -> Result<Storable> {
+    let suggestion = db.get_place_edit_suggestion(id)?;
+    if suggestion.status != SuggestionStatus::Pending {
+        return Err(ParameterError::SuggestionNotPending.into());
+    }
+    let (old_place, _) = db.get_place(suggestion.suggested.id.as_str())?;
+    authorize_reviewer(db, permissions, reviewer, &old_place, Some(&suggestion))?;
+    if old_place.revision != suggestion.base_revision {
+        return Err(ParameterError::OutdatedSuggestion.into());
+    }
+    let PlaceEditSuggestion { suggested, .. } = suggestion;
+    // The submitter remains the author of the new revision
+    let place = Place {
+        created: Activity::now(suggested.created.by.clone()),
+        ..suggested
+    };
+    // Only the responsible organization is allowed to accept
+    // the addition or removal of its moderated tags
+    let (reviewer_email, reviewer_org) = match reviewer {
+        SuggestionReviewer::User(email) => (Some(email), None),
+        SuggestionReviewer::Organization(org) => (None, Some(org)),
+    };
+    let clearance_org_ids =
+        super::authorize_editing_of_tagged_entry(db, &old_place.tags, &place.tags, reviewer_org)?;
+    super::authorize_editing_of_locked_fields(db, &old_place, &place, reviewer_email)?;
+    let log = review_log(reviewer, None);
+    db.review_place_edit_suggestion(id, SuggestionStatus::Accepted, &log)?;
+    super::record_audit_log(
+        db,
+        &log,
+        AuditAction::PlaceAcceptSuggestion,
+        AuditEntityKind::Place,
+        &[place.id.as_str()],
+        Some(format!("suggestion {}", id)),
+    )?;
+    Ok(Storable {
+        place,
+        clearance_org_ids,
+        last_cleared_revision: old_place.revision,
+    })
+}
+
+/// Decline a pending suggestion.
+pub fn decline_place_edit_suggestion<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    reviewer: SuggestionReviewer,
+    id: &str,
+    reason: Option<String>,
+) -> Result<()> {
+    let suggestion = db.get_place_edit_suggestion(id)?;
+    if suggestion.status != SuggestionStatus::Pending {
+        return Err(ParameterError::SuggestionNotPending.into());
+    }
+    let (place, _) = db.get_place(suggestion.suggested.id.as_str())?;
+    authorize_reviewer(db, permissions, reviewer, &place, Some(&suggestion))?;
+    let log = review_log(reviewer, reason);
+    db.review_place_edit_suggestion(id, SuggestionStatus::Declined, &log)?;
+    super::record_audit_log(
+        db,
+        &log,
+        AuditAction::PlaceDeclineSuggestion,
+        AuditEntityKind::Place,
+        &[place.id.as_str()],
+        Some(format!("suggestion {}", id)),
+    )?;
+    Ok(())
+}
+
+fn review_log(reviewer: SuggestionReviewer, comment: Option<String>) -> ActivityLog {
+    match reviewer {
+        SuggestionReviewer::User(email) => ActivityLog {
+            activity: Activity::now(Some(email.into())),
+            context: None,
+            comment,
+        },
+        SuggestionReviewer::Organization(org) => ActivityLog {
+            activity: Activity::now(None),
+            context: Some(org.id.to_string()),
+            comment,
+        },
+    }
+}
+
+fn authorize_reviewer<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    reviewer: SuggestionReviewer,
+    place: &Place,
+    suggestion: Option<&PlaceEditSuggestion>,
+) -> Result<()> {
+    match reviewer {
+        SuggestionReviewer::User(email) => {
+            let is_owner = db
+                .get_place_ownership(place.id.as_str())?
+                .map_or(false, |ownership| ownership.owner.as_str() == email);
+            if !is_owner {
+                super::authorize_user_by_permission(
+                    db,
+                    permissions,
+                    email,
+                    Permission::PlaceReview,
+                )?;
+                super::authorize_user_regions_for_places(db, email, &[place.id.as_str()])?;
+            }
+        }
+        SuggestionReviewer::Organization(org) => {
+            let is_moderated = |tags: &[String]| {
+                org.moderated_tags
+                    .iter()
+                    .any(|moderated_tag| tags.contains(&moderated_tag.label))
+            };
+            if !is_moderated(&place.tags)
+                && !suggestion.map_or(false, |s| is_moderated(&s.suggested.tags))
+            {
+                return Err(ParameterError::Forbidden.into());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{create_user, MockDb};
+    use super::*;
+
+    fn setup() -> MockDb {
+        let mut db = MockDb::default();
+        let place = Place::build()
+            .id("p1")
+            .title("foo")
+            .tags(vec!["moderated"])
+            .finish();
+        db.entries.borrow_mut().push((place, ReviewStatus::Created));
+        db.orgs = vec![Organization {
+            id: "org".into(),
+            name: "org".into(),
+            api_token: "secret".into(),
+            moderated_tags: vec![ModeratedTag {
+                label: "moderated".into(),
+                allow_add: false,
+                allow_remove: false,
+                require_clearance: false,
+            }],
+        }];
+        create_user(&db, "scout@foo.bar", Role::Scout);
+        db
+    }
+
+    fn suggest_removal_of_moderated_tag(db: &MockDb) -> PlaceEditSuggestion {
+        let (place, _) = db.get_place("p1").unwrap();
+        let mut update = UpdatePlace::from(place.clone());
+        update.version = place.revision.next().into();
+        update.tags = vec![];
+        update.telephone = Some("123".into());
+        assert!(matches!(
+            super::super::prepare_updated_place(db, place.id.clone(), update.clone(), None, None),
+            Err(Error::Parameter(ParameterError::ModeratedTag))
+        ));
+        suggest_place_edit(db, place.id, update, None).unwrap()
+    }
+
+    #[test]
+    fn accept_suggestion_by_organization() {
+        let db = setup();
+        let suggestion = suggest_removal_of_moderated_tag(&db);
+        assert_eq!(SuggestionStatus::Pending, suggestion.status);
+        let org = db.orgs[0].clone();
+        let pending = load_pending_place_edit_suggestions(
+            &db,
+            &Default::default(),
+            SuggestionReviewer::Organization(&org),
+            None,
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(vec![suggestion.clone()], pending);
+
+        // Only the responsible organization is allowed to accept
+        // the removal of the moderated tag
+        assert!(matches!(
+            accept_place_edit_suggestion(
+                &db,
+                &Default::default(),
+                SuggestionReviewer::User("scout@foo.bar"),
+                suggestion.id.as_str(),
+            ),
+            Err(Error::Parameter(ParameterError::ModeratedTag))
+        ));
+        let storable = accept_place_edit_suggestion(
+            &db,
+            &Default::default(),
+            SuggestionReviewer::Organization(&org),
+            suggestion.id.as_str(),
+        )
+        .unwrap();
+        assert!(storable.place.tags.is_empty());
+        assert_eq!(
+            Some("123"),
+            storable
+                .place
+                .contact
+                .as_ref()
+                .and_then(|c| c.phone.as_deref())
+        );
+        assert_eq!(
+            SuggestionStatus::Accepted,
+            db.get_place_edit_suggestion(suggestion.id.as_str())
+                .unwrap()
+                .status
+        );
+        assert_eq!(1, db.audit_log.borrow().len());
+
+        // A suggestion could only be reviewed once
+        assert!(matches!(
+            decline_place_edit_suggestion(
+                &db,
+                &Default::default(),
+                SuggestionReviewer::Organization(&org),
+                suggestion.id.as_str(),
+                None,
+            ),
+            Err(Error::Parameter(ParameterError::SuggestionNotPending))
+        ));
+    }
+
+    #[test]
+    fn decline_suggestion_by_scout() {
+        let db = setup();
+        let suggestion = suggest_removal_of_moderated_tag(&db);
+        assert!(matches!(
+            decline_place_edit_suggestion(
+                &db,
+                &Default::default(),
+                SuggestionReviewer::User("nobody@foo.bar"),
+                suggestion.id.as_str(),
+                None,
+            ),
+            Err(Error::Parameter(ParameterError::Unauthorized))
+        ));
+        decline_place_edit_suggestion(
+            &db,
+            &Default::default(),
+            SuggestionReviewer::User("scout@foo.bar"),
+            suggestion.id.as_str(),
+            Some("wrong".into()),
+        )
+        .unwrap();
+        let suggestion = db
+            .get_place_edit_suggestion(suggestion.id.as_str())
+            .unwrap();
+        assert_eq!(SuggestionStatus::Declined, suggestion.status);
+        assert_eq!(
+            Some("wrong"),
+            suggestion.reviewed.unwrap().comment.as_deref()
+        );
+    }
+
+    #[test]
+    fn reject_outdated_suggestion() {
+        let db = setup();
+        let suggestion = suggest_removal_of_moderated_tag(&db);
+        db.entries.borrow_mut()[0].0.revision = suggestion.base_revision.next();
+        let org = db.orgs[0].clone();
+        assert!(matches!(
+            accept_place_edit_suggestion(
+                &db,
+                &Default::default(),
+                SuggestionReviewer::Organization(&org),
+                suggestion.id.as_str(),
+            ),
+            Err(Error::Parameter(ParameterError::OutdatedSuggestion))
+        ));
+    }
+}
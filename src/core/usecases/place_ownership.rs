@@ -0,0 +1,177 @@
+use super::refresh_user_token_for_place_ownership;
+use crate::core::prelude::*;
+
+/// Request to become the owner of a place.
+///
+/// The returned token must be sent to the contact e-mail
+/// address of the place. The ownership only takes effect
+/// after the token has been confirmed.
+pub fn request_place_ownership<D: Db>(
+    db: &D,
+    user_email: &str,
+    place_id: &str,
+) -> Result<(EmailNonce, Place)> {
+    let user = db.get_user_by_email(user_email)?;
+    if !user.email_confirmed {
+        return Err(ParameterError::EmailNotConfirmed.into());
+    }
+    let (place, _) = db.get_place(place_id)?;
+    if place
+        .contact
+        .as_ref()
+        .and_then(|c| c.email.as_ref())
+        .is_none()
+    {
+        return Err(ParameterError::MissingContactEmail.into());
+    }
+    info!(
+        "Requesting ownership of place {} for user ({})",
+        place.id, user.email
+    );
+    let email_nonce = refresh_user_token_for_place_ownership(db, user.email, place.id.to_string())?;
+    Ok((email_nonce, place))
+}
+
+/// Assign the owner of a place after the
+/// corresponding user token has been consumed.
+pub fn confirm_place_ownership_with_user_token<D: Db>(
+    db: &D,
+    token: UserToken,
+) -> Result<PlaceOwnership> {
+    let UserToken {
+        email_nonce,
+        place_id,
+        ..
+    } = token;
+    let place_id = place_id.ok_or(ParameterError::TokenInvalid)?;
+    info!(
+        "Assigning user ({}) as owner of place {}",
+        email_nonce.email, place_id
+    );
+    Ok(db.set_place_owner(&place_id, &email_nonce.email)?)
+}
+
+/// Lock fields of a place that should only be modified by its owner.
+pub fn lock_place_fields<D: Db>(
+    db: &D,
+    user_email: &str,
+    place_id: &str,
+    fields: &[PlaceField],
+) -> Result<()> {
+    let ownership = db
+        .get_place_ownership(place_id)?
+        .ok_or(ParameterError::Forbidden)?;
+    if ownership.owner.as_str() != user_email {
+        return Err(ParameterError::Forbidden.into());
+    }
+    let mut unique_fields = Vec::with_capacity(fields.len());
+    for field in fields {
+        if !unique_fields.contains(field) {
+            unique_fields.push(*field);
+        }
+    }
+    Ok(db.update_locked_place_fields(place_id, &unique_fields)?)
+}
+
+/// Reject modifications of locked fields by anyone but the owner.
+pub fn authorize_editing_of_locked_fields<D: Db>(
+    db: &D,
+    old: &Place,
+    new: &Place,
+    editor_email: Option<&str>,
+) -> Result<()> {
+    let ownership = match db.get_place_ownership(old.id.as_str())? {
+        Some(ownership) => ownership,
+        None => return Ok(()),
+    };
+    if editor_email == Some(ownership.owner.as_str()) {
+        return Ok(());
+    }
+    if let Some(field) = ownership
+        .locked_fields
+        .into_iter()
+        .find(|field| field.is_modified(old, new))
+    {
+        return Err(ParameterError::LockedField(field).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        consume_user_token,
+        tests::{create_user, MockDb},
+    };
+    use super::*;
+
+    fn setup() -> MockDb {
+        let db = MockDb::default();
+        create_user(&db, "owner@foo.bar", Role::User);
+        let mut place = Place::build().id("p1").title("foo").finish();
+        place.contact = Some(Contact {
+            email: Some("contact@foo.bar".into()),
+            ..Default::default()
+        });
+        db.entries.borrow_mut().push((place, ReviewStatus::Created));
+        db
+    }
+
+    #[test]
+    fn claim_ownership_after_confirmation() {
+        let db = setup();
+        let (email_nonce, place) = request_place_ownership(&db, "owner@foo.bar", "p1").unwrap();
+        assert_eq!("owner@foo.bar", email_nonce.email);
+        assert_eq!("p1", place.id.as_str());
+        assert!(db.get_place_ownership("p1").unwrap().is_none());
+        let token = consume_user_token(&db, &email_nonce).unwrap();
+        let ownership = confirm_place_ownership_with_user_token(&db, token).unwrap();
+        assert_eq!("owner@foo.bar", ownership.owner.as_str());
+        assert_eq!(Some(ownership), db.get_place_ownership("p1").unwrap());
+    }
+
+    #[test]
+    fn claim_ownership_without_contact_email() {
+        let db = setup();
+        db.entries.borrow_mut()[0].0.contact = None;
+        assert!(matches!(
+            request_place_ownership(&db, "owner@foo.bar", "p1"),
+            Err(Error::Parameter(ParameterError::MissingContactEmail))
+        ));
+    }
+
+    #[test]
+    fn only_the_owner_can_lock_fields() {
+        let db = setup();
+        db.set_place_owner("p1", "owner@foo.bar").unwrap();
+        assert!(matches!(
+            lock_place_fields(&db, "other@foo.bar", "p1", &[PlaceField::Title]),
+            Err(Error::Parameter(ParameterError::Forbidden))
+        ));
+        lock_place_fields(&db, "owner@foo.bar", "p1", &[PlaceField::Title]).unwrap();
+        assert_eq!(
+            vec![PlaceField::Title],
+            db.get_place_ownership("p1").unwrap().unwrap().locked_fields
+        );
+    }
+
+    #[test]
+    fn reject_modification_of_locked_fields() {
+        let db = setup();
+        db.set_place_owner("p1", "owner@foo.bar").unwrap();
+        db.update_locked_place_fields("p1", &[PlaceField::Title])
+            .unwrap();
+        let (old, _) = db.get_place("p1").unwrap();
+        let mut new = old.clone();
+        new.description = "bar".into();
+        assert!(authorize_editing_of_locked_fields(&db, &old, &new, None).is_ok());
+        new.title = "bar".into();
+        assert!(matches!(
+            authorize_editing_of_locked_fields(&db, &old, &new, Some("other@foo.bar")),
+            Err(Error::Parameter(ParameterError::LockedField(
+                PlaceField::Title
+            )))
+        ));
+        assert!(authorize_editing_of_locked_fields(&db, &old, &new, Some("owner@foo.bar")).is_ok());
+    }
+}
@@ -3,7 +3,7 @@ use crate::core::{
     prelude::*,
     util::{extract_hash_tags, remove_hash_tags},
 };
-use ofdb_core::{bbox, tag};
+use ofdb_core::bbox;
 
 const DEFAULT_RESULT_LIMIT: usize = 100;
 
@@ -20,6 +20,7 @@ pub fn query_events<D: Db>(db: &D, index: &dyn IdIndex, query: EventQuery) -> Re
         start_max,
         tags,
         text,
+        org_tag,
         limit,
     } = query;
 
@@ -30,6 +31,10 @@ pub fn query_events<D: Db>(db: &D, index: &dyn IdIndex, query: EventQuery) -> Re
             hash_tags.push(hashtag.to_owned());
         }
     }
+    let mut hash_tags = super::canonical_hash_tags(db, hash_tags)?;
+    if let Some(ref org_tag) = org_tag {
+        hash_tags.push(org_tag.to_owned());
+    }
 
     let text = text.as_deref().map(remove_hash_tags).and_then(|text| {
         if text.trim().is_empty() {
@@ -39,16 +44,18 @@ pub fn query_events<D: Db>(db: &D, index: &dyn IdIndex, query: EventQuery) -> Re
         }
     });
 
-    let text_tags = text
-        .as_deref()
-        .map(tag::split_text_into_tags)
-        .unwrap_or_default();
+    let text_tags = match text.as_deref() {
+        Some(text) => super::split_text_into_tags_with_synonyms(db, text)?,
+        None => vec![],
+    };
+    let hash_tag_alternatives = super::hash_tag_alternatives(db, &hash_tags)?;
 
     let visible_events_query = IndexQuery {
         include_bbox: visible_bbox,
         exclude_bbox: None,
         categories: vec![Category::ID_EVENT],
         hash_tags,
+        hash_tag_alternatives,
         text_tags,
         text,
         ts_min_lb: start_min,
@@ -111,5 +118,11 @@ pub fn query_events<D: Db>(db: &D, index: &dyn IdIndex, query: EventQuery) -> Re
         }
     }
 
+    if let Some(ref org_tag) = org_tag {
+        if let Some(org_id) = db.map_tag_to_clearance_org_id(org_tag)? {
+            events = super::clearance::event::clear_repo_results(db, &org_id, org_tag, events)?;
+        }
+    }
+
     Ok(events)
 }
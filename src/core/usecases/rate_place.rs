@@ -25,7 +25,12 @@ impl Storable {
     }
 }
 
-pub fn prepare_new_rating<D: Db>(db: &D, r: NewPlaceRating) -> Result<Storable> {
+/// Each author is only allowed to rate a place once per context.
+pub fn prepare_new_rating<D: Db>(
+    db: &D,
+    r: NewPlaceRating,
+    author: RatingAuthor,
+) -> Result<Storable> {
     if r.comment.is_empty() {
         return Err(Error::Parameter(ParameterError::EmptyComment));
     }
@@ -38,6 +43,18 @@ pub fn prepare_new_rating<D: Db>(db: &D, r: NewPlaceRating) -> Result<Storable>
     let comment_id = Id::new();
     let (place, status) = db.get_place(&r.entry)?;
     debug_assert_eq!(place.id, r.entry.as_str().into());
+    let context = r.context.into();
+    if db
+        .load_ratings_of_place(place.id.as_ref())?
+        .into_iter()
+        .any(|rating| rating.context == context && rating.author.as_ref() == Some(&author))
+    {
+        return Err(Error::Parameter(ParameterError::RatingExists));
+    }
+    let comment_author = match author {
+        RatingAuthor::User(ref email) => Some(CommentAuthor::User(email.clone())),
+        RatingAuthor::AnonymousSession(_) => None,
+    };
     let rating = Rating {
         id: rating_id.clone(),
         place_id: r.entry.into(),
@@ -45,8 +62,9 @@ pub fn prepare_new_rating<D: Db>(db: &D, r: NewPlaceRating) -> Result<Storable>
         archived_at: None,
         title: r.title,
         value: r_value,
-        context: r.context.into(),
+        context,
         source: r.source,
+        author: Some(author),
     };
     let comment = Comment {
         id: comment_id,
@@ -54,6 +72,9 @@ pub fn prepare_new_rating<D: Db>(db: &D, r: NewPlaceRating) -> Result<Storable>
         created_at: now,
         archived_at: None,
         text: r.comment,
+        author: comment_author,
+        reply_to: None,
+        is_response: false,
     };
     Ok(Storable(place, status, rating, comment))
 }
@@ -75,6 +96,10 @@ mod tests {
     use super::super::*;
     use super::*;
 
+    fn anonymous() -> RatingAuthor {
+        RatingAuthor::AnonymousSession("session".into())
+    }
+
     #[test]
     fn rate_non_existing_entry() {
         let db = MockDb::default();
@@ -89,6 +114,7 @@ mod tests {
                 value: ofdb_boundary::RatingValue::from(2),
                 source: Some("source".into()),
             },
+            anonymous(),
         )
         .is_err());
     }
@@ -109,6 +135,7 @@ mod tests {
                 value: ofdb_boundary::RatingValue::from(2),
                 source: Some("source".into()),
             },
+            anonymous(),
         )
         .is_err());
     }
@@ -129,6 +156,7 @@ mod tests {
                 value: ofdb_boundary::RatingValue::from(3),
                 source: Some("source".into()),
             },
+            anonymous(),
         )
         .is_err());
         assert!(prepare_new_rating(
@@ -142,6 +170,7 @@ mod tests {
                 value: ofdb_boundary::RatingValue::from(-2),
                 source: Some("source".into()),
             },
+            anonymous(),
         )
         .is_err());
     }
@@ -162,6 +191,7 @@ mod tests {
                 value: ofdb_boundary::RatingValue::from(2),
                 source: Some("source".into()),
             },
+            anonymous(),
         )
         .unwrap();
         assert!(store_new_rating(&db, c).is_ok());
@@ -171,4 +201,50 @@ mod tests {
         assert_eq!(db.ratings.borrow()[0].place_id, "foo".into());
         assert_eq!(db.comments.borrow()[0].rating_id, db.ratings.borrow()[0].id);
     }
+
+    #[test]
+    fn rate_only_once_per_context() {
+        let mut db = MockDb::default();
+        let p = Place::build().id("foo").finish();
+        db.entries = vec![(p, ReviewStatus::Created)].into();
+        let new_rating = |context| NewPlaceRating {
+            entry: "foo".into(),
+            comment: "comment".into(),
+            title: "title".into(),
+            context,
+            user: None,
+            value: ofdb_boundary::RatingValue::from(2),
+            source: None,
+        };
+        let user = RatingAuthor::User("user@example.com".into());
+        let c = prepare_new_rating(
+            &db,
+            new_rating(ofdb_boundary::RatingContext::Fairness),
+            user.clone(),
+        )
+        .unwrap();
+        assert!(store_new_rating(&db, c).is_ok());
+        assert_eq!(Some(user.clone()), db.ratings.borrow()[0].author);
+        // Same context
+        assert!(prepare_new_rating(
+            &db,
+            new_rating(ofdb_boundary::RatingContext::Fairness),
+            user.clone()
+        )
+        .is_err());
+        // Other context
+        assert!(prepare_new_rating(
+            &db,
+            new_rating(ofdb_boundary::RatingContext::Diversity),
+            user
+        )
+        .is_ok());
+        // Other author
+        assert!(prepare_new_rating(
+            &db,
+            new_rating(ofdb_boundary::RatingContext::Fairness),
+            anonymous()
+        )
+        .is_ok());
+    }
 }
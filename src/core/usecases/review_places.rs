@@ -28,6 +28,14 @@ pub fn review_places<D: Db>(db: &D, ids: &[&str], review: Review) -> Result<usiz
         comment,
     };
     let place_count = db.review_places(ids, status, &activity_log)?;
+    super::record_audit_log(
+        db,
+        &activity_log,
+        AuditAction::PlaceReview,
+        AuditEntityKind::Place,
+        ids,
+        Some(format!("{:?}", status).to_lowercase()),
+    )?;
     info!(
         "Changed review status of {} places to {}",
         place_count,
@@ -0,0 +1,323 @@
+use super::prepare_tag_list;
+use crate::core::{prelude::*, util::validate};
+
+#[derive(Debug, Clone)]
+pub struct NewSavedSearch {
+    pub name: String,
+    pub bbox: MapBbox,
+    // Category ids
+    pub categories: Vec<String>,
+    pub hash_tags: Vec<String>,
+    pub text: Option<String>,
+    pub org_tag: Option<String>,
+    pub alert: bool,
+}
+
+fn validate_saved_search(new: NewSavedSearch) -> Result<NewSavedSearch> {
+    let NewSavedSearch {
+        name,
+        bbox,
+        categories,
+        hash_tags,
+        text,
+        org_tag,
+        alert,
+    } = new;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ParameterError::SearchName.into());
+    }
+    validate::bbox(&bbox)?;
+    let mut categories: Vec<_> = categories
+        .iter()
+        .map(|c| c.trim())
+        .filter(|c| !c.is_empty())
+        .map(ToOwned::to_owned)
+        .collect();
+    categories.sort_unstable();
+    categories.dedup();
+    let hash_tags = prepare_tag_list(hash_tags.iter().map(String::as_str));
+    let text = text.map(|t| t.trim().to_owned()).filter(|t| !t.is_empty());
+    let org_tag = org_tag
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty());
+    Ok(NewSavedSearch {
+        name: name.to_owned(),
+        bbox,
+        categories,
+        hash_tags,
+        text,
+        org_tag,
+        alert,
+    })
+}
+
+fn load_owned_saved_search<D: Db>(db: &D, user_email: &str, id: &str) -> Result<SavedSearch> {
+    let saved_search = db.get_saved_search(id)?;
+    if saved_search.user_email != user_email {
+        return Err(ParameterError::Forbidden.into());
+    }
+    Ok(saved_search)
+}
+
+pub fn create_saved_search<D: Db>(
+    db: &D,
+    user_email: &str,
+    new: NewSavedSearch,
+) -> Result<SavedSearch> {
+    let NewSavedSearch {
+        name,
+        bbox,
+        categories,
+        hash_tags,
+        text,
+        org_tag,
+        alert,
+    } = validate_saved_search(new)?;
+    let saved_search = SavedSearch {
+        id: Id::new(),
+        user_email: user_email.into(),
+        name,
+        bbox,
+        categories,
+        hash_tags,
+        text,
+        org_tag,
+        alert,
+        created_at: TimestampMs::now(),
+    };
+    db.create_saved_search(&saved_search)?;
+    Ok(saved_search)
+}
+
+pub fn update_saved_search<D: Db>(
+    db: &D,
+    user_email: &str,
+    id: &str,
+    new: NewSavedSearch,
+) -> Result<SavedSearch> {
+    let old = load_owned_saved_search(db, user_email, id)?;
+    let NewSavedSearch {
+        name,
+        bbox,
+        categories,
+        hash_tags,
+        text,
+        org_tag,
+        alert,
+    } = validate_saved_search(new)?;
+    let saved_search = SavedSearch {
+        name,
+        bbox,
+        categories,
+        hash_tags,
+        text,
+        org_tag,
+        alert,
+        ..old
+    };
+    db.update_saved_search(&saved_search)?;
+    Ok(saved_search)
+}
+
+pub fn delete_saved_search<D: Db>(db: &D, user_email: &str, id: &str) -> Result<()> {
+    load_owned_saved_search(db, user_email, id)?;
+    Ok(db.delete_saved_search(id)?)
+}
+
+pub fn get_saved_searches<D: Db>(db: &D, user_email: &str) -> Result<Vec<SavedSearch>> {
+    Ok(db.saved_searches_by_email(user_email)?)
+}
+
+fn contains_all_words(
+    search: &SavedSearch,
+    title: &str,
+    description: &str,
+    tags: &[String],
+) -> bool {
+    let text = match search.text {
+        Some(ref text) => text.to_lowercase(),
+        None => return true,
+    };
+    let title = title.to_lowercase();
+    let description = description.to_lowercase();
+    text.split_whitespace().all(|word| {
+        title.contains(word) || description.contains(word) || tags.iter().any(|t| t.contains(word))
+    })
+}
+
+fn contains_all_hash_tags(search: &SavedSearch, tags: &[String]) -> bool {
+    search.hash_tags.iter().all(|t| tags.contains(t))
+}
+
+// The organization tag of a search only restricts the results
+// to cleared entries and is ignored when matching single entries.
+pub fn place_matches_saved_search(
+    search: &SavedSearch,
+    place: &Place,
+    all_categories: &[Category],
+) -> bool {
+    if !search.bbox.contains_point(place.location.pos) {
+        return false;
+    }
+    if !search.categories.is_empty() {
+        let ids: Vec<Id> = search
+            .categories
+            .iter()
+            .map(|c| c.as_str().into())
+            .collect();
+        let category_tags = Category::merge_ids_into_tags_with(all_categories, &ids, vec![]);
+        if !category_tags.iter().any(|t| place.tags.contains(t)) {
+            return false;
+        }
+    }
+    contains_all_hash_tags(search, &place.tags)
+        && contains_all_words(search, &place.title, &place.description, &place.tags)
+}
+
+pub fn event_matches_saved_search(search: &SavedSearch, event: &Event) -> bool {
+    let pos = match event.location {
+        Some(ref location) => location.pos,
+        None => return false,
+    };
+    if !search.bbox.contains_point(pos) {
+        return false;
+    }
+    if !search.categories.is_empty() && !search.categories.iter().any(|c| c == Category::ID_EVENT) {
+        return false;
+    }
+    contains_all_hash_tags(search, &event.tags)
+        && contains_all_words(
+            search,
+            &event.title,
+            event.description.as_deref().unwrap_or_default(),
+            &event.tags,
+        )
+}
+
+/// All saved searches with alerts that match the place
+/// but did not match its previous revision.
+pub fn saved_search_alerts_for_place<D: Db>(
+    db: &D,
+    place: &Place,
+    previous: Option<&Place>,
+) -> Result<Vec<SavedSearch>> {
+    let all_categories = db.all_categories()?;
+    Ok(db
+        .all_saved_searches_with_alert()?
+        .into_iter()
+        .filter(|s| place_matches_saved_search(s, place, &all_categories))
+        .filter(|s| !previous.map_or(false, |p| place_matches_saved_search(s, p, &all_categories)))
+        .collect())
+}
+
+/// All saved searches with alerts that match the event
+/// but did not match it before the update.
+pub fn saved_search_alerts_for_event<D: Db>(
+    db: &D,
+    event: &Event,
+    previous: Option<&Event>,
+) -> Result<Vec<SavedSearch>> {
+    Ok(db
+        .all_saved_searches_with_alert()?
+        .into_iter()
+        .filter(|s| event_matches_saved_search(s, event))
+        .filter(|s| !previous.map_or(false, |e| event_matches_saved_search(s, e)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::MockDb;
+    use super::*;
+
+    fn new_saved_search() -> NewSavedSearch {
+        NewSavedSearch {
+            name: "Organic food".into(),
+            bbox: MapBbox::new(
+                MapPoint::from_lat_lng_deg(40.0, 0.0),
+                MapPoint::from_lat_lng_deg(50.0, 10.0),
+            ),
+            categories: vec![Category::ID_NON_PROFIT.into()],
+            hash_tags: vec!["#Organic".into()],
+            text: Some("food".into()),
+            org_tag: None,
+            alert: true,
+        }
+    }
+
+    #[test]
+    fn only_owners_may_modify_saved_searches() {
+        let db = MockDb::default();
+        let saved = create_saved_search(&db, "a@foo.bar", new_saved_search()).unwrap();
+        assert_eq!(vec!["organic".to_string()], saved.hash_tags);
+        assert!(
+            update_saved_search(&db, "b@foo.bar", saved.id.as_ref(), new_saved_search()).is_err()
+        );
+        assert!(delete_saved_search(&db, "b@foo.bar", saved.id.as_ref()).is_err());
+        let updated = update_saved_search(
+            &db,
+            "a@foo.bar",
+            saved.id.as_ref(),
+            NewSavedSearch {
+                alert: false,
+                ..new_saved_search()
+            },
+        )
+        .unwrap();
+        assert!(!updated.alert);
+        assert_eq!(saved.created_at, updated.created_at);
+        assert_eq!(vec![updated], get_saved_searches(&db, "a@foo.bar").unwrap());
+        assert!(delete_saved_search(&db, "a@foo.bar", saved.id.as_ref()).is_ok());
+        assert!(get_saved_searches(&db, "a@foo.bar").unwrap().is_empty());
+    }
+
+    #[test]
+    fn reject_saved_search_without_name() {
+        let db = MockDb::default();
+        let new = NewSavedSearch {
+            name: " ".into(),
+            ..new_saved_search()
+        };
+        assert!(create_saved_search(&db, "a@foo.bar", new).is_err());
+    }
+
+    #[test]
+    fn alert_only_for_newly_matching_places() {
+        let db = MockDb::default();
+        db.categories.replace(Category::built_in());
+        create_saved_search(&db, "a@foo.bar", new_saved_search()).unwrap();
+        let previous = Place::build()
+            .id("foo")
+            .title("Food coop")
+            .pos(MapPoint::from_lat_lng_deg(45.0, 5.0))
+            .tags(vec![Category::TAG_NON_PROFIT])
+            .finish();
+        let place = Place {
+            tags: vec![Category::TAG_NON_PROFIT.into(), "organic".into()],
+            ..previous.clone()
+        };
+        assert!(saved_search_alerts_for_place(&db, &previous, None)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            1,
+            saved_search_alerts_for_place(&db, &place, Some(&previous))
+                .unwrap()
+                .len()
+        );
+        assert!(saved_search_alerts_for_place(&db, &place, Some(&place))
+            .unwrap()
+            .is_empty());
+        let outside = Place {
+            location: Location {
+                pos: MapPoint::from_lat_lng_deg(10.0, 5.0),
+                address: None,
+            },
+            ..place
+        };
+        assert!(saved_search_alerts_for_place(&db, &outside, None)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,8 +1,9 @@
+use super::statistics::{sorted_by_count, PlaceCount};
 use crate::core::{prelude::*, util};
-use ofdb_core::{bbox, tag};
+use ofdb_core::bbox;
 use ofdb_entities::geo::MapBbox;
 
-use std::collections::HashMap;
+use std::{cmp::Ordering, collections::HashMap, fmt, str::FromStr};
 
 #[rustfmt::skip]
 #[derive(Debug, Clone)]
@@ -14,6 +15,14 @@ pub struct SearchRequest<'a> {
     pub hash_tags  : Vec<&'a str>,
     pub text       : Option<&'a str>,
     pub status     : Vec<ReviewStatus>,
+    /// Only places with at least one link of this status
+    pub link_status: Option<LinkStatus>,
+    /// The language of the text
+    pub lang       : Option<Language>,
+    /// The order of paginated results
+    pub sort       : Option<SearchSort>,
+    /// Continue after the last result of the previous page
+    pub cursor     : Option<&'a str>,
 }
 
 pub fn clear_search_results<D: Db>(
@@ -46,6 +55,7 @@ pub fn clear_search_results<D: Db>(
                     db.load_place_revision(&place.id, *last_cleared_revision)?;
                 debug_assert_eq!(*last_cleared_revision, last_cleared_place.revision);
                 let Place {
+                    created,
                     description,
                     id,
                     location: Location { pos, .. },
@@ -59,6 +69,7 @@ pub fn clear_search_results<D: Db>(
                 }
                 // Ratings are independent of the revision
                 let ratings = place.ratings;
+                let score = place.score;
                 // Replace the actual/current search result item with the last cleared revision
                 place = IndexedPlace {
                     id: id.into(),
@@ -68,6 +79,8 @@ pub fn clear_search_results<D: Db>(
                     status: Some(current_status),
                     tags,
                     title,
+                    changed_at: Some(created.at),
+                    score,
                 };
             } else {
                 // Skip newly created but not yet cleared entry
@@ -79,12 +92,12 @@ pub fn clear_search_results<D: Db>(
     Ok(cleared_results)
 }
 
-pub fn search<D: Db>(
+// Returns `None` if no places can match the request.
+fn visible_places_query<'a, D: Db>(
     db: &D,
-    index: &dyn PlaceIndex,
-    req: SearchRequest,
-    limit: usize,
-) -> Result<(Vec<IndexedPlace>, Vec<IndexedPlace>)> {
+    req: SearchRequest<'a>,
+    ids_with_link_status: &'a mut Vec<Id>,
+) -> Result<Option<IndexQuery<'a, 'a>>> {
     let SearchRequest {
         bbox: visible_bbox,
         ids,
@@ -93,13 +106,34 @@ pub fn search<D: Db>(
         hash_tags: req_hash_tags,
         text,
         status,
+        link_status,
+        lang,
+        sort: _,
+        cursor: _,
     } = req;
 
+    let ids = if let Some(link_status) = link_status {
+        *ids_with_link_status = super::place_ids_with_link_status(db, link_status)?;
+        let ids_with_link_status: &'a Vec<Id> = ids_with_link_status;
+        let ids: Vec<_> = ids_with_link_status
+            .iter()
+            .map(Id::as_str)
+            .filter(|id| ids.is_empty() || ids.contains(id))
+            .collect();
+        if ids.is_empty() {
+            return Ok(None);
+        }
+        ids
+    } else {
+        ids
+    };
+
     let mut hash_tags = text.map(util::extract_hash_tags).unwrap_or_default();
     hash_tags.reserve(req_hash_tags.len() + 1);
     for hash_tag in req_hash_tags {
         hash_tags.push(hash_tag.to_owned());
     }
+    let mut hash_tags = super::canonical_hash_tags(db, hash_tags)?;
     if let Some(org_tag) = org_tag {
         hash_tags.push(org_tag.to_owned());
     }
@@ -112,21 +146,41 @@ pub fn search<D: Db>(
         }
     });
 
-    let text_tags = text
-        .as_deref()
-        .map(tag::split_text_into_tags)
-        .unwrap_or_default();
+    let text_tags = match text.as_deref() {
+        Some(text) => super::split_text_into_tags_with_synonyms(db, text)?,
+        None => vec![],
+    };
+    let hash_tag_alternatives = super::hash_tag_alternatives(db, &hash_tags)?;
+    let all_categories = db.all_categories()?;
 
-    let visible_places_query = IndexQuery {
+    Ok(Some(IndexQuery {
         include_bbox: Some(visible_bbox),
         exclude_bbox: None,
         categories,
+        all_categories,
         ids,
         hash_tags,
+        hash_tag_alternatives,
         text_tags,
         text,
+        lang,
         status: Some(status),
         ..Default::default()
+    }))
+}
+
+pub fn search<D: Db>(
+    db: &D,
+    index: &dyn PlaceIndex,
+    req: SearchRequest,
+    limit: usize,
+) -> Result<(Vec<IndexedPlace>, Vec<IndexedPlace>)> {
+    let visible_bbox = req.bbox;
+    let org_tag = req.org_tag;
+    let mut ids_with_link_status = vec![];
+    let visible_places_query = match visible_places_query(db, req, &mut ids_with_link_status)? {
+        Some(query) => query,
+        None => return Ok((vec![], vec![])),
     };
 
     // 1st query: Search for visible results only
@@ -169,6 +223,321 @@ pub fn search<D: Db>(
     Ok((visible_places, invisible_places))
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchSort {
+    /// Highest total rating first
+    Rating,
+    /// Best match of the text first, boosted by the rating
+    Relevance,
+    /// Nearest to the center of the bbox first
+    Distance,
+    /// Most recently changed first
+    RecentlyChanged,
+    /// By title
+    Alphabetical,
+}
+
+impl SearchSort {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Rating => "rating",
+            Self::Relevance => "relevance",
+            Self::Distance => "distance",
+            Self::RecentlyChanged => "recently_changed",
+            Self::Alphabetical => "alphabetical",
+        }
+    }
+}
+
+impl fmt::Display for SearchSort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SearchSort {
+    type Err = ParameterError;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "rating" => Self::Rating,
+            "relevance" => Self::Relevance,
+            "distance" => Self::Distance,
+            "recently_changed" => Self::RecentlyChanged,
+            "alphabetical" => Self::Alphabetical,
+            _ => return Err(ParameterError::SearchSort),
+        })
+    }
+}
+
+impl SearchSort {
+    fn place_sort_order(self, center: MapPoint) -> PlaceSortOrder {
+        match self {
+            Self::Rating => PlaceSortOrder::Rating,
+            Self::Relevance => PlaceSortOrder::Relevance,
+            Self::Distance => PlaceSortOrder::Distance(center),
+            Self::RecentlyChanged => PlaceSortOrder::RecentlyChanged,
+            Self::Alphabetical => PlaceSortOrder::Title,
+        }
+    }
+}
+
+fn to_cursor(sort: SearchSort, position: &PlaceSortPosition) -> String {
+    let key = match &position.key {
+        PlaceSortKey::Number(number) => number.to_string(),
+        PlaceSortKey::Text(text) => text.clone(),
+    };
+    format!("{}:{}:{}", sort, key, position.id)
+}
+
+fn from_cursor(
+    sort: SearchSort,
+    cursor: &str,
+) -> std::result::Result<PlaceSortPosition, ParameterError> {
+    let mut parts = cursor.splitn(2, ':');
+    let cursor_sort = parts.next().and_then(|s| s.parse::<SearchSort>().ok());
+    if cursor_sort != Some(sort) {
+        return Err(ParameterError::Cursor);
+    }
+    let mut parts = parts.next().ok_or(ParameterError::Cursor)?.rsplitn(2, ':');
+    let id = parts.next().ok_or(ParameterError::Cursor)?;
+    let key = parts.next().ok_or(ParameterError::Cursor)?;
+    let key = match sort {
+        SearchSort::Alphabetical => PlaceSortKey::Text(key.to_owned()),
+        _ => PlaceSortKey::Number(key.parse().map_err(|_| ParameterError::Cursor)?),
+    };
+    Ok(PlaceSortPosition {
+        key,
+        id: id.to_owned(),
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchPage {
+    pub visible: Vec<IndexedPlace>,
+    pub invisible: Vec<IndexedPlace>,
+    /// Request the next page of visible results
+    /// with this cursor
+    pub next_cursor: Option<String>,
+}
+
+/// Search with an explicit order of the results that
+/// could be paginated by a cursor.
+///
+/// The places are sorted by their current revision, even
+/// if the last cleared revision is returned instead.
+///
+/// Invisible results are only returned on the first page.
+pub fn search_sorted<D: Db>(
+    db: &D,
+    index: &dyn PlaceIndex,
+    req: SearchRequest,
+    limit: usize,
+) -> Result<SearchPage> {
+    let visible_bbox = req.bbox;
+    let org_tag = req.org_tag;
+    let sort = req.sort.unwrap_or(SearchSort::Rating);
+    let order = sort.place_sort_order(visible_bbox.center());
+    let cursor = req
+        .cursor
+        .map(|cursor| from_cursor(sort, cursor))
+        .transpose()?;
+    let mut ids_with_link_status = vec![];
+    let visible_places_query = match visible_places_query(db, req, &mut ids_with_link_status)? {
+        Some(query) => query,
+        None => {
+            return Ok(SearchPage {
+                visible: vec![],
+                invisible: vec![],
+                next_cursor: None,
+            })
+        }
+    };
+    let clearance_org_id = match org_tag {
+        Some(org_tag) => db.map_tag_to_clearance_org_id(org_tag)?,
+        None => None,
+    };
+    let clear_places = |places: Vec<IndexedPlace>| match (org_tag, &clearance_org_id) {
+        (Some(org_tag), Some(org_id)) => clear_search_results(db, org_id, org_tag, places),
+        _ => Ok(places),
+    };
+
+    // Places that are removed while clearing the results
+    // are replaced by the following places
+    let mut visible = Vec::with_capacity(limit);
+    let first_page = cursor.is_none();
+    let mut last_position = cursor;
+    let next_cursor = loop {
+        let remaining = limit - visible.len();
+        let mut places = index
+            .query_places_sorted(
+                &visible_places_query,
+                order,
+                last_position.as_ref(),
+                remaining + 1,
+            )
+            .map_err(RepoError::Other)?;
+        let has_more = places.len() > remaining;
+        places.truncate(remaining);
+        if let Some((position, _)) = places.last() {
+            last_position = Some(position.clone());
+        }
+        let places = places.into_iter().map(|(_, place)| place).collect();
+        visible.extend(clear_places(places)?);
+        if !has_more {
+            break None;
+        }
+        if visible.len() >= limit {
+            break last_position.map(|position| to_cursor(sort, &position));
+        }
+    };
+
+    let invisible = if first_page && visible.len() < limit {
+        let invisible_places_query = IndexQuery {
+            include_bbox: Some(bbox::extend_bbox(&visible_bbox)),
+            exclude_bbox: visible_places_query.include_bbox,
+            ..visible_places_query
+        };
+        let invisible_places = index
+            .query_places_sorted(&invisible_places_query, order, None, limit - visible.len())
+            .map_err(RepoError::Other)?
+            .into_iter()
+            .map(|(_, place)| place)
+            .collect();
+        clear_places(invisible_places)?
+    } else {
+        vec![]
+    };
+
+    Ok(SearchPage {
+        visible,
+        invisible,
+        next_cursor,
+    })
+}
+
+const MAX_FACET_TAGS: usize = 20;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SearchFacets {
+    pub total_count: u64,
+    /// Keyed by the category id
+    pub categories: Vec<PlaceCount>,
+    /// Only the most frequent tags
+    pub tags: Vec<PlaceCount>,
+    pub status: Vec<(ReviewStatus, u64)>,
+    /// Keyed by the total rating rounded down, i.e. 1
+    /// counts all places with a rating from 1.0 to 1.9
+    pub ratings: Vec<(i64, u64)>,
+}
+
+/// Count the visible places that match the request.
+///
+/// Pending clearances are not considered.
+pub fn search_facets<D: Db>(
+    db: &D,
+    index: &dyn PlaceIndex,
+    req: SearchRequest,
+) -> Result<SearchFacets> {
+    let mut ids_with_link_status = vec![];
+    let query = match visible_places_query(db, req, &mut ids_with_link_status)? {
+        Some(query) => query,
+        None => return Ok(SearchFacets::default()),
+    };
+    let IndexedPlaceFacets {
+        total_count,
+        tags: tag_counts,
+        status,
+        ratings,
+    } = index.query_place_facets(&query).map_err(RepoError::Other)?;
+    let all_categories = db.all_categories()?;
+    let mut categories = HashMap::new();
+    let mut tags = HashMap::new();
+    for (tag, count) in tag_counts {
+        let (_, mut tag_categories) =
+            Category::split_from_tags_with(&all_categories, vec![tag.clone()]);
+        if let Some(category) = tag_categories.pop() {
+            categories.insert(category.id.to_string(), count);
+        } else {
+            tags.insert(tag, count);
+        }
+    }
+    let mut tags = sorted_by_count(tags);
+    tags.truncate(MAX_FACET_TAGS);
+    Ok(SearchFacets {
+        total_count,
+        categories: sorted_by_count(categories),
+        tags,
+        status: status.into_iter().collect(),
+        ratings: ratings.into_iter().collect(),
+    })
+}
+
+pub const MAX_CLUSTER_ZOOM: u8 = 22;
+
+// The number of grid cells along the width of a map tile
+const CLUSTER_CELLS_PER_TILE: f64 = 4.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaceCluster {
+    pub count: u64,
+    pub centroid: MapPoint,
+    /// The id of the category of most places
+    pub category: Option<String>,
+}
+
+fn cluster_cell_size(zoom: u8) -> f64 {
+    360.0 / f64::from(1u32 << zoom) / CLUSTER_CELLS_PER_TILE
+}
+
+/// Aggregate the visible places that match the request
+/// into clusters for the given zoom level of the map.
+///
+/// Pending clearances are not considered.
+pub fn search_clusters<D: Db>(
+    db: &D,
+    index: &dyn PlaceIndex,
+    req: SearchRequest,
+    zoom: u8,
+) -> Result<Vec<PlaceCluster>> {
+    if zoom > MAX_CLUSTER_ZOOM {
+        return Err(ParameterError::Zoom.into());
+    }
+    let mut ids_with_link_status = vec![];
+    let query = match visible_places_query(db, req, &mut ids_with_link_status)? {
+        Some(query) => query,
+        None => return Ok(vec![]),
+    };
+    let mut clusters: Vec<_> = index
+        .query_place_clusters(&query, cluster_cell_size(zoom))
+        .map_err(RepoError::Other)?
+        .into_iter()
+        .map(|cluster| {
+            let IndexedPlaceCluster {
+                count,
+                centroid,
+                categories,
+            } = cluster;
+            let category = sorted_by_count(categories)
+                .into_iter()
+                .next()
+                .map(|PlaceCount { key, .. }| key);
+            PlaceCluster {
+                count,
+                centroid,
+                category,
+            }
+        })
+        .collect();
+    clusters.sort_by(|lhs, rhs| {
+        rhs.count.cmp(&lhs.count).then_with(|| {
+            let lhs = (lhs.centroid.lat().to_deg(), lhs.centroid.lng().to_deg());
+            let rhs = (rhs.centroid.lat().to_deg(), rhs.centroid.lng().to_deg());
+            lhs.partial_cmp(&rhs).unwrap_or(Ordering::Equal)
+        })
+    });
+    Ok(clusters)
+}
+
 /// The global search usecase is like the one
 /// of usual internet search engines that exists
 /// of only one single search input.
@@ -0,0 +1,277 @@
+use super::authorize_user_by_permission;
+use crate::core::prelude::*;
+use chrono::{prelude::*, Duration};
+use ofdb_core::user::RolePermissions;
+use std::collections::{BTreeMap, HashMap};
+
+const TOP_TAGS_LIMIT: usize = 20;
+
+/// The number of items within a week or month
+/// that starts at the given day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeriodCount {
+    pub period_start: NaiveDate,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingClearanceCount {
+    pub org_id: Id,
+    pub org_name: String,
+    pub places: u64,
+    pub events: u64,
+}
+
+/// The number of existing places with the given
+/// category, tag, country or state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceCount {
+    pub key: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statistics {
+    pub places_created_per_week: Vec<PeriodCount>,
+    pub places_updated_per_week: Vec<PeriodCount>,
+    /// Grouped by the start of the events
+    pub events_per_month: Vec<PeriodCount>,
+    pub users_registered_per_week: Vec<PeriodCount>,
+    pub ratings_per_week: Vec<PeriodCount>,
+    pub pending_clearances: Vec<PendingClearanceCount>,
+    pub places_by_category: Vec<PlaceCount>,
+    pub places_by_tag: Vec<PlaceCount>,
+    pub places_by_country: Vec<PlaceCount>,
+    /// States are prefixed by their country, e.g. "DE/Berlin"
+    pub places_by_state: Vec<PlaceCount>,
+}
+
+pub fn statistics<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    login_email: &str,
+) -> Result<Statistics> {
+    authorize_user_by_permission(db, permissions, login_email, Permission::StatsRead)?;
+
+    let (created, updated): (Vec<_>, Vec<_>) = db
+        .place_revision_times()?
+        .into_iter()
+        .partition(|(rev, _)| rev.is_initial());
+    let places_created_per_week = count_per_week(created.into_iter().map(|(_, at)| at.into()));
+    let places_updated_per_week = count_per_week(updated.into_iter().map(|(_, at)| at.into()));
+    let events_per_month = count_per_month(db.event_start_times()?.into_iter().map(Into::into));
+    let users_registered_per_week =
+        count_per_week(db.user_registration_times()?.into_iter().map(Into::into));
+    let ratings_per_week = count_per_week(db.rating_creation_times()?.into_iter().map(Into::into));
+
+    let mut pending_clearances = vec![];
+    for org in db.all_orgs()? {
+        if !org.moderated_tags.iter().any(|t| t.require_clearance) {
+            continue;
+        }
+        pending_clearances.push(PendingClearanceCount {
+            places: db.count_pending_clearances_for_places(&org.id)?,
+            events: db.count_pending_clearances_for_events(&org.id)?,
+            org_id: org.id,
+            org_name: org.name,
+        });
+    }
+
+    let all_categories = db.all_categories()?;
+    let mut categories = HashMap::new();
+    let mut tags = HashMap::new();
+    let mut countries = HashMap::new();
+    let mut states = HashMap::new();
+    for (place, status) in db.all_places()? {
+        if !status.exists() {
+            continue;
+        }
+        let (place_tags, place_categories) =
+            Category::split_from_tags_with(&all_categories, place.tags);
+        for category in place_categories {
+            *categories.entry(category.tag).or_insert(0) += 1;
+        }
+        for tag in place_tags {
+            *tags.entry(tag).or_insert(0) += 1;
+        }
+        if let Some(address) = place.location.address {
+            if let Some(country) = address.country {
+                if let Some(state) = address.state {
+                    *states.entry(format!("{}/{}", country, state)).or_insert(0) += 1;
+                }
+                *countries.entry(country).or_insert(0) += 1;
+            }
+        }
+    }
+    let mut places_by_tag = sorted_by_count(tags);
+    places_by_tag.truncate(TOP_TAGS_LIMIT);
+
+    Ok(Statistics {
+        places_created_per_week,
+        places_updated_per_week,
+        events_per_month,
+        users_registered_per_week,
+        ratings_per_week,
+        pending_clearances,
+        places_by_category: sorted_by_count(categories),
+        places_by_tag,
+        places_by_country: sorted_by_count(countries),
+        places_by_state: sorted_by_count(states),
+    })
+}
+
+pub(super) fn sorted_by_count(counts: HashMap<String, u64>) -> Vec<PlaceCount> {
+    let mut counts: Vec<_> = counts
+        .into_iter()
+        .map(|(key, count)| PlaceCount { key, count })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+    counts
+}
+
+fn start_of_week(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday().into())
+}
+
+fn start_of_month(date: NaiveDate) -> NaiveDate {
+    NaiveDate::from_ymd(date.year(), date.month(), 1)
+}
+
+fn next_month(date: NaiveDate) -> NaiveDate {
+    if date.month() == 12 {
+        NaiveDate::from_ymd(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd(date.year(), date.month() + 1, 1)
+    }
+}
+
+fn count_per_week(times: impl Iterator<Item = NaiveDateTime>) -> Vec<PeriodCount> {
+    count_per_period(times, start_of_week, |date| date + Duration::weeks(1))
+}
+
+fn count_per_month(times: impl Iterator<Item = NaiveDateTime>) -> Vec<PeriodCount> {
+    count_per_period(times, start_of_month, next_month)
+}
+
+// Periods without any items between the first
+// and the last period are included with a count of 0.
+fn count_per_period(
+    times: impl Iterator<Item = NaiveDateTime>,
+    period_start: impl Fn(NaiveDate) -> NaiveDate,
+    next_period: impl Fn(NaiveDate) -> NaiveDate,
+) -> Vec<PeriodCount> {
+    let mut counts = BTreeMap::new();
+    for time in times {
+        *counts.entry(period_start(time.date())).or_insert(0) += 1;
+    }
+    let (first, last) = match (counts.keys().next(), counts.keys().next_back()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return vec![],
+    };
+    let mut periods = vec![];
+    let mut period = first;
+    while period <= last {
+        periods.push(PeriodCount {
+            period_start: period,
+            count: counts.get(&period).copied().unwrap_or(0),
+        });
+        period = next_period(period);
+    }
+    periods
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{create_user, MockDb};
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd(y, m, d).and_hms(12, 0, 0)
+    }
+
+    #[test]
+    fn count_per_week_with_gaps() {
+        // 2020-11-30 is a Monday
+        let times = vec![at(2020, 12, 6), at(2020, 11, 30), at(2020, 12, 15)];
+        assert_eq!(
+            vec![
+                PeriodCount {
+                    period_start: NaiveDate::from_ymd(2020, 11, 30),
+                    count: 2,
+                },
+                PeriodCount {
+                    period_start: NaiveDate::from_ymd(2020, 12, 7),
+                    count: 0,
+                },
+                PeriodCount {
+                    period_start: NaiveDate::from_ymd(2020, 12, 14),
+                    count: 1,
+                },
+            ],
+            count_per_week(times.into_iter())
+        );
+    }
+
+    #[test]
+    fn count_per_month_across_years() {
+        let times = vec![at(2020, 12, 31), at(2021, 2, 1)];
+        let counts = count_per_month(times.into_iter());
+        assert_eq!(3, counts.len());
+        assert_eq!(NaiveDate::from_ymd(2021, 1, 1), counts[1].period_start);
+        assert_eq!(
+            vec![1, 0, 1],
+            counts.iter().map(|c| c.count).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn break_down_places_by_category_tag_and_region() {
+        let db = MockDb::default();
+        create_user(&db, "admin@foo.bar", Role::Admin);
+        for (id, tags, country, state) in &[
+            ("a", vec!["non-profit", "bio"], Some("DE"), Some("Berlin")),
+            ("b", vec!["commercial", "bio"], Some("DE"), None),
+            ("c", vec!["non-profit", "fair"], None, None),
+        ] {
+            let mut place = Place::build().id(id).tags(tags.clone()).finish();
+            place.location.address = Some(Address {
+                country: country.map(Into::into),
+                state: state.map(Into::into),
+                ..Default::default()
+            });
+            db.entries.borrow_mut().push((place, ReviewStatus::Created));
+        }
+        let archived = Place::build().id("d").tags(vec!["bio"]).finish();
+        db.entries
+            .borrow_mut()
+            .push((archived, ReviewStatus::Archived));
+
+        assert!(matches!(
+            statistics(&db, &Default::default(), "user@foo.bar"),
+            Err(Error::Parameter(ParameterError::Unauthorized))
+        ));
+        let stats = statistics(&db, &Default::default(), "admin@foo.bar").unwrap();
+        let counts = |counts: &[PlaceCount]| {
+            counts
+                .iter()
+                .map(|c| (c.key.clone(), c.count))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec![("non-profit".into(), 2), ("commercial".into(), 1)],
+            counts(&stats.places_by_category)
+        );
+        assert_eq!(
+            vec![("bio".into(), 2), ("fair".into(), 1)],
+            counts(&stats.places_by_tag)
+        );
+        assert_eq!(vec![("DE".into(), 2)], counts(&stats.places_by_country));
+        assert_eq!(
+            vec![("DE/Berlin".into(), 1)],
+            counts(&stats.places_by_state)
+        );
+        let created: u64 = stats.places_created_per_week.iter().map(|c| c.count).sum();
+        assert_eq!(4, created);
+        assert!(stats.places_updated_per_week.is_empty());
+    }
+}
@@ -40,7 +40,11 @@ pub enum NewEventMode<'a> {
 }
 
 #[derive(Debug, Clone)]
-pub struct Storable(Event);
+pub struct Storable {
+    event: Event,
+    clearance_org_ids: Vec<Id>,
+    last_cleared_event: Option<Event>,
+}
 
 pub fn import_new_event<D: Db>(
     db: &D,
@@ -83,7 +87,7 @@ pub fn import_new_event<D: Db>(
         })
         .transpose()?;
     let mut new_tags = super::prepare_tag_list(tags.unwrap_or_default().iter().map(String::as_str));
-    let _clearance_org_ids = if let Some(org) = org {
+    let clearance_org_ids = if let Some(org) = org {
         // Implicitly add missing owned tags to prevent events with
         // undefined ownership!
         let org_tag_count = new_tags
@@ -154,8 +158,10 @@ pub fn import_new_event<D: Db>(
     } else {
         super::authorize_editing_of_tagged_entry(db, &[], &new_tags, None)?
     };
-    // TODO: Record pending clearance for events
-    debug_assert!(_clearance_org_ids.is_empty());
+    let last_cleared_event = match mode {
+        NewEventMode::Update(id) if !clearance_org_ids.is_empty() => Some(db.get_event(id)?),
+        _ => None,
+    };
     new_tags.sort_unstable();
     new_tags.dedup();
 
@@ -291,26 +297,55 @@ pub fn import_new_event<D: Db>(
     };
     let event = event.auto_correct();
     event.validate()?;
-    Ok(Storable(event))
+    Ok(Storable {
+        event,
+        clearance_org_ids,
+        last_cleared_event,
+    })
 }
 
 pub fn store_created_event<D: Db>(db: &D, storable: Storable) -> Result<Event> {
-    let Storable(event) = storable;
+    let Storable {
+        event,
+        clearance_org_ids,
+        last_cleared_event,
+    } = storable;
+    debug_assert!(last_cleared_event.is_none());
     debug!("Storing newly created event: {:?}", event);
     for t in &event.tags {
         db.create_tag_if_it_does_not_exist(&Tag { id: t.clone() })?;
     }
     db.create_event(event.clone())?;
+    if !clearance_org_ids.is_empty() {
+        let pending_clearance = PendingClearanceForEvent {
+            event_id: event.id.clone(),
+            created_at: TimestampMs::now(),
+            last_cleared_event: None,
+        };
+        super::clearance::event::add_pending_clearance(db, &clearance_org_ids, &pending_clearance)?;
+    }
     Ok(event)
 }
 
 pub fn store_updated_event<D: Db>(db: &D, storable: Storable) -> Result<Event> {
-    let Storable(event) = storable;
+    let Storable {
+        event,
+        clearance_org_ids,
+        last_cleared_event,
+    } = storable;
     debug!("Storing updated event: {:?}", event);
     for t in &event.tags {
         db.create_tag_if_it_does_not_exist(&Tag { id: t.clone() })?;
     }
     db.update_event(&event)?;
+    if !clearance_org_ids.is_empty() {
+        let pending_clearance = PendingClearanceForEvent {
+            event_id: event.id.clone(),
+            created_at: TimestampMs::now(),
+            last_cleared_event,
+        };
+        super::clearance::event::add_pending_clearance(db, &clearance_org_ids, &pending_clearance)?;
+    }
     Ok(event)
 }
 
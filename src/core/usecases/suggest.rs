@@ -0,0 +1,131 @@
+use crate::core::prelude::*;
+use ofdb_entities::geo::MapBbox;
+
+use std::collections::HashMap;
+
+/// The maximum number of places that are considered
+/// for ranking the suggestions.
+const MAX_CANDIDATES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SuggestionKind {
+    Title,
+    Tag,
+}
+
+/// A completion of the text that has been typed so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub text: String,
+    pub kind: SuggestionKind,
+    /// The number of places within the bounding box
+    /// with this title or tag
+    pub count: u64,
+}
+
+pub fn suggest(
+    index: &dyn PlaceIndex,
+    bbox: MapBbox,
+    prefix: &str,
+    limit: usize,
+) -> Result<Vec<Suggestion>> {
+    let prefix = prefix.trim().trim_start_matches('#');
+    if prefix.is_empty() {
+        return Ok(vec![]);
+    }
+    let query = IndexQuery {
+        include_bbox: Some(bbox),
+        prefix: Some(prefix.to_owned()),
+        // Only visible places
+        status: Some(vec![]),
+        ..Default::default()
+    };
+    let candidates = index
+        .query_places(&query, MAX_CANDIDATES)
+        .map_err(RepoError::Other)?;
+    Ok(rank_suggestions(prefix, candidates, limit))
+}
+
+// The candidates are expected to be ordered by their rating.
+// Suggestions that apply to more places are ranked first.
+fn rank_suggestions(prefix: &str, candidates: Vec<IndexedPlace>, limit: usize) -> Vec<Suggestion> {
+    let prefix = prefix.to_lowercase();
+    let mut suggestions: Vec<Suggestion> = vec![];
+    let mut positions: HashMap<(SuggestionKind, String), usize> = HashMap::new();
+    let mut add = |kind, text: String| {
+        let key = (kind, text.to_lowercase());
+        if let Some(pos) = positions.get(&key) {
+            suggestions[*pos].count += 1;
+        } else {
+            positions.insert(key, suggestions.len());
+            suggestions.push(Suggestion {
+                text,
+                kind,
+                count: 1,
+            });
+        }
+    };
+    for IndexedPlace { title, tags, .. } in candidates {
+        let matching_tags: Vec<_> = tags
+            .into_iter()
+            .filter(|tag| tag.starts_with(&prefix))
+            .collect();
+        // The index also matches titles with umlauts or accents
+        // that cannot be detected by a simple comparison here.
+        if matching_tags.is_empty() || title_matches(&title, &prefix) {
+            add(SuggestionKind::Title, title);
+        }
+        for tag in matching_tags {
+            add(SuggestionKind::Tag, tag);
+        }
+    }
+    // The sort is stable, i.e. the order of the
+    // candidates is preserved for equal counts.
+    suggestions.sort_by(|lhs, rhs| rhs.count.cmp(&lhs.count));
+    suggestions.truncate(limit);
+    suggestions
+}
+
+fn title_matches(title: &str, prefix: &str) -> bool {
+    let title = title.to_lowercase();
+    title.starts_with(prefix)
+        || title
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| word.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place(title: &str, tags: &[&str]) -> IndexedPlace {
+        IndexedPlace {
+            title: title.into(),
+            tags: tags.iter().map(|t| (*t).to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rank_suggestions_by_count() {
+        let candidates = vec![
+            place("Bioladen am Markt", &["bio", "lebensmittel"]),
+            place("Weltladen", &["bio", "fairtrade"]),
+            place("Bioladen am Markt", &["bioland"]),
+            place("Café Bioläden", &["biodiversity"]),
+        ];
+        let suggestions = rank_suggestions("Bio", candidates, 4);
+        assert_eq!(
+            vec![
+                ("Bioladen am Markt", SuggestionKind::Title, 2),
+                ("bio", SuggestionKind::Tag, 2),
+                ("bioland", SuggestionKind::Tag, 1),
+                ("Café Bioläden", SuggestionKind::Title, 1),
+            ],
+            suggestions
+                .iter()
+                .map(|s| (s.text.as_str(), s.kind, s.count))
+                .collect::<Vec<_>>()
+        );
+    }
+}
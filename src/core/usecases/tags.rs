@@ -0,0 +1,347 @@
+use super::{authorize_user_by_permission, prepare_tag_list, record_audit_log};
+use crate::core::prelude::*;
+use ofdb_core::{tag, user::RolePermissions};
+
+/// Split the text into tags including all synonymous tags.
+pub fn split_text_into_tags_with_synonyms<D: Db>(db: &D, text: &str) -> Result<Vec<String>> {
+    let tags = tag::split_text_into_tags(text);
+    if tags.is_empty() {
+        return Ok(tags);
+    }
+    Ok(tag::expand_synonyms(tags, &db.all_tag_synonyms()?))
+}
+
+/// Replace synonyms among the hash tags by their tag, e.g.
+/// for finding places and events after tags have been merged.
+pub fn canonical_hash_tags<D: Db>(db: &D, hash_tags: Vec<String>) -> Result<Vec<String>> {
+    if hash_tags.is_empty() {
+        return Ok(hash_tags);
+    }
+    let synonyms = db.all_tag_synonyms()?;
+    Ok(hash_tags
+        .into_iter()
+        .map(|t| tag::canonical_tag(&t.to_lowercase(), &synonyms).to_owned())
+        .collect())
+}
+
+pub(super) fn prepare_tag(tag: &str) -> Result<String> {
+    let mut tags = prepare_tag_list(std::iter::once(tag));
+    if tags.len() != 1 {
+        return Err(ParameterError::Tag.into());
+    }
+    Ok(tags.remove(0))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagDetails {
+    pub id: String,
+    pub description: Option<String>,
+    pub synonyms: Vec<String>,
+}
+
+pub fn get_tag_details<D: Db>(db: &D, tag: &str) -> Result<TagDetails> {
+    let tag = prepare_tag(tag)?;
+    let description = db.get_tag_description(&tag)?;
+    let synonyms: Vec<_> = db
+        .all_tag_synonyms()?
+        .into_iter()
+        .filter(|s| s.tag == tag)
+        .map(|s| s.synonym)
+        .collect();
+    if description.is_none()
+        && synonyms.is_empty()
+        && !db.all_tags()?.into_iter().any(|t| t.id == tag)
+    {
+        return Err(RepoError::NotFound.into());
+    }
+    Ok(TagDetails {
+        id: tag,
+        description,
+        synonyms,
+    })
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UpdateTag {
+    pub description: Option<String>,
+    pub synonyms: Vec<String>,
+}
+
+/// Replace the description and the synonyms of a tag.
+pub fn update_tag<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    account_email: &str,
+    tag: &str,
+    update: UpdateTag,
+) -> Result<TagDetails> {
+    authorize_user_by_permission(db, permissions, account_email, Permission::TagManage)?;
+    let tag = prepare_tag(tag)?;
+    let UpdateTag {
+        description,
+        synonyms,
+    } = update;
+    let description = description
+        .map(|d| d.trim().to_owned())
+        .filter(|d| !d.is_empty());
+    let synonyms: Vec<_> = prepare_tag_list(synonyms.iter().map(String::as_str))
+        .into_iter()
+        .filter(|s| *s != tag)
+        .collect();
+    // Only a single level of synonyms is supported
+    let all_synonyms = db.all_tag_synonyms()?;
+    if all_synonyms
+        .iter()
+        .any(|s| s.synonym == tag || synonyms.contains(&s.tag))
+    {
+        return Err(ParameterError::TagSynonym.into());
+    }
+    if db
+        .all_taxonomy_tags()?
+        .iter()
+        .any(|t| synonyms.contains(&t.tag))
+    {
+        return Err(ParameterError::CuratedTag.into());
+    }
+    info!("Updating tag '{}' with {} synonym(s)", tag, synonyms.len());
+    db.set_tag_description(&tag, description.as_deref())?;
+    db.replace_tag_synonyms(&tag, &synonyms)?;
+    Ok(TagDetails {
+        id: tag,
+        description,
+        synonyms,
+    })
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MergedTags {
+    pub places: Vec<Place>,
+    pub events: Vec<Event>,
+}
+
+/// Replace the tags by another tag in all places and events.
+///
+/// Places are updated with a new revision. The merged tags
+/// become synonyms of the remaining tag.
+pub fn merge_tags<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    account_email: &str,
+    tags: &[String],
+    into: &str,
+) -> Result<MergedTags> {
+    authorize_user_by_permission(db, permissions, account_email, Permission::TagManage)?;
+    let into = prepare_tag(into)?;
+    let tags: Vec<_> = prepare_tag_list(tags.iter().map(String::as_str))
+        .into_iter()
+        .filter(|t| *t != into)
+        .collect();
+    if tags.is_empty() {
+        return Err(ParameterError::Tag.into());
+    }
+    if db.all_tag_synonyms()?.iter().any(|s| s.synonym == into) {
+        return Err(ParameterError::TagSynonym.into());
+    }
+    if db
+        .all_taxonomy_tags()?
+        .iter()
+        .any(|t| tags.contains(&t.tag))
+    {
+        return Err(ParameterError::CuratedTag.into());
+    }
+    // Merging would bypass the clearance by organizations
+    if db
+        .all_orgs()?
+        .iter()
+        .flat_map(|org| &org.moderated_tags)
+        .any(|t| t.label == into || tags.contains(&t.label))
+    {
+        return Err(ParameterError::ModeratedTag.into());
+    }
+    info!("Merging tags {:?} into '{}'", tags, into);
+
+    let replace_tags = |old_tags: &[String]| {
+        if !old_tags.iter().any(|t| tags.contains(t)) {
+            return None;
+        }
+        let mut new_tags: Vec<_> = old_tags
+            .iter()
+            .map(|t| if tags.contains(t) { &into } else { t })
+            .cloned()
+            .collect();
+        new_tags.sort_unstable();
+        new_tags.dedup();
+        Some(new_tags)
+    };
+    let log = ActivityLog {
+        activity: Activity::now(Some(account_email.into())),
+        context: None,
+        comment: None,
+    };
+    db.create_tag_if_it_does_not_exist(&Tag { id: into.clone() })?;
+
+    let mut places = vec![];
+    let mut confirmed_place_ids = vec![];
+    for (place, status) in db.all_places()? {
+        if !status.exists() {
+            continue;
+        }
+        if let Some(tags) = replace_tags(&place.tags) {
+            let place = Place {
+                revision: place.revision.next(),
+                created: log.activity.clone(),
+                tags,
+                ..place
+            };
+            db.create_or_update_place(place.clone())?;
+            if status == ReviewStatus::Confirmed {
+                confirmed_place_ids.push(place.id.clone());
+            }
+            places.push(place);
+        }
+    }
+    // A new revision would otherwise reset the review status
+    if !confirmed_place_ids.is_empty() {
+        let ids: Vec<_> = confirmed_place_ids.iter().map(Id::as_str).collect();
+        db.review_places(&ids, ReviewStatus::Confirmed, &log)?;
+    }
+
+    let mut events = vec![];
+    for event in db.all_events_chronologically()? {
+        if let Some(tags) = replace_tags(&event.tags) {
+            let event = Event { tags, ..event };
+            db.update_event(&event)?;
+            events.push(event);
+        }
+    }
+
+    let details = Some(format!("{} -> {}", tags.join(", "), into));
+    let place_ids: Vec<_> = places.iter().map(|p| p.id.as_str()).collect();
+    record_audit_log(
+        db,
+        &log,
+        AuditAction::TagMerge,
+        AuditEntityKind::Place,
+        &place_ids,
+        details.clone(),
+    )?;
+    let event_ids: Vec<_> = events.iter().map(|e| e.id.as_str()).collect();
+    record_audit_log(
+        db,
+        &log,
+        AuditAction::TagMerge,
+        AuditEntityKind::Event,
+        &event_ids,
+        details,
+    )?;
+
+    let mut synonyms: Vec<_> = db
+        .all_tag_synonyms()?
+        .into_iter()
+        .filter(|s| s.tag == into || tags.contains(&s.tag))
+        .map(|s| s.synonym)
+        .chain(tags.iter().cloned())
+        .collect();
+    synonyms.sort_unstable();
+    synonyms.dedup();
+    db.replace_tag_synonyms(&into, &synonyms)?;
+    for tag in &tags {
+        db.replace_tag_synonyms(tag, &[])?;
+        db.set_tag_description(tag, None)?;
+    }
+    let tags: Vec<_> = tags.iter().map(String::as_str).collect();
+    db.delete_tags(&tags)?;
+    info!(
+        "Merged tags of {} places and {} events",
+        places.len(),
+        events.len()
+    );
+    Ok(MergedTags { places, events })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{create_user, MockDb};
+    use super::*;
+
+    #[test]
+    fn merge_tags_of_places_and_events() {
+        let db = MockDb::default();
+        let permissions = RolePermissions::default();
+        create_user(&db, "admin@foo.bar", Role::Admin);
+        create_user(&db, "scout@foo.bar", Role::Scout);
+        let place = Place::build()
+            .id("place")
+            .tags(vec!["repair-cafe", "fair"])
+            .finish();
+        db.entries.borrow_mut().push((place, ReviewStatus::Created));
+        let other = Place::build().id("other").tags(vec!["fair"]).finish();
+        db.entries.borrow_mut().push((other, ReviewStatus::Created));
+        db.events.borrow_mut().push(Event {
+            id: "event".into(),
+            title: "Repair".into(),
+            description: None,
+            start: chrono::NaiveDateTime::from_timestamp(0, 0),
+            end: None,
+            location: None,
+            contact: None,
+            tags: vec!["reparaturcafe".into()],
+            homepage: None,
+            created_by: None,
+            registration: None,
+            archived: None,
+            image_url: None,
+            image_link_url: None,
+        });
+        let tags = vec!["repair-cafe".to_string(), "reparaturcafe".to_string()];
+
+        assert!(merge_tags(&db, &permissions, "scout@foo.bar", &tags, "repaircafe").is_err());
+        let merged = merge_tags(&db, &permissions, "admin@foo.bar", &tags, "repaircafe").unwrap();
+        assert_eq!(1, merged.places.len());
+        assert_eq!(1, merged.events.len());
+
+        let (place, _) = db.get_place("place").unwrap();
+        assert_eq!(Revision::from(1), place.revision);
+        assert_eq!(vec!["fair", "repaircafe"], place.tags);
+        assert_eq!(
+            Some("admin@foo.bar"),
+            place.created.by.as_ref().map(AsRef::<str>::as_ref)
+        );
+        assert_eq!(vec!["repaircafe"], db.get_event("event").unwrap().tags);
+        assert_eq!(2, db.audit_log.borrow().len());
+
+        let details = get_tag_details(&db, "repaircafe").unwrap();
+        assert_eq!(tags, details.synonyms);
+        assert_eq!(
+            vec!["reparaturcafe", "repair-cafe", "repaircafe"],
+            split_text_into_tags_with_synonyms(&db, "Reparaturcafe").unwrap()
+        );
+    }
+
+    #[test]
+    fn update_tag_description_and_synonyms() {
+        let db = MockDb::default();
+        let permissions = RolePermissions::default();
+        create_user(&db, "admin@foo.bar", Role::Admin);
+        assert!(get_tag_details(&db, "organic").is_err());
+        let update = UpdateTag {
+            description: Some("Organic food and farming".into()),
+            synonyms: vec!["Bio".into(), "organic".into()],
+        };
+        let details = update_tag(&db, &permissions, "admin@foo.bar", "organic", update).unwrap();
+        assert_eq!(vec!["bio"], details.synonyms);
+        assert_eq!(details, get_tag_details(&db, "organic").unwrap());
+
+        // Synonyms must not have synonyms themselves
+        let update = UpdateTag {
+            description: None,
+            synonyms: vec!["organic".into()],
+        };
+        assert!(update_tag(&db, &permissions, "admin@foo.bar", "eco", update).is_err());
+        let update = UpdateTag {
+            description: None,
+            synonyms: vec!["oeko".into()],
+        };
+        assert!(update_tag(&db, &permissions, "admin@foo.bar", "bio", update).is_err());
+    }
+}
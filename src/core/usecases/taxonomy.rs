@@ -0,0 +1,240 @@
+use super::{authorize_user_by_permission, tags::prepare_tag};
+use crate::core::prelude::*;
+use ofdb_core::{tag, user::RolePermissions};
+
+use std::collections::HashMap;
+
+/// A curated tag with all of its children.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxonomyNode {
+    pub tag: String,
+    pub description: Option<String>,
+    pub children: Vec<TaxonomyNode>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewTaxonomyNode {
+    pub tag: String,
+    pub children: Vec<NewTaxonomyNode>,
+}
+
+fn build_taxonomy_nodes<D: Db>(
+    db: &D,
+    taxonomy: &[TaxonomyTag],
+    parent: Option<&str>,
+) -> Result<Vec<TaxonomyNode>> {
+    let mut nodes = vec![];
+    for t in taxonomy.iter().filter(|t| t.parent.as_deref() == parent) {
+        nodes.push(TaxonomyNode {
+            tag: t.tag.clone(),
+            description: db.get_tag_description(&t.tag)?,
+            children: build_taxonomy_nodes(db, taxonomy, Some(&t.tag))?,
+        });
+    }
+    // Siblings are ordered alphabetically
+    nodes.sort_by(|lhs, rhs| lhs.tag.cmp(&rhs.tag));
+    Ok(nodes)
+}
+
+/// The tree of curated tags starting with the top-level themes.
+pub fn get_taxonomy<D: Db>(db: &D) -> Result<Vec<TaxonomyNode>> {
+    let taxonomy = db.all_taxonomy_tags()?;
+    build_taxonomy_nodes(db, &taxonomy, None)
+}
+
+fn flatten_taxonomy_nodes(
+    nodes: Vec<NewTaxonomyNode>,
+    parent: Option<&str>,
+    taxonomy: &mut Vec<TaxonomyTag>,
+) -> Result<()> {
+    for NewTaxonomyNode { tag, children } in nodes {
+        let tag = prepare_tag(&tag)?;
+        if taxonomy.iter().any(|t| t.tag == tag) {
+            return Err(ParameterError::Taxonomy.into());
+        }
+        taxonomy.push(TaxonomyTag {
+            tag: tag.clone(),
+            parent: parent.map(ToOwned::to_owned),
+        });
+        flatten_taxonomy_nodes(children, Some(&tag), taxonomy)?;
+    }
+    Ok(())
+}
+
+/// Replace the whole taxonomy.
+pub fn replace_taxonomy<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    account_email: &str,
+    nodes: Vec<NewTaxonomyNode>,
+) -> Result<Vec<TaxonomyNode>> {
+    authorize_user_by_permission(db, permissions, account_email, Permission::TagManage)?;
+    let mut taxonomy = vec![];
+    flatten_taxonomy_nodes(nodes, None, &mut taxonomy)?;
+    // Synonyms are found when searching for their tag
+    let synonyms = db.all_tag_synonyms()?;
+    if taxonomy
+        .iter()
+        .any(|t| synonyms.iter().any(|s| s.synonym == t.tag))
+    {
+        return Err(ParameterError::TagSynonym.into());
+    }
+    info!("Replacing taxonomy with {} tags", taxonomy.len());
+    for t in &taxonomy {
+        db.create_tag_if_it_does_not_exist(&Tag { id: t.tag.clone() })?;
+    }
+    db.replace_taxonomy(&taxonomy)?;
+    get_taxonomy(db)
+}
+
+/// The alternatives of all hash tags, i.e. the children of
+/// tags that are part of the taxonomy and all synonyms.
+/// Searching for a hash tag also finds its alternatives.
+pub fn hash_tag_alternatives<D: Db>(
+    db: &D,
+    hash_tags: &[String],
+) -> Result<HashMap<String, Vec<String>>> {
+    if hash_tags.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let taxonomy = db.all_taxonomy_tags()?;
+    let synonyms = db.all_tag_synonyms()?;
+    Ok(hash_tags
+        .iter()
+        .map(|t| t.to_lowercase())
+        .map(|t| {
+            let mut tags = vec![t.clone()];
+            tags.extend(tag::descendants(&t, &taxonomy));
+            let alternatives: Vec<_> = tag::expand_synonyms(tags, &synonyms)
+                .into_iter()
+                .filter(|a| *a != t)
+                .collect();
+            (t, alternatives)
+        })
+        .filter(|(_, alternatives)| !alternatives.is_empty())
+        .collect())
+}
+
+/// Curated tags that start with the given prefix, e.g. for
+/// suggesting tags when creating places. Synonyms are replaced
+/// by their curated tag.
+pub fn suggest_curated_tags<D: Db>(db: &D, prefix: &str, limit: usize) -> Result<Vec<String>> {
+    let prefix = prefix.trim().trim_start_matches('#').to_lowercase();
+    if prefix.is_empty() {
+        return Ok(vec![]);
+    }
+    let taxonomy = db.all_taxonomy_tags()?;
+    let is_curated = |tag: &str| taxonomy.iter().any(|t| t.tag == tag);
+    let synonyms = db.all_tag_synonyms()?;
+    let mut suggestions: Vec<_> = taxonomy
+        .iter()
+        .map(|t| &t.tag)
+        .filter(|t| t.starts_with(&prefix))
+        .chain(
+            synonyms
+                .iter()
+                .filter(|s| s.synonym.starts_with(&prefix) && is_curated(&s.tag))
+                .map(|s| &s.tag),
+        )
+        .cloned()
+        .collect();
+    suggestions.sort_unstable();
+    suggestions.dedup();
+    suggestions.truncate(limit);
+    Ok(suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{create_user, MockDb};
+    use super::*;
+
+    fn node(tag: &str, children: Vec<NewTaxonomyNode>) -> NewTaxonomyNode {
+        NewTaxonomyNode {
+            tag: tag.into(),
+            children,
+        }
+    }
+
+    fn food_taxonomy() -> Vec<NewTaxonomyNode> {
+        vec![
+            node(
+                "Food",
+                vec![
+                    node("organic-shop", vec![node("unpacked", vec![])]),
+                    node("#food-coop", vec![]),
+                ],
+            ),
+            node("mobility", vec![]),
+        ]
+    }
+
+    #[test]
+    fn replace_and_get_taxonomy() {
+        let db = MockDb::default();
+        let permissions = RolePermissions::default();
+        create_user(&db, "admin@foo.bar", Role::Admin);
+        db.set_tag_description("food", Some("Food and groceries"))
+            .unwrap();
+        let taxonomy =
+            replace_taxonomy(&db, &permissions, "admin@foo.bar", food_taxonomy()).unwrap();
+        assert_eq!(taxonomy, get_taxonomy(&db).unwrap());
+        assert_eq!(2, taxonomy.len());
+        let food = &taxonomy[0];
+        assert_eq!("food", food.tag);
+        assert_eq!(Some("Food and groceries"), food.description.as_deref());
+        assert_eq!(
+            vec!["food-coop", "organic-shop"],
+            food.children
+                .iter()
+                .map(|n| n.tag.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("unpacked", food.children[1].children[0].tag);
+        assert_eq!(5, db.all_tags().unwrap().len());
+
+        db.replace_tag_synonyms("food-coop", &["foodcoop".into()])
+            .unwrap();
+        let alternatives =
+            hash_tag_alternatives(&db, &["food".into(), "fair".into(), "foodcoop".into()]).unwrap();
+        assert_eq!(2, alternatives.len());
+        assert_eq!(
+            vec!["organic-shop", "food-coop", "unpacked", "foodcoop"],
+            alternatives["food"]
+        );
+        assert_eq!(vec!["food-coop"], alternatives["foodcoop"]);
+    }
+
+    #[test]
+    fn reject_invalid_taxonomy() {
+        let db = MockDb::default();
+        let permissions = RolePermissions::default();
+        create_user(&db, "admin@foo.bar", Role::Admin);
+        let duplicate = vec![node("food", vec![node("food", vec![])])];
+        assert!(replace_taxonomy(&db, &permissions, "admin@foo.bar", duplicate).is_err());
+        db.replace_tag_synonyms("organic-shop", &["bioladen".into()])
+            .unwrap();
+        let synonym = vec![node("food", vec![node("bioladen", vec![])])];
+        assert!(replace_taxonomy(&db, &permissions, "admin@foo.bar", synonym).is_err());
+        assert!(replace_taxonomy(&db, &permissions, "user@foo.bar", food_taxonomy()).is_err());
+        assert!(get_taxonomy(&db).unwrap().is_empty());
+    }
+
+    #[test]
+    fn suggest_curated_tags_and_synonyms() {
+        let db = MockDb::default();
+        let permissions = RolePermissions::default();
+        create_user(&db, "admin@foo.bar", Role::Admin);
+        replace_taxonomy(&db, &permissions, "admin@foo.bar", food_taxonomy()).unwrap();
+        db.replace_tag_synonyms("food-coop", &["foodcoop".into()])
+            .unwrap();
+        db.replace_tag_synonyms("other", &["food-sharing".into()])
+            .unwrap();
+        assert_eq!(
+            vec!["food", "food-coop"],
+            suggest_curated_tags(&db, "#Foo", 10).unwrap()
+        );
+        assert_eq!(vec!["food"], suggest_curated_tags(&db, "foo", 1).unwrap());
+        assert!(suggest_curated_tags(&db, " ", 10).unwrap().is_empty());
+    }
+}
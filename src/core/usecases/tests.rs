@@ -50,12 +50,24 @@ impl Key for Comment {
     }
 }
 
+impl Key for SavedSearch {
+    fn key(&self) -> &str {
+        self.id.as_ref()
+    }
+}
+
 impl Key for Rating {
     fn key(&self) -> &str {
         &self.id.as_ref()
     }
 }
 
+impl Key for Image {
+    fn key(&self) -> &str {
+        self.id.as_ref()
+    }
+}
+
 impl Key for BboxSubscription {
     fn key(&self) -> &str {
         self.id.as_ref()
@@ -76,9 +88,21 @@ pub struct MockDb {
     pub users: RefCell<Vec<User>>,
     pub ratings: RefCell<Vec<Rating>>,
     pub comments: RefCell<Vec<Comment>>,
+    pub rating_flags: RefCell<Vec<(String, ActivityLog)>>,
     pub bbox_subscriptions: RefCell<Vec<BboxSubscription>>,
     pub orgs: Vec<Organization>,
     pub token: RefCell<Vec<UserToken>>,
+    pub user_regions: RefCell<Vec<(String, MapBbox)>>,
+    pub audit_log: RefCell<Vec<AuditLogEntry>>,
+    pub place_owners: RefCell<Vec<PlaceOwnership>>,
+    pub place_edit_suggestions: RefCell<Vec<PlaceEditSuggestion>>,
+    pub images: RefCell<Vec<Image>>,
+    pub link_checks: RefCell<Vec<LinkCheck>>,
+    pub saved_searches: RefCell<Vec<SavedSearch>>,
+    pub tag_synonyms: RefCell<Vec<TagSynonym>>,
+    pub tag_descriptions: RefCell<Vec<(String, String)>>,
+    pub taxonomy: RefCell<Vec<TaxonomyTag>>,
+    pub categories: RefCell<Vec<Category>>,
 }
 
 impl UserTokenRepo for MockDb {
@@ -118,8 +142,13 @@ impl UserTokenRepo for MockDb {
         Ok(len_before - len_after)
     }
 
-    fn get_user_token_by_email(&self, _email: &str) -> RepoResult<UserToken> {
-        unimplemented!()
+    fn get_user_token_by_email(&self, email: &str) -> RepoResult<UserToken> {
+        self.token
+            .borrow()
+            .iter()
+            .find(|x| x.email_nonce.email == email)
+            .cloned()
+            .ok_or(RepoError::NotFound)
     }
 }
 
@@ -152,6 +181,28 @@ impl PlaceIndex for DummySearchEngine {
     fn query_places(&self, _query: &IndexQuery, _limit: usize) -> Fallible<Vec<IndexedPlace>> {
         unimplemented!();
     }
+
+    fn query_places_sorted(
+        &self,
+        _query: &IndexQuery,
+        _order: PlaceSortOrder,
+        _after: Option<&PlaceSortPosition>,
+        _limit: usize,
+    ) -> Fallible<Vec<(PlaceSortPosition, IndexedPlace)>> {
+        unimplemented!();
+    }
+
+    fn query_place_clusters(
+        &self,
+        _query: &IndexQuery,
+        _cell_size: f64,
+    ) -> Fallible<Vec<IndexedPlaceCluster>> {
+        unimplemented!();
+    }
+
+    fn query_place_facets(&self, _query: &IndexQuery) -> Fallible<IndexedPlaceFacets> {
+        unimplemented!();
+    }
 }
 
 impl PlaceIndexer for DummySearchEngine {
@@ -209,6 +260,16 @@ fn update<T: Clone + Key>(objects: &mut Vec<T>, e: &T) -> RepoResult<()> {
     Ok(())
 }
 
+/// Add a confirmed user with the password "secret"
+pub fn create_user(db: &MockDb, email: &str, role: Role) {
+    db.users.borrow_mut().push(User {
+        email: email.into(),
+        email_confirmed: true,
+        password: "secret".parse::<Password>().unwrap(),
+        role,
+    });
+}
+
 impl PlaceRepo for MockDb {
     fn create_or_update_place(&self, place: Place) -> RepoResult<()> {
         create_or_replace(
@@ -280,8 +341,27 @@ impl PlaceRepo for MockDb {
         unimplemented!();
     }
 
-    fn load_place_revision(&self, _id: &str, _rev: Revision) -> RepoResult<(Place, ReviewStatus)> {
-        unimplemented!();
+    fn load_place_revision(&self, id: &str, rev: Revision) -> RepoResult<(Place, ReviewStatus)> {
+        // Only the current revision is available
+        self.entries
+            .borrow()
+            .iter()
+            .find(|(p, _)| p.id.as_str() == id && p.revision == rev)
+            .cloned()
+            .ok_or(RepoError::NotFound)
+    }
+
+    fn load_place_revisions_created_by(
+        &self,
+        user_email: &str,
+    ) -> RepoResult<Vec<(Place, ReviewStatus)>> {
+        Ok(self
+            .entries
+            .borrow()
+            .iter()
+            .filter(|(p, _)| p.created.by.as_ref().map(|e| e.as_str()) == Some(user_email))
+            .cloned()
+            .collect())
     }
 }
 
@@ -381,6 +461,46 @@ impl UserGateway for MockDb {
     fn update_user(&self, u: &User) -> RepoResult<()> {
         update(&mut self.users.borrow_mut(), u)
     }
+
+    fn update_user_email(&self, email: &str, new_email: &str) -> RepoResult<()> {
+        let mut users = self.users.borrow_mut();
+        let user = users
+            .iter_mut()
+            .find(|u| u.email == email)
+            .ok_or(RepoError::NotFound)?;
+        user.email = new_email.to_owned();
+        Ok(())
+    }
+
+    fn anonymize_user_by_email(
+        &self,
+        _email: &str,
+        _pseudonym: &str,
+        _anonymized_at: TimestampMs,
+    ) -> RepoResult<AnonymizedUser> {
+        unimplemented!();
+    }
+
+    fn all_anonymized_users(&self) -> RepoResult<Vec<AnonymizedUser>> {
+        unimplemented!();
+    }
+
+    fn replace_user_regions_by_email(&self, email: &str, regions: &[MapBbox]) -> RepoResult<()> {
+        let mut user_regions = self.user_regions.borrow_mut();
+        user_regions.retain(|(e, _)| e != email);
+        user_regions.extend(regions.iter().map(|bbox| (email.to_owned(), *bbox)));
+        Ok(())
+    }
+
+    fn user_regions_by_email(&self, email: &str) -> RepoResult<Vec<MapBbox>> {
+        Ok(self
+            .user_regions
+            .borrow()
+            .iter()
+            .filter(|(e, _)| e == email)
+            .map(|(_, bbox)| *bbox)
+            .collect())
+    }
 }
 
 impl CommentRepository for MockDb {
@@ -423,10 +543,17 @@ impl CommentRepository for MockDb {
     }
     fn archive_comments_of_ratings(
         &self,
-        _rating_ids: &[&str],
+        rating_ids: &[&str],
         _activity: &Activity,
     ) -> RepoResult<usize> {
-        unimplemented!();
+        let mut count = 0;
+        for c in self.comments.borrow_mut().iter_mut() {
+            if c.archived_at.is_none() && rating_ids.iter().any(|id| c.rating_id.as_str() == *id) {
+                c.archived_at = Some(Timestamp::now());
+                count += 1;
+            }
+        }
+        Ok(count)
     }
     fn archive_comments_of_places(
         &self,
@@ -449,6 +576,9 @@ impl OrganizationRepo for MockDb {
             .ok_or(RepoError::NotFound)?;
         Ok(o.clone())
     }
+    fn all_orgs(&self) -> RepoResult<Vec<Organization>> {
+        Ok(self.orgs.clone())
+    }
     fn map_tag_to_clearance_org_id(&self, tag: &str) -> RepoResult<Option<Id>> {
         Ok(self
             .orgs
@@ -513,11 +643,31 @@ impl RatingRepository for MockDb {
             .collect())
     }
 
-    fn load_place_ids_of_ratings(&self, _ids: &[&str]) -> RepoResult<Vec<String>> {
+    fn load_ratings_created_by(&self, _user_email: &str) -> RepoResult<Vec<Rating>> {
         unimplemented!();
     }
-    fn archive_ratings(&self, _ids: &[&str], _activity: &Activity) -> RepoResult<usize> {
-        unimplemented!();
+
+    fn load_place_ids_of_ratings(&self, ids: &[&str]) -> RepoResult<Vec<String>> {
+        let mut place_ids: Vec<_> = self
+            .ratings
+            .borrow()
+            .iter()
+            .filter(|r| ids.iter().any(|id| r.id.as_str() == *id))
+            .map(|r| r.place_id.to_string())
+            .collect();
+        place_ids.sort_unstable();
+        place_ids.dedup();
+        Ok(place_ids)
+    }
+    fn archive_ratings(&self, ids: &[&str], _activity: &Activity) -> RepoResult<usize> {
+        let mut count = 0;
+        for r in self.ratings.borrow_mut().iter_mut() {
+            if r.archived_at.is_none() && ids.iter().any(|id| r.id.as_str() == *id) {
+                r.archived_at = Some(Timestamp::now());
+                count += 1;
+            }
+        }
+        Ok(count)
     }
     fn archive_ratings_of_places(
         &self,
@@ -526,6 +676,36 @@ impl RatingRepository for MockDb {
     ) -> RepoResult<usize> {
         unimplemented!();
     }
+
+    fn flag_rating(&self, id: &str, activity: &Activity, comment: Option<&str>) -> RepoResult<()> {
+        self.rating_flags.borrow_mut().push((
+            id.to_owned(),
+            ActivityLog {
+                activity: activity.clone(),
+                context: None,
+                comment: comment.map(ToOwned::to_owned),
+            },
+        ));
+        Ok(())
+    }
+
+    fn load_flagged_ratings(&self, _pagination: &Pagination) -> RepoResult<Vec<FlaggedRating>> {
+        let mut flagged_ratings: Vec<FlaggedRating> = vec![];
+        for (id, flag) in self.rating_flags.borrow().iter().rev() {
+            if let Some(flagged) = flagged_ratings
+                .iter_mut()
+                .find(|f| f.rating.id.as_str() == id)
+            {
+                flagged.flags.push(flag.clone());
+            } else if let Ok(rating) = self.load_rating(id) {
+                flagged_ratings.push(FlaggedRating {
+                    rating,
+                    flags: vec![flag.clone()],
+                });
+            }
+        }
+        Ok(flagged_ratings)
+    }
 }
 
 impl PlaceClearanceRepo for MockDb {
@@ -568,6 +748,396 @@ impl PlaceClearanceRepo for MockDb {
     fn cleanup_pending_clearances_for_places(&self, _org_id: &Id) -> RepoResult<u64> {
         Ok(0)
     }
+
+    fn revert_pending_clearances_for_places(
+        &self,
+        _org_id: &Id,
+        _place_ids: &[&str],
+    ) -> RepoResult<usize> {
+        Ok(0)
+    }
+}
+
+impl EventClearanceRepo for MockDb {
+    fn add_pending_clearance_for_events(
+        &self,
+        org_ids: &[Id],
+        _pending_clearance: &PendingClearanceForEvent,
+    ) -> RepoResult<usize> {
+        Ok(org_ids.len())
+    }
+
+    fn count_pending_clearances_for_events(&self, _org_id: &Id) -> RepoResult<u64> {
+        Ok(0)
+    }
+
+    fn list_pending_clearances_for_events(
+        &self,
+        _org_id: &Id,
+        _pagination: &Pagination,
+    ) -> RepoResult<Vec<PendingClearanceForEvent>> {
+        Ok(vec![])
+    }
+
+    fn load_pending_clearances_for_events(
+        &self,
+        _org_id: &Id,
+        _event_ids: &[&str],
+    ) -> RepoResult<Vec<PendingClearanceForEvent>> {
+        Ok(vec![])
+    }
+
+    fn update_pending_clearances_for_events(
+        &self,
+        _org_id: &Id,
+        _clearances: &[ClearanceForEvent],
+    ) -> RepoResult<usize> {
+        Ok(0)
+    }
+}
+
+impl AuditLogRepo for MockDb {
+    fn add_audit_log_entries(&self, entries: &[AuditLogEntry]) -> RepoResult<()> {
+        self.audit_log.borrow_mut().extend_from_slice(entries);
+        Ok(())
+    }
+
+    fn query_audit_log(
+        &self,
+        query: &AuditLogQuery,
+        pagination: &Pagination,
+    ) -> RepoResult<Vec<AuditLogEntry>> {
+        let entries = self
+            .audit_log
+            .borrow()
+            .iter()
+            .rev()
+            .filter(|e| {
+                query
+                    .since
+                    .map(|since| e.log.activity.at >= since)
+                    .unwrap_or(true)
+            })
+            .filter(|e| {
+                query
+                    .actor_email
+                    .map(|email| e.log.activity.by.as_ref().map(|by| by.as_str()) == Some(email))
+                    .unwrap_or(true)
+            })
+            .filter(|e| query.entity_id.map(|id| e.entity.id == id).unwrap_or(true))
+            .filter(|e| query.action.map(|a| e.action == a).unwrap_or(true))
+            .skip(pagination.offset.unwrap_or(0) as usize)
+            .take(pagination.limit.map(|l| l as usize).unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+        Ok(entries)
+    }
+}
+
+impl PlaceOwnershipRepo for MockDb {
+    fn set_place_owner(&self, place_id: &str, owner_email: &str) -> RepoResult<PlaceOwnership> {
+        let ownership = PlaceOwnership {
+            place_id: place_id.into(),
+            owner: owner_email.into(),
+            created_at: TimestampMs::now(),
+            locked_fields: vec![],
+        };
+        let mut place_owners = self.place_owners.borrow_mut();
+        place_owners.retain(|o| o.place_id.as_str() != place_id);
+        place_owners.push(ownership.clone());
+        Ok(ownership)
+    }
+
+    fn get_place_ownership(&self, place_id: &str) -> RepoResult<Option<PlaceOwnership>> {
+        Ok(self
+            .place_owners
+            .borrow()
+            .iter()
+            .find(|o| o.place_id.as_str() == place_id)
+            .cloned())
+    }
+
+    fn update_locked_place_fields(&self, place_id: &str, fields: &[PlaceField]) -> RepoResult<()> {
+        let mut place_owners = self.place_owners.borrow_mut();
+        let ownership = place_owners
+            .iter_mut()
+            .find(|o| o.place_id.as_str() == place_id)
+            .ok_or(RepoError::NotFound)?;
+        ownership.locked_fields = fields.to_vec();
+        Ok(())
+    }
+}
+
+impl PlaceEditSuggestionRepo for MockDb {
+    fn add_place_edit_suggestion(&self, suggestion: &PlaceEditSuggestion) -> RepoResult<()> {
+        self.place_edit_suggestions
+            .borrow_mut()
+            .push(suggestion.clone());
+        Ok(())
+    }
+
+    fn get_place_edit_suggestion(&self, id: &str) -> RepoResult<PlaceEditSuggestion> {
+        self.place_edit_suggestions
+            .borrow()
+            .iter()
+            .find(|s| s.id.as_str() == id)
+            .cloned()
+            .ok_or(RepoError::NotFound)
+    }
+
+    fn query_place_edit_suggestions(
+        &self,
+        query: &PlaceEditSuggestionQuery,
+        pagination: &Pagination,
+    ) -> RepoResult<Vec<PlaceEditSuggestion>> {
+        let entries = self.entries.borrow();
+        let offset = pagination.offset.unwrap_or(0) as usize;
+        let limit = pagination.limit.unwrap_or(u64::MAX) as usize;
+        Ok(self
+            .place_edit_suggestions
+            .borrow()
+            .iter()
+            .filter(|s| {
+                query
+                    .place_id
+                    .map_or(true, |id| s.suggested.id.as_str() == id)
+            })
+            .filter(|s| query.status.map_or(true, |status| s.status == status))
+            .filter(|s| {
+                query.tags.is_empty()
+                    || entries.iter().any(|(p, _)| {
+                        p.id == s.suggested.id && p.tags.iter().any(|t| query.tags.contains(t))
+                    })
+            })
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    fn review_place_edit_suggestion(
+        &self,
+        id: &str,
+        status: SuggestionStatus,
+        log: &ActivityLog,
+    ) -> RepoResult<()> {
+        let mut suggestions = self.place_edit_suggestions.borrow_mut();
+        let suggestion = suggestions
+            .iter_mut()
+            .find(|s| s.id.as_str() == id && s.status == SuggestionStatus::Pending)
+            .ok_or(RepoError::NotFound)?;
+        suggestion.status = status;
+        suggestion.reviewed = Some(log.clone());
+        Ok(())
+    }
+}
+
+impl ImageRepo for MockDb {
+    fn add_image(&self, image: &Image) -> RepoResult<()> {
+        create(&mut self.images.borrow_mut(), image.clone())
+    }
+
+    fn get_image(&self, id: &str) -> RepoResult<Image> {
+        self.images
+            .borrow()
+            .iter()
+            .find(|i| i.id.as_str() == id)
+            .cloned()
+            .ok_or(RepoError::NotFound)
+    }
+}
+
+impl LinkCheckRepo for MockDb {
+    fn save_link_checks(&self, checks: &[LinkCheck]) -> RepoResult<()> {
+        self.delete_link_checks(checks)?;
+        self.link_checks.borrow_mut().extend(checks.iter().cloned());
+        Ok(())
+    }
+
+    fn delete_link_checks(&self, checks: &[LinkCheck]) -> RepoResult<()> {
+        self.link_checks
+            .borrow_mut()
+            .retain(|x| !checks.iter().any(|c| c.owner == x.owner && c.url == x.url));
+        Ok(())
+    }
+
+    fn query_link_checks(
+        &self,
+        query: &LinkCheckQuery,
+        pagination: &Pagination,
+    ) -> RepoResult<Vec<LinkCheck>> {
+        let offset = pagination.offset.unwrap_or(0) as usize;
+        let limit = pagination.limit.unwrap_or(u64::MAX) as usize;
+        Ok(self
+            .link_checks
+            .borrow()
+            .iter()
+            .filter(|c| query.owner_kind.map_or(true, |kind| c.owner.kind == kind))
+            .filter(|c| {
+                query
+                    .status
+                    .map_or(true, |status| c.outcome.status == status)
+            })
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+}
+
+impl StatisticsRepo for MockDb {
+    fn place_revision_times(&self) -> RepoResult<Vec<(Revision, TimestampMs)>> {
+        Ok(self
+            .entries
+            .borrow()
+            .iter()
+            .map(|(p, _)| (p.revision, p.created.at))
+            .collect())
+    }
+
+    fn event_start_times(&self) -> RepoResult<Vec<Timestamp>> {
+        Ok(self
+            .events
+            .borrow()
+            .iter()
+            .filter(|e| e.archived.is_none())
+            .map(|e| e.start.into())
+            .collect())
+    }
+
+    fn user_registration_times(&self) -> RepoResult<Vec<TimestampMs>> {
+        Ok(vec![])
+    }
+
+    fn rating_creation_times(&self) -> RepoResult<Vec<Timestamp>> {
+        Ok(self.ratings.borrow().iter().map(|r| r.created_at).collect())
+    }
+}
+
+impl SavedSearchRepo for MockDb {
+    fn create_saved_search(&self, saved_search: &SavedSearch) -> RepoResult<()> {
+        create(&mut self.saved_searches.borrow_mut(), saved_search.clone())
+    }
+
+    fn update_saved_search(&self, saved_search: &SavedSearch) -> RepoResult<()> {
+        let old = get(&self.saved_searches.borrow(), saved_search.id.as_ref())?;
+        update(
+            &mut self.saved_searches.borrow_mut(),
+            &SavedSearch {
+                user_email: old.user_email,
+                created_at: old.created_at,
+                ..saved_search.clone()
+            },
+        )
+    }
+
+    fn delete_saved_search(&self, id: &str) -> RepoResult<()> {
+        let mut saved_searches = self.saved_searches.borrow_mut();
+        let len_before = saved_searches.len();
+        saved_searches.retain(|s| s.id.as_str() != id);
+        if saved_searches.len() == len_before {
+            return Err(RepoError::NotFound);
+        }
+        Ok(())
+    }
+
+    fn get_saved_search(&self, id: &str) -> RepoResult<SavedSearch> {
+        get(&self.saved_searches.borrow(), id)
+    }
+
+    fn saved_searches_by_email(&self, user_email: &str) -> RepoResult<Vec<SavedSearch>> {
+        Ok(self
+            .saved_searches
+            .borrow()
+            .iter()
+            .filter(|s| s.user_email == user_email)
+            .cloned()
+            .collect())
+    }
+
+    fn all_saved_searches_with_alert(&self) -> RepoResult<Vec<SavedSearch>> {
+        Ok(self
+            .saved_searches
+            .borrow()
+            .iter()
+            .filter(|s| s.alert)
+            .cloned()
+            .collect())
+    }
+}
+
+impl TagRepo for MockDb {
+    fn all_tag_synonyms(&self) -> RepoResult<Vec<TagSynonym>> {
+        Ok(self.tag_synonyms.borrow().clone())
+    }
+
+    fn replace_tag_synonyms(&self, tag: &str, synonyms: &[String]) -> RepoResult<()> {
+        let mut tag_synonyms = self.tag_synonyms.borrow_mut();
+        tag_synonyms.retain(|s| s.tag != tag && !synonyms.contains(&s.synonym));
+        tag_synonyms.extend(synonyms.iter().map(|synonym| TagSynonym {
+            synonym: synonym.clone(),
+            tag: tag.to_owned(),
+        }));
+        Ok(())
+    }
+
+    fn get_tag_description(&self, tag: &str) -> RepoResult<Option<String>> {
+        Ok(self
+            .tag_descriptions
+            .borrow()
+            .iter()
+            .find(|(t, _)| t == tag)
+            .map(|(_, description)| description.clone()))
+    }
+
+    fn set_tag_description(&self, tag: &str, description: Option<&str>) -> RepoResult<()> {
+        let mut tag_descriptions = self.tag_descriptions.borrow_mut();
+        tag_descriptions.retain(|(t, _)| t != tag);
+        if let Some(description) = description {
+            tag_descriptions.push((tag.to_owned(), description.to_owned()));
+        }
+        Ok(())
+    }
+
+    fn delete_tags(&self, tags: &[&str]) -> RepoResult<usize> {
+        let mut all_tags = self.tags.borrow_mut();
+        let len_before = all_tags.len();
+        all_tags.retain(|t| !tags.contains(&t.id.as_str()));
+        Ok(len_before - all_tags.len())
+    }
+
+    fn all_taxonomy_tags(&self) -> RepoResult<Vec<TaxonomyTag>> {
+        Ok(self.taxonomy.borrow().clone())
+    }
+
+    fn replace_taxonomy(&self, taxonomy: &[TaxonomyTag]) -> RepoResult<()> {
+        *self.taxonomy.borrow_mut() = taxonomy.to_vec();
+        Ok(())
+    }
+}
+
+impl CategoryRepo for MockDb {
+    fn all_categories(&self) -> RepoResult<Vec<Category>> {
+        Ok(self.categories.borrow().clone())
+    }
+
+    fn create_category(&self, category: &Category) -> RepoResult<()> {
+        create(&mut self.categories.borrow_mut(), category.clone())
+    }
+
+    fn update_category(&self, category: &Category) -> RepoResult<()> {
+        update(&mut self.categories.borrow_mut(), category)
+    }
+
+    fn delete_category(&self, id: &str) -> RepoResult<()> {
+        let mut categories = self.categories.borrow_mut();
+        let len_before = categories.len();
+        categories.retain(|c| c.id.as_str() != id);
+        if categories.len() == len_before {
+            return Err(RepoError::NotFound);
+        }
+        Ok(())
+    }
 }
 
 impl Db for MockDb {
@@ -92,9 +92,9 @@ impl From<Place> for UpdatePlace {
 }
 
 pub struct Storable {
-    place: Place,
-    clearance_org_ids: Vec<Id>,
-    last_cleared_revision: Revision,
+    pub(super) place: Place,
+    pub(super) clearance_org_ids: Vec<Id>,
+    pub(super) last_cleared_revision: Revision,
 }
 
 pub fn prepare_updated_place<D: Db>(
@@ -104,6 +104,26 @@ pub fn prepare_updated_place<D: Db>(
     created_by_email: Option<&str>,
     created_by_org: Option<&Organization>,
 ) -> Result<Storable> {
+    let (old_place, place) = prepare_place_revision(db, place_id, e, created_by_email)?;
+    let clearance_org_ids =
+        super::authorize_editing_of_tagged_entry(db, &old_place.tags, &place.tags, created_by_org)?;
+    super::authorize_editing_of_locked_fields(db, &old_place, &place, created_by_email)?;
+    Ok(Storable {
+        place,
+        clearance_org_ids,
+        last_cleared_revision: old_place.revision,
+    })
+}
+
+// Validates the new revision of a place without any authorization.
+//
+// Returns both the current and the new revision of the place.
+pub(super) fn prepare_place_revision<D: Db>(
+    db: &D,
+    place_id: Id,
+    e: UpdatePlace,
+    created_by_email: Option<&str>,
+) -> Result<(Place, Place)> {
     let UpdatePlace {
         version,
         title,
@@ -143,29 +163,22 @@ pub fn prepare_updated_place<D: Db>(
         Some(address)
     };
 
-    let (revision, last_cleared_revision, old_tags, license) = {
-        let (old_place, _review_status) = db.get_place(place_id.as_str())?;
-        // Check for revision conflict (optimistic locking)
-        let revision = Revision::from(version);
-        if old_place.revision.next() != revision {
-            return Err(RepoError::InvalidVersion.into());
-        }
-        let last_cleared_revision = old_place.revision;
-        // The license is immutable
-        let license = old_place.license;
-        // The existing tags are needed for authorization
-        let old_tags = old_place.tags;
-        (revision, last_cleared_revision, old_tags, license)
-    };
+    // The existing place is needed for authorization
+    let (old_place, _review_status) = db.get_place(place_id.as_str())?;
+    // Check for revision conflict (optimistic locking)
+    let revision = Revision::from(version);
+    if old_place.revision.next() != revision {
+        return Err(RepoError::InvalidVersion.into());
+    }
+    // The license is immutable
+    let license = old_place.license.clone();
 
     let categories: Vec<_> = categories.into_iter().map(Id::from).collect();
     let new_tags = super::prepare_tag_list(
-        Category::merge_ids_into_tags(&categories, tags)
+        Category::merge_ids_into_tags_with(&db.all_categories()?, &categories, tags)
             .iter()
             .map(String::as_str),
     );
-    let clearance_org_ids =
-        super::authorize_editing_of_tagged_entry(db, &old_tags, &new_tags, created_by_org)?;
 
     let homepage = homepage
         .and_then(|ref url| parse_url_param(url).transpose())
@@ -217,11 +230,7 @@ pub fn prepare_updated_place<D: Db>(
         tags: new_tags,
     };
     place.validate()?;
-    Ok(Storable {
-        place,
-        clearance_org_ids,
-        last_cleared_revision,
-    })
+    Ok((old_place, place))
 }
 
 pub fn store_updated_place<D: Db>(db: &D, s: Storable) -> Result<(Place, Vec<Rating>)> {
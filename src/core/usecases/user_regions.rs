@@ -0,0 +1,127 @@
+use super::{authorize_user_by_permission, record_audit_log};
+use crate::core::{prelude::*, util::validate};
+use ofdb_core::user::RolePermissions;
+
+/// Restrict the moderation permissions of a user to the given regions.
+///
+/// An empty list of regions removes all restrictions.
+pub fn change_user_regions<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    account_email: &str,
+    user_email: &str,
+    regions: &[MapBbox],
+) -> Result<()> {
+    authorize_user_by_permission(db, permissions, account_email, Permission::UserChangeRole)?;
+    for bbox in regions {
+        validate::bbox(bbox)?;
+    }
+    info!("Changing regions of {} into {:?}", user_email, regions);
+    db.replace_user_regions_by_email(user_email, regions)?;
+    record_audit_log(
+        db,
+        &Activity::now(Some(account_email.into())).into(),
+        AuditAction::UserChangeRegions,
+        AuditEntityKind::User,
+        &[user_email],
+        Some(format!("{} region(s)", regions.len())),
+    )
+}
+
+pub fn user_regions<D: Db>(
+    db: &D,
+    permissions: &RolePermissions,
+    account_email: &str,
+    user_email: &str,
+) -> Result<Vec<MapBbox>> {
+    if account_email != user_email {
+        authorize_user_by_permission(db, permissions, account_email, Permission::UserChangeRole)?;
+    }
+    Ok(db.user_regions_by_email(user_email)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        archive_ratings,
+        tests::{create_user, MockDb},
+    };
+    use super::*;
+
+    fn region() -> MapBbox {
+        MapBbox::new(
+            MapPoint::from_lat_lng_deg(40.0, 0.0),
+            MapPoint::from_lat_lng_deg(50.0, 10.0),
+        )
+    }
+
+    #[test]
+    fn only_admins_may_change_regions() {
+        let db = MockDb::default();
+        let permissions = RolePermissions::default();
+        create_user(&db, "admin@foo.bar", Role::Admin);
+        create_user(&db, "scout@foo.bar", Role::Scout);
+        assert!(change_user_regions(
+            &db,
+            &permissions,
+            "scout@foo.bar",
+            "scout@foo.bar",
+            &[region()]
+        )
+        .is_err());
+        assert!(change_user_regions(
+            &db,
+            &permissions,
+            "admin@foo.bar",
+            "scout@foo.bar",
+            &[region()]
+        )
+        .is_ok());
+        assert_eq!(
+            vec![region()],
+            user_regions(&db, &permissions, "scout@foo.bar", "scout@foo.bar").unwrap()
+        );
+        assert!(user_regions(&db, &permissions, "scout@foo.bar", "admin@foo.bar").is_err());
+    }
+
+    #[test]
+    fn scouts_may_only_moderate_their_region() {
+        let db = MockDb::default();
+        let permissions = RolePermissions::default();
+        create_user(&db, "scout@foo.bar", Role::Scout);
+        db.replace_user_regions_by_email("scout@foo.bar", &[region()])
+            .unwrap();
+        let inside = Place::build()
+            .id("inside")
+            .pos(MapPoint::from_lat_lng_deg(45.0, 5.0))
+            .finish();
+        let outside = Place::build()
+            .id("outside")
+            .pos(MapPoint::from_lat_lng_deg(10.0, 5.0))
+            .finish();
+        db.entries
+            .borrow_mut()
+            .push((inside, ReviewStatus::Created));
+        db.entries
+            .borrow_mut()
+            .push((outside, ReviewStatus::Created));
+        for (id, place_id) in &[("r1", "inside"), ("r2", "outside")] {
+            db.ratings.borrow_mut().push(Rating {
+                id: (*id).into(),
+                place_id: (*place_id).into(),
+                created_at: Timestamp::now(),
+                archived_at: None,
+                title: "title".into(),
+                value: RatingValue::from(1i8),
+                context: RatingContext::Diversity,
+                source: None,
+                author: None,
+            });
+        }
+        assert!(archive_ratings(&db, &permissions, "scout@foo.bar", &["r2"]).is_err());
+        assert_eq!(
+            1,
+            archive_ratings(&db, &permissions, "scout@foo.bar", &["r1"]).unwrap()
+        );
+    }
+}
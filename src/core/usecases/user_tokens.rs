@@ -3,6 +3,31 @@ use crate::core::prelude::*;
 use chrono::{Duration, Utc};
 
 pub fn refresh_user_token<D: Db>(db: &D, email: String) -> Result<EmailNonce> {
+    replace_user_token(db, email, None, None)
+}
+
+pub fn refresh_user_token_for_new_email<D: Db>(
+    db: &D,
+    email: String,
+    new_email: String,
+) -> Result<EmailNonce> {
+    replace_user_token(db, email, Some(new_email), None)
+}
+
+pub fn refresh_user_token_for_place_ownership<D: Db>(
+    db: &D,
+    email: String,
+    place_id: String,
+) -> Result<EmailNonce> {
+    replace_user_token(db, email, None, Some(place_id))
+}
+
+fn replace_user_token<D: Db>(
+    db: &D,
+    email: String,
+    new_email: Option<String>,
+    place_id: Option<String>,
+) -> Result<EmailNonce> {
     let email_nonce = EmailNonce {
         email,
         nonce: Nonce::new(),
@@ -10,6 +35,8 @@ pub fn refresh_user_token<D: Db>(db: &D, email: String) -> Result<EmailNonce> {
     let token = UserToken {
         email_nonce,
         expires_at: Timestamp::from(Utc::now() + Duration::days(1)),
+        new_email,
+        place_id,
     };
     Ok(db.replace_user_token(token)?)
 }
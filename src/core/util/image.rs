@@ -0,0 +1,192 @@
+use super::super::{
+    entities::ImageFormat,
+    error::{Error, ParameterError},
+};
+use image::{imageops::FilterType, io::Reader, DynamicImage, GenericImageView, ImageOutputFormat};
+use std::io::Cursor;
+
+/// Uploaded images are scaled down to fit into a square of this size.
+pub const MAX_IMAGE_SIZE: u32 = 1920;
+
+pub const THUMBNAIL_SIZE: u32 = 320;
+
+// Decoding images with huge dimensions would exhaust the memory
+const MAX_IMAGE_PIXELS: u64 = 50_000_000;
+
+const JPEG_QUALITY: u8 = 85;
+
+#[derive(Debug)]
+pub struct ProcessedImage {
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+    pub thumbnail: Vec<u8>,
+}
+
+/// Validate, scale and re-encode an uploaded image.
+///
+/// Re-encoding discards all metadata of the uploaded file
+/// including EXIF data like GPS coordinates or camera details.
+/// The EXIF orientation is applied to the pixels before.
+/// Photos are stored as JPEG and all other images as PNG to
+/// preserve transparency.
+pub fn process_uploaded_image(data: &[u8]) -> Result<ProcessedImage, Error> {
+    let input_format = image::guess_format(data).map_err(|_| ParameterError::ImageFormat)?;
+    let format = match input_format {
+        image::ImageFormat::Jpeg => ImageFormat::Jpeg,
+        image::ImageFormat::Png | image::ImageFormat::Gif | image::ImageFormat::Bmp => {
+            ImageFormat::Png
+        }
+        _ => return Err(ParameterError::ImageFormat.into()),
+    };
+    let (width, height) = Reader::with_format(Cursor::new(data), input_format)
+        .into_dimensions()
+        .map_err(|_| ParameterError::ImageFormat)?;
+    if u64::from(width) * u64::from(height) > MAX_IMAGE_PIXELS {
+        return Err(ParameterError::ImageTooLarge.into());
+    }
+    let img = image::load_from_memory_with_format(data, input_format)
+        .map_err(|_| ParameterError::ImageFormat)?;
+    let img = apply_orientation(img, exif_orientation(data));
+    let img = if width > MAX_IMAGE_SIZE || height > MAX_IMAGE_SIZE {
+        img.resize(MAX_IMAGE_SIZE, MAX_IMAGE_SIZE, FilterType::Lanczos3)
+    } else {
+        img
+    };
+    let thumbnail = img.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+    let (width, height) = img.dimensions();
+    Ok(ProcessedImage {
+        format,
+        width,
+        height,
+        data: encode(&img, format)?,
+        thumbnail: encode(&thumbnail, format)?,
+    })
+}
+
+// Images without EXIF data are not transformed (orientation 1)
+fn exif_orientation(data: &[u8]) -> u32 {
+    exif::Reader::new()
+        .read_from_container(&mut Cursor::new(data))
+        .ok()
+        .and_then(|exif| {
+            exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+                .and_then(|field| field.value.get_uint(0))
+        })
+        .unwrap_or(1)
+}
+
+// The transformations of the EXIF orientations 2 to 8
+fn apply_orientation(img: DynamicImage, orientation: u32) -> DynamicImage {
+    match orientation {
+        2 => img.fliph(),
+        3 => img.rotate180(),
+        4 => img.flipv(),
+        5 => img.rotate90().fliph(),
+        6 => img.rotate90(),
+        7 => img.rotate270().fliph(),
+        8 => img.rotate270(),
+        _ => img,
+    }
+}
+
+fn encode(img: &DynamicImage, format: ImageFormat) -> Result<Vec<u8>, Error> {
+    let mut buf = Vec::new();
+    let res = match format {
+        // JPEG does not support an alpha channel
+        ImageFormat::Jpeg => DynamicImage::ImageRgb8(img.to_rgb())
+            .write_to(&mut buf, ImageOutputFormat::Jpeg(JPEG_QUALITY)),
+        ImageFormat::Png => img.write_to(&mut buf, ImageOutputFormat::Png),
+    };
+    res.map_err(|err| Error::Internal(format!("Failed to encode image: {}", err)))?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb, Rgba};
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let img = ImageBuffer::from_pixel(width, height, Rgba([255u8, 0, 0, 128]));
+        let mut buf = Vec::new();
+        DynamicImage::ImageRgba8(img)
+            .write_to(&mut buf, ImageOutputFormat::Png)
+            .unwrap();
+        buf
+    }
+
+    #[test]
+    fn scale_down_large_images() {
+        let processed = process_uploaded_image(&png(3840, 960)).unwrap();
+        assert_eq!(ImageFormat::Png, processed.format);
+        assert_eq!((1920, 480), (processed.width, processed.height));
+        let thumbnail = image::load_from_memory(&processed.thumbnail).unwrap();
+        assert_eq!((320, 80), thumbnail.dimensions());
+    }
+
+    // A landscape photo with the left half red and the right half
+    // blue that must be rotated by 90 degrees clockwise for display
+    fn jpeg_with_orientation(orientation: u8) -> Vec<u8> {
+        let img = ImageBuffer::from_fn(64, 32, |x, _| {
+            if x < 32 {
+                Rgb([255u8, 0, 0])
+            } else {
+                Rgb([0, 0, 255])
+            }
+        });
+        let mut jpeg = Vec::new();
+        DynamicImage::ImageRgb8(img)
+            .write_to(&mut jpeg, ImageOutputFormat::Jpeg(JPEG_QUALITY))
+            .unwrap();
+        // TIFF header (big endian) with a single IFD entry
+        // for the orientation tag (0x0112) of type SHORT
+        let mut app1 = b"Exif\0\0".to_vec();
+        app1.extend_from_slice(&[b'M', b'M', 0, 42, 0, 0, 0, 8]);
+        app1.extend_from_slice(&[0, 1]);
+        app1.extend_from_slice(&[0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, orientation, 0, 0]);
+        app1.extend_from_slice(&[0, 0, 0, 0]);
+        let len = (app1.len() + 2) as u16;
+        // Insert the APP1 segment directly after the SOI marker
+        let mut data = jpeg[..2].to_vec();
+        data.extend_from_slice(&[0xff, 0xe1]);
+        data.extend_from_slice(&len.to_be_bytes());
+        data.extend_from_slice(&app1);
+        data.extend_from_slice(&jpeg[2..]);
+        data
+    }
+
+    #[test]
+    fn apply_exif_orientation() {
+        let data = jpeg_with_orientation(6);
+        assert_eq!(6, exif_orientation(&data));
+        let processed = process_uploaded_image(&data).unwrap();
+        assert_eq!(ImageFormat::Jpeg, processed.format);
+        assert_eq!((32, 64), (processed.width, processed.height));
+        let img = image::load_from_memory(&processed.data).unwrap().to_rgb();
+        // The left half is on top after rotating clockwise
+        let top = img.get_pixel(16, 8);
+        let bottom = img.get_pixel(16, 56);
+        assert!(top[0] > 200 && top[2] < 50, "{:?}", top);
+        assert!(bottom[0] < 50 && bottom[2] > 200, "{:?}", bottom);
+        let thumbnail = image::load_from_memory(&processed.thumbnail).unwrap();
+        assert!(thumbnail.height() > thumbnail.width());
+        // The orientation is not kept after re-encoding
+        assert_eq!(1, exif_orientation(&processed.data));
+    }
+
+    #[test]
+    fn keep_size_of_small_images() {
+        let processed = process_uploaded_image(&png(100, 50)).unwrap();
+        assert_eq!((100, 50), (processed.width, processed.height));
+    }
+
+    #[test]
+    fn reject_unsupported_formats() {
+        assert!(matches!(
+            process_uploaded_image(b"<svg></svg>"),
+            Err(Error::Parameter(ParameterError::ImageFormat))
+        ));
+    }
+}
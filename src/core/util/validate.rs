@@ -28,6 +28,15 @@ fn license(s: &str) -> Result<(), ParameterError> {
     }
 }
 
+/// Licenses of uploaded images and whether they require an attribution.
+pub fn image_license(s: &str) -> Result<bool, ParameterError> {
+    match s {
+        "CC0-1.0" => Ok(false),
+        "CC-BY-4.0" | "CC-BY-SA-4.0" => Ok(true),
+        _ => Err(ParameterError::License),
+    }
+}
+
 pub fn bbox(bbox: &MapBbox) -> Result<(), ParameterError> {
     if !bbox.is_valid() || bbox.is_empty() {
         return Err(ParameterError::Bbox);
@@ -7,7 +7,10 @@ use diesel::{
     prelude::{Connection as DieselConnection, *},
     result::{DatabaseErrorKind, Error as DieselError},
 };
-use std::result;
+use std::{
+    collections::{BTreeMap, HashMap},
+    result,
+};
 
 type Result<T> = result::Result<T, RepoError>;
 
@@ -560,7 +563,7 @@ impl PlaceRepo for SqliteConnection {
         } = activity_log;
         let changed_at = activity.at.into_inner();
         let changed_by = if let Some(ref email) = activity.by {
-            Some(resolve_user_created_by_email(self, email.as_ref())?)
+            Some(resolve_user_created_by_email(self, email)?)
         } else {
             None
         };
@@ -935,6 +938,52 @@ impl PlaceRepo for SqliteConnection {
         let row = query.first::<models::JoinedPlaceRevision>(self)?;
         Ok(load_place(self, row)?)
     }
+
+    fn load_place_revisions_created_by(
+        &self,
+        user_email: &str,
+    ) -> Result<Vec<(Place, ReviewStatus)>> {
+        use schema::place::dsl;
+        use schema::place_revision::dsl as rev_dsl;
+
+        let created_by = resolve_user_created_by_email(self, user_email)?;
+        let rows = schema::place_revision::table
+            .inner_join(schema::place::table)
+            .select((
+                rev_dsl::rowid,
+                rev_dsl::rev,
+                rev_dsl::created_at,
+                rev_dsl::created_by,
+                rev_dsl::current_status,
+                rev_dsl::title,
+                rev_dsl::description,
+                rev_dsl::lat,
+                rev_dsl::lon,
+                rev_dsl::street,
+                rev_dsl::zip,
+                rev_dsl::city,
+                rev_dsl::country,
+                rev_dsl::state,
+                rev_dsl::contact_name,
+                rev_dsl::email,
+                rev_dsl::phone,
+                rev_dsl::homepage,
+                rev_dsl::opening_hours,
+                rev_dsl::founded_on,
+                rev_dsl::image_url,
+                rev_dsl::image_link_url,
+                dsl::id,
+                dsl::license,
+            ))
+            .filter(rev_dsl::created_by.eq(created_by))
+            .order_by(rev_dsl::created_at)
+            .load::<models::JoinedPlaceRevision>(self)?;
+        let mut results = Vec::with_capacity(rows.len());
+        for row in rows {
+            results.push(load_place(self, row)?);
+        }
+        Ok(results)
+    }
 }
 
 fn into_new_event_with_tags(
@@ -1026,6 +1075,50 @@ fn into_new_event_with_tags(
     ))
 }
 
+fn resolve_comment_rowid(conn: &SqliteConnection, id: &str) -> Result<i64> {
+    use schema::place_rating_comment::dsl;
+    Ok(schema::place_rating_comment::table
+        .select(dsl::rowid)
+        .filter(dsl::id.eq(id))
+        .first::<i64>(conn)
+        .map_err(|e| {
+            log::warn!("Failed to resolve place rating comment id '{}': {}", id, e);
+            e
+        })?)
+}
+
+// Replaces the rowids of parent comments by their ids
+fn load_comments_with_reply_to(
+    conn: &SqliteConnection,
+    rows: Vec<models::PlaceRatingComment>,
+) -> Result<Vec<Comment>> {
+    use schema::place_rating_comment::dsl;
+    let reply_to_rowids: Vec<_> = rows.iter().filter_map(|row| row.reply_to).collect();
+    let reply_to_ids: HashMap<i64, String> = if reply_to_rowids.is_empty() {
+        Default::default()
+    } else {
+        schema::place_rating_comment::table
+            .select((dsl::rowid, dsl::id))
+            .filter(dsl::rowid.eq_any(&reply_to_rowids))
+            .load::<(i64, String)>(conn)?
+            .into_iter()
+            .collect()
+    };
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let reply_to = row
+                .reply_to
+                .and_then(|rowid| reply_to_ids.get(&rowid))
+                .map(|id| Id::from(id.as_str()));
+            Comment {
+                reply_to,
+                ..row.into()
+            }
+        })
+        .collect())
+}
+
 fn resolve_event_id(conn: &SqliteConnection, uid: &str) -> Result<i64> {
     use schema::events::dsl;
     Ok(dsl::events
@@ -1321,6 +1414,11 @@ impl EventGateway for SqliteConnection {
             debug_assert_eq!(id, *ids.first().unwrap());
         }
         diesel::delete(et_dsl::event_tags.filter(et_dsl::event_id.eq(id))).execute(self)?;
+        diesel::delete(
+            schema::organization_event_clearance::table
+                .filter(schema::organization_event_clearance::event_rowid.eq(id)),
+        )
+        .execute(self)?;
         diesel::delete(e_dsl::events.filter(e_dsl::id.eq(id))).execute(self)?;
         Ok(true)
     }
@@ -1358,9 +1456,13 @@ fn resolve_user_created_by_email(conn: &SqliteConnection, email: &str) -> Result
 
 impl UserGateway for SqliteConnection {
     fn create_user(&self, u: &User) -> Result<()> {
+        use schema::users::dsl;
         let new_user = models::NewUser::from(u);
         diesel::insert_into(schema::users::table)
-            .values(&new_user)
+            .values((
+                &new_user,
+                dsl::created_at.eq(Some(TimestampMs::now().into_inner())),
+            ))
             .execute(self)?;
         Ok(())
     }
@@ -1374,12 +1476,193 @@ impl UserGateway for SqliteConnection {
         Ok(())
     }
 
+    fn update_user_email(&self, email: &str, new_email: &str) -> Result<()> {
+        use schema::users::dsl;
+        let count = diesel::update(dsl::users.filter(dsl::email.eq(email)))
+            .set(dsl::email.eq(new_email))
+            .execute(self)?;
+        if count == 0 {
+            return Err(RepoError::NotFound);
+        }
+        debug_assert_eq!(1, count);
+        Ok(())
+    }
+
     fn delete_user_by_email(&self, email: &str) -> Result<()> {
         use schema::users::dsl;
         diesel::delete(dsl::users.filter(dsl::email.eq(email))).execute(self)?;
         Ok(())
     }
 
+    fn anonymize_user_by_email(
+        &self,
+        email: &str,
+        pseudonym: &str,
+        anonymized_at: TimestampMs,
+    ) -> Result<AnonymizedUser> {
+        use num_traits::ToPrimitive;
+        let user_id = resolve_user_created_by_email(self, email)?;
+
+        // Count all authored activities that will refer to the pseudonym
+        let place_revisions = {
+            use schema::place_revision::dsl;
+            dsl::place_revision
+                .filter(dsl::created_by.eq(user_id))
+                .count()
+                .get_result::<i64>(self)?
+        };
+        let place_reviews = {
+            use schema::place_revision_review::dsl;
+            dsl::place_revision_review
+                .filter(dsl::created_by.eq(user_id))
+                .count()
+                .get_result::<i64>(self)?
+        };
+        let place_ratings = {
+            use schema::place_rating::dsl;
+            dsl::place_rating
+                .filter(dsl::created_by.eq(user_id).or(dsl::archived_by.eq(user_id)))
+                .count()
+                .get_result::<i64>(self)?
+        };
+        let place_rating_comments = {
+            use schema::place_rating_comment::dsl;
+            dsl::place_rating_comment
+                .filter(dsl::created_by.eq(user_id).or(dsl::archived_by.eq(user_id)))
+                .count()
+                .get_result::<i64>(self)?
+        };
+        let events = {
+            use schema::events::dsl;
+            dsl::events
+                .filter(dsl::created_by.eq(user_id))
+                .count()
+                .get_result::<i64>(self)?
+        };
+
+        // Delete all personal data that is not referenced by activities
+        {
+            use schema::bbox_subscriptions::dsl;
+            diesel::delete(dsl::bbox_subscriptions.filter(dsl::user_id.eq(user_id)))
+                .execute(self)?;
+        }
+        {
+            use schema::saved_search::dsl;
+            diesel::delete(dsl::saved_search.filter(dsl::user_id.eq(user_id))).execute(self)?;
+        }
+        {
+            use schema::user_tokens::dsl;
+            diesel::delete(dsl::user_tokens.filter(dsl::user_id.eq(user_id))).execute(self)?;
+        }
+        {
+            use schema::user_regions::dsl;
+            diesel::delete(dsl::user_regions.filter(dsl::user_id.eq(user_id))).execute(self)?;
+        }
+        {
+            use schema::place_owner::dsl;
+            diesel::delete(dsl::place_owner.filter(dsl::user_id.eq(user_id))).execute(self)?;
+        }
+        {
+            use schema::audit_log::dsl;
+            diesel::update(
+                dsl::audit_log
+                    .filter(dsl::entity_kind.eq(AuditEntityKind::User.to_string()))
+                    .filter(dsl::entity_id.eq(email)),
+            )
+            .set(dsl::entity_id.eq(pseudonym))
+            .execute(self)?;
+        }
+
+        // The row is kept to preserve the references. An empty
+        // password hash never matches, i.e. login is impossible.
+        {
+            use schema::users::dsl;
+            let count = diesel::update(dsl::users.filter(dsl::id.eq(user_id)))
+                .set((
+                    dsl::email.eq(pseudonym),
+                    dsl::email_confirmed.eq(false),
+                    dsl::password.eq(""),
+                    dsl::role.eq(Role::Guest.to_i16().unwrap_or_default()),
+                ))
+                .execute(self)?;
+            debug_assert_eq!(1, count);
+        }
+
+        let anonymized_user = AnonymizedUser {
+            pseudonym: pseudonym.to_owned(),
+            anonymized_at,
+            place_revisions: place_revisions as u64,
+            place_reviews: place_reviews as u64,
+            place_ratings: place_ratings as u64,
+            place_rating_comments: place_rating_comments as u64,
+            events: events as u64,
+        };
+        diesel::insert_into(schema::anonymized_users::table)
+            .values(models::NewAnonymizedUser::from(&anonymized_user))
+            .execute(self)?;
+        Ok(anonymized_user)
+    }
+
+    fn all_anonymized_users(&self) -> Result<Vec<AnonymizedUser>> {
+        use schema::anonymized_users::dsl;
+        Ok(dsl::anonymized_users
+            .order_by(dsl::anonymized_at.desc())
+            .load::<models::AnonymizedUser>(self)?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    fn replace_user_regions_by_email(&self, email: &str, regions: &[MapBbox]) -> Result<()> {
+        use schema::user_regions::dsl;
+        let user_id = resolve_user_created_by_email(self, email)?;
+        diesel::delete(dsl::user_regions.filter(dsl::user_id.eq(user_id))).execute(self)?;
+        let insertables: Vec<_> = regions
+            .iter()
+            .map(|bbox| {
+                let (south_west_lat, south_west_lng) = bbox.southwest().to_lat_lng_deg();
+                let (north_east_lat, north_east_lng) = bbox.northeast().to_lat_lng_deg();
+                models::NewUserRegion {
+                    user_id,
+                    south_west_lat,
+                    south_west_lng,
+                    north_east_lat,
+                    north_east_lng,
+                }
+            })
+            .collect();
+        diesel::insert_into(schema::user_regions::table)
+            .values(&insertables)
+            .execute(self)?;
+        Ok(())
+    }
+
+    fn user_regions_by_email(&self, email: &str) -> Result<Vec<MapBbox>> {
+        use schema::user_regions::dsl as r_dsl;
+        use schema::users::dsl as u_dsl;
+        Ok(r_dsl::user_regions
+            .inner_join(u_dsl::users)
+            .filter(u_dsl::email.eq(email))
+            .select((
+                r_dsl::south_west_lat,
+                r_dsl::south_west_lng,
+                r_dsl::north_east_lat,
+                r_dsl::north_east_lng,
+            ))
+            .order_by(r_dsl::rowid)
+            .load::<(f64, f64, f64, f64)>(self)?
+            .into_iter()
+            .map(
+                |(south_west_lat, south_west_lng, north_east_lat, north_east_lng)| {
+                    MapBbox::new(
+                        MapPoint::from_lat_lng_deg(south_west_lat, south_west_lng),
+                        MapPoint::from_lat_lng_deg(north_east_lat, north_east_lng),
+                    )
+                },
+            )
+            .collect())
+    }
+
     fn get_user_by_email(&self, email: &str) -> Result<User> {
         use schema::users::dsl;
         Ok(dsl::users
@@ -1425,19 +1708,28 @@ impl RatingRepository for SqliteConnection {
             value,
             context,
             source,
+            author,
         } = rating;
         let parent_rowid = resolve_place_rowid(self, &place_id)?;
+        let (created_by, author_session) = match author {
+            Some(RatingAuthor::User(email)) => {
+                (Some(resolve_user_created_by_email(self, &email)?), None)
+            }
+            Some(RatingAuthor::AnonymousSession(session)) => (None, Some(session)),
+            None => (None, None),
+        };
         let new_place_rating = models::NewPlaceRating {
             id: id.into(),
             parent_rowid,
             created_at: created_at.into_inner(),
-            created_by: None,
+            created_by,
             archived_at: archived_at.map(Timestamp::into_inner),
             archived_by: None,
             title,
             value: i8::from(value).into(),
             context: util::rating_context_to_string(context),
             source,
+            author_session,
         };
         let _count = diesel::insert_into(schema::place_rating::table)
             .values(&new_place_rating)
@@ -1449,8 +1741,10 @@ impl RatingRepository for SqliteConnection {
     fn load_ratings(&self, ids: &[&str]) -> Result<Vec<Rating>> {
         use schema::place::dsl;
         use schema::place_rating::dsl as rating_dsl;
+        use schema::users::dsl as u_dsl;
         Ok(schema::place_rating::table
             .inner_join(schema::place::table)
+            .left_outer_join(u_dsl::users)
             .select((
                 rating_dsl::rowid,
                 rating_dsl::created_at,
@@ -1462,7 +1756,9 @@ impl RatingRepository for SqliteConnection {
                 rating_dsl::value,
                 rating_dsl::context,
                 rating_dsl::source,
+                rating_dsl::author_session,
                 dsl::id,
+                u_dsl::email.nullable(),
             ))
             .filter(rating_dsl::id.eq_any(ids))
             .filter(rating_dsl::archived_at.is_null())
@@ -1481,8 +1777,10 @@ impl RatingRepository for SqliteConnection {
     fn load_ratings_of_place(&self, place_id: &str) -> Result<Vec<Rating>> {
         use schema::place::dsl;
         use schema::place_rating::dsl as rating_dsl;
+        use schema::users::dsl as u_dsl;
         Ok(schema::place_rating::table
             .inner_join(schema::place::table)
+            .left_outer_join(u_dsl::users)
             .select((
                 rating_dsl::rowid,
                 rating_dsl::created_at,
@@ -1494,7 +1792,9 @@ impl RatingRepository for SqliteConnection {
                 rating_dsl::value,
                 rating_dsl::context,
                 rating_dsl::source,
+                rating_dsl::author_session,
                 dsl::id,
+                u_dsl::email.nullable(),
             ))
             .filter(dsl::id.eq(place_id))
             .filter(rating_dsl::archived_at.is_null())
@@ -1504,6 +1804,37 @@ impl RatingRepository for SqliteConnection {
             .collect())
     }
 
+    fn load_ratings_created_by(&self, user_email: &str) -> Result<Vec<Rating>> {
+        use schema::place::dsl;
+        use schema::place_rating::dsl as rating_dsl;
+        use schema::users::dsl as u_dsl;
+        let created_by = resolve_user_created_by_email(self, user_email)?;
+        Ok(schema::place_rating::table
+            .inner_join(schema::place::table)
+            .left_outer_join(u_dsl::users)
+            .select((
+                rating_dsl::rowid,
+                rating_dsl::created_at,
+                rating_dsl::created_by,
+                rating_dsl::archived_at,
+                rating_dsl::archived_by,
+                rating_dsl::id,
+                rating_dsl::title,
+                rating_dsl::value,
+                rating_dsl::context,
+                rating_dsl::source,
+                rating_dsl::author_session,
+                dsl::id,
+                u_dsl::email.nullable(),
+            ))
+            .filter(rating_dsl::created_by.eq(created_by))
+            .order_by(rating_dsl::created_at)
+            .load::<models::PlaceRating>(self)?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
     fn load_place_ids_of_ratings(&self, ids: &[&str]) -> Result<Vec<String>> {
         use schema::place::dsl;
         use schema::place_rating::dsl as rating_dsl;
@@ -1518,7 +1849,7 @@ impl RatingRepository for SqliteConnection {
         use schema::place_rating::dsl;
         let archived_at = Some(activity.at.into_inner());
         let archived_by = if let Some(ref email) = activity.by {
-            Some(resolve_user_created_by_email(self, email.as_ref())?)
+            Some(resolve_user_created_by_email(self, email)?)
         } else {
             None
         };
@@ -1541,7 +1872,7 @@ impl RatingRepository for SqliteConnection {
         use schema::place_rating::dsl as rating_dsl;
         let archived_at = Some(activity.at.into_inner());
         let archived_by = if let Some(ref email) = activity.by {
-            Some(resolve_user_created_by_email(self, email.as_ref())?)
+            Some(resolve_user_created_by_email(self, email)?)
         } else {
             None
         };
@@ -1562,6 +1893,84 @@ impl RatingRepository for SqliteConnection {
         ))
         .execute(self)?)
     }
+
+    fn flag_rating(&self, id: &str, activity: &Activity, comment: Option<&str>) -> Result<()> {
+        let parent_rowid = resolve_rating_rowid(self, id)?;
+        let created_by = if let Some(ref email) = activity.by {
+            Some(resolve_user_created_by_email(self, email)?)
+        } else {
+            None
+        };
+        let new_flag = models::NewPlaceRatingFlag {
+            parent_rowid,
+            created_at: activity.at.into_inner(),
+            created_by,
+            comment,
+        };
+        diesel::insert_into(schema::place_rating_flag::table)
+            .values(&new_flag)
+            .execute(self)?;
+        Ok(())
+    }
+
+    fn load_flagged_ratings(&self, pagination: &Pagination) -> Result<Vec<FlaggedRating>> {
+        use schema::place_rating::dsl as rating_dsl;
+        use schema::place_rating_flag::dsl;
+        use schema::users::dsl as u_dsl;
+        let rows = schema::place_rating_flag::table
+            .inner_join(schema::place_rating::table)
+            .left_outer_join(u_dsl::users)
+            .select((
+                rating_dsl::id,
+                dsl::created_at,
+                u_dsl::email.nullable(),
+                dsl::comment,
+            ))
+            .filter(rating_dsl::archived_at.is_null())
+            .order_by(dsl::created_at.desc())
+            .then_order_by(dsl::rowid.desc()) // disambiguation of equal time stamps
+            .load::<(String, i64, Option<String>, Option<String>)>(self)?;
+
+        // Group the flags by rating, most recently flagged ratings first
+        let mut flags_of_ratings: Vec<(String, Vec<ActivityLog>)> = vec![];
+        for (rating_id, created_at, created_by, comment) in rows {
+            let flag = ActivityLog {
+                activity: Activity {
+                    at: TimestampMs::from_inner(created_at),
+                    by: created_by.map(Into::into),
+                },
+                context: None,
+                comment,
+            };
+            if let Some((_, flags)) = flags_of_ratings.iter_mut().find(|(id, _)| *id == rating_id) {
+                flags.push(flag);
+            } else {
+                flags_of_ratings.push((rating_id, vec![flag]));
+            }
+        }
+
+        // Pagination
+        let offset = pagination.offset.unwrap_or(0) as usize;
+        let limit = pagination.limit.map(|limit| limit as usize);
+        let flags_of_ratings: Vec<_> = flags_of_ratings
+            .into_iter()
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+
+        let ids: Vec<_> = flags_of_ratings.iter().map(|(id, _)| id.as_str()).collect();
+        let mut ratings = self.load_ratings(&ids)?;
+        Ok(flags_of_ratings
+            .into_iter()
+            .filter_map(|(id, flags)| {
+                let index = ratings.iter().position(|r| r.id.as_str() == id)?;
+                Some(FlaggedRating {
+                    rating: ratings.swap_remove(index),
+                    flags,
+                })
+            })
+            .collect())
+    }
 }
 
 impl CommentRepository for SqliteConnection {
@@ -1572,17 +1981,36 @@ impl CommentRepository for SqliteConnection {
             created_at,
             archived_at,
             text,
-            ..
+            author,
+            reply_to,
+            is_response,
         } = comment;
         let parent_rowid = resolve_rating_rowid(self, rating_id.as_ref())?;
+        let (created_by, created_by_org) = match author {
+            Some(CommentAuthor::User(ref email)) => {
+                (Some(resolve_user_created_by_email(self, email)?), None)
+            }
+            Some(CommentAuthor::Organization(ref org_id)) => {
+                (None, Some(resolve_organization_rowid(self, org_id)?))
+            }
+            None => (None, None),
+        };
+        let reply_to = if let Some(ref reply_to) = reply_to {
+            Some(resolve_comment_rowid(self, reply_to.as_ref())?)
+        } else {
+            None
+        };
         let new_place_rating_comment = models::NewPlaceRatingComment {
             id: id.into(),
             parent_rowid,
             created_at: created_at.into_inner(),
-            created_by: None,
+            created_by,
             archived_at: archived_at.map(Timestamp::into_inner),
             archived_by: None,
             text,
+            reply_to,
+            created_by_org,
+            response: is_response,
         };
         let _count = diesel::insert_into(schema::place_rating_comment::table)
             .values(&new_place_rating_comment)
@@ -1592,12 +2020,16 @@ impl CommentRepository for SqliteConnection {
     }
 
     fn load_comments(&self, ids: &[&str]) -> Result<Vec<Comment>> {
+        use schema::organization::dsl as org_dsl;
         use schema::place_rating::dsl as rating_dsl;
         use schema::place_rating_comment::dsl as comment_dsl;
+        use schema::users::dsl as u_dsl;
         // TODO: Split loading into chunks of fixed size
         info!("Loading multiple ({}) comments at once", ids.len());
-        Ok(schema::place_rating_comment::table
+        let rows = schema::place_rating_comment::table
             .inner_join(schema::place_rating::table)
+            .left_outer_join(u_dsl::users)
+            .left_outer_join(org_dsl::organization)
             .select((
                 comment_dsl::rowid,
                 comment_dsl::created_at,
@@ -1606,14 +2038,16 @@ impl CommentRepository for SqliteConnection {
                 comment_dsl::archived_by,
                 comment_dsl::id,
                 comment_dsl::text,
+                comment_dsl::reply_to,
+                comment_dsl::response,
                 rating_dsl::id,
+                u_dsl::email.nullable(),
+                org_dsl::id.nullable(),
             ))
             .filter(comment_dsl::id.eq_any(ids))
             .filter(comment_dsl::archived_at.is_null())
-            .load::<models::PlaceRatingComment>(self)?
-            .into_iter()
-            .map(Into::into)
-            .collect())
+            .load::<models::PlaceRatingComment>(self)?;
+        load_comments_with_reply_to(self, rows)
     }
 
     fn load_comment(&self, id: &str) -> Result<Comment> {
@@ -1623,10 +2057,14 @@ impl CommentRepository for SqliteConnection {
     }
 
     fn load_comments_of_rating(&self, rating_id: &str) -> Result<Vec<Comment>> {
+        use schema::organization::dsl as org_dsl;
         use schema::place_rating::dsl as rating_dsl;
         use schema::place_rating_comment::dsl as comment_dsl;
-        Ok(schema::place_rating_comment::table
+        use schema::users::dsl as u_dsl;
+        let rows = schema::place_rating_comment::table
             .inner_join(schema::place_rating::table)
+            .left_outer_join(u_dsl::users)
+            .left_outer_join(org_dsl::organization)
             .select((
                 comment_dsl::rowid,
                 comment_dsl::created_at,
@@ -1635,21 +2073,23 @@ impl CommentRepository for SqliteConnection {
                 comment_dsl::archived_by,
                 comment_dsl::id,
                 comment_dsl::text,
+                comment_dsl::reply_to,
+                comment_dsl::response,
                 rating_dsl::id,
+                u_dsl::email.nullable(),
+                org_dsl::id.nullable(),
             ))
             .filter(rating_dsl::id.eq(rating_id))
             .filter(comment_dsl::archived_at.is_null())
-            .load::<models::PlaceRatingComment>(self)?
-            .into_iter()
-            .map(Into::into)
-            .collect())
+            .load::<models::PlaceRatingComment>(self)?;
+        load_comments_with_reply_to(self, rows)
     }
 
     fn archive_comments(&self, ids: &[&str], activity: &Activity) -> Result<usize> {
         use schema::place_rating_comment::dsl;
         let archived_at = Some(activity.at.into_inner());
         let archived_by = if let Some(ref email) = activity.by {
-            Some(resolve_user_created_by_email(self, email.as_ref())?)
+            Some(resolve_user_created_by_email(self, email)?)
         } else {
             None
         };
@@ -1676,7 +2116,7 @@ impl CommentRepository for SqliteConnection {
         use schema::place_rating_comment::dsl as comment_dsl;
         let archived_at = Some(activity.at.into_inner());
         let archived_by = if let Some(ref email) = activity.by {
-            Some(resolve_user_created_by_email(self, email.as_ref())?)
+            Some(resolve_user_created_by_email(self, email)?)
         } else {
             None
         };
@@ -1704,7 +2144,7 @@ impl CommentRepository for SqliteConnection {
         use schema::place_rating_comment::dsl as comment_dsl;
         let archived_at = Some(activity.at.into_inner());
         let archived_by = if let Some(ref email) = activity.by {
-            Some(resolve_user_created_by_email(self, email.as_ref())?)
+            Some(resolve_user_created_by_email(self, email)?)
         } else {
             None
         };
@@ -1843,6 +2283,28 @@ impl Db for SqliteConnection {
     }
 }
 
+fn load_org(conn: &SqliteConnection, org: models::Organization) -> Result<Organization> {
+    use schema::organization_tag::dsl as org_tag_dsl;
+    let models::Organization {
+        rowid,
+        id,
+        name,
+        api_token,
+    } = org;
+    let moderated_tags = org_tag_dsl::organization_tag
+        .filter(org_tag_dsl::org_rowid.eq(rowid))
+        .load::<models::OrganizationTag>(conn)?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    Ok(Organization {
+        id: id.into(),
+        name,
+        api_token,
+        moderated_tags,
+    })
+}
+
 impl OrganizationRepo for SqliteConnection {
     fn create_org(&mut self, mut o: Organization) -> Result<()> {
         let org_id = o.id.clone();
@@ -1883,30 +2345,21 @@ impl OrganizationRepo for SqliteConnection {
     }
 
     fn get_org_by_api_token(&self, token: &str) -> Result<Organization> {
-        use schema::{organization::dsl as org_dsl, organization_tag::dsl as org_tag_dsl};
-
-        let models::Organization {
-            rowid,
-            id,
-            name,
-            api_token,
-        } = org_dsl::organization
+        use schema::organization::dsl as org_dsl;
+        let org = org_dsl::organization
             .filter(org_dsl::api_token.eq(token))
             .first(self)?;
+        load_org(self, org)
+    }
 
-        let moderated_tags = org_tag_dsl::organization_tag
-            .filter(org_tag_dsl::org_rowid.eq(rowid))
-            .load::<models::OrganizationTag>(self)?
+    fn all_orgs(&self) -> Result<Vec<Organization>> {
+        use schema::organization::dsl as org_dsl;
+        org_dsl::organization
+            .order_by(org_dsl::rowid)
+            .load::<models::Organization>(self)?
             .into_iter()
-            .map(Into::into)
-            .collect();
-
-        Ok(Organization {
-            id: id.into(),
-            name,
-            api_token,
-            moderated_tags,
-        })
+            .map(|org| load_org(self, org))
+            .collect()
     }
 
     fn map_tag_to_clearance_org_id(&self, tag: &str) -> Result<Option<Id>> {
@@ -1971,11 +2424,30 @@ impl PlaceClearanceRepo for SqliteConnection {
                 place_rowid,
                 created_at,
                 last_cleared_revision,
+                reverted_at: None,
             };
-            insert_count +=
+            let rows_inserted =
                 diesel::insert_or_ignore_into(schema::organization_place_clearance::table)
                     .values(&insertable)
                     .execute(self)?;
+            if rows_inserted == 0 {
+                // The place has been modified again after the organization
+                // decided to keep the last cleared revision. The clearance
+                // is reopened as if it had been created just now.
+                use schema::organization_place_clearance::dsl;
+                diesel::update(
+                    schema::organization_place_clearance::table
+                        .filter(dsl::org_rowid.eq(org_rowid))
+                        .filter(dsl::place_rowid.eq(place_rowid))
+                        .filter(dsl::reverted_at.is_not_null()),
+                )
+                .set((
+                    dsl::created_at.eq(created_at),
+                    dsl::reverted_at.eq(None::<i64>),
+                ))
+                .execute(self)?;
+            }
+            insert_count += rows_inserted;
         }
         Ok(insert_count)
     }
@@ -1991,6 +2463,7 @@ impl PlaceClearanceRepo for SqliteConnection {
                         .filter(org_dsl::id.eq(org_id.as_str())),
                 ),
             )
+            .filter(dsl::reverted_at.is_null())
             .count()
             .get_result::<i64>(self)? as u64)
     }
@@ -2013,6 +2486,7 @@ impl PlaceClearanceRepo for SqliteConnection {
                         .filter(org_dsl::id.eq(org_id.as_str())),
                 ),
             )
+            .filter(dsl::reverted_at.is_null())
             .order_by(dsl::created_at)
             .into_boxed();
 
@@ -2087,6 +2561,7 @@ impl PlaceClearanceRepo for SqliteConnection {
                 place_rowid,
                 created_at,
                 last_cleared_revision,
+                reverted_at: None,
             };
             let rows_affected = diesel::update(schema::organization_place_clearance::table)
                 .set(&updatable)
@@ -2131,27 +2606,227 @@ impl PlaceClearanceRepo for SqliteConnection {
         .execute(self)?;
         Ok(delete_count as u64)
     }
-}
 
-impl UserTokenRepo for SqliteConnection {
-    fn replace_user_token(&self, token: UserToken) -> Result<EmailNonce> {
-        use schema::user_tokens::dsl;
-        let user_id = resolve_user_created_by_email(self, &token.email_nonce.email)?;
-        let model = models::NewUserToken {
-            user_id,
-            nonce: token.email_nonce.nonce.to_string(),
-            expires_at: token.expires_at.into_inner(),
-        };
-        // Insert...
-        if diesel::insert_into(schema::user_tokens::table)
-            .values(&model)
+    fn revert_pending_clearances_for_places(
+        &self,
+        org_id: &Id,
+        place_ids: &[&str],
+    ) -> Result<usize> {
+        let org_rowid = resolve_organization_rowid(self, org_id)?;
+        use schema::organization_place_clearance::dsl;
+        use schema::place::dsl as place_dsl;
+        let reverted_at = TimestampMs::now().into_inner();
+        Ok(diesel::update(
+            schema::organization_place_clearance::table
+                .filter(dsl::org_rowid.eq(org_rowid))
+                .filter(
+                    dsl::place_rowid.eq_any(
+                        schema::place::table
+                            .select(place_dsl::rowid)
+                            .filter(place_dsl::id.eq_any(place_ids)),
+                    ),
+                )
+                .filter(dsl::reverted_at.is_null()),
+        )
+        .set(dsl::reverted_at.eq(Some(reverted_at)))
+        .execute(self)?)
+    }
+}
+
+fn into_event_snapshot(conn: &SqliteConnection, event: Event) -> Result<String> {
+    let created_by_email = event.created_by.clone();
+    let (new_event, tags) = into_new_event_with_tags(conn, event)?;
+    let snapshot = util::event_snapshot_from_new_event(new_event, created_by_email, tags);
+    Ok(serde_json::to_string(&snapshot).map_err(anyhow::Error::from)?)
+}
+
+fn load_pending_clearance_for_event(
+    row: models::PendingClearanceForEvent,
+) -> Result<PendingClearanceForEvent> {
+    let models::PendingClearanceForEvent {
+        event_id,
+        created_at,
+        last_cleared_state,
+    } = row;
+    let last_cleared_event = if let Some(state) = last_cleared_state {
+        let snapshot = serde_json::from_str(&state).map_err(anyhow::Error::from)?;
+        Some(util::event_from_snapshot(event_id.clone(), snapshot))
+    } else {
+        None
+    };
+    Ok(PendingClearanceForEvent {
+        event_id: event_id.into(),
+        created_at: TimestampMs::from_inner(created_at),
+        last_cleared_event,
+    })
+}
+
+impl EventClearanceRepo for SqliteConnection {
+    fn add_pending_clearance_for_events(
+        &self,
+        org_ids: &[Id],
+        pending_clearance: &PendingClearanceForEvent,
+    ) -> Result<usize> {
+        let PendingClearanceForEvent {
+            event_id,
+            created_at,
+            last_cleared_event,
+        } = pending_clearance;
+        let event_rowid = resolve_event_id(self, event_id.as_str())?;
+        let created_at = created_at.into_inner();
+        let last_cleared_state = if let Some(event) = last_cleared_event {
+            Some(into_event_snapshot(self, event.clone())?)
+        } else {
+            None
+        };
+        let mut insert_count = 0;
+        for org_id in org_ids {
+            let org_rowid = resolve_organization_rowid(self, org_id)?;
+            let insertable = models::NewPendingClearanceForEvent {
+                org_rowid,
+                event_rowid,
+                created_at,
+                last_cleared_state: last_cleared_state.clone(),
+            };
+            insert_count +=
+                diesel::insert_or_ignore_into(schema::organization_event_clearance::table)
+                    .values(&insertable)
+                    .execute(self)?;
+        }
+        Ok(insert_count)
+    }
+
+    fn count_pending_clearances_for_events(&self, org_id: &Id) -> Result<u64> {
+        use schema::events::dsl as e_dsl;
+        use schema::organization::dsl as org_dsl;
+        use schema::organization_event_clearance::dsl;
+        Ok(schema::organization_event_clearance::table
+            .inner_join(schema::events::table)
+            .filter(
+                dsl::org_rowid.eq_any(
+                    schema::organization::table
+                        .select(org_dsl::rowid)
+                        .filter(org_dsl::id.eq(org_id.as_str())),
+                ),
+            )
+            .filter(e_dsl::archived.is_null())
+            .count()
+            .get_result::<i64>(self)? as u64)
+    }
+
+    fn list_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        pagination: &Pagination,
+    ) -> Result<Vec<PendingClearanceForEvent>> {
+        use schema::events::dsl as e_dsl;
+        use schema::organization::dsl as org_dsl;
+        use schema::organization_event_clearance::dsl;
+        let mut query = schema::organization_event_clearance::table
+            .inner_join(schema::events::table)
+            .select((e_dsl::uid, dsl::created_at, dsl::last_cleared_state))
+            .filter(
+                dsl::org_rowid.eq_any(
+                    schema::organization::table
+                        .select(org_dsl::rowid)
+                        .filter(org_dsl::id.eq(org_id.as_str())),
+                ),
+            )
+            .filter(e_dsl::archived.is_null())
+            .order_by(dsl::created_at)
+            .into_boxed();
+
+        // Pagination
+        let offset = pagination.offset.unwrap_or(0);
+        if offset > 0 {
+            query = query.offset(offset as i64);
+        }
+        if let Some(limit) = pagination.limit {
+            query = query.limit(limit as i64);
+        }
+
+        query
+            .load::<models::PendingClearanceForEvent>(self)?
+            .into_iter()
+            .map(load_pending_clearance_for_event)
+            .collect()
+    }
+
+    fn load_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        event_ids: &[&str],
+    ) -> Result<Vec<PendingClearanceForEvent>> {
+        use schema::events::dsl as e_dsl;
+        use schema::organization::dsl as org_dsl;
+        use schema::organization_event_clearance::dsl;
+        schema::organization_event_clearance::table
+            .inner_join(schema::events::table)
+            .select((e_dsl::uid, dsl::created_at, dsl::last_cleared_state))
+            .filter(
+                dsl::org_rowid.eq_any(
+                    schema::organization::table
+                        .select(org_dsl::rowid)
+                        .filter(org_dsl::id.eq(org_id.as_str())),
+                ),
+            )
+            .filter(e_dsl::uid.eq_any(event_ids))
+            .load::<models::PendingClearanceForEvent>(self)?
+            .into_iter()
+            .map(load_pending_clearance_for_event)
+            .collect()
+    }
+
+    fn update_pending_clearances_for_events(
+        &self,
+        org_id: &Id,
+        clearances: &[ClearanceForEvent],
+    ) -> Result<usize> {
+        use schema::events::dsl as e_dsl;
+        use schema::organization_event_clearance::dsl;
+        let org_rowid = resolve_organization_rowid(self, org_id)?;
+        let event_ids: Vec<_> = clearances
+            .iter()
+            .map(|clearance| clearance.event_id.as_str())
+            .collect();
+        let rows_affected = diesel::delete(
+            schema::organization_event_clearance::table
+                .filter(dsl::org_rowid.eq(org_rowid))
+                .filter(
+                    dsl::event_rowid.eq_any(
+                        schema::events::table
+                            .select(e_dsl::id)
+                            .filter(e_dsl::uid.eq_any(event_ids)),
+                    ),
+                ),
+        )
+        .execute(self)?;
+        debug_assert!(rows_affected <= clearances.len());
+        Ok(rows_affected)
+    }
+}
+
+impl UserTokenRepo for SqliteConnection {
+    fn replace_user_token(&self, token: UserToken) -> Result<EmailNonce> {
+        use schema::user_tokens::dsl;
+        let user_id = resolve_user_created_by_email(self, &token.email_nonce.email)?;
+        let model = models::NewUserToken {
+            user_id,
+            nonce: token.email_nonce.nonce.to_string(),
+            expires_at: token.expires_at.into_inner(),
+            new_email: token.new_email,
+            place_id: token.place_id,
+        };
+        // Update...
+        if diesel::update(schema::user_tokens::table)
+            .filter(dsl::user_id.eq(model.user_id))
+            .set(&model)
             .execute(self)?
             == 0
         {
-            // ...or update
-            let _count = diesel::update(schema::user_tokens::table)
-                .filter(dsl::user_id.eq(model.user_id))
-                .set(&model)
+            // ...or insert, because each user has at most one token
+            let _count = diesel::insert_into(schema::user_tokens::table)
+                .values(&model)
                 .execute(self)?;
             debug_assert_eq!(1, _count);
         }
@@ -2190,9 +2865,1097 @@ impl UserTokenRepo for SqliteConnection {
         use schema::users::dsl as u_dsl;
         Ok(t_dsl::user_tokens
             .inner_join(u_dsl::users)
-            .select((u_dsl::id, t_dsl::nonce, t_dsl::expires_at, u_dsl::email))
+            .select((
+                u_dsl::id,
+                t_dsl::nonce,
+                t_dsl::expires_at,
+                t_dsl::new_email,
+                t_dsl::place_id,
+                u_dsl::email,
+            ))
             .filter(u_dsl::email.eq(email))
             .first::<models::UserTokenEntity>(self)?
             .into())
     }
 }
+
+impl CaptchaRepo for SqliteConnection {
+    fn create_captcha(&self, nonce: &Nonce, captcha: &Captcha) -> Result<()> {
+        let model = models::NewCaptcha::from((nonce, captcha));
+        diesel::insert_into(schema::captcha::table)
+            .values(&model)
+            .execute(self)?;
+        Ok(())
+    }
+
+    fn update_captcha(&self, nonce: &Nonce, captcha: &Captcha) -> Result<()> {
+        use schema::captcha::dsl;
+        let model = models::NewCaptcha::from((nonce, captcha));
+        let count = diesel::update(schema::captcha::table)
+            .filter(dsl::nonce.eq(&model.nonce))
+            .set(&model)
+            .execute(self)?;
+        if count == 0 {
+            return Err(RepoError::NotFound);
+        }
+        debug_assert_eq!(1, count);
+        Ok(())
+    }
+
+    fn load_captcha(&self, nonce: &Nonce) -> Result<Captcha> {
+        use schema::captcha::dsl;
+        Ok(schema::captcha::table
+            .filter(dsl::nonce.eq(nonce.to_string()))
+            .first::<models::Captcha>(self)?
+            .into())
+    }
+
+    fn consume_captcha(&self, nonce: &Nonce) -> Result<Captcha> {
+        use schema::captcha::dsl;
+        let captcha = self.load_captcha(nonce)?;
+        if diesel::delete(schema::captcha::table.filter(dsl::nonce.eq(nonce.to_string())))
+            .execute(self)?
+            == 0
+        {
+            return Err(RepoError::NotFound);
+        }
+        Ok(captcha)
+    }
+
+    fn delete_expired_captchas(&self, created_before: Timestamp) -> Result<usize> {
+        use schema::captcha::dsl;
+        Ok(diesel::delete(
+            schema::captcha::table.filter(dsl::created_at.lt(created_before.into_inner())),
+        )
+        .execute(self)?)
+    }
+}
+
+fn load_place_ownership(from: models::PlaceOwner) -> Result<PlaceOwnership> {
+    let models::PlaceOwner {
+        created_at,
+        locked_fields,
+        place_id,
+        owner_email,
+    } = from;
+    let locked_fields = locked_fields
+        .split(',')
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            field
+                .parse()
+                .map_err(|_| RepoError::Other(anyhow!("Invalid place field: {}", field)))
+        })
+        .collect::<Result<_>>()?;
+    Ok(PlaceOwnership {
+        place_id: place_id.into(),
+        owner: owner_email.into(),
+        created_at: TimestampMs::from_inner(created_at),
+        locked_fields,
+    })
+}
+
+impl PlaceOwnershipRepo for SqliteConnection {
+    fn set_place_owner(&self, place_id: &str, owner_email: &str) -> Result<PlaceOwnership> {
+        use schema::place_owner::dsl;
+        let place_rowid = resolve_place_rowid(self, &Id::from(place_id))?;
+        let user_id = resolve_user_created_by_email(self, owner_email)?;
+        diesel::delete(dsl::place_owner.filter(dsl::place_rowid.eq(place_rowid))).execute(self)?;
+        let created_at = TimestampMs::now();
+        diesel::insert_into(schema::place_owner::table)
+            .values(&models::NewPlaceOwner {
+                place_rowid,
+                user_id,
+                created_at: created_at.into_inner(),
+                locked_fields: String::new(),
+            })
+            .execute(self)?;
+        Ok(PlaceOwnership {
+            place_id: place_id.into(),
+            owner: owner_email.into(),
+            created_at,
+            locked_fields: vec![],
+        })
+    }
+
+    fn get_place_ownership(&self, place_id: &str) -> Result<Option<PlaceOwnership>> {
+        use schema::place::dsl as place_dsl;
+        use schema::place_owner::dsl;
+        use schema::users::dsl as user_dsl;
+        schema::place_owner::table
+            .inner_join(schema::place::table)
+            .inner_join(schema::users::table)
+            .select((
+                dsl::created_at,
+                dsl::locked_fields,
+                place_dsl::id,
+                user_dsl::email,
+            ))
+            .filter(place_dsl::id.eq(place_id))
+            .first::<models::PlaceOwner>(self)
+            .optional()?
+            .map(load_place_ownership)
+            .transpose()
+    }
+
+    fn update_locked_place_fields(&self, place_id: &str, fields: &[PlaceField]) -> Result<()> {
+        use schema::place_owner::dsl;
+        let place_rowid = resolve_place_rowid(self, &Id::from(place_id))?;
+        let locked_fields = fields
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let count = diesel::update(dsl::place_owner.filter(dsl::place_rowid.eq(place_rowid)))
+            .set(dsl::locked_fields.eq(locked_fields))
+            .execute(self)?;
+        if count == 0 {
+            return Err(RepoError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+fn resolve_user_email_by_id(conn: &SqliteConnection, user_id: i64) -> Result<String> {
+    use schema::users::dsl;
+    Ok(dsl::users
+        .select(dsl::email)
+        .filter(dsl::id.eq(user_id))
+        .first(conn)?)
+}
+
+fn load_place_edit_suggestion(
+    conn: &SqliteConnection,
+    from: models::PlaceEditSuggestion,
+) -> Result<PlaceEditSuggestion> {
+    let models::PlaceEditSuggestion {
+        id,
+        base_rev,
+        created_at,
+        created_by,
+        suggested_state,
+        status,
+        reviewed_at,
+        reviewed_by,
+        review_context,
+        review_comment,
+        place_id,
+        place_license,
+    } = from;
+    let base_revision = Revision::from(base_rev as u64);
+    let created = Activity {
+        at: TimestampMs::from_inner(created_at),
+        by: created_by
+            .map(|user_id| resolve_user_email_by_id(conn, user_id))
+            .transpose()?
+            .map(Into::into),
+    };
+    let snapshot = serde_json::from_str(&suggested_state).map_err(anyhow::Error::from)?;
+    let suggested = util::place_from_snapshot(
+        place_id,
+        place_license,
+        base_revision.next(),
+        created,
+        snapshot,
+    );
+    let status = status
+        .parse()
+        .map_err(|_| RepoError::Other(anyhow!("Invalid suggestion status: {}", status)))?;
+    let reviewed = if let Some(reviewed_at) = reviewed_at {
+        Some(ActivityLog {
+            activity: Activity {
+                at: TimestampMs::from_inner(reviewed_at),
+                by: reviewed_by
+                    .map(|user_id| resolve_user_email_by_id(conn, user_id))
+                    .transpose()?
+                    .map(Into::into),
+            },
+            context: review_context,
+            comment: review_comment,
+        })
+    } else {
+        None
+    };
+    Ok(PlaceEditSuggestion {
+        id: id.into(),
+        base_revision,
+        suggested,
+        status,
+        reviewed,
+    })
+}
+
+impl PlaceEditSuggestionRepo for SqliteConnection {
+    fn add_place_edit_suggestion(&self, suggestion: &PlaceEditSuggestion) -> Result<()> {
+        let PlaceEditSuggestion {
+            id,
+            base_revision,
+            suggested,
+            status,
+            reviewed: _,
+        } = suggestion;
+        let place_rowid = resolve_place_rowid(self, &suggested.id)?;
+        let created_by = if let Some(ref email) = suggested.created.by {
+            Some(resolve_user_created_by_email(self, email.as_ref())?)
+        } else {
+            None
+        };
+        let created_at = suggested.created.at.into_inner();
+        let snapshot = util::place_snapshot_from_place(suggested.clone());
+        let suggested_state = serde_json::to_string(&snapshot).map_err(anyhow::Error::from)?;
+        diesel::insert_into(schema::place_edit_suggestion::table)
+            .values(&models::NewPlaceEditSuggestion {
+                id: id.as_str(),
+                place_rowid,
+                base_rev: u64::from(*base_revision) as i64,
+                created_at,
+                created_by,
+                suggested_state,
+                status: status.to_string(),
+            })
+            .execute(self)?;
+        Ok(())
+    }
+
+    fn get_place_edit_suggestion(&self, id: &str) -> Result<PlaceEditSuggestion> {
+        use schema::place::dsl as place_dsl;
+        use schema::place_edit_suggestion::dsl;
+        let row = schema::place_edit_suggestion::table
+            .inner_join(schema::place::table)
+            .select((
+                dsl::id,
+                dsl::base_rev,
+                dsl::created_at,
+                dsl::created_by,
+                dsl::suggested_state,
+                dsl::status,
+                dsl::reviewed_at,
+                dsl::reviewed_by,
+                dsl::review_context,
+                dsl::review_comment,
+                place_dsl::id,
+                place_dsl::license,
+            ))
+            .filter(dsl::id.eq(id))
+            .first::<models::PlaceEditSuggestion>(self)?;
+        load_place_edit_suggestion(self, row)
+    }
+
+    fn query_place_edit_suggestions(
+        &self,
+        query: &PlaceEditSuggestionQuery,
+        pagination: &Pagination,
+    ) -> Result<Vec<PlaceEditSuggestion>> {
+        use schema::place::dsl as place_dsl;
+        use schema::place_edit_suggestion::dsl;
+
+        let mut q = schema::place_edit_suggestion::table
+            .inner_join(schema::place::table)
+            .select((
+                dsl::id,
+                dsl::base_rev,
+                dsl::created_at,
+                dsl::created_by,
+                dsl::suggested_state,
+                dsl::status,
+                dsl::reviewed_at,
+                dsl::reviewed_by,
+                dsl::review_context,
+                dsl::review_comment,
+                place_dsl::id,
+                place_dsl::license,
+            ))
+            .order_by(dsl::created_at)
+            .then_order_by(dsl::rowid) // disambiguation of equal time stamps
+            .into_boxed();
+
+        if let Some(place_id) = query.place_id {
+            q = q.filter(place_dsl::id.eq(place_id));
+        }
+        if let Some(status) = query.status {
+            q = q.filter(dsl::status.eq(status.to_string()));
+        }
+        if !query.tags.is_empty() {
+            use schema::place_revision::dsl as rev_dsl;
+            use schema::place_revision_tag::dsl as tag_dsl;
+            // TODO: Diesel 1.4.5 does not allow to use a subselect
+            // with joins in the following filter and requires to
+            // temporarily load the subselect results into memory
+            let tagged_place_rowids = schema::place_revision::table
+                .inner_join(
+                    schema::place::table.on(rev_dsl::parent_rowid
+                        .eq(place_dsl::rowid)
+                        .and(rev_dsl::rev.eq(place_dsl::current_rev))),
+                )
+                .inner_join(
+                    schema::place_revision_tag::table.on(tag_dsl::parent_rowid.eq(rev_dsl::rowid)),
+                )
+                .select(place_dsl::rowid)
+                .filter(tag_dsl::tag.eq_any(query.tags))
+                .distinct()
+                .load::<i64>(self)?;
+            q = q.filter(dsl::place_rowid.eq_any(tagged_place_rowids));
+        }
+
+        // Pagination
+        let offset = pagination.offset.unwrap_or(0);
+        if offset > 0 {
+            q = q.offset(offset as i64);
+        }
+        if let Some(limit) = pagination.limit {
+            q = q.limit(limit as i64);
+        }
+
+        q.load::<models::PlaceEditSuggestion>(self)?
+            .into_iter()
+            .map(|row| load_place_edit_suggestion(self, row))
+            .collect()
+    }
+
+    fn review_place_edit_suggestion(
+        &self,
+        id: &str,
+        status: SuggestionStatus,
+        log: &ActivityLog,
+    ) -> Result<()> {
+        use schema::place_edit_suggestion::dsl;
+        let ActivityLog {
+            activity,
+            context,
+            comment,
+        } = log;
+        let reviewed_by = if let Some(ref email) = activity.by {
+            Some(resolve_user_created_by_email(self, email.as_ref())?)
+        } else {
+            None
+        };
+        let count = diesel::update(
+            schema::place_edit_suggestion::table
+                .filter(dsl::id.eq(id))
+                .filter(dsl::status.eq(SuggestionStatus::Pending.to_string())),
+        )
+        .set((
+            dsl::status.eq(status.to_string()),
+            dsl::reviewed_at.eq(Some(activity.at.into_inner())),
+            dsl::reviewed_by.eq(reviewed_by),
+            dsl::review_context.eq(context.as_ref()),
+            dsl::review_comment.eq(comment.as_ref()),
+        ))
+        .execute(self)?;
+        if count == 0 {
+            return Err(RepoError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+fn load_audit_log_entry(from: models::AuditLogEntry) -> Result<AuditLogEntry> {
+    let models::AuditLogEntry {
+        rowid: _,
+        at,
+        actor: _,
+        action,
+        entity_kind,
+        entity_id,
+        details,
+        context,
+        comment,
+        actor_email,
+    } = from;
+    let action = action
+        .parse()
+        .map_err(|_| RepoError::Other(anyhow!("Invalid audit action: {}", action)))?;
+    let kind = entity_kind
+        .parse()
+        .map_err(|_| RepoError::Other(anyhow!("Invalid audit entity kind: {}", entity_kind)))?;
+    Ok(AuditLogEntry {
+        log: ActivityLog {
+            activity: Activity {
+                at: TimestampMs::from_inner(at),
+                by: actor_email.map(Into::into),
+            },
+            context,
+            comment,
+        },
+        action,
+        entity: AuditEntity {
+            kind,
+            id: entity_id,
+        },
+        details,
+    })
+}
+
+impl AuditLogRepo for SqliteConnection {
+    fn add_audit_log_entries(&self, entries: &[AuditLogEntry]) -> Result<()> {
+        let mut insertables = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let actor = if let Some(ref email) = entry.log.activity.by {
+                Some(resolve_user_created_by_email(self, email)?)
+            } else {
+                None
+            };
+            insertables.push(models::NewAuditLogEntry {
+                at: entry.log.activity.at.into_inner(),
+                actor,
+                action: entry.action.to_string(),
+                entity_kind: entry.entity.kind.to_string(),
+                entity_id: &entry.entity.id,
+                details: entry.details.as_deref(),
+                context: entry.log.context.as_deref(),
+                comment: entry.log.comment.as_deref(),
+            });
+        }
+        diesel::insert_into(schema::audit_log::table)
+            .values(&insertables)
+            .execute(self)?;
+        Ok(())
+    }
+
+    fn query_audit_log(
+        &self,
+        query: &AuditLogQuery,
+        pagination: &Pagination,
+    ) -> Result<Vec<AuditLogEntry>> {
+        use schema::{audit_log::dsl, users::dsl as u_dsl};
+
+        let mut q = dsl::audit_log
+            .left_outer_join(u_dsl::users)
+            .select((
+                dsl::rowid,
+                dsl::at,
+                dsl::actor,
+                dsl::action,
+                dsl::entity_kind,
+                dsl::entity_id,
+                dsl::details,
+                dsl::context,
+                dsl::comment,
+                u_dsl::email.nullable(),
+            ))
+            .order_by(dsl::at.desc())
+            .then_order_by(dsl::rowid.desc()) // disambiguation of equal time stamps
+            .into_boxed();
+
+        // Since (inclusive)
+        if let Some(since) = query.since {
+            q = q.filter(dsl::at.ge(since.into_inner()));
+        }
+        if let Some(actor_email) = query.actor_email {
+            let actor = u_dsl::users
+                .select(u_dsl::id)
+                .filter(u_dsl::email.eq(actor_email))
+                .first::<i64>(self)
+                .optional()?;
+            if let Some(actor) = actor {
+                q = q.filter(dsl::actor.eq(actor));
+            } else {
+                return Ok(vec![]);
+            }
+        }
+        if let Some(entity_id) = query.entity_id {
+            q = q.filter(dsl::entity_id.eq(entity_id));
+        }
+        if let Some(action) = query.action {
+            q = q.filter(dsl::action.eq(action.to_string()));
+        }
+
+        // Pagination
+        let offset = pagination.offset.unwrap_or(0);
+        if offset > 0 {
+            q = q.offset(offset as i64);
+        }
+        if let Some(limit) = pagination.limit {
+            q = q.limit(limit as i64);
+        }
+
+        q.load::<models::AuditLogEntry>(self)?
+            .into_iter()
+            .map(load_audit_log_entry)
+            .collect()
+    }
+}
+
+impl ImageRepo for SqliteConnection {
+    fn add_image(&self, image: &Image) -> Result<()> {
+        let created_by = if let Some(ref email) = image.uploaded.by {
+            Some(resolve_user_created_by_email(self, email)?)
+        } else {
+            None
+        };
+        diesel::insert_into(schema::image::table)
+            .values(&models::NewImage {
+                id: image.id.as_str(),
+                format: image.format.to_string(),
+                width: i64::from(image.width),
+                height: i64::from(image.height),
+                license: &image.license,
+                attribution: image.attribution.as_deref(),
+                created_at: image.uploaded.at.into_inner(),
+                created_by,
+            })
+            .execute(self)?;
+        Ok(())
+    }
+
+    fn get_image(&self, id: &str) -> Result<Image> {
+        use schema::{image::dsl, users::dsl as u_dsl};
+        let models::Image {
+            id,
+            format,
+            width,
+            height,
+            license,
+            attribution,
+            created_at,
+            created_by_email,
+        } = dsl::image
+            .left_outer_join(u_dsl::users)
+            .select((
+                dsl::id,
+                dsl::format,
+                dsl::width,
+                dsl::height,
+                dsl::license,
+                dsl::attribution,
+                dsl::created_at,
+                u_dsl::email.nullable(),
+            ))
+            .filter(dsl::id.eq(id))
+            .first::<models::Image>(self)?;
+        let format = format
+            .parse()
+            .map_err(|_| RepoError::Other(anyhow!("Invalid image format: {}", format)))?;
+        Ok(Image {
+            id: id.into(),
+            format,
+            width: width as u32,
+            height: height as u32,
+            license,
+            attribution,
+            uploaded: Activity {
+                at: TimestampMs::from_inner(created_at),
+                by: created_by_email.map(Into::into),
+            },
+        })
+    }
+}
+
+fn load_link_check(from: models::LinkCheck) -> Result<LinkCheck> {
+    let models::LinkCheck {
+        rowid: _,
+        owner_kind,
+        owner_id,
+        url,
+        status,
+        http_status,
+        redirect_url,
+        checked_at,
+    } = from;
+    let kind = owner_kind
+        .parse()
+        .map_err(|_| RepoError::Other(anyhow!("Invalid link owner kind: {}", owner_kind)))?;
+    let status = status
+        .parse()
+        .map_err(|_| RepoError::Other(anyhow!("Invalid link status: {}", status)))?;
+    let url = url
+        .parse()
+        .map_err(|_| RepoError::Other(anyhow!("Invalid URL: {}", url)))?;
+    Ok(LinkCheck {
+        owner: LinkOwner {
+            kind,
+            id: owner_id.into(),
+        },
+        url,
+        outcome: LinkCheckOutcome {
+            status,
+            http_status: http_status.map(|s| s as u16),
+            redirect_url,
+        },
+        checked_at: TimestampMs::from_inner(checked_at),
+    })
+}
+
+impl LinkCheckRepo for SqliteConnection {
+    fn save_link_checks(&self, checks: &[LinkCheck]) -> Result<()> {
+        let insertables: Vec<_> = checks
+            .iter()
+            .map(|check| models::NewLinkCheck {
+                owner_kind: check.owner.kind.to_string(),
+                owner_id: check.owner.id.as_str(),
+                url: check.url.as_str(),
+                status: check.outcome.status.to_string(),
+                http_status: check.outcome.http_status.map(i32::from),
+                redirect_url: check.outcome.redirect_url.as_deref(),
+                checked_at: check.checked_at.into_inner(),
+            })
+            .collect();
+        diesel::replace_into(schema::link_check::table)
+            .values(&insertables)
+            .execute(self)?;
+        Ok(())
+    }
+
+    fn delete_link_checks(&self, checks: &[LinkCheck]) -> Result<()> {
+        use schema::link_check::dsl;
+        for check in checks {
+            diesel::delete(
+                dsl::link_check
+                    .filter(dsl::owner_kind.eq(check.owner.kind.to_string()))
+                    .filter(dsl::owner_id.eq(check.owner.id.as_str()))
+                    .filter(dsl::url.eq(check.url.as_str())),
+            )
+            .execute(self)?;
+        }
+        Ok(())
+    }
+
+    fn query_link_checks(
+        &self,
+        query: &LinkCheckQuery,
+        pagination: &Pagination,
+    ) -> Result<Vec<LinkCheck>> {
+        use schema::link_check::dsl;
+
+        let mut q = dsl::link_check
+            .order_by(dsl::owner_kind)
+            .then_order_by(dsl::owner_id)
+            .then_order_by(dsl::url)
+            .into_boxed();
+        if let Some(kind) = query.owner_kind {
+            q = q.filter(dsl::owner_kind.eq(kind.to_string()));
+        }
+        if let Some(status) = query.status {
+            q = q.filter(dsl::status.eq(status.to_string()));
+        }
+
+        // Pagination
+        let offset = pagination.offset.unwrap_or(0);
+        if offset > 0 {
+            q = q.offset(offset as i64);
+        }
+        if let Some(limit) = pagination.limit {
+            q = q.limit(limit as i64);
+        }
+
+        q.load::<models::LinkCheck>(self)?
+            .into_iter()
+            .map(load_link_check)
+            .collect()
+    }
+}
+
+impl StatisticsRepo for SqliteConnection {
+    fn place_revision_times(&self) -> Result<Vec<(Revision, TimestampMs)>> {
+        use schema::place_revision::dsl;
+        Ok(dsl::place_revision
+            .select((dsl::rev, dsl::created_at))
+            .load::<(i64, i64)>(self)?
+            .into_iter()
+            .map(|(rev, created_at)| {
+                (
+                    Revision::from(rev as u64),
+                    TimestampMs::from_inner(created_at),
+                )
+            })
+            .collect())
+    }
+
+    fn event_start_times(&self) -> Result<Vec<Timestamp>> {
+        use schema::events::dsl;
+        Ok(dsl::events
+            .select(dsl::start)
+            .filter(dsl::archived.is_null())
+            .load::<i64>(self)?
+            .into_iter()
+            .map(Timestamp::from_inner)
+            .collect())
+    }
+
+    fn user_registration_times(&self) -> Result<Vec<TimestampMs>> {
+        use schema::users::dsl;
+        Ok(dsl::users
+            .select(dsl::created_at)
+            .filter(dsl::created_at.is_not_null())
+            .load::<Option<i64>>(self)?
+            .into_iter()
+            .flatten()
+            .map(TimestampMs::from_inner)
+            .collect())
+    }
+
+    fn rating_creation_times(&self) -> Result<Vec<Timestamp>> {
+        use schema::place_rating::dsl;
+        Ok(dsl::place_rating
+            .select(dsl::created_at)
+            .load::<i64>(self)?
+            .into_iter()
+            .map(Timestamp::from_inner)
+            .collect())
+    }
+}
+
+fn load_saved_search(from: models::SavedSearch) -> SavedSearch {
+    let models::SavedSearch {
+        id,
+        name,
+        south_west_lat,
+        south_west_lng,
+        north_east_lat,
+        north_east_lng,
+        categories,
+        hash_tags,
+        text,
+        org_tag,
+        alert,
+        created_at,
+        user_email,
+    } = from;
+    let split = |list: String| {
+        list.split(',')
+            .filter(|item| !item.is_empty())
+            .map(ToOwned::to_owned)
+            .collect()
+    };
+    let south_west =
+        MapPoint::try_from_lat_lng_deg(south_west_lat, south_west_lng).unwrap_or_default();
+    let north_east =
+        MapPoint::try_from_lat_lng_deg(north_east_lat, north_east_lng).unwrap_or_default();
+    SavedSearch {
+        id: id.into(),
+        user_email,
+        name,
+        bbox: MapBbox::new(south_west, north_east),
+        categories: split(categories),
+        hash_tags: split(hash_tags),
+        text,
+        org_tag,
+        alert,
+        created_at: TimestampMs::from_inner(created_at),
+    }
+}
+
+impl SavedSearchRepo for SqliteConnection {
+    fn create_saved_search(&self, saved_search: &SavedSearch) -> Result<()> {
+        let user_id = resolve_user_created_by_email(self, &saved_search.user_email)?;
+        let (south_west_lat, south_west_lng) = saved_search.bbox.southwest().to_lat_lng_deg();
+        let (north_east_lat, north_east_lng) = saved_search.bbox.northeast().to_lat_lng_deg();
+        diesel::insert_into(schema::saved_search::table)
+            .values(&models::NewSavedSearch {
+                id: saved_search.id.as_str(),
+                user_id,
+                name: &saved_search.name,
+                south_west_lat,
+                south_west_lng,
+                north_east_lat,
+                north_east_lng,
+                categories: saved_search.categories.join(","),
+                hash_tags: saved_search.hash_tags.join(","),
+                text: saved_search.text.as_deref(),
+                org_tag: saved_search.org_tag.as_deref(),
+                alert: saved_search.alert,
+                created_at: saved_search.created_at.into_inner(),
+            })
+            .execute(self)?;
+        Ok(())
+    }
+
+    fn update_saved_search(&self, saved_search: &SavedSearch) -> Result<()> {
+        use schema::saved_search::dsl;
+        let (south_west_lat, south_west_lng) = saved_search.bbox.southwest().to_lat_lng_deg();
+        let (north_east_lat, north_east_lng) = saved_search.bbox.northeast().to_lat_lng_deg();
+        let count = diesel::update(dsl::saved_search.filter(dsl::id.eq(saved_search.id.as_str())))
+            .set((
+                dsl::name.eq(&saved_search.name),
+                dsl::south_west_lat.eq(south_west_lat),
+                dsl::south_west_lng.eq(south_west_lng),
+                dsl::north_east_lat.eq(north_east_lat),
+                dsl::north_east_lng.eq(north_east_lng),
+                dsl::categories.eq(saved_search.categories.join(",")),
+                dsl::hash_tags.eq(saved_search.hash_tags.join(",")),
+                dsl::text.eq(saved_search.text.as_deref()),
+                dsl::org_tag.eq(saved_search.org_tag.as_deref()),
+                dsl::alert.eq(saved_search.alert),
+            ))
+            .execute(self)?;
+        if count == 0 {
+            return Err(RepoError::NotFound);
+        }
+        Ok(())
+    }
+
+    fn delete_saved_search(&self, id: &str) -> Result<()> {
+        use schema::saved_search::dsl;
+        let count = diesel::delete(dsl::saved_search.filter(dsl::id.eq(id))).execute(self)?;
+        if count == 0 {
+            return Err(RepoError::NotFound);
+        }
+        Ok(())
+    }
+
+    fn get_saved_search(&self, id: &str) -> Result<SavedSearch> {
+        use schema::saved_search::dsl;
+        use schema::users::dsl as u_dsl;
+        Ok(dsl::saved_search
+            .inner_join(u_dsl::users)
+            .select((
+                dsl::id,
+                dsl::name,
+                dsl::south_west_lat,
+                dsl::south_west_lng,
+                dsl::north_east_lat,
+                dsl::north_east_lng,
+                dsl::categories,
+                dsl::hash_tags,
+                dsl::text,
+                dsl::org_tag,
+                dsl::alert,
+                dsl::created_at,
+                u_dsl::email,
+            ))
+            .filter(dsl::id.eq(id))
+            .first::<models::SavedSearch>(self)
+            .map(load_saved_search)?)
+    }
+
+    fn saved_searches_by_email(&self, user_email: &str) -> Result<Vec<SavedSearch>> {
+        use schema::saved_search::dsl;
+        use schema::users::dsl as u_dsl;
+        Ok(dsl::saved_search
+            .inner_join(u_dsl::users)
+            .select((
+                dsl::id,
+                dsl::name,
+                dsl::south_west_lat,
+                dsl::south_west_lng,
+                dsl::north_east_lat,
+                dsl::north_east_lng,
+                dsl::categories,
+                dsl::hash_tags,
+                dsl::text,
+                dsl::org_tag,
+                dsl::alert,
+                dsl::created_at,
+                u_dsl::email,
+            ))
+            .filter(u_dsl::email.eq(user_email))
+            .order_by(dsl::created_at)
+            .load::<models::SavedSearch>(self)?
+            .into_iter()
+            .map(load_saved_search)
+            .collect())
+    }
+
+    fn all_saved_searches_with_alert(&self) -> Result<Vec<SavedSearch>> {
+        use schema::saved_search::dsl;
+        use schema::users::dsl as u_dsl;
+        Ok(dsl::saved_search
+            .inner_join(u_dsl::users)
+            .select((
+                dsl::id,
+                dsl::name,
+                dsl::south_west_lat,
+                dsl::south_west_lng,
+                dsl::north_east_lat,
+                dsl::north_east_lng,
+                dsl::categories,
+                dsl::hash_tags,
+                dsl::text,
+                dsl::org_tag,
+                dsl::alert,
+                dsl::created_at,
+                u_dsl::email,
+            ))
+            .filter(dsl::alert.eq(true))
+            .load::<models::SavedSearch>(self)?
+            .into_iter()
+            .map(load_saved_search)
+            .collect())
+    }
+}
+
+impl TagRepo for SqliteConnection {
+    fn all_tag_synonyms(&self) -> Result<Vec<TagSynonym>> {
+        use schema::tag_synonym::dsl;
+        Ok(dsl::tag_synonym
+            .order_by(dsl::tag)
+            .then_order_by(dsl::synonym)
+            .load::<models::TagSynonym>(self)?
+            .into_iter()
+            .map(TagSynonym::from)
+            .collect())
+    }
+
+    fn replace_tag_synonyms(&self, tag: &str, synonyms: &[String]) -> Result<()> {
+        use schema::tag_synonym::dsl;
+        diesel::delete(dsl::tag_synonym.filter(dsl::tag.eq(tag).or(dsl::synonym.eq_any(synonyms))))
+            .execute(self)?;
+        let insertable: Vec<_> = synonyms
+            .iter()
+            .map(|synonym| models::TagSynonym {
+                synonym: synonym.to_owned(),
+                tag: tag.to_owned(),
+            })
+            .collect();
+        diesel::insert_into(schema::tag_synonym::table)
+            .values(&insertable)
+            .execute(self)?;
+        Ok(())
+    }
+
+    fn get_tag_description(&self, tag: &str) -> Result<Option<String>> {
+        use schema::tag_description::dsl;
+        Ok(dsl::tag_description
+            .select(dsl::description)
+            .filter(dsl::tag.eq(tag))
+            .first::<String>(self)
+            .optional()?)
+    }
+
+    fn set_tag_description(&self, tag: &str, description: Option<&str>) -> Result<()> {
+        use schema::tag_description::dsl;
+        diesel::delete(dsl::tag_description.filter(dsl::tag.eq(tag))).execute(self)?;
+        if let Some(description) = description {
+            diesel::insert_into(schema::tag_description::table)
+                .values(&models::NewTagDescription { tag, description })
+                .execute(self)?;
+        }
+        Ok(())
+    }
+
+    fn delete_tags(&self, tags: &[&str]) -> Result<usize> {
+        use schema::tags::dsl;
+        Ok(diesel::delete(dsl::tags.filter(dsl::id.eq_any(tags))).execute(self)?)
+    }
+
+    fn all_taxonomy_tags(&self) -> Result<Vec<TaxonomyTag>> {
+        use schema::tag_taxonomy::dsl;
+        Ok(dsl::tag_taxonomy
+            .order_by(dsl::tag)
+            .load::<models::TaxonomyTag>(self)?
+            .into_iter()
+            .map(TaxonomyTag::from)
+            .collect())
+    }
+
+    fn replace_taxonomy(&self, taxonomy: &[TaxonomyTag]) -> Result<()> {
+        diesel::delete(schema::tag_taxonomy::table).execute(self)?;
+        let insertable: Vec<_> = taxonomy
+            .iter()
+            .map(|t| models::TaxonomyTag {
+                tag: t.tag.clone(),
+                parent: t.parent.clone(),
+            })
+            .collect();
+        diesel::insert_into(schema::tag_taxonomy::table)
+            .values(&insertable)
+            .execute(self)?;
+        Ok(())
+    }
+}
+
+fn resolve_category_rowid(conn: &SqliteConnection, id: &str) -> Result<i64> {
+    use schema::category::dsl;
+    Ok(schema::category::table
+        .select(dsl::rowid)
+        .filter(dsl::id.eq(id))
+        .first::<i64>(conn)
+        .map_err(|e| {
+            log::warn!("Failed to resolve category id '{}': {}", id, e);
+            e
+        })?)
+}
+
+fn insert_category_names(
+    conn: &SqliteConnection,
+    category_rowid: i64,
+    category: &Category,
+) -> Result<()> {
+    let insertable: Vec<_> = category
+        .names
+        .iter()
+        .map(|(lang, name)| models::NewCategoryName {
+            category_rowid,
+            lang,
+            name,
+        })
+        .collect();
+    diesel::insert_into(schema::category_name::table)
+        .values(&insertable)
+        .execute(conn)?;
+    Ok(())
+}
+
+impl CategoryRepo for SqliteConnection {
+    fn all_categories(&self) -> Result<Vec<Category>> {
+        use schema::{category::dsl, category_name::dsl as name_dsl};
+        let mut names = name_dsl::category_name
+            .load::<models::CategoryName>(self)?
+            .into_iter()
+            .fold(HashMap::<_, BTreeMap<_, _>>::new(), |mut names, n| {
+                names
+                    .entry(n.category_rowid)
+                    .or_default()
+                    .insert(n.lang, n.name);
+                names
+            });
+        Ok(dsl::category
+            .order_by(dsl::rowid)
+            .load::<models::Category>(self)?
+            .into_iter()
+            .map(|c| Category {
+                id: c.id.into(),
+                tag: c.tag,
+                names: names.remove(&c.rowid).unwrap_or_default(),
+                icon: c.icon,
+                color: c.color,
+            })
+            .collect())
+    }
+
+    fn create_category(&self, category: &Category) -> Result<()> {
+        let new_category = models::NewCategory {
+            id: category.id.as_str(),
+            tag: &category.tag,
+            icon: category.icon.as_deref(),
+            color: category.color.as_deref(),
+        };
+        self.transaction::<_, RepoError, _>(|| {
+            diesel::insert_into(schema::category::table)
+                .values(&new_category)
+                .execute(self)?;
+            let rowid = resolve_category_rowid(self, category.id.as_str())?;
+            insert_category_names(self, rowid, category)
+        })
+    }
+
+    fn update_category(&self, category: &Category) -> Result<()> {
+        use schema::{category::dsl, category_name::dsl as name_dsl};
+        let new_category = models::NewCategory {
+            id: category.id.as_str(),
+            tag: &category.tag,
+            icon: category.icon.as_deref(),
+            color: category.color.as_deref(),
+        };
+        self.transaction::<_, RepoError, _>(|| {
+            let rowid = resolve_category_rowid(self, category.id.as_str())?;
+            diesel::update(dsl::category.filter(dsl::rowid.eq(rowid)))
+                .set(&new_category)
+                .execute(self)?;
+            diesel::delete(name_dsl::category_name.filter(name_dsl::category_rowid.eq(rowid)))
+                .execute(self)?;
+            insert_category_names(self, rowid, category)
+        })
+    }
+
+    fn delete_category(&self, id: &str) -> Result<()> {
+        use schema::{category::dsl, category_name::dsl as name_dsl};
+        self.transaction::<_, RepoError, _>(|| {
+            let rowid = resolve_category_rowid(self, id)?;
+            diesel::delete(name_dsl::category_name.filter(name_dsl::category_rowid.eq(rowid)))
+                .execute(self)?;
+            diesel::delete(dsl::category.filter(dsl::rowid.eq(rowid))).execute(self)?;
+            Ok(())
+        })
+    }
+}
@@ -174,6 +174,7 @@ pub struct NewPlaceRating {
     pub value: i16,
     pub context: String,
     pub source: Option<String>,
+    pub author_session: Option<String>,
 }
 
 #[derive(Queryable)]
@@ -188,8 +189,19 @@ pub struct PlaceRating {
     pub value: i16,
     pub context: String,
     pub source: Option<String>,
+    pub author_session: Option<String>,
     // Joined columns
     pub place_id: String,
+    pub created_by_email: Option<String>,
+}
+
+#[derive(Insertable)]
+#[table_name = "place_rating_flag"]
+pub struct NewPlaceRatingFlag<'a> {
+    pub parent_rowid: i64,
+    pub created_at: i64,
+    pub created_by: Option<i64>,
+    pub comment: Option<&'a str>,
 }
 
 #[derive(Insertable)]
@@ -202,6 +214,9 @@ pub struct NewPlaceRatingComment {
     pub archived_by: Option<i64>,
     pub id: String,
     pub text: String,
+    pub reply_to: Option<i64>,
+    pub created_by_org: Option<i64>,
+    pub response: bool,
 }
 
 #[derive(Queryable)]
@@ -213,8 +228,12 @@ pub struct PlaceRatingComment {
     pub archived_by: Option<i64>,
     pub id: String,
     pub text: String,
+    pub reply_to: Option<i64>,
+    pub response: bool,
 
     pub rating_id: String,
+    pub created_by_email: Option<String>,
+    pub created_by_org_id: Option<String>,
 }
 
 #[derive(Insertable, AsChangeset)]
@@ -334,6 +353,61 @@ pub struct Tag {
     pub id: String,
 }
 
+#[derive(Queryable, Insertable)]
+#[table_name = "tag_synonym"]
+pub struct TagSynonym {
+    pub synonym: String,
+    pub tag: String,
+}
+
+#[derive(Insertable)]
+#[table_name = "tag_description"]
+pub struct NewTagDescription<'a> {
+    pub tag: &'a str,
+    pub description: &'a str,
+}
+
+#[derive(Queryable, Insertable)]
+#[table_name = "tag_taxonomy"]
+pub struct TaxonomyTag {
+    pub tag: String,
+    pub parent: Option<String>,
+}
+
+#[derive(Queryable)]
+pub struct Category {
+    pub rowid: i64,
+    pub id: String,
+    pub tag: String,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[table_name = "category"]
+#[changeset_options(treat_none_as_null = "true")]
+pub struct NewCategory<'a> {
+    pub id: &'a str,
+    pub tag: &'a str,
+    pub icon: Option<&'a str>,
+    pub color: Option<&'a str>,
+}
+
+#[derive(Queryable)]
+pub struct CategoryName {
+    pub category_rowid: i64,
+    pub lang: String,
+    pub name: String,
+}
+
+#[derive(Insertable)]
+#[table_name = "category_name"]
+pub struct NewCategoryName<'a> {
+    pub category_rowid: i64,
+    pub lang: &'a str,
+    pub name: &'a str,
+}
+
 #[derive(Insertable, AsChangeset)]
 #[table_name = "users"]
 pub struct NewUser<'a> {
@@ -350,6 +424,148 @@ pub struct UserEntity {
     pub email_confirmed: bool,
     pub password: String,
     pub role: i16,
+    pub created_at: Option<i64>,
+}
+
+#[derive(Insertable)]
+#[table_name = "user_regions"]
+pub struct NewUserRegion {
+    pub user_id: i64,
+    pub south_west_lat: f64,
+    pub south_west_lng: f64,
+    pub north_east_lat: f64,
+    pub north_east_lng: f64,
+}
+
+#[derive(Insertable)]
+#[table_name = "anonymized_users"]
+pub struct NewAnonymizedUser<'a> {
+    pub pseudonym: &'a str,
+    pub anonymized_at: i64,
+    pub place_revisions: i64,
+    pub place_reviews: i64,
+    pub place_ratings: i64,
+    pub place_rating_comments: i64,
+    pub events: i64,
+}
+
+#[derive(Queryable)]
+pub struct AnonymizedUser {
+    pub rowid: i64,
+    pub pseudonym: String,
+    pub anonymized_at: i64,
+    pub place_revisions: i64,
+    pub place_reviews: i64,
+    pub place_ratings: i64,
+    pub place_rating_comments: i64,
+    pub events: i64,
+}
+
+#[derive(Insertable)]
+#[table_name = "audit_log"]
+pub struct NewAuditLogEntry<'a> {
+    pub at: i64,
+    pub actor: Option<i64>,
+    pub action: String,
+    pub entity_kind: String,
+    pub entity_id: &'a str,
+    pub details: Option<&'a str>,
+    pub context: Option<&'a str>,
+    pub comment: Option<&'a str>,
+}
+
+#[derive(Queryable)]
+pub struct AuditLogEntry {
+    pub rowid: i64,
+    pub at: i64,
+    pub actor: Option<i64>,
+    pub action: String,
+    pub entity_kind: String,
+    pub entity_id: String,
+    pub details: Option<String>,
+    pub context: Option<String>,
+    pub comment: Option<String>,
+    // Joined columns
+    pub actor_email: Option<String>,
+}
+
+#[derive(Insertable)]
+#[table_name = "place_owner"]
+pub struct NewPlaceOwner {
+    pub place_rowid: i64,
+    pub user_id: i64,
+    pub created_at: i64,
+    pub locked_fields: String,
+}
+
+#[derive(Queryable)]
+pub struct PlaceOwner {
+    pub created_at: i64,
+    pub locked_fields: String,
+    // Joined columns
+    pub place_id: String,
+    pub owner_email: String,
+}
+
+#[derive(Insertable)]
+#[table_name = "place_edit_suggestion"]
+pub struct NewPlaceEditSuggestion<'a> {
+    pub id: &'a str,
+    pub place_rowid: i64,
+    pub base_rev: i64,
+    pub created_at: i64,
+    pub created_by: Option<i64>,
+    pub suggested_state: String,
+    pub status: String,
+}
+
+#[derive(Queryable)]
+pub struct PlaceEditSuggestion {
+    pub id: String,
+    pub base_rev: i64,
+    pub created_at: i64,
+    pub created_by: Option<i64>,
+    pub suggested_state: String,
+    pub status: String,
+    pub reviewed_at: Option<i64>,
+    pub reviewed_by: Option<i64>,
+    pub review_context: Option<String>,
+    pub review_comment: Option<String>,
+    // Joined columns
+    pub place_id: String,
+    pub place_license: String,
+}
+
+// Stored as JSON in place_edit_suggestion.suggested_state
+#[derive(Serialize, Deserialize)]
+pub struct PlaceSnapshot {
+    pub title: String,
+    pub description: String,
+    pub lat: f64,
+    pub lng: f64,
+    pub street: Option<String>,
+    pub zip: Option<String>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub state: Option<String>,
+    pub contact_name: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub homepage: Option<String>,
+    pub opening_hours: Option<String>,
+    // ISO 8601 date, e.g. "2020-11-30"
+    pub founded_on: Option<String>,
+    pub image_url: Option<String>,
+    pub image_link_url: Option<String>,
+    pub tags: Vec<String>,
+    pub custom_links: Vec<CustomLinkSnapshot>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CustomLinkSnapshot {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
 }
 
 #[derive(Insertable)]
@@ -363,6 +579,42 @@ pub struct NewBboxSubscription<'a> {
     pub north_east_lng: f64,
 }
 
+#[derive(Insertable)]
+#[table_name = "saved_search"]
+pub struct NewSavedSearch<'a> {
+    pub id: &'a str,
+    pub user_id: i64,
+    pub name: &'a str,
+    pub south_west_lat: f64,
+    pub south_west_lng: f64,
+    pub north_east_lat: f64,
+    pub north_east_lng: f64,
+    pub categories: String,
+    pub hash_tags: String,
+    pub text: Option<&'a str>,
+    pub org_tag: Option<&'a str>,
+    pub alert: bool,
+    pub created_at: i64,
+}
+
+#[derive(Queryable)]
+pub struct SavedSearch {
+    pub id: String,
+    pub name: String,
+    pub south_west_lat: f64,
+    pub south_west_lng: f64,
+    pub north_east_lat: f64,
+    pub north_east_lng: f64,
+    pub categories: String,
+    pub hash_tags: String,
+    pub text: Option<String>,
+    pub org_tag: Option<String>,
+    pub alert: bool,
+    pub created_at: i64,
+    // Joined columns
+    pub user_email: String,
+}
+
 #[derive(Queryable)]
 pub struct BboxSubscriptionEntity {
     pub id: i64,
@@ -378,10 +630,13 @@ pub struct BboxSubscriptionEntity {
 
 #[derive(Insertable, AsChangeset)]
 #[table_name = "user_tokens"]
+#[changeset_options(treat_none_as_null = "true")]
 pub struct NewUserToken {
     pub user_id: i64,
     pub nonce: String,
     pub expires_at: i64,
+    pub new_email: Option<String>,
+    pub place_id: Option<String>,
 }
 
 #[derive(Queryable)]
@@ -389,10 +644,31 @@ pub struct UserTokenEntity {
     pub user_id: i64,
     pub nonce: String,
     pub expires_at: i64,
+    pub new_email: Option<String>,
+    pub place_id: Option<String>,
     // Joined columns
     pub user_email: String,
 }
 
+#[derive(Insertable, AsChangeset)]
+#[table_name = "captcha"]
+#[changeset_options(treat_none_as_null = "true")]
+pub struct NewCaptcha {
+    pub nonce: String,
+    pub created_at: i64,
+    pub kind: Option<i16>,
+    pub answer: Option<String>,
+}
+
+#[derive(Queryable)]
+pub struct Captcha {
+    pub rowid: i64,
+    pub nonce: String,
+    pub created_at: i64,
+    pub kind: Option<i16>,
+    pub answer: Option<String>,
+}
+
 #[derive(Insertable, AsChangeset)]
 #[table_name = "organization_place_clearance"]
 #[changeset_options(treat_none_as_null = "true")]
@@ -401,6 +677,7 @@ pub struct NewPendingClearanceForPlace {
     pub place_rowid: i64,
     pub created_at: i64,
     pub last_cleared_revision: Option<i64>,
+    pub reverted_at: Option<i64>,
 }
 
 #[derive(Queryable)]
@@ -409,3 +686,94 @@ pub struct PendingClearanceForPlace {
     pub created_at: i64,
     pub last_cleared_revision: Option<i64>,
 }
+
+#[derive(Insertable)]
+#[table_name = "organization_event_clearance"]
+pub struct NewPendingClearanceForEvent {
+    pub org_rowid: i64,
+    pub event_rowid: i64,
+    pub created_at: i64,
+    pub last_cleared_state: Option<String>,
+}
+
+#[derive(Queryable)]
+pub struct PendingClearanceForEvent {
+    pub event_id: String,
+    pub created_at: i64,
+    pub last_cleared_state: Option<String>,
+}
+
+// Stored as JSON in organization_event_clearance.last_cleared_state
+#[derive(Serialize, Deserialize)]
+pub struct EventSnapshot {
+    pub title: String,
+    pub description: Option<String>,
+    pub start: i64,
+    pub end: Option<i64>,
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
+    pub street: Option<String>,
+    pub zip: Option<String>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub state: Option<String>,
+    pub email: Option<String>,
+    pub telephone: Option<String>,
+    pub homepage: Option<String>,
+    pub created_by_email: Option<String>,
+    pub registration: Option<i16>,
+    pub organizer: Option<String>,
+    pub image_url: Option<String>,
+    pub image_link_url: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Insertable)]
+#[table_name = "image"]
+pub struct NewImage<'a> {
+    pub id: &'a str,
+    pub format: String,
+    pub width: i64,
+    pub height: i64,
+    pub license: &'a str,
+    pub attribution: Option<&'a str>,
+    pub created_at: i64,
+    pub created_by: Option<i64>,
+}
+
+#[derive(Queryable)]
+pub struct Image {
+    pub id: String,
+    pub format: String,
+    pub width: i64,
+    pub height: i64,
+    pub license: String,
+    pub attribution: Option<String>,
+    pub created_at: i64,
+    // Joined columns
+    pub created_by_email: Option<String>,
+}
+
+#[derive(Insertable)]
+#[table_name = "link_check"]
+pub struct NewLinkCheck<'a> {
+    pub owner_kind: String,
+    pub owner_id: &'a str,
+    pub url: &'a str,
+    pub status: String,
+    pub http_status: Option<i32>,
+    pub redirect_url: Option<&'a str>,
+    pub checked_at: i64,
+}
+
+#[derive(Queryable)]
+pub struct LinkCheck {
+    pub rowid: i64,
+    pub owner_kind: String,
+    pub owner_id: String,
+    pub url: String,
+    pub status: String,
+    pub http_status: Option<i32>,
+    pub redirect_url: Option<String>,
+    pub checked_at: i64,
+}
@@ -8,6 +8,47 @@ table! {
     }
 }
 
+table! {
+    tag_synonym (synonym) {
+        synonym -> Text,
+        tag -> Text,
+    }
+}
+
+table! {
+    tag_description (tag) {
+        tag -> Text,
+        description -> Text,
+    }
+}
+
+table! {
+    tag_taxonomy (tag) {
+        tag -> Text,
+        parent -> Nullable<Text>,
+    }
+}
+
+table! {
+    category (rowid) {
+        rowid -> BigInt,
+        id -> Text,
+        tag -> Text,
+        icon -> Nullable<Text>,
+        color -> Nullable<Text>,
+    }
+}
+
+table! {
+    category_name (category_rowid, lang) {
+        category_rowid -> BigInt,
+        lang -> Text,
+        name -> Text,
+    }
+}
+
+joinable!(category_name -> category (category_rowid));
+
 ///////////////////////////////////////////////////////////////////////
 // Organizations
 ///////////////////////////////////////////////////////////////////////
@@ -41,12 +82,28 @@ table! {
         created_at -> BigInt,
         // last cleared revision or NULL if the place is new and has not been cleared yet
         last_cleared_revision -> Nullable<BigInt>,
+        // set if the last cleared revision has been kept permanently
+        reverted_at -> Nullable<BigInt>,
     }
 }
 
 joinable!(organization_place_clearance -> organization (org_rowid));
 joinable!(organization_place_clearance -> place (place_rowid));
 
+table! {
+    organization_event_clearance (rowid) {
+        rowid -> BigInt,
+        org_rowid -> BigInt,
+        event_rowid -> BigInt,
+        created_at -> BigInt,
+        // JSON snapshot of the last cleared event or NULL if the event is new and has not been cleared yet
+        last_cleared_state -> Nullable<Text>,
+    }
+}
+
+joinable!(organization_event_clearance -> organization (org_rowid));
+joinable!(organization_event_clearance -> events (event_rowid));
+
 ///////////////////////////////////////////////////////////////////////
 // Users
 ///////////////////////////////////////////////////////////////////////
@@ -58,6 +115,7 @@ table! {
         email_confirmed -> Bool,
         password -> Text,
         role -> SmallInt,
+        created_at -> Nullable<BigInt>,
     }
 }
 
@@ -67,11 +125,73 @@ table! {
         user_id -> BigInt,
         expires_at -> BigInt,
         nonce -> Text,
+        new_email -> Nullable<Text>,
+        place_id -> Nullable<Text>,
     }
 }
 
 joinable!(user_tokens -> users (user_id));
 
+table! {
+    user_regions (rowid) {
+        rowid -> BigInt,
+        user_id -> BigInt,
+        south_west_lat -> Double,
+        south_west_lng -> Double,
+        north_east_lat -> Double,
+        north_east_lng -> Double,
+    }
+}
+
+joinable!(user_regions -> users (user_id));
+
+table! {
+    anonymized_users (rowid) {
+        rowid -> BigInt,
+        pseudonym -> Text,
+        anonymized_at -> BigInt,
+        place_revisions -> BigInt,
+        place_reviews -> BigInt,
+        place_ratings -> BigInt,
+        place_rating_comments -> BigInt,
+        events -> BigInt,
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+// Audit log
+///////////////////////////////////////////////////////////////////////
+
+table! {
+    audit_log (rowid) {
+        rowid -> BigInt,
+        at -> BigInt,
+        actor -> Nullable<BigInt>,
+        action -> Text,
+        entity_kind -> Text,
+        entity_id -> Text,
+        details -> Nullable<Text>,
+        context -> Nullable<Text>,
+        comment -> Nullable<Text>,
+    }
+}
+
+joinable!(audit_log -> users (actor));
+
+///////////////////////////////////////////////////////////////////////
+// Captchas
+///////////////////////////////////////////////////////////////////////
+
+table! {
+    captcha (rowid) {
+        rowid -> BigInt,
+        nonce -> Text,
+        created_at -> BigInt,
+        kind -> Nullable<SmallInt>,
+        answer -> Nullable<Text>,
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////
 // Places
 ///////////////////////////////////////////////////////////////////////
@@ -115,6 +235,39 @@ table! {
 
 joinable!(place_revision -> place (parent_rowid));
 
+table! {
+    place_owner (place_rowid) {
+        place_rowid -> BigInt,
+        user_id -> BigInt,
+        created_at -> BigInt,
+        // comma-separated list of locked fields
+        locked_fields -> Text,
+    }
+}
+
+joinable!(place_owner -> place (place_rowid));
+joinable!(place_owner -> users (user_id));
+
+table! {
+    place_edit_suggestion (rowid) {
+        rowid -> BigInt,
+        id -> Text,
+        place_rowid -> BigInt,
+        base_rev -> BigInt,
+        created_at -> BigInt,
+        created_by -> Nullable<BigInt>,
+        // JSON snapshot of the suggested place revision
+        suggested_state -> Text,
+        status -> Text,
+        reviewed_at -> Nullable<BigInt>,
+        reviewed_by -> Nullable<BigInt>,
+        review_context -> Nullable<Text>,
+        review_comment -> Nullable<Text>,
+    }
+}
+
+joinable!(place_edit_suggestion -> place (place_rowid));
+
 table! {
     place_revision_tag (parent_rowid, tag) {
         parent_rowid -> BigInt,
@@ -163,10 +316,12 @@ table! {
         value -> SmallInt,
         context -> Text,
         source -> Nullable<Text>,
+        author_session -> Nullable<Text>,
     }
 }
 
 joinable!(place_rating -> place (parent_rowid));
+joinable!(place_rating -> users (created_by));
 
 table! {
     place_rating_comment (rowid) {
@@ -178,10 +333,28 @@ table! {
         archived_by -> Nullable<BigInt>,
         id -> Text,
         text -> Text,
+        reply_to -> Nullable<BigInt>,
+        created_by_org -> Nullable<BigInt>,
+        response -> Bool,
     }
 }
 
 joinable!(place_rating_comment -> place_rating (parent_rowid));
+joinable!(place_rating_comment -> users (created_by));
+joinable!(place_rating_comment -> organization (created_by_org));
+
+table! {
+    place_rating_flag (rowid) {
+        rowid -> BigInt,
+        parent_rowid -> BigInt,
+        created_at -> BigInt,
+        created_by -> Nullable<BigInt>,
+        comment -> Nullable<Text>,
+    }
+}
+
+joinable!(place_rating_flag -> place_rating (parent_rowid));
+joinable!(place_rating_flag -> users (created_by));
 
 ///////////////////////////////////////////////////////////////////////
 // Events
@@ -248,15 +421,77 @@ table! {
 
 joinable!(bbox_subscriptions -> users (user_id));
 
+table! {
+    image (rowid) {
+        rowid -> BigInt,
+        id -> Text,
+        format -> Text,
+        width -> BigInt,
+        height -> BigInt,
+        license -> Text,
+        attribution -> Nullable<Text>,
+        created_at -> BigInt,
+        created_by -> Nullable<BigInt>,
+    }
+}
+
+joinable!(image -> users (created_by));
+
+table! {
+    link_check (rowid) {
+        rowid -> BigInt,
+        owner_kind -> Text,
+        owner_id -> Text,
+        url -> Text,
+        status -> Text,
+        http_status -> Nullable<Integer>,
+        redirect_url -> Nullable<Text>,
+        checked_at -> BigInt,
+    }
+}
+
+table! {
+    saved_search (rowid) {
+        rowid -> BigInt,
+        id -> Text,
+        user_id -> BigInt,
+        name -> Text,
+        south_west_lat -> Double,
+        south_west_lng -> Double,
+        north_east_lat -> Double,
+        north_east_lng -> Double,
+        // comma-separated list of category ids
+        categories -> Text,
+        // comma-separated list of tags
+        hash_tags -> Text,
+        text -> Nullable<Text>,
+        org_tag -> Nullable<Text>,
+        alert -> Bool,
+        created_at -> BigInt,
+    }
+}
+
+joinable!(saved_search -> users (user_id));
+
 ///////////////////////////////////////////////////////////////////////
 
 allow_tables_to_appear_in_same_query!(
+    anonymized_users,
+    audit_log,
     bbox_subscriptions,
+    captcha,
+    category,
+    category_name,
     events,
     event_tags,
+    image,
+    link_check,
     place,
+    place_edit_suggestion,
+    place_owner,
     place_rating,
     place_rating_comment,
+    place_rating_flag,
     place_revision,
     place_revision_review,
     place_revision_tag,
@@ -264,7 +499,13 @@ allow_tables_to_appear_in_same_query!(
     organization,
     organization_tag,
     organization_place_clearance,
+    organization_event_clearance,
+    saved_search,
+    tag_description,
+    tag_synonym,
+    tag_taxonomy,
     tags,
+    user_regions,
     users,
     user_tokens,
 );
@@ -166,6 +166,266 @@ pub(crate) fn event_from_event_entity_and_tags(e: EventEntity, tag_rels: &[Event
     }
 }
 
+pub(crate) fn event_snapshot_from_new_event(
+    new_event: NewEvent,
+    created_by_email: Option<String>,
+    tags: Vec<String>,
+) -> EventSnapshot {
+    let NewEvent {
+        title,
+        description,
+        start,
+        end,
+        lat,
+        lng,
+        street,
+        zip,
+        city,
+        country,
+        state,
+        email,
+        telephone,
+        homepage,
+        registration,
+        organizer,
+        image_url,
+        image_link_url,
+        ..
+    } = new_event;
+    EventSnapshot {
+        title,
+        description,
+        start,
+        end,
+        lat,
+        lng,
+        street,
+        zip,
+        city,
+        country,
+        state,
+        email,
+        telephone,
+        homepage,
+        created_by_email,
+        registration,
+        organizer,
+        image_url,
+        image_link_url,
+        tags,
+    }
+}
+
+pub(crate) fn event_from_snapshot(uid: String, snapshot: EventSnapshot) -> e::Event {
+    let EventSnapshot {
+        title,
+        description,
+        start,
+        end,
+        lat,
+        lng,
+        street,
+        zip,
+        city,
+        country,
+        state,
+        email,
+        telephone,
+        homepage,
+        created_by_email,
+        registration,
+        organizer,
+        image_url,
+        image_link_url,
+        tags,
+    } = snapshot;
+    // The snapshot is not stored in the events table
+    let id = 0;
+    let entity = EventEntity {
+        id,
+        uid,
+        title,
+        description,
+        start,
+        end,
+        lat,
+        lng,
+        street,
+        zip,
+        city,
+        country,
+        state,
+        email,
+        telephone,
+        homepage,
+        created_by_id: None,
+        registration,
+        organizer,
+        archived: None,
+        image_url,
+        image_link_url,
+        created_by_email,
+    };
+    let tag_rels: Vec<_> = tags
+        .into_iter()
+        .map(|tag| EventTag { event_id: id, tag })
+        .collect();
+    event_from_event_entity_and_tags(entity, &tag_rels)
+}
+
+pub(crate) fn place_snapshot_from_place(place: e::Place) -> PlaceSnapshot {
+    let e::Place {
+        title,
+        description,
+        location: e::Location { pos, address },
+        contact,
+        opening_hours,
+        founded_on,
+        links,
+        tags,
+        ..
+    } = place;
+    let e::Address {
+        street,
+        zip,
+        city,
+        country,
+        state,
+    } = address.unwrap_or_default();
+    let e::Contact {
+        name: contact_name,
+        email,
+        phone,
+    } = contact.unwrap_or_default();
+    let e::Links {
+        homepage,
+        image,
+        image_href,
+        custom,
+    } = links.unwrap_or_default();
+    PlaceSnapshot {
+        title,
+        description,
+        lat: pos.lat().to_deg(),
+        lng: pos.lng().to_deg(),
+        street,
+        zip,
+        city,
+        country,
+        state,
+        contact_name,
+        email: email.map(Into::into),
+        phone,
+        homepage: homepage.map(|url| url.to_string()),
+        opening_hours: opening_hours.map(Into::into),
+        founded_on: founded_on.map(|d| d.to_string()),
+        image_url: image.map(|url| url.to_string()),
+        image_link_url: image_href.map(|url| url.to_string()),
+        tags,
+        custom_links: custom
+            .into_iter()
+            .map(
+                |e::CustomLink {
+                     url,
+                     title,
+                     description,
+                 }| CustomLinkSnapshot {
+                    url: url.to_string(),
+                    title,
+                    description,
+                },
+            )
+            .collect(),
+    }
+}
+
+pub(crate) fn place_from_snapshot(
+    id: String,
+    license: String,
+    revision: e::Revision,
+    created: e::Activity,
+    snapshot: PlaceSnapshot,
+) -> e::Place {
+    let PlaceSnapshot {
+        title,
+        description,
+        lat,
+        lng,
+        street,
+        zip,
+        city,
+        country,
+        state,
+        contact_name,
+        email,
+        phone,
+        homepage,
+        opening_hours,
+        founded_on,
+        image_url,
+        image_link_url,
+        tags,
+        custom_links,
+    } = snapshot;
+    let address = e::Address {
+        street,
+        zip,
+        city,
+        country,
+        state,
+    };
+    let links = e::Links {
+        homepage: homepage.and_then(load_url),
+        image: image_url.and_then(load_url),
+        image_href: image_link_url.and_then(load_url),
+        custom: custom_links
+            .into_iter()
+            .filter_map(
+                |CustomLinkSnapshot {
+                     url,
+                     title,
+                     description,
+                 }| {
+                    load_url(url).map(|url| e::CustomLink {
+                        url,
+                        title,
+                        description,
+                    })
+                },
+            )
+            .collect(),
+    };
+    let links_empty = links.homepage.is_none()
+        && links.image.is_none()
+        && links.image_href.is_none()
+        && links.custom.is_empty();
+    e::Place {
+        id: id.into(),
+        license,
+        revision,
+        created,
+        title,
+        description,
+        location: e::Location {
+            pos: MapPoint::try_from_lat_lng_deg(lat, lng).unwrap_or_default(),
+            address: if address.is_empty() {
+                None
+            } else {
+                Some(address)
+            },
+        },
+        contact: Some(e::Contact {
+            name: contact_name,
+            email: email.map(Into::into),
+            phone,
+        }),
+        opening_hours: opening_hours.map(Into::into),
+        founded_on: founded_on.and_then(|d| d.parse().ok()),
+        links: if links_empty { None } else { Some(links) },
+        tags,
+    }
+}
+
 impl From<Tag> for e::Tag {
     fn from(t: Tag) -> e::Tag {
         e::Tag { id: t.id }
@@ -178,6 +438,20 @@ impl From<e::Tag> for Tag {
     }
 }
 
+impl From<TagSynonym> for e::TagSynonym {
+    fn from(from: TagSynonym) -> Self {
+        let TagSynonym { synonym, tag } = from;
+        Self { synonym, tag }
+    }
+}
+
+impl From<TaxonomyTag> for e::TaxonomyTag {
+    fn from(from: TaxonomyTag) -> Self {
+        let TaxonomyTag { tag, parent } = from;
+        Self { tag, parent }
+    }
+}
+
 impl<'a> From<&'a e::User> for NewUser<'a> {
     fn from(u: &'a e::User) -> NewUser<'a> {
         use num_traits::ToPrimitive;
@@ -227,14 +501,25 @@ impl From<PlaceRatingComment> for e::Comment {
             created_at,
             archived_at,
             text,
+            response,
+            created_by_email,
+            created_by_org_id,
             ..
         } = c;
+        let author = created_by_email
+            .map(|email| e::CommentAuthor::User(email.into()))
+            .or_else(|| created_by_org_id.map(|id| e::CommentAuthor::Organization(id.into())));
         Self {
             id: id.into(),
             rating_id: rating_id.into(),
             created_at: Timestamp::from_inner(created_at),
             archived_at: archived_at.map(Timestamp::from_inner),
             text,
+            author,
+            // The parent comment is referenced by its rowid and
+            // needs to be resolved separately
+            reply_to: None,
+            is_response: response,
         }
     }
 }
@@ -250,8 +535,13 @@ impl From<PlaceRating> for e::Rating {
             context,
             value,
             source,
+            author_session,
+            created_by_email,
             ..
         } = r;
+        let author = created_by_email
+            .map(|email| e::RatingAuthor::User(email.into()))
+            .or_else(|| author_session.map(e::RatingAuthor::AnonymousSession));
         Self {
             id: id.into(),
             place_id: place_id.into(),
@@ -261,6 +551,7 @@ impl From<PlaceRating> for e::Rating {
             value: (value as i8).into(),
             context: rating_context_from_str(&context).unwrap(),
             source,
+            author,
         }
     }
 }
@@ -289,6 +580,53 @@ impl From<BboxSubscriptionEntity> for e::BboxSubscription {
     }
 }
 
+impl<'a> From<&'a e::AnonymizedUser> for NewAnonymizedUser<'a> {
+    fn from(from: &'a e::AnonymizedUser) -> Self {
+        let e::AnonymizedUser {
+            pseudonym,
+            anonymized_at,
+            place_revisions,
+            place_reviews,
+            place_ratings,
+            place_rating_comments,
+            events,
+        } = from;
+        Self {
+            pseudonym,
+            anonymized_at: anonymized_at.into_inner(),
+            place_revisions: *place_revisions as i64,
+            place_reviews: *place_reviews as i64,
+            place_ratings: *place_ratings as i64,
+            place_rating_comments: *place_rating_comments as i64,
+            events: *events as i64,
+        }
+    }
+}
+
+impl From<AnonymizedUser> for e::AnonymizedUser {
+    fn from(from: AnonymizedUser) -> Self {
+        let AnonymizedUser {
+            rowid: _,
+            pseudonym,
+            anonymized_at,
+            place_revisions,
+            place_reviews,
+            place_ratings,
+            place_rating_comments,
+            events,
+        } = from;
+        Self {
+            pseudonym,
+            anonymized_at: e::TimestampMs::from_inner(anonymized_at),
+            place_revisions: place_revisions as u64,
+            place_reviews: place_reviews as u64,
+            place_ratings: place_ratings as u64,
+            place_rating_comments: place_rating_comments as u64,
+            events: events as u64,
+        }
+    }
+}
+
 impl From<UserTokenEntity> for e::UserToken {
     fn from(from: UserTokenEntity) -> Self {
         Self {
@@ -297,6 +635,62 @@ impl From<UserTokenEntity> for e::UserToken {
                 nonce: from.nonce.parse::<Nonce>().unwrap_or_default(),
             },
             expires_at: Timestamp::from_inner(from.expires_at),
+            new_email: from.new_email,
+            place_id: from.place_id,
+        }
+    }
+}
+
+pub(crate) fn captcha_kind_into_i16(kind: e::CaptchaKind) -> i16 {
+    match kind {
+        e::CaptchaKind::Image => 1,
+        e::CaptchaKind::Arithmetic => 2,
+    }
+}
+
+pub(crate) fn captcha_kind_from_i16(kind: i16) -> Option<e::CaptchaKind> {
+    match kind {
+        1 => Some(e::CaptchaKind::Image),
+        2 => Some(e::CaptchaKind::Arithmetic),
+        _ => {
+            log::error!("Invalid captcha kind: {}", kind);
+            None
+        }
+    }
+}
+
+impl<'a> From<(&'a Nonce, &'a e::Captcha)> for NewCaptcha {
+    fn from(from: (&'a Nonce, &'a e::Captcha)) -> Self {
+        let (nonce, captcha) = from;
+        let (kind, answer) = if let Some((kind, answer)) = &captcha.challenge {
+            (Some(captcha_kind_into_i16(*kind)), Some(answer.clone()))
+        } else {
+            (None, None)
+        };
+        Self {
+            nonce: nonce.to_string(),
+            created_at: captcha.created_at.into_inner(),
+            kind,
+            answer,
+        }
+    }
+}
+
+impl From<Captcha> for e::Captcha {
+    fn from(from: Captcha) -> Self {
+        let Captcha {
+            created_at,
+            kind,
+            answer,
+            ..
+        } = from;
+        let challenge = match (kind.and_then(captcha_kind_from_i16), answer) {
+            (Some(kind), Some(answer)) => Some((kind, answer)),
+            _ => None,
+        };
+        Self {
+            created_at: Timestamp::from_inner(created_at),
+            challenge,
         }
     }
 }
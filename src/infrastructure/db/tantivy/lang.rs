@@ -0,0 +1,149 @@
+//! Language-specific text analysis
+
+use crate::core::entities::Language;
+
+use strum::IntoEnumIterator;
+use tantivy::tokenizer::{
+    AsciiFoldingFilter, Language as StemmerLanguage, LowerCaser, RemoveLongFilter, SimpleTokenizer,
+    Stemmer, StopWordFilter, TextAnalyzer,
+};
+
+use super::MAX_TOKEN_LEN;
+
+const GERMAN_STOP_WORDS: &[&str] = &[
+    "aber", "alle", "als", "also", "am", "an", "auch", "auf", "aus", "bei", "bin", "bis", "bist",
+    "da", "damit", "dann", "das", "dass", "dem", "den", "der", "des", "die", "dies", "diese",
+    "doch", "du", "durch", "ein", "eine", "einem", "einen", "einer", "eines", "er", "es", "für",
+    "hat", "hier", "ich", "ihr", "im", "in", "ist", "ja", "kann", "mit", "nach", "nicht", "noch",
+    "nur", "oder", "sich", "sie", "sind", "so", "über", "um", "und", "uns", "unser", "unsere",
+    "vom", "von", "vor", "war", "was", "wie", "wir", "wird", "zu", "zum", "zur",
+];
+
+const ENGLISH_STOP_WORDS: &[&str] = &[
+    "a", "about", "all", "also", "an", "and", "are", "as", "at", "be", "but", "by", "can", "for",
+    "from", "has", "have", "if", "in", "into", "is", "it", "its", "no", "not", "of", "on", "or",
+    "our", "so", "such", "than", "that", "the", "their", "them", "then", "there", "these", "they",
+    "this", "to", "was", "we", "were", "which", "will", "with", "you", "your",
+];
+
+fn stop_words(lang: Language) -> &'static [&'static str] {
+    match lang {
+        Language::German => GERMAN_STOP_WORDS,
+        Language::English => ENGLISH_STOP_WORDS,
+    }
+}
+
+fn stemmer_language(lang: Language) -> StemmerLanguage {
+    match lang {
+        Language::German => StemmerLanguage::German,
+        Language::English => StemmerLanguage::English,
+    }
+}
+
+pub fn tokenizer_name(lang: Language) -> &'static str {
+    match lang {
+        Language::German => "text_de",
+        Language::English => "text_en",
+    }
+}
+
+/// Text analysis without any knowledge about the language.
+///
+/// Umlauts and accents are folded into their ASCII
+/// counterparts, e.g. "Gärten" becomes "garten".
+pub fn generic_text_analyzer() -> TextAnalyzer {
+    TextAnalyzer::from(SimpleTokenizer)
+        .filter(RemoveLongFilter::limit(MAX_TOKEN_LEN))
+        .filter(LowerCaser)
+        .filter(AsciiFoldingFilter)
+}
+
+/// Text analysis with stop words and stemming.
+///
+/// Stemming is applied before folding umlauts and
+/// accents, because the stemmers depend on them.
+pub fn text_analyzer(lang: Language) -> TextAnalyzer {
+    let stop_words = stop_words(lang).iter().map(|w| (*w).to_owned()).collect();
+    TextAnalyzer::from(SimpleTokenizer)
+        .filter(RemoveLongFilter::limit(MAX_TOKEN_LEN))
+        .filter(LowerCaser)
+        .filter(StopWordFilter::remove(stop_words))
+        .filter(Stemmer::new(stemmer_language(lang)))
+        .filter(AsciiFoldingFilter)
+}
+
+/// Guess the language of a text by counting stop words.
+///
+/// Returns `None` if no stop words are found or if the
+/// text contains equally many stop words of multiple
+/// languages.
+pub fn detect_language<'a>(texts: impl IntoIterator<Item = &'a str>) -> Option<Language> {
+    let mut counts: Vec<_> = Language::iter().map(|lang| (lang, 0usize)).collect();
+    for text in texts {
+        for word in text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+        {
+            let word = word.to_lowercase();
+            for (lang, count) in counts.iter_mut() {
+                if stop_words(*lang).contains(&word.as_str()) {
+                    *count += 1;
+                }
+            }
+        }
+    }
+    counts.sort_by(|(_, lhs), (_, rhs)| rhs.cmp(lhs));
+    match counts.as_slice() {
+        [(lang, first), (_, second), ..] if first > second => Some(*lang),
+        [(lang, first)] if *first > 0 => Some(*lang),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(mut analyzer: TextAnalyzer, text: &str) -> Vec<String> {
+        let mut tokens = vec![];
+        let mut stream = analyzer.token_stream(text);
+        while stream.advance() {
+            tokens.push(stream.token().text.clone());
+        }
+        tokens
+    }
+
+    #[test]
+    fn fold_umlauts_and_stem_german_words() {
+        assert_eq!(
+            tokens(text_analyzer(Language::German), "Gärten"),
+            tokens(text_analyzer(Language::German), "Garten")
+        );
+        assert_eq!(
+            tokens(text_analyzer(Language::German), "Bioläden"),
+            tokens(text_analyzer(Language::German), "Bioladen")
+        );
+        assert_eq!(vec!["garten"], tokens(generic_text_analyzer(), "Gärten"));
+    }
+
+    #[test]
+    fn remove_stop_words() {
+        assert_eq!(
+            tokens(text_analyzer(Language::English), "gardens"),
+            tokens(text_analyzer(Language::English), "the gardens of")
+        );
+    }
+
+    #[test]
+    fn detect_language_by_stop_words() {
+        assert_eq!(
+            Some(Language::German),
+            detect_language(vec!["Ein Laden für die ganze Familie"])
+        );
+        assert_eq!(
+            Some(Language::English),
+            detect_language(vec!["Organic food", "The shop of the town"])
+        );
+        assert_eq!(None, detect_language(vec!["Bioladen"]));
+    }
+}
@@ -1,36 +1,47 @@
+use self::tile_cache::TileCache;
 use crate::core::{
     db::{
         EventAndPlaceIndexer, EventIndexer, IdIndex, IdIndexer, IndexQuery, IndexQueryMode,
-        IndexedPlace, Indexer, PlaceIndex, PlaceIndexer,
+        IndexedPlace, IndexedPlaceCluster, IndexedPlaceFacets, Indexer, PlaceIndex, PlaceIndexer,
+        PlaceSortKey, PlaceSortOrder, PlaceSortPosition,
     },
     entities::{
-        Address, AvgRatingValue, AvgRatings, Category, Contact, Event, Id, Place, RatingContext,
-        ReviewStatus, ReviewStatusPrimitive,
+        Address, AvgRatingValue, AvgRatings, Category, Contact, Event, Id, Language, Place,
+        RatingContext, ReviewStatus, ReviewStatusPrimitive,
     },
     util::{
-        geo::{LatCoord, LngCoord, MapPoint},
-        time::Timestamp,
+        geo::{Distance, LatCoord, LngCoord, MapPoint},
+        time::{Timestamp, TimestampMs},
     },
 };
 
 use anyhow::{bail, Result as Fallible};
 use failure::Fail;
 use num_traits::ToPrimitive;
+use ofdb_core::tile::TileCoord;
 use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
     ops::Bound,
     path::Path,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, MutexGuard},
 };
 use strum::IntoEnumIterator;
 use tantivy::{
-    collector::TopDocs,
-    query::{BooleanQuery, Occur, Query, QueryParser, RangeQuery, TermQuery},
+    collector::{Collector, FacetCollector, FacetCounts, SegmentCollector, TopDocs},
+    fastfield::{BytesFastFieldReader, FastFieldReader},
+    query::{
+        BooleanQuery, FuzzyTermQuery, Occur, Query, QueryParser, RangeQuery, RegexQuery, TermQuery,
+    },
     schema::*,
-    tokenizer::{LowerCaser, RawTokenizer, RemoveLongFilter, SimpleTokenizer, TextAnalyzer},
+    tokenizer::{LowerCaser, RawTokenizer, RemoveLongFilter, TextAnalyzer},
     DocAddress, DocId, Document, Index, IndexReader, IndexWriter, ReloadPolicy, Score,
-    SegmentReader,
+    SegmentLocalId, SegmentReader, TantivyError,
 };
 
+mod lang;
+mod tile_cache;
+
 const OVERALL_INDEX_HEAP_SIZE_IN_BYTES: usize = 50_000_000;
 
 const PLACE_KIND_FLAG: i64 = 1;
@@ -45,6 +56,14 @@ fn get_category_kind_flag(category: &Category) -> i64 {
     }
 }
 
+fn known_categories(query: &IndexQuery) -> Vec<Category> {
+    if query.all_categories.is_empty() {
+        Category::built_in()
+    } else {
+        query.all_categories.clone()
+    }
+}
+
 // Shared fields for both places and events
 struct IndexedFields {
     kind: Field,
@@ -63,6 +82,8 @@ struct IndexedFields {
     address_state: Field,
     contact_name: Field,
     tag: Field,
+    // Single level facets of all tags for counting
+    tag_facet: Field,
     ratings_diversity: Field,
     ratings_fairness: Field,
     ratings_humanity: Field,
@@ -70,6 +91,20 @@ struct IndexedFields {
     ratings_solidarity: Field,
     ratings_transparency: Field,
     total_rating: Field,
+    changed_at: Field,
+    lang: Field,
+    // The id and the lowercase title of places as fast
+    // fields for sorting without loading stored documents
+    sort_id: Field,
+    sort_title: Field,
+    german: LanguageFields,
+    english: LanguageFields,
+}
+
+// Text fields that are analyzed for a specific language
+struct LanguageFields {
+    title: Field,
+    description: Field,
 }
 
 impl IndexedFields {
@@ -97,13 +132,31 @@ impl IndexedFields {
         // Text fields that are returned as part of the search result
         // additionally need to be stored explicitly
         let stored_text_options = indexed_text_options.clone().set_stored();
+        let lang_options = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default()
+                .set_tokenizer(ID_TOKENIZER)
+                .set_index_option(IndexRecordOption::Basic),
+        );
         let mut schema_builder = SchemaBuilder::default();
+        let mut add_language_fields = |lang| {
+            let options = TextOptions::default().set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_tokenizer(lang::tokenizer_name(lang))
+                    .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+            );
+            LanguageFields {
+                title: schema_builder.add_text_field(&format!("tit_{}", lang), options.clone()),
+                description: schema_builder.add_text_field(&format!("dsc_{}", lang), options),
+            }
+        };
+        let german = add_language_fields(Language::German);
+        let english = add_language_fields(Language::English);
         let fields = Self {
             kind: schema_builder.add_i64_field("kind", INDEXED),
             id: schema_builder.add_text_field("id", id_options),
-            status: schema_builder.add_i64_field("status", INDEXED | STORED),
-            lat: schema_builder.add_f64_field("lat", INDEXED | STORED),
-            lng: schema_builder.add_f64_field("lon", INDEXED | STORED),
+            status: schema_builder.add_i64_field("status", INDEXED | STORED | FAST),
+            lat: schema_builder.add_f64_field("lat", INDEXED | STORED | FAST),
+            lng: schema_builder.add_f64_field("lon", INDEXED | STORED | FAST),
             ts_min: schema_builder.add_i64_field("ts_min", INDEXED | STORED),
             ts_max: schema_builder.add_i64_field("ts_max", INDEXED | STORED),
             title: schema_builder.add_text_field("tit", stored_text_options.clone()),
@@ -117,6 +170,7 @@ impl IndexedFields {
                 .add_text_field("adr_country", indexed_text_options.clone()),
             address_state: schema_builder.add_text_field("adr_state", indexed_text_options),
             tag: schema_builder.add_text_field("tag", tag_options),
+            tag_facet: schema_builder.add_facet_field("tag_facet"),
             ratings_diversity: schema_builder.add_f64_field("rat_diversity", STORED),
             ratings_fairness: schema_builder.add_f64_field("rat_fairness", STORED),
             ratings_humanity: schema_builder.add_f64_field("rat_humanity", STORED),
@@ -124,10 +178,43 @@ impl IndexedFields {
             ratings_solidarity: schema_builder.add_f64_field("rat_solidarity", STORED),
             ratings_transparency: schema_builder.add_f64_field("rat_transparency", STORED),
             total_rating: schema_builder.add_u64_field("rat_total", STORED | FAST),
+            changed_at: schema_builder.add_i64_field("changed_at", STORED | FAST),
+            lang: schema_builder.add_text_field("lang", lang_options),
+            sort_id: schema_builder.add_bytes_field("sort_id"),
+            sort_title: schema_builder.add_bytes_field("sort_title"),
+            german,
+            english,
         };
         (fields, schema_builder.build())
     }
 
+    fn language_fields(&self, lang: Language) -> &LanguageFields {
+        match lang {
+            Language::German => &self.german,
+            Language::English => &self.english,
+        }
+    }
+
+    // The title and description are additionally analyzed for the
+    // detected language or for all languages if the detection fails.
+    fn add_language_texts(&self, doc: &mut Document, title: &str, description: Option<&str>) {
+        let detected = lang::detect_language(std::iter::once(title).chain(description));
+        let languages: Vec<_> = match detected {
+            Some(lang) => {
+                doc.add_text(self.lang, &lang.to_string());
+                vec![lang]
+            }
+            None => Language::iter().collect(),
+        };
+        for lang in languages {
+            let fields = self.language_fields(lang);
+            doc.add_text(fields.title, title);
+            if let Some(description) = description {
+                doc.add_text(fields.description, description);
+            }
+        }
+    }
+
     fn read_indexed_place(&self, doc: &Document) -> IndexedPlace {
         let mut lat: Option<LatCoord> = Default::default();
         let mut lng: Option<LngCoord> = Default::default();
@@ -203,6 +290,13 @@ impl IndexedFields {
                     place.ratings.transparency = fv.value().f64_value().into();
                 }
                 fv if fv.field() == self.total_rating => (),
+                fv if fv.field() == self.tag_facet => (),
+                fv if fv.field() == self.sort_id => (),
+                fv if fv.field() == self.sort_title => (),
+                fv if fv.field() == self.changed_at => {
+                    debug_assert!(place.changed_at.is_none());
+                    place.changed_at = Some(TimestampMs::from_inner(fv.value().i64_value()));
+                }
                 // Address fields are currently not stored
                 //fv if fv.field() == self.address_street => (),
                 //fv if fv.field() == self.address_city => (),
@@ -228,6 +322,7 @@ pub(crate) struct TantivyIndex {
     index_reader: IndexReader,
     index_writer: IndexWriter,
     text_query_parser: QueryParser,
+    language_query_parsers: HashMap<Language, QueryParser>,
 }
 
 const ID_TOKENIZER: &str = "raw";
@@ -236,6 +331,32 @@ const TEXT_TOKENIZER: &str = "default";
 
 const MAX_TOKEN_LEN: usize = 40;
 
+// Longer words in the query text may contain more typos
+fn fuzzy_distance(token: &str) -> u8 {
+    match token.chars().count() {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
+}
+
+// Required or excluded terms and phrases must match exactly
+fn has_query_operators(text: &str) -> bool {
+    text.contains('"')
+        || text
+            .split_whitespace()
+            .any(|word| word.starts_with('+') || word.starts_with('-'))
+}
+
+fn analyze_text(text: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut stream = lang::generic_text_analyzer().token_stream(text);
+    while stream.advance() {
+        tokens.push(stream.token().text.clone());
+    }
+    tokens
+}
+
 fn register_tokenizers(index: &Index) {
     // Predefined tokenizers
     debug_assert!(index.tokenizers().get(ID_TOKENIZER).is_some());
@@ -246,10 +367,14 @@ fn register_tokenizers(index: &Index) {
         .filter(LowerCaser)
         .filter(RemoveLongFilter::limit(MAX_TOKEN_LEN));
     index.tokenizers().register(TAG_TOKENIZER, tag_tokenizer);
-    let text_tokenizer = TextAnalyzer::from(SimpleTokenizer)
-        .filter(LowerCaser)
-        .filter(RemoveLongFilter::limit(MAX_TOKEN_LEN));
-    index.tokenizers().register(TEXT_TOKENIZER, text_tokenizer);
+    index
+        .tokenizers()
+        .register(TEXT_TOKENIZER, lang::generic_text_analyzer());
+    for lang in Language::iter() {
+        index
+            .tokenizers()
+            .register(lang::tokenizer_name(lang), lang::text_analyzer(lang));
+    }
 }
 
 fn f64_to_u64(val: f64, min: f64, max: f64) -> u64 {
@@ -295,6 +420,28 @@ fn u64_to_avg_rating(val: u64) -> AvgRatingValue {
     .into()
 }
 
+fn boost_score_by_rating(original_score: Score, total_rating: AvgRatingValue) -> Score {
+    let total_rating = f64::from(total_rating);
+    let boost_factor = if total_rating < f64::from(AvgRatingValue::default()) {
+        // Negative ratings result in a boost factor < 1
+        (total_rating - f64::from(AvgRatingValue::min()))
+            / (f64::from(AvgRatingValue::default()) - f64::from(AvgRatingValue::min()))
+    } else {
+        // Default rating results in a boost factor of 1
+        // Positive ratings result in a boost factor > 1
+        // The total rating is scaled by the number of different rating context
+        // variants to achieve better results by emphasizing the rating factor.
+        1.0 + f64::from(RatingContext::total_count())
+            * (total_rating - f64::from(AvgRatingValue::default()))
+    };
+    // Transform the original score by log2() to narrow the range. Otherwise
+    // the rating boost factor is not powerful enough to promote highly
+    // rated entries over entries that received a much higher score.
+    debug_assert!(original_score >= 0.0);
+    let unboosted_score = (1.0 + original_score).log2();
+    unboosted_score * (boost_factor as f32)
+}
+
 #[derive(Copy, Clone, Debug)]
 enum TopDocsMode {
     Score,
@@ -338,27 +485,96 @@ impl TantivyIndex {
         let index_writer = index
             .writer(OVERALL_INDEX_HEAP_SIZE_IN_BYTES)
             .map_err(Fail::compat)?;
-        let text_query_parser = QueryParser::for_index(
-            &index,
-            vec![
-                fields.title,
-                fields.description,
-                fields.address_street,
-                fields.address_city,
-                fields.address_zip,
-                fields.address_country,
-                fields.address_state,
-                fields.contact_name,
-            ],
-        );
+        let generic_text_fields = vec![
+            fields.title,
+            fields.description,
+            fields.address_street,
+            fields.address_city,
+            fields.address_zip,
+            fields.address_country,
+            fields.address_state,
+            fields.contact_name,
+        ];
+        let language_query_parsers = Language::iter()
+            .map(|lang| {
+                let language_fields = fields.language_fields(lang);
+                let mut text_fields = generic_text_fields.clone();
+                text_fields.push(language_fields.title);
+                text_fields.push(language_fields.description);
+                (lang, QueryParser::for_index(&index, text_fields))
+            })
+            .collect();
+        let mut all_text_fields = generic_text_fields;
+        for lang in Language::iter() {
+            let language_fields = fields.language_fields(lang);
+            all_text_fields.push(language_fields.title);
+            all_text_fields.push(language_fields.description);
+        }
+        let text_query_parser = QueryParser::for_index(&index, all_text_fields);
         Ok(Self {
             fields,
             index_reader,
             index_writer,
             text_query_parser,
+            language_query_parsers,
         })
     }
 
+    fn build_fuzzy_text_query(&self, text: &str) -> Option<Box<dyn Query>> {
+        let mut queries: Vec<(Occur, Box<dyn Query>)> = vec![];
+        for token in analyze_text(text) {
+            let distance = fuzzy_distance(&token);
+            if distance == 0 {
+                continue;
+            }
+            for field in &[self.fields.title, self.fields.tag] {
+                let term = Term::from_field_text(*field, &token);
+                let query = FuzzyTermQuery::new(term, distance, true);
+                queries.push((Occur::Should, Box::new(query)));
+            }
+        }
+        if queries.is_empty() {
+            None
+        } else {
+            Some(Box::new(BooleanQuery::from(queries)))
+        }
+    }
+
+    fn build_prefix_query(&self, prefix: &str) -> Option<Box<dyn Query>> {
+        let mut tokens = analyze_text(prefix);
+        let last_token = tokens.pop()?;
+        // All preceding words of the title are complete
+        let mut title_queries: Vec<(Occur, Box<dyn Query>)> = tokens
+            .into_iter()
+            .map(|token| {
+                let term = Term::from_field_text(self.fields.title, &token);
+                let query: Box<dyn Query> =
+                    Box::new(TermQuery::new(term, IndexRecordOption::Basic));
+                (Occur::Must, query)
+            })
+            .collect();
+        let last_token_pattern = format!("{}.*", regex::escape(&last_token));
+        let tag_pattern = format!("{}.*", regex::escape(&prefix.trim().to_lowercase()));
+        let (last_token_query, tag_query) = match (
+            RegexQuery::from_pattern(&last_token_pattern, self.fields.title),
+            RegexQuery::from_pattern(&tag_pattern, self.fields.tag),
+        ) {
+            (Ok(last_token_query), Ok(tag_query)) => (last_token_query, tag_query),
+            (Err(err), _) | (_, Err(err)) => {
+                warn!("Failed to build query for prefix '{}': {:?}", prefix, err);
+                return None;
+            }
+        };
+        title_queries.push((Occur::Must, Box::new(last_token_query)));
+        Some(Box::new(BooleanQuery::from(vec![
+            (
+                Occur::Should,
+                Box::new(BooleanQuery::from(title_queries)) as Box<dyn Query>,
+            ),
+            (Occur::Should, Box::new(tag_query)),
+        ])))
+    }
+
     fn build_query(
         &self,
         query_mode: IndexQueryMode,
@@ -520,7 +736,9 @@ impl TantivyIndex {
             }
         }
 
-        let merged_tags = Category::merge_ids_into_tags(
+        let all_categories = known_categories(query);
+        let merged_tags = Category::merge_ids_into_tags_with(
+            &all_categories,
             &query
                 .categories
                 .iter()
@@ -528,7 +746,7 @@ impl TantivyIndex {
                 .collect::<Vec<_>>(),
             query.hash_tags.clone(),
         );
-        let (tags, categories) = Category::split_from_tags(merged_tags);
+        let (tags, categories) = Category::split_from_tags_with(&all_categories, merged_tags);
 
         // Categories (= mapped to predefined tags + separate sub-query + kind)
         let mut kinds_mask = 0i64;
@@ -591,9 +809,29 @@ impl TantivyIndex {
         for tag in &tags {
             debug!("Query hash tag (mandatory): {}", tag);
             debug_assert!(!tag.trim().is_empty());
-            let tag_term = Term::from_field_text(self.fields.tag, &tag.to_lowercase());
-            let tag_query = TermQuery::new(tag_term, IndexRecordOption::Basic);
-            sub_queries.push((Occur::Must, Box::new(tag_query)));
+            let tag = tag.to_lowercase();
+            let tag_term = Term::from_field_text(self.fields.tag, &tag);
+            let tag_query: Box<dyn Query> = match query.hash_tag_alternatives.get(&tag) {
+                Some(alternatives) if !alternatives.is_empty() => {
+                    debug!("Query alternatives of hash tag {}: {:?}", tag, alternatives);
+                    let mut tag_terms = Vec::with_capacity(1 + alternatives.len());
+                    tag_terms.push(tag_term);
+                    for alternative in alternatives {
+                        tag_terms.push(Term::from_field_text(self.fields.tag, alternative));
+                    }
+                    Box::new(BooleanQuery::new_multiterms_query(tag_terms))
+                }
+                _ => Box::new(TermQuery::new(tag_term, IndexRecordOption::Basic)),
+            };
+            sub_queries.push((Occur::Must, tag_query));
+        }
+
+        // Prefix (mandatory)
+        if let Some(prefix) = &query.prefix {
+            debug!("Query prefix: {}", prefix);
+            if let Some(prefix_query) = self.build_prefix_query(prefix) {
+                sub_queries.push((Occur::Must, prefix_query));
+            }
         }
 
         let mut text_and_tags_queries: Vec<(Occur, Box<dyn Query>)> =
@@ -604,8 +842,24 @@ impl TantivyIndex {
             debug!("Query text: {}", text);
             debug_assert!(!text.trim().is_empty());
             let text = text.to_lowercase();
-            match self.text_query_parser.parse_query(&text) {
+            let text_query_parser = query
+                .lang
+                .and_then(|lang| self.language_query_parsers.get(&lang))
+                .unwrap_or(&self.text_query_parser);
+            let fuzzy_text_query = if has_query_operators(&text) {
+                None
+            } else {
+                self.build_fuzzy_text_query(&text)
+            };
+            match text_query_parser.parse_query(&text) {
                 Ok(text_query) => {
+                    let text_query: Box<dyn Query> = match fuzzy_text_query {
+                        Some(fuzzy_text_query) => Box::new(BooleanQuery::from(vec![
+                            (Occur::Should, text_query),
+                            (Occur::Should, fuzzy_text_query),
+                        ])),
+                        None => text_query,
+                    };
                     if query.hash_tags.is_empty() && query.text_tags.is_empty() {
                         sub_queries.push((Occur::Must, Box::new(text_query)));
                     } else {
@@ -702,10 +956,10 @@ impl TantivyIndex {
                 let top_docs = searcher
                     .search(&search_query, &collector)
                     .map_err(Fail::compat)?;
-                for (_, doc_addr) in top_docs {
+                for (score, doc_addr) in top_docs {
                     match searcher.doc(doc_addr) {
                         Ok(doc) => {
-                            doc_collector.collect_document(doc_addr, doc);
+                            doc_collector.collect_document(doc_addr, score.into(), doc);
                         }
                         Err(err) => {
                             warn!("Failed to load document {:?}: {}", doc_addr, err);
@@ -723,10 +977,11 @@ impl TantivyIndex {
                 let top_docs = searcher
                     .search(&search_query, &collector)
                     .map_err(Fail::compat)?;
-                for (_, doc_addr) in top_docs {
+                for (total_rating, doc_addr) in top_docs {
+                    let score = u64_to_avg_rating(total_rating).into();
                     match searcher.doc(doc_addr) {
                         Ok(doc) => {
-                            doc_collector.collect_document(doc_addr, doc);
+                            doc_collector.collect_document(doc_addr, score, doc);
                         }
                         Err(err) => {
                             warn!("Failed to load document {:?}: {}", doc_addr, err);
@@ -745,38 +1000,20 @@ impl TantivyIndex {
                             .unwrap();
 
                         move |doc: DocId, original_score: Score| {
-                            let total_rating =
-                                f64::from(u64_to_avg_rating(total_rating_reader.get(doc)));
-                            let boost_factor =
-                                if total_rating < f64::from(AvgRatingValue::default()) {
-                                    // Negative ratings result in a boost factor < 1
-                                    (total_rating - f64::from(AvgRatingValue::min()))
-                                        / (f64::from(AvgRatingValue::default())
-                                            - f64::from(AvgRatingValue::min()))
-                                } else {
-                                    // Default rating results in a boost factor of 1
-                                    // Positive ratings result in a boost factor > 1
-                                    // The total rating is scaled by the number of different rating context
-                                    // variants to achieve better results by emphasizing the rating factor.
-                                    1.0 + f64::from(RatingContext::total_count())
-                                        * (total_rating - f64::from(AvgRatingValue::default()))
-                                };
-                            // Transform the original score by log2() to narrow the range. Otherwise
-                            // the rating boost factor is not powerful enough to promote highly
-                            // rated entries over entries that received a much higher score.
-                            debug_assert!(original_score >= 0.0);
-                            let unboosted_score = (1.0 + original_score).log2();
-                            unboosted_score * (boost_factor as f32)
+                            boost_score_by_rating(
+                                original_score,
+                                u64_to_avg_rating(total_rating_reader.get(doc)),
+                            )
                         }
                     })
                 };
                 let top_docs = searcher
                     .search(&search_query, &collector)
                     .map_err(Fail::compat)?;
-                for (_, doc_addr) in top_docs {
+                for (score, doc_addr) in top_docs {
                     match searcher.doc(doc_addr) {
                         Ok(doc) => {
-                            doc_collector.collect_document(doc_addr, doc);
+                            doc_collector.collect_document(doc_addr, score.into(), doc);
                         }
                         Err(err) => {
                             warn!("Failed to load document {:?}: {}", doc_addr, err);
@@ -790,7 +1027,7 @@ impl TantivyIndex {
 }
 
 trait DocumentCollector {
-    fn collect_document(&mut self, doc_addr: DocAddress, doc: Document);
+    fn collect_document(&mut self, doc_addr: DocAddress, score: f64, doc: Document);
 }
 
 struct IdCollector {
@@ -814,7 +1051,7 @@ impl From<IdCollector> for Vec<Id> {
 }
 
 impl DocumentCollector for IdCollector {
-    fn collect_document(&mut self, doc_addr: DocAddress, doc: Document) {
+    fn collect_document(&mut self, doc_addr: DocAddress, _score: f64, doc: Document) {
         if let Some(id) = doc.get_first(self.id_field).and_then(Value::text) {
             self.collected_ids.push(Id::from(id));
         } else {
@@ -847,9 +1084,356 @@ impl<'a> From<IndexedPlaceCollector<'a>> for Vec<IndexedPlace> {
 }
 
 impl<'a> DocumentCollector for IndexedPlaceCollector<'a> {
-    fn collect_document(&mut self, _doc_addr: DocAddress, doc: Document) {
-        self.collected_places
-            .push(self.fields.read_indexed_place(&doc));
+    fn collect_document(&mut self, _doc_addr: DocAddress, score: f64, doc: Document) {
+        let mut place = self.fields.read_indexed_place(&doc);
+        place.score = score;
+        self.collected_places.push(place);
+    }
+}
+
+fn fast_field_err(field: &str) -> TantivyError {
+    TantivyError::SchemaError(format!("{} is not a fast field", field))
+}
+
+// Counts the review status and the total rating of all
+// matching places by reading fast fields instead of
+// loading the stored documents.
+struct PlaceFacetsCollector {
+    status_field: Field,
+    total_rating_field: Field,
+}
+
+#[derive(Default)]
+struct PlaceFacetCounts {
+    total_count: u64,
+    status: BTreeMap<ReviewStatus, u64>,
+    ratings: BTreeMap<i64, u64>,
+}
+
+struct PlaceFacetsSegmentCollector {
+    status_reader: FastFieldReader<i64>,
+    total_rating_reader: FastFieldReader<u64>,
+    counts: PlaceFacetCounts,
+}
+
+impl Collector for PlaceFacetsCollector {
+    type Fruit = PlaceFacetCounts;
+    type Child = PlaceFacetsSegmentCollector;
+
+    fn for_segment(
+        &self,
+        _segment_local_id: SegmentLocalId,
+        segment: &SegmentReader,
+    ) -> tantivy::Result<Self::Child> {
+        let fast_fields = segment.fast_fields();
+        let status_reader = fast_fields
+            .i64(self.status_field)
+            .ok_or_else(|| fast_field_err("status"))?;
+        let total_rating_reader = fast_fields
+            .u64(self.total_rating_field)
+            .ok_or_else(|| fast_field_err("rat_total"))?;
+        Ok(PlaceFacetsSegmentCollector {
+            status_reader,
+            total_rating_reader,
+            counts: Default::default(),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(&self, segment_fruits: Vec<PlaceFacetCounts>) -> tantivy::Result<Self::Fruit> {
+        let mut merged = PlaceFacetCounts::default();
+        for counts in segment_fruits {
+            merged.total_count += counts.total_count;
+            for (status, count) in counts.status {
+                *merged.status.entry(status).or_insert(0) += count;
+            }
+            for (rating, count) in counts.ratings {
+                *merged.ratings.entry(rating).or_insert(0) += count;
+            }
+        }
+        Ok(merged)
+    }
+}
+
+impl SegmentCollector for PlaceFacetsSegmentCollector {
+    type Fruit = PlaceFacetCounts;
+
+    fn collect(&mut self, doc: DocId, _score: Score) {
+        let counts = &mut self.counts;
+        counts.total_count += 1;
+        let status = self.status_reader.get(doc) as ReviewStatusPrimitive;
+        if let Some(status) = ReviewStatus::try_from(status) {
+            *counts.status.entry(status).or_insert(0) += 1;
+        }
+        // Compensate the limited precision of the stored value
+        // to round down integral ratings correctly
+        let total_rating = f64::from(u64_to_avg_rating(self.total_rating_reader.get(doc)));
+        let rating = (total_rating + 1e-9).floor() as i64;
+        *counts.ratings.entry(rating).or_insert(0) += 1;
+    }
+
+    fn harvest(self) -> Self::Fruit {
+        self.counts
+    }
+}
+
+#[derive(Default)]
+struct PlaceClusterAccumulator {
+    count: u64,
+    lat_sum: f64,
+    lng_sum: f64,
+}
+
+// Aggregates all matching places into the cells of a
+// regular grid by reading their position from fast fields.
+struct PlaceClustersCollector {
+    lat_field: Field,
+    lng_field: Field,
+    cell_size: f64,
+}
+
+type GridCell = (i64, i64);
+
+struct PlaceClustersSegmentCollector {
+    lat_reader: FastFieldReader<f64>,
+    lng_reader: FastFieldReader<f64>,
+    cell_size: f64,
+    clusters: HashMap<GridCell, PlaceClusterAccumulator>,
+}
+
+impl Collector for PlaceClustersCollector {
+    type Fruit = HashMap<GridCell, PlaceClusterAccumulator>;
+    type Child = PlaceClustersSegmentCollector;
+
+    fn for_segment(
+        &self,
+        _segment_local_id: SegmentLocalId,
+        segment: &SegmentReader,
+    ) -> tantivy::Result<Self::Child> {
+        let fast_fields = segment.fast_fields();
+        let lat_reader = fast_fields
+            .f64(self.lat_field)
+            .ok_or_else(|| fast_field_err("lat"))?;
+        let lng_reader = fast_fields
+            .f64(self.lng_field)
+            .ok_or_else(|| fast_field_err("lon"))?;
+        Ok(PlaceClustersSegmentCollector {
+            lat_reader,
+            lng_reader,
+            cell_size: self.cell_size,
+            clusters: Default::default(),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_fruits: Vec<HashMap<GridCell, PlaceClusterAccumulator>>,
+    ) -> tantivy::Result<Self::Fruit> {
+        let mut merged: HashMap<GridCell, PlaceClusterAccumulator> = HashMap::new();
+        for clusters in segment_fruits {
+            for (cell, cluster) in clusters {
+                let merged_cluster = merged.entry(cell).or_default();
+                merged_cluster.count += cluster.count;
+                merged_cluster.lat_sum += cluster.lat_sum;
+                merged_cluster.lng_sum += cluster.lng_sum;
+            }
+        }
+        Ok(merged)
+    }
+}
+
+impl SegmentCollector for PlaceClustersSegmentCollector {
+    type Fruit = HashMap<GridCell, PlaceClusterAccumulator>;
+
+    fn collect(&mut self, doc: DocId, _score: Score) {
+        let pos = MapPoint::from_lat_lng_deg(self.lat_reader.get(doc), self.lng_reader.get(doc));
+        if !pos.is_valid() {
+            return;
+        }
+        let lat = pos.lat().to_deg();
+        let lng = pos.lng().to_deg();
+        let cell = (
+            (lat / self.cell_size).floor() as i64,
+            (lng / self.cell_size).floor() as i64,
+        );
+        let cluster = self.clusters.entry(cell).or_default();
+        cluster.count += 1;
+        cluster.lat_sum += lat;
+        cluster.lng_sum += lng;
+    }
+
+    fn harvest(self) -> Self::Fruit {
+        self.clusters
+    }
+}
+
+// Collects the first places in the requested order that are
+// positioned after the given position. The sort keys and ids
+// are read from fast fields, i.e. no stored documents are
+// loaded before the requested page has been selected.
+struct SortedPlacesCollector<'a> {
+    fields: &'a IndexedFields,
+    order: PlaceSortOrder,
+    top_docs_mode: TopDocsMode,
+    after: Option<&'a PlaceSortPosition>,
+    limit: usize,
+}
+
+enum SortKeyReader {
+    Rating(FastFieldReader<u64>),
+    Relevance(TopDocsMode, FastFieldReader<u64>),
+    Distance(MapPoint, FastFieldReader<f64>, FastFieldReader<f64>),
+    RecentlyChanged(FastFieldReader<i64>),
+    Title(BytesFastFieldReader),
+}
+
+impl SortKeyReader {
+    fn key(&self, doc: DocId, score: Score) -> PlaceSortKey {
+        match self {
+            Self::Rating(total_rating_reader) => {
+                PlaceSortKey::Number(-f64::from(u64_to_avg_rating(total_rating_reader.get(doc))))
+            }
+            Self::Relevance(top_docs_mode, total_rating_reader) => {
+                let total_rating = u64_to_avg_rating(total_rating_reader.get(doc));
+                let score = match top_docs_mode {
+                    TopDocsMode::Score => f64::from(score),
+                    TopDocsMode::Rating => f64::from(total_rating),
+                    TopDocsMode::ScoreBoostedByRating => {
+                        f64::from(boost_score_by_rating(score, total_rating))
+                    }
+                };
+                PlaceSortKey::Number(-score)
+            }
+            Self::Distance(center, lat_reader, lng_reader) => {
+                let pos = MapPoint::from_lat_lng_deg(lat_reader.get(doc), lng_reader.get(doc));
+                PlaceSortKey::Number(
+                    MapPoint::distance(*center, pos)
+                        .map(Distance::to_meters)
+                        .unwrap_or(std::f64::MAX),
+                )
+            }
+            Self::RecentlyChanged(changed_at_reader) => {
+                PlaceSortKey::Number(-(changed_at_reader.get(doc) as f64))
+            }
+            Self::Title(title_reader) => PlaceSortKey::Text(
+                String::from_utf8_lossy(title_reader.get_bytes(doc)).into_owned(),
+            ),
+        }
+    }
+}
+
+type SortedDocs = Vec<(PlaceSortPosition, DocAddress)>;
+
+fn sort_and_truncate_docs(docs: &mut SortedDocs, limit: usize) {
+    docs.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+    docs.truncate(limit);
+}
+
+struct SortedPlacesSegmentCollector {
+    segment_local_id: SegmentLocalId,
+    key_reader: SortKeyReader,
+    id_reader: BytesFastFieldReader,
+    after: Option<PlaceSortPosition>,
+    limit: usize,
+    docs: SortedDocs,
+}
+
+impl<'a> Collector for SortedPlacesCollector<'a> {
+    type Fruit = SortedDocs;
+    type Child = SortedPlacesSegmentCollector;
+
+    fn for_segment(
+        &self,
+        segment_local_id: SegmentLocalId,
+        segment: &SegmentReader,
+    ) -> tantivy::Result<Self::Child> {
+        let fast_fields = segment.fast_fields();
+        let total_rating_reader = || {
+            fast_fields
+                .u64(self.fields.total_rating)
+                .ok_or_else(|| fast_field_err("rat_total"))
+        };
+        let key_reader = match self.order {
+            PlaceSortOrder::Rating => SortKeyReader::Rating(total_rating_reader()?),
+            PlaceSortOrder::Relevance => {
+                SortKeyReader::Relevance(self.top_docs_mode, total_rating_reader()?)
+            }
+            PlaceSortOrder::Distance(center) => SortKeyReader::Distance(
+                center,
+                fast_fields
+                    .f64(self.fields.lat)
+                    .ok_or_else(|| fast_field_err("lat"))?,
+                fast_fields
+                    .f64(self.fields.lng)
+                    .ok_or_else(|| fast_field_err("lon"))?,
+            ),
+            PlaceSortOrder::RecentlyChanged => SortKeyReader::RecentlyChanged(
+                fast_fields
+                    .i64(self.fields.changed_at)
+                    .ok_or_else(|| fast_field_err("changed_at"))?,
+            ),
+            PlaceSortOrder::Title => SortKeyReader::Title(
+                fast_fields
+                    .bytes(self.fields.sort_title)
+                    .ok_or_else(|| fast_field_err("sort_title"))?,
+            ),
+        };
+        let id_reader = fast_fields
+            .bytes(self.fields.sort_id)
+            .ok_or_else(|| fast_field_err("sort_id"))?;
+        Ok(SortedPlacesSegmentCollector {
+            segment_local_id,
+            key_reader,
+            id_reader,
+            after: self.after.cloned(),
+            limit: self.limit,
+            docs: Vec::with_capacity(2 * self.limit),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        matches!(self.order, PlaceSortOrder::Relevance)
+    }
+
+    fn merge_fruits(&self, segment_fruits: Vec<SortedDocs>) -> tantivy::Result<Self::Fruit> {
+        let mut merged: SortedDocs = segment_fruits.into_iter().flatten().collect();
+        sort_and_truncate_docs(&mut merged, self.limit);
+        Ok(merged)
+    }
+}
+
+impl SegmentCollector for SortedPlacesSegmentCollector {
+    type Fruit = SortedDocs;
+
+    fn collect(&mut self, doc: DocId, score: Score) {
+        let position = PlaceSortPosition {
+            key: self.key_reader.key(doc, score),
+            id: String::from_utf8_lossy(self.id_reader.get_bytes(doc)).into_owned(),
+        };
+        if let Some(after) = &self.after {
+            if position.cmp(after) != Ordering::Greater {
+                return;
+            }
+        }
+        self.docs
+            .push((position, DocAddress(self.segment_local_id, doc)));
+        // Only keep the first places while collecting
+        // to limit the memory consumption
+        if self.docs.len() >= 2 * self.limit {
+            sort_and_truncate_docs(&mut self.docs, self.limit);
+        }
+    }
+
+    fn harvest(mut self) -> Self::Fruit {
+        sort_and_truncate_docs(&mut self.docs, self.limit);
+        self.docs
     }
 }
 
@@ -899,10 +1483,17 @@ impl PlaceIndexer for TantivyIndex {
             doc.add_i64(self.fields.status, status);
         }
         doc.add_text(self.fields.id, place.id.as_ref());
+        doc.add_bytes(self.fields.sort_id, place.id.as_str().as_bytes().to_vec());
+        doc.add_bytes(
+            self.fields.sort_title,
+            place.title.to_lowercase().into_bytes(),
+        );
         doc.add_f64(self.fields.lat, place.location.pos.lat().to_deg());
         doc.add_f64(self.fields.lng, place.location.pos.lng().to_deg());
         doc.add_text(self.fields.title, &place.title);
         doc.add_text(self.fields.description, &place.description);
+        self.fields
+            .add_language_texts(&mut doc, &place.title, Some(&place.description));
         if let Some(ref address) = place.location.address {
             let Address {
                 street,
@@ -935,8 +1526,13 @@ impl PlaceIndexer for TantivyIndex {
         }
         for tag in &place.tags {
             doc.add_text(self.fields.tag, tag);
+            doc.add_facet(
+                self.fields.tag_facet,
+                Facet::from_path(std::iter::once(tag)),
+            );
         }
         doc.add_u64(self.fields.total_rating, avg_rating_to_u64(ratings.total()));
+        doc.add_i64(self.fields.changed_at, place.created.at.into_inner());
         doc.add_f64(self.fields.ratings_diversity, ratings.diversity.into());
         doc.add_f64(self.fields.ratings_fairness, ratings.fairness.into());
         doc.add_f64(self.fields.ratings_humanity, ratings.humanity.into());
@@ -998,6 +1594,8 @@ impl EventIndexer for TantivyIndex {
         if let Some(ref description) = event.description {
             doc.add_text(self.fields.description, description);
         }
+        self.fields
+            .add_language_texts(&mut doc, &event.title, event.description.as_deref());
         if let Some(ref contact) = event.contact {
             let Contact { name, .. } = contact;
             if let Some(contact_name) = name {
@@ -1018,33 +1616,248 @@ impl PlaceIndex for TantivyIndex {
         self.query_documents(IndexQueryMode::WithRating, query, limit, collector)
             .map(Into::into)
     }
+
+    // Only the stored documents of the returned places are loaded
+    fn query_places_sorted(
+        &self,
+        query: &IndexQuery,
+        order: PlaceSortOrder,
+        after: Option<&PlaceSortPosition>,
+        limit: usize,
+    ) -> Fallible<Vec<(PlaceSortPosition, IndexedPlace)>> {
+        if limit == 0 {
+            bail!("Invalid limit: {}", limit);
+        }
+        let (search_query, top_docs_mode) = self.build_query(IndexQueryMode::WithRating, query);
+        let searcher = self.index_reader.searcher();
+        let collector = SortedPlacesCollector {
+            fields: &self.fields,
+            order,
+            top_docs_mode,
+            after,
+            limit,
+        };
+        let sorted_docs = searcher
+            .search(&search_query, &collector)
+            .map_err(Fail::compat)?;
+        let mut places = Vec::with_capacity(sorted_docs.len());
+        for (position, doc_addr) in sorted_docs {
+            match searcher.doc(doc_addr) {
+                Ok(doc) => {
+                    let mut place = self.fields.read_indexed_place(&doc);
+                    match (order, &position.key) {
+                        (PlaceSortOrder::Rating, PlaceSortKey::Number(key))
+                        | (PlaceSortOrder::Relevance, PlaceSortKey::Number(key)) => {
+                            place.score = -key;
+                        }
+                        _ => (),
+                    }
+                    places.push((position, place));
+                }
+                Err(err) => {
+                    warn!("Failed to load document {:?}: {}", doc_addr, err);
+                }
+            }
+        }
+        Ok(places)
+    }
+
+    // All matching places are counted without any limit
+    fn query_place_facets(&self, query: &IndexQuery) -> Fallible<IndexedPlaceFacets> {
+        let (search_query, _) = self.build_query(IndexQueryMode::WithoutRating, query);
+        let searcher = self.index_reader.searcher();
+        let mut tag_collector = FacetCollector::for_field(self.fields.tag_facet);
+        tag_collector.add_facet(Facet::root());
+        let counts_collector = PlaceFacetsCollector {
+            status_field: self.fields.status,
+            total_rating_field: self.fields.total_rating,
+        };
+        let (tag_counts, counts): (FacetCounts, PlaceFacetCounts) = searcher
+            .search(&search_query, &(tag_collector, counts_collector))
+            .map_err(Fail::compat)?;
+        let PlaceFacetCounts {
+            total_count,
+            status,
+            ratings,
+        } = counts;
+        let tags = tag_counts
+            .get(Facet::root())
+            // The encoded string of a single level facet is the tag
+            .map(|(facet, count)| (facet.encoded_str().to_owned(), count))
+            .collect();
+        Ok(IndexedPlaceFacets {
+            total_count,
+            tags,
+            status,
+            ratings,
+        })
+    }
+
+    // All matching places are aggregated without any limit.
+    // Places are counted per category by separate queries
+    // for the tag of each category.
+    fn query_place_clusters(
+        &self,
+        query: &IndexQuery,
+        cell_size: f64,
+    ) -> Fallible<Vec<IndexedPlaceCluster>> {
+        debug_assert!(cell_size > 0.0);
+        let (search_query, _) = self.build_query(IndexQueryMode::WithoutRating, query);
+        let searcher = self.index_reader.searcher();
+        let collector = PlaceClustersCollector {
+            lat_field: self.fields.lat,
+            lng_field: self.fields.lng,
+            cell_size,
+        };
+        let clusters = searcher
+            .search(&search_query, &collector)
+            .map_err(Fail::compat)?;
+        let mut category_counts: HashMap<GridCell, HashMap<String, u64>> = HashMap::new();
+        for category in known_categories(query) {
+            let tag_query = TermQuery::new(
+                Term::from_field_text(self.fields.tag, &category.tag),
+                IndexRecordOption::Basic,
+            );
+            let category_query = BooleanQuery::from(vec![
+                (
+                    Occur::Must,
+                    Box::new(search_query.clone()) as Box<dyn Query>,
+                ),
+                (Occur::Must, Box::new(tag_query) as Box<dyn Query>),
+            ]);
+            let category_clusters = searcher
+                .search(&category_query, &collector)
+                .map_err(Fail::compat)?;
+            for (cell, cluster) in category_clusters {
+                category_counts
+                    .entry(cell)
+                    .or_default()
+                    .insert(category.id.to_string(), cluster.count);
+            }
+        }
+        Ok(clusters
+            .into_iter()
+            .map(|(cell, cluster)| {
+                let PlaceClusterAccumulator {
+                    count,
+                    lat_sum,
+                    lng_sum,
+                } = cluster;
+                let count_f64 = count as f64;
+                IndexedPlaceCluster {
+                    count,
+                    centroid: MapPoint::from_lat_lng_deg(lat_sum / count_f64, lng_sum / count_f64),
+                    categories: category_counts.remove(&cell).unwrap_or_default(),
+                }
+            })
+            .collect())
+    }
 }
 
 impl EventAndPlaceIndexer for TantivyIndex {}
 
 #[derive(Clone)]
-pub struct SearchEngine(Arc<Mutex<Box<dyn EventAndPlaceIndexer + Send>>>);
+pub struct SearchEngine {
+    index: Arc<Mutex<Box<dyn EventAndPlaceIndexer + Send>>>,
+    tile_cache: Arc<Mutex<TileCache>>,
+}
 
 impl SearchEngine {
+    fn new(index: TantivyIndex) -> Self {
+        Self {
+            index: Arc::new(Mutex::new(Box::new(index))),
+            tile_cache: Default::default(),
+        }
+    }
+
     #[allow(dead_code)]
     pub fn init_in_ram() -> Fallible<SearchEngine> {
         let index = TantivyIndex::create_in_ram()?;
-        Ok(SearchEngine(Arc::new(Mutex::new(Box::new(index)))))
+        Ok(SearchEngine::new(index))
     }
 
     pub fn init_with_path<P: AsRef<Path>>(path: Option<P>) -> Fallible<SearchEngine> {
         let index = TantivyIndex::create(path)?;
-        Ok(SearchEngine(Arc::new(Mutex::new(Box::new(index)))))
+        Ok(SearchEngine::new(index))
+    }
+
+    fn lock_tile_cache(&self) -> MutexGuard<TileCache> {
+        match self.tile_cache.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Load an encoded map tile from the cache or create it.
+    ///
+    /// Cached tiles are invalidated when places within
+    /// the tile are modified.
+    pub fn get_or_create_tile<E>(
+        &self,
+        tile: TileCoord,
+        create: impl FnOnce() -> Result<Vec<u8>, E>,
+    ) -> Result<Arc<Vec<u8>>, E> {
+        let generation = {
+            let tile_cache = self.lock_tile_cache();
+            if let Some(bytes) = tile_cache.get(tile) {
+                return Ok(bytes);
+            }
+            tile_cache.generation()
+        };
+        // The cache must not be locked while creating the tile
+        let bytes = create()?;
+        Ok(self.lock_tile_cache().insert(tile, generation, bytes))
+    }
+
+    pub fn invalidate_all_tiles(&self) {
+        self.lock_tile_cache().invalidate_all();
+    }
+
+    /// Add or update a place while (re-)building the whole index.
+    ///
+    /// The previous position of the place is not looked up and
+    /// no tiles are invalidated. Instead all tiles must be
+    /// invalidated once after all places have been indexed.
+    pub fn add_or_update_place_in_bulk(
+        &self,
+        place: &Place,
+        status: ReviewStatus,
+        ratings: &AvgRatings,
+    ) -> Fallible<()> {
+        let inner = match self.index.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        inner.add_or_update_place(place, status, ratings)
+    }
+
+    // The position of a place as currently found in the index
+    fn indexed_place_pos(
+        inner: &(dyn EventAndPlaceIndexer + Send),
+        id: &str,
+    ) -> Fallible<Option<MapPoint>> {
+        let query = IndexQuery {
+            ids: vec![id],
+            ..Default::default()
+        };
+        Ok(inner
+            .query_places(&query, 1)?
+            .into_iter()
+            .next()
+            .map(|place| place.pos))
     }
 }
 
 impl Indexer for SearchEngine {
     fn flush_index(&mut self) -> Fallible<()> {
-        let mut inner = match self.0.lock() {
+        let mut inner = match self.index.lock() {
             Ok(guard) => guard,
             Err(poisoned) => poisoned.into_inner(),
         };
-        inner.flush_index()
+        inner.flush_index()?;
+        drop(inner);
+        self.lock_tile_cache().invalidate_flushed();
+        Ok(())
     }
 }
 
@@ -1055,7 +1868,7 @@ impl IdIndex for SearchEngine {
         query: &IndexQuery,
         limit: usize,
     ) -> Fallible<Vec<Id>> {
-        let inner = match self.0.lock() {
+        let inner = match self.index.lock() {
             Ok(guard) => guard,
             Err(poisoned) => poisoned.into_inner(),
         };
@@ -1065,22 +1878,58 @@ impl IdIndex for SearchEngine {
 
 impl IdIndexer for SearchEngine {
     fn remove_by_id(&self, id: &Id) -> Fallible<()> {
-        let inner = match self.0.lock() {
+        let inner = match self.index.lock() {
             Ok(guard) => guard,
             Err(poisoned) => poisoned.into_inner(),
         };
+        // Only events are removed from the index while places
+        // are archived, i.e. no tiles need to be invalidated
         inner.remove_by_id(id)
     }
 }
 
 impl PlaceIndex for SearchEngine {
     fn query_places(&self, query: &IndexQuery, limit: usize) -> Fallible<Vec<IndexedPlace>> {
-        let inner = match self.0.lock() {
+        let inner = match self.index.lock() {
             Ok(guard) => guard,
             Err(poisoned) => poisoned.into_inner(),
         };
         inner.query_places(query, limit)
     }
+
+    fn query_places_sorted(
+        &self,
+        query: &IndexQuery,
+        order: PlaceSortOrder,
+        after: Option<&PlaceSortPosition>,
+        limit: usize,
+    ) -> Fallible<Vec<(PlaceSortPosition, IndexedPlace)>> {
+        let inner = match self.index.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        inner.query_places_sorted(query, order, after, limit)
+    }
+
+    fn query_place_facets(&self, query: &IndexQuery) -> Fallible<IndexedPlaceFacets> {
+        let inner = match self.index.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        inner.query_place_facets(query)
+    }
+
+    fn query_place_clusters(
+        &self,
+        query: &IndexQuery,
+        cell_size: f64,
+    ) -> Fallible<Vec<IndexedPlaceCluster>> {
+        let inner = match self.index.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        inner.query_place_clusters(query, cell_size)
+    }
 }
 
 impl PlaceIndexer for SearchEngine {
@@ -1090,17 +1939,25 @@ impl PlaceIndexer for SearchEngine {
         status: ReviewStatus,
         ratings: &AvgRatings,
     ) -> Fallible<()> {
-        let inner = match self.0.lock() {
+        let inner = match self.index.lock() {
             Ok(guard) => guard,
             Err(poisoned) => poisoned.into_inner(),
         };
-        inner.add_or_update_place(place, status, ratings)
+        let old_pos = Self::indexed_place_pos(&**inner, place.id.as_ref())?;
+        inner.add_or_update_place(place, status, ratings)?;
+        drop(inner);
+        let mut tile_cache = self.lock_tile_cache();
+        if let Some(pos) = old_pos {
+            tile_cache.invalidate_modified(pos);
+        }
+        tile_cache.invalidate_modified(place.location.pos);
+        Ok(())
     }
 }
 
 impl EventIndexer for SearchEngine {
     fn add_or_update_event(&self, event: &Event) -> Fallible<()> {
-        let inner = match self.0.lock() {
+        let inner = match self.index.lock() {
             Ok(guard) => guard,
             Err(poisoned) => poisoned.into_inner(),
         };
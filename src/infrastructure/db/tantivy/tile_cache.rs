@@ -0,0 +1,105 @@
+//! Cache of encoded map tiles
+
+use crate::core::util::geo::MapPoint;
+use ofdb_core::tile::{self, TileCoord};
+
+use std::{collections::HashMap, sync::Arc};
+
+/// The cache is cleared when exceeding this number of tiles.
+const MAX_CACHED_TILES: usize = 10_000;
+
+#[derive(Default)]
+pub struct TileCache {
+    tiles: HashMap<TileCoord, Arc<Vec<u8>>>,
+    // Incremented on every invalidation to detect tiles that
+    // have been created concurrently from outdated data
+    generation: u64,
+    // Positions of modified places that only become visible
+    // after the index has been flushed
+    pending_positions: Vec<MapPoint>,
+}
+
+impl TileCache {
+    pub fn get(&self, tile: TileCoord) -> Option<Arc<Vec<u8>>> {
+        self.tiles.get(&tile).cloned()
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Only cache the tile if it has been created from
+    /// data of the current generation.
+    pub fn insert(&mut self, tile: TileCoord, generation: u64, bytes: Vec<u8>) -> Arc<Vec<u8>> {
+        let bytes = Arc::new(bytes);
+        if generation != self.generation {
+            return bytes;
+        }
+        if self.tiles.len() >= MAX_CACHED_TILES {
+            self.tiles.clear();
+        }
+        self.tiles.insert(tile, Arc::clone(&bytes));
+        bytes
+    }
+
+    fn invalidate(&mut self, pos: MapPoint) {
+        self.generation += 1;
+        if self.tiles.is_empty() {
+            return;
+        }
+        for z in 0..=tile::MAX_ZOOM {
+            for tile in TileCoord::covering(pos, z, tile::BUFFER) {
+                self.tiles.remove(&tile);
+            }
+        }
+    }
+
+    /// Invalidate all tiles that contain the position of a
+    /// modified place, both now and after the next flush.
+    pub fn invalidate_modified(&mut self, pos: MapPoint) {
+        self.invalidate(pos);
+        self.pending_positions.push(pos);
+    }
+
+    pub fn invalidate_flushed(&mut self) {
+        for pos in std::mem::take(&mut self.pending_positions) {
+            self.invalidate(pos);
+        }
+    }
+
+    /// Invalidate all tiles, e.g. after the categories
+    /// have been modified.
+    pub fn invalidate_all(&mut self) {
+        self.generation += 1;
+        self.tiles.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalidate_tiles_of_modified_places() {
+        let mut cache = TileCache::default();
+        let pos = MapPoint::from_lat_lng_deg(48.5, 9.0);
+        let tile = TileCoord::covering(pos, 12, 0.0)[0];
+        let other_tile = TileCoord::new(12, 0, 0).unwrap();
+        cache.insert(tile, cache.generation(), vec![1]);
+        cache.insert(other_tile, cache.generation(), vec![2]);
+        assert!(cache.get(tile).is_some());
+
+        let generation = cache.generation();
+        cache.invalidate_modified(pos);
+        assert!(cache.get(tile).is_none());
+        assert!(cache.get(other_tile).is_some());
+
+        // Created before the invalidation
+        cache.insert(tile, generation, vec![1]);
+        assert!(cache.get(tile).is_none());
+
+        cache.insert(tile, cache.generation(), vec![1]);
+        cache.invalidate_flushed();
+        assert!(cache.get(tile).is_none());
+    }
+}
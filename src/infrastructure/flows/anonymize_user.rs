@@ -0,0 +1,108 @@
+use super::*;
+use crate::core::error::Error;
+use diesel::connection::Connection;
+use ofdb_core::user::RolePermissions;
+
+pub fn anonymize_user(
+    connections: &sqlite::Connections,
+    permissions: &RolePermissions,
+    account_email: &str,
+    user_email: &str,
+) -> Result<AnonymizedUser> {
+    let mut rollback_err: Option<Error> = None;
+    let connection = connections.exclusive()?;
+    Ok(connection
+        .transaction::<_, diesel::result::Error, _>(|| {
+            usecases::anonymize_user(&*connection, permissions, account_email, user_email).map_err(
+                |err| {
+                    warn!("Failed to anonymize user '{}': {}", user_email, err);
+                    rollback_err = Some(err);
+                    diesel::result::Error::RollbackTransaction
+                },
+            )
+        })
+        .map_err(|err| rollback_err.unwrap_or_else(|| Error::from(RepoError::from(err))))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::prelude::*;
+    use crate::core::util::geo::MapBbox;
+
+    #[test]
+    fn should_keep_place_history_after_anonymizing_the_author() {
+        let fixture = BackendFixture::new();
+        fixture.create_user(
+            usecases::NewUser {
+                email: "author@some.org".to_string(),
+                password: "secret1".to_string(),
+            },
+            None,
+        );
+        fixture.create_user(
+            usecases::NewUser {
+                email: "admin@some.org".to_string(),
+                password: "secret1".to_string(),
+            },
+            Some(Role::Admin),
+        );
+        let place_id = fixture.create_place(0.into(), Some("author@some.org"));
+        assert!(usecases::subscribe_to_bbox(
+            &*fixture.db_connections.exclusive().unwrap(),
+            "author@some.org".to_string(),
+            MapBbox::new(
+                MapPoint::from_lat_lng_deg(0.0, 0.0),
+                MapPoint::from_lat_lng_deg(1.0, 1.0)
+            ),
+        )
+        .is_ok());
+
+        let anonymized_user = super::anonymize_user(
+            &fixture.db_connections,
+            &fixture.permissions,
+            "admin@some.org",
+            "author@some.org",
+        )
+        .unwrap();
+        assert_eq!(1, anonymized_user.place_revisions);
+        assert_eq!(0, anonymized_user.events);
+        assert!(fixture.try_get_user("author@some.org").is_none());
+        assert!(usecases::login_with_email(
+            &*fixture.db_connections.shared().unwrap(),
+            &usecases::Credentials {
+                email: "author@some.org",
+                password: "secret1",
+            }
+        )
+        .is_err());
+
+        // The history refers to the pseudonym
+        let (place, _) = fixture.try_get_place(&place_id).unwrap();
+        assert_eq!(
+            Some(Email::from(anonymized_user.pseudonym.clone())),
+            place.created.by
+        );
+        let db = fixture.db_connections.shared().unwrap();
+        assert!(db
+            .all_bbox_subscriptions()
+            .unwrap()
+            .iter()
+            .all(|s| s.user_email != anonymized_user.pseudonym));
+        assert_eq!(
+            vec![anonymized_user.clone()],
+            usecases::anonymized_users(&*db, &fixture.permissions, "admin@some.org").unwrap()
+        );
+
+        // The audit log doesn't reveal the e-mail address
+        let audit_log = db
+            .query_audit_log(&Default::default(), &Default::default())
+            .unwrap();
+        assert_eq!(1, audit_log.len());
+        assert_eq!(AuditAction::UserAnonymize, audit_log[0].action);
+        assert_eq!(anonymized_user.pseudonym, audit_log[0].entity.id);
+        assert_eq!(
+            Some(Email::from("admin@some.org")),
+            audit_log[0].log.activity.by
+        );
+    }
+}
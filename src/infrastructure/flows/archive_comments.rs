@@ -1,8 +1,10 @@
 use super::*;
 use diesel::connection::Connection;
+use ofdb_core::user::RolePermissions;
 
 pub fn archive_comments(
     connections: &sqlite::Connections,
+    permissions: &RolePermissions,
     account_email: &str,
     ids: &[&str],
 ) -> Result<usize> {
@@ -10,11 +12,13 @@ pub fn archive_comments(
     let connection = connections.exclusive()?;
     Ok(connection
         .transaction::<_, diesel::result::Error, _>(|| {
-            usecases::archive_comments(&*connection, account_email, ids).map_err(|err| {
-                warn!("Failed to archive {} comments: {}", ids.len(), err);
-                repo_err = Some(err);
-                diesel::result::Error::RollbackTransaction
-            })
+            usecases::archive_comments(&*connection, permissions, account_email, ids).map_err(
+                |err| {
+                    warn!("Failed to archive {} comments: {}", ids.len(), err);
+                    repo_err = Some(err);
+                    diesel::result::Error::RollbackTransaction
+                },
+            )
         })
         .map_err(|err| {
             if let Some(repo_err) = repo_err {
@@ -34,7 +38,12 @@ mod tests {
         account_email: &str,
         ids: &[&str],
     ) -> super::Result<usize> {
-        super::archive_comments(&fixture.db_connections, account_email, ids)
+        super::archive_comments(
+            &fixture.db_connections,
+            &fixture.permissions,
+            account_email,
+            ids,
+        )
     }
 
     #[test]
@@ -5,13 +5,13 @@ use diesel::connection::Connection;
 fn exec_archive_events(
     connections: &sqlite::Connections,
     ids: &[&str],
-    _archived_by_email: &str,
+    archived_by_email: &str,
 ) -> Result<usize> {
     let mut repo_err = None;
     let connection = connections.exclusive()?;
     Ok(connection
         .transaction::<_, diesel::result::Error, _>(|| {
-            usecases::archive_events(&*connection, ids).map_err(|err| {
+            usecases::archive_events(&*connection, ids, archived_by_email).map_err(|err| {
                 warn!("Failed to archive {} events: {}", ids.len(), err);
                 repo_err = Some(err);
                 diesel::result::Error::RollbackTransaction
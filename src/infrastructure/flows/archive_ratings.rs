@@ -1,22 +1,25 @@
 use super::*;
 
 use diesel::connection::Connection;
+use ofdb_core::user::RolePermissions;
 
 pub fn exec_archive_ratings(
     connections: &sqlite::Connections,
+    permissions: &RolePermissions,
     account_email: &str,
     ids: &[&str],
 ) -> Result<usize> {
-    //TODO: check if user is allowed to archive the ratings
     let mut repo_err = None;
     let connection = connections.exclusive()?;
     Ok(connection
         .transaction::<_, diesel::result::Error, _>(|| {
-            usecases::archive_ratings(&*connection, account_email, ids).map_err(|err| {
-                warn!("Failed to archive {} ratings: {}", ids.len(), err);
-                repo_err = Some(err);
-                diesel::result::Error::RollbackTransaction
-            })
+            usecases::archive_ratings(&*connection, permissions, account_email, ids).map_err(
+                |err| {
+                    warn!("Failed to archive {} ratings: {}", ids.len(), err);
+                    repo_err = Some(err);
+                    diesel::result::Error::RollbackTransaction
+                },
+            )
         })
         .map_err(|err| {
             if let Some(repo_err) = repo_err {
@@ -76,10 +79,11 @@ pub fn post_archive_ratings(
 pub fn archive_ratings(
     connections: &sqlite::Connections,
     indexer: &mut dyn PlaceIndexer,
+    permissions: &RolePermissions,
     account_email: &str,
     ids: &[&str],
 ) -> Result<usize> {
-    let count = exec_archive_ratings(connections, account_email, ids)?;
+    let count = exec_archive_ratings(connections, permissions, account_email, ids)?;
     post_archive_ratings(connections, indexer, ids)?;
     Ok(count)
 }
@@ -92,6 +96,7 @@ mod tests {
         super::archive_ratings(
             &fixture.db_connections,
             &mut *fixture.search_engine.borrow_mut(),
+            &fixture.permissions,
             "scout@foo.tld",
             ids,
         )
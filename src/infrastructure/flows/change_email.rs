@@ -0,0 +1,157 @@
+use super::*;
+use crate::core::error::Error;
+use diesel::connection::Connection;
+use ofdb_core::gateways::notify::NotificationGateway;
+
+pub fn change_email_request(
+    connections: &sqlite::Connections,
+    notify: &dyn NotificationGateway,
+    email: &str,
+    password: &str,
+    new_email: &str,
+) -> Result<EmailNonce> {
+    let mut rollback_err: Option<Error> = None;
+    let connection = connections.exclusive()?;
+    let email_nonce = connection
+        .transaction::<_, diesel::result::Error, _>(|| {
+            usecases::request_email_change(&*connection, email, password, new_email).map_err(
+                |err| {
+                    warn!(
+                        "Failed to request e-mail change for user '{}': {}",
+                        email, err
+                    );
+                    rollback_err = Some(err);
+                    diesel::result::Error::RollbackTransaction
+                },
+            )
+        })
+        .map_err(|err| rollback_err.unwrap_or_else(|| Error::from(RepoError::from(err))))?;
+    // The confirmation is sent to the new address
+    notify.user_email_change_requested(&email_nonce, new_email);
+    Ok(email_nonce)
+}
+
+pub fn change_email_with_email_nonce(
+    connections: &sqlite::Connections,
+    notify: &dyn NotificationGateway,
+    email_nonce: EmailNonce,
+) -> Result<User> {
+    let connection = connections.exclusive()?;
+
+    // The token should be consumed only once, even if the
+    // following transaction for updating the user fails!
+    let mut rollback_err: Option<Error> = None;
+    let token = connection
+        .transaction::<_, diesel::result::Error, _>(|| {
+            usecases::consume_user_token(&*connection, &email_nonce).map_err(|err| {
+                warn!(
+                    "Missing or invalid token to change e-mail for user '{}': {}",
+                    email_nonce.email, err
+                );
+                rollback_err = Some(err);
+                diesel::result::Error::RollbackTransaction
+            })
+        })
+        .map_err(|err| rollback_err.unwrap_or_else(|| Error::from(RepoError::from(err))))?;
+
+    // The consumed nonce must match the request parameters
+    debug_assert!(token.email_nonce == email_nonce);
+
+    let mut rollback_err: Option<Error> = None;
+    let user = connection
+        .transaction::<_, diesel::result::Error, _>(|| {
+            usecases::change_email_with_user_token(&*connection, token).map_err(|err| {
+                warn!(
+                    "Failed to change e-mail of user '{}': {}",
+                    email_nonce.email, err
+                );
+                rollback_err = Some(err);
+                diesel::result::Error::RollbackTransaction
+            })
+        })
+        .map_err(|err| rollback_err.unwrap_or_else(|| Error::from(RepoError::from(err))))?;
+
+    // Inform the previous address about the change
+    notify.user_email_changed(&email_nonce.email, &user.email);
+    Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::prelude::*;
+
+    #[test]
+    fn should_change_email_after_confirmation() {
+        let fixture = BackendFixture::new();
+        fixture.create_user(
+            usecases::NewUser {
+                email: "old@some.org".to_string(),
+                password: "secret1".to_string(),
+            },
+            None,
+        );
+
+        // Wrong password
+        assert!(super::change_email_request(
+            &fixture.db_connections,
+            &fixture.notify,
+            "old@some.org",
+            "wrong",
+            "new@some.org",
+        )
+        .is_err());
+
+        let email_nonce = super::change_email_request(
+            &fixture.db_connections,
+            &fixture.notify,
+            "old@some.org",
+            "secret1",
+            "new@some.org",
+        )
+        .unwrap();
+        assert_eq!("old@some.org", email_nonce.email);
+
+        // The token could not be used for resetting the password
+        assert!(flows::reset_password_with_email_nonce(
+            &fixture.db_connections,
+            email_nonce.clone(),
+            "secret2".parse::<Password>().unwrap()
+        )
+        .is_err());
+
+        // ...and it has been consumed by the previous attempt
+        assert!(super::change_email_with_email_nonce(
+            &fixture.db_connections,
+            &fixture.notify,
+            email_nonce,
+        )
+        .is_err());
+
+        let email_nonce = super::change_email_request(
+            &fixture.db_connections,
+            &fixture.notify,
+            "old@some.org",
+            "secret1",
+            "new@some.org",
+        )
+        .unwrap();
+        let user = super::change_email_with_email_nonce(
+            &fixture.db_connections,
+            &fixture.notify,
+            email_nonce,
+        )
+        .unwrap();
+        assert_eq!("new@some.org", user.email);
+
+        let credentials = usecases::Credentials {
+            email: "new@some.org",
+            password: "secret1",
+        };
+        assert!(usecases::login_with_email(
+            &*fixture.db_connections.shared().unwrap(),
+            &credentials
+        )
+        .is_ok());
+        assert!(fixture.try_get_user("old@some.org").is_none());
+    }
+}
@@ -1,8 +1,10 @@
 use super::*;
 use diesel::connection::Connection;
+use ofdb_core::user::RolePermissions;
 
 pub fn change_user_role(
     connections: &sqlite::Connections,
+    permissions: &RolePermissions,
     account_email: &str,
     user_email: &str,
     role: Role,
@@ -11,13 +13,12 @@ pub fn change_user_role(
     let connection = connections.exclusive()?;
     Ok(connection
         .transaction::<_, diesel::result::Error, _>(|| {
-            usecases::change_user_role(&*connection, account_email, user_email, role).map_err(
-                |err| {
+            usecases::change_user_role(&*connection, permissions, account_email, user_email, role)
+                .map_err(|err| {
                     warn!("Failed to change role for email {}: {}", user_email, err);
                     repo_err = Some(err);
                     diesel::result::Error::RollbackTransaction
-                },
-            )
+                })
         })
         .map_err(|err| {
             if let Some(repo_err) = repo_err {
@@ -38,7 +39,13 @@ mod tests {
         user_email: &str,
         role: Role,
     ) -> super::Result<()> {
-        super::change_user_role(&fixture.db_connections, account_email, user_email, role)
+        super::change_user_role(
+            &fixture.db_connections,
+            &fixture.permissions,
+            account_email,
+            user_email,
+            role,
+        )
     }
 
     #[test]
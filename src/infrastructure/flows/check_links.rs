@@ -0,0 +1,98 @@
+use super::*;
+use ofdb_core::gateways::link_check::LinkCheckGateway;
+use std::time::Duration;
+
+/// Check all links of places and events that have not
+/// been checked within the `recheck_interval`.
+///
+/// Returns the number of requested links.
+pub fn check_links(
+    connections: &sqlite::Connections,
+    checker: &dyn LinkCheckGateway,
+    recheck_interval: Duration,
+) -> Result<usize> {
+    let checked_before = TimestampMs::from_inner(
+        TimestampMs::now().into_inner() - recheck_interval.as_millis() as i64,
+    );
+    let usecases::LinkCheckPlan {
+        due,
+        reused,
+        obsolete,
+    } = usecases::plan_link_checks(&*connections.shared()?, checked_before)?;
+    {
+        let db = connections.exclusive()?;
+        db.delete_link_checks(&obsolete)?;
+        db.save_link_checks(&reused)?;
+    }
+    let count = due.len();
+    for (url, owners) in due {
+        // The database must not be locked while waiting for the response
+        let outcome = checker.check_link(&url);
+        if outcome.status.is_dead() {
+            info!("Link {} is {}", url, outcome.status);
+        }
+        let checked_at = TimestampMs::now();
+        let checks: Vec<_> = owners
+            .into_iter()
+            .map(|owner| LinkCheck {
+                owner,
+                url: url.clone(),
+                outcome: outcome.clone(),
+                checked_at,
+            })
+            .collect();
+        connections.exclusive()?.save_link_checks(&checks)?;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::prelude::*;
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct MockChecker {
+        requested: RefCell<Vec<String>>,
+    }
+
+    impl LinkCheckGateway for MockChecker {
+        fn check_link(&self, url: &Url) -> LinkCheckOutcome {
+            self.requested.borrow_mut().push(url.to_string());
+            LinkCheckOutcome {
+                status: LinkStatus::Broken,
+                http_status: Some(404),
+                redirect_url: None,
+            }
+        }
+    }
+
+    #[test]
+    fn should_check_links_only_once_within_interval() {
+        let fixture = BackendFixture::new();
+        let id = fixture.create_place(1.into(), None);
+        let checker = MockChecker::default();
+        let interval = Duration::from_secs(3600);
+
+        assert_eq!(
+            1,
+            check_links(&fixture.db_connections, &checker, interval).unwrap()
+        );
+        assert_eq!(
+            vec!["https://www.example1.com/".to_string()],
+            *checker.requested.borrow()
+        );
+        assert_eq!(
+            0,
+            check_links(&fixture.db_connections, &checker, interval).unwrap()
+        );
+        assert_eq!(1, checker.requested.borrow().len());
+
+        let db = fixture.db_connections.shared().unwrap();
+        assert_eq!(
+            vec![Id::from(id)],
+            usecases::place_ids_with_link_status(&*db, LinkStatus::Broken).unwrap()
+        );
+    }
+}
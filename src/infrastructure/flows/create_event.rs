@@ -60,6 +60,12 @@ pub fn create_event(
             event.id, err
         );
     }
+    if let Err(err) = notify_saved_search_alerts_for_event(connections, notify, &event, None) {
+        error!(
+            "Failed to send saved search alerts for newly added event {}: {}",
+            event.id, err
+        );
+    }
 
     Ok(event)
 }
@@ -78,3 +84,19 @@ fn notify_event_created(
     }
     Ok(())
 }
+
+pub(super) fn notify_saved_search_alerts_for_event(
+    connections: &sqlite::Connections,
+    notify: &dyn NotificationGateway,
+    event: &Event,
+    previous: Option<&Event>,
+) -> Result<()> {
+    let saved_searches = {
+        let connection = connections.shared()?;
+        usecases::saved_search_alerts_for_event(&*connection, event, previous)?
+    };
+    for saved_search in saved_searches {
+        notify.event_matches_saved_search(&saved_search.user_email, &saved_search.name, event);
+    }
+    Ok(())
+}
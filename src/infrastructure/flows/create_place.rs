@@ -1,5 +1,6 @@
 use super::*;
 use crate::core::error::RepoError;
+use crate::infrastructure::IMAGE_BASE_URL;
 use diesel::Connection;
 use ofdb_core::gateways::notify::NotificationGateway;
 
@@ -17,12 +18,19 @@ pub fn create_place(
         let mut prepare_err = None;
         connection
             .transaction::<_, diesel::result::Error, _>(|| {
-                match usecases::prepare_new_place(
-                    &*connection,
-                    new_place,
-                    created_by_email,
-                    created_by_org,
-                ) {
+                // Uploaded images must exist
+                let image = match new_place.image_url {
+                    Some(ref url) => usecases::check_image_url(&*connection, &IMAGE_BASE_URL, url),
+                    None => Ok(()),
+                };
+                match image.and_then(|()| {
+                    usecases::prepare_new_place(
+                        &*connection,
+                        new_place,
+                        created_by_email,
+                        created_by_org,
+                    )
+                }) {
                     Ok(storable) => {
                         let (place, ratings) = usecases::store_new_place(&*connection, storable)
                             .map_err(|err| {
@@ -63,6 +71,12 @@ pub fn create_place(
             place.id, err
         );
     }
+    if let Err(err) = notify_saved_search_alerts_for_place(connections, notify, &place, None) {
+        error!(
+            "Failed to send saved search alerts for newly added place {}: {}",
+            place.id, err
+        );
+    }
 
     Ok(place)
 }
@@ -82,3 +96,30 @@ fn notify_place_added(
     notify.place_added(&email_addresses, place, all_categories);
     Ok(())
 }
+
+pub(super) fn notify_saved_search_alerts_for_place(
+    connections: &sqlite::Connections,
+    notify: &dyn NotificationGateway,
+    place: &Place,
+    previous: Option<&Place>,
+) -> Result<()> {
+    let (saved_searches, all_categories) = {
+        let connection = connections.shared()?;
+        let saved_searches =
+            usecases::saved_search_alerts_for_place(&*connection, place, previous)?;
+        if saved_searches.is_empty() {
+            return Ok(());
+        }
+        let all_categories = connection.all_categories()?;
+        (saved_searches, all_categories)
+    };
+    for saved_search in saved_searches {
+        notify.place_matches_saved_search(
+            &saved_search.user_email,
+            &saved_search.name,
+            place,
+            all_categories.clone(),
+        );
+    }
+    Ok(())
+}
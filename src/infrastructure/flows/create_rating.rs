@@ -6,6 +6,7 @@ pub fn create_rating(
     connections: &sqlite::Connections,
     indexer: &mut dyn PlaceIndexer,
     rate_entry: usecases::NewPlaceRating,
+    author: RatingAuthor,
 ) -> Result<(String, String)> {
     // Add new rating to existing entry
     let (rating_id, comment_id, place, status, ratings) = {
@@ -13,7 +14,7 @@ pub fn create_rating(
         let mut prepare_err = None;
         connection
             .transaction::<_, diesel::result::Error, _>(|| {
-                match usecases::prepare_new_rating(&*connection, rate_entry) {
+                match usecases::prepare_new_rating(&*connection, rate_entry, author) {
                     Ok(storable) => {
                         let rating_id = storable.rating_id().to_owned();
                         let comment_id = storable.comment_id().to_owned();
@@ -0,0 +1,85 @@
+use super::*;
+
+use diesel::connection::Connection;
+use ofdb_core::user::RolePermissions;
+
+fn exec_merge_tags(
+    connections: &sqlite::Connections,
+    permissions: &RolePermissions,
+    account_email: &str,
+    tags: &[String],
+    into: &str,
+) -> Result<usecases::MergedTags> {
+    let mut repo_err = None;
+    let connection = connections.exclusive()?;
+    Ok(connection
+        .transaction::<_, diesel::result::Error, _>(|| {
+            usecases::merge_tags(&*connection, permissions, account_email, tags, into).map_err(
+                |err| {
+                    warn!("Failed to merge tags {:?} into '{}': {}", tags, into, err);
+                    repo_err = Some(err);
+                    diesel::result::Error::RollbackTransaction
+                },
+            )
+        })
+        .map_err(|err| {
+            if let Some(repo_err) = repo_err {
+                repo_err
+            } else {
+                RepoError::from(err).into()
+            }
+        })?)
+}
+
+fn post_merge_tags<I: EventAndPlaceIndexer>(
+    connections: &sqlite::Connections,
+    indexer: &mut I,
+    merged: &usecases::MergedTags,
+) -> Result<()> {
+    let db = connections.shared()?;
+    let ids: Vec<_> = merged.places.iter().map(|p| p.id.as_str()).collect();
+    for (place, status) in db.get_places(&ids)? {
+        let ratings = match db.load_ratings_of_place(place.id.as_str()) {
+            Ok(ratings) => ratings,
+            Err(err) => {
+                error!(
+                    "Failed to load ratings of place {} after merging tags: {}",
+                    place.id, err
+                );
+                continue;
+            }
+        };
+        if let Err(err) = usecases::reindex_place(indexer, &place, status, &ratings) {
+            error!(
+                "Failed to (re-)index place {} after merging tags: {}",
+                place.id, err
+            );
+        }
+    }
+    for event in &merged.events {
+        if let Err(err) = usecases::index_event(indexer, event) {
+            error!(
+                "Failed to (re-)index event {} after merging tags: {}",
+                event.id, err
+            );
+        }
+    }
+    if let Err(err) = indexer.flush_index() {
+        error!("Failed to flush search index after merging tags: {}", err);
+    }
+    Ok(())
+}
+
+pub fn merge_tags<I: EventAndPlaceIndexer>(
+    connections: &sqlite::Connections,
+    indexer: &mut I,
+    permissions: &RolePermissions,
+    account_email: &str,
+    tags: &[String],
+    into: &str,
+) -> Result<usecases::MergedTags> {
+    let merged = exec_merge_tags(connections, permissions, account_email, tags, into)?;
+    // TODO: Move post processing to a separate task/thread that doesn't delay this request?
+    post_merge_tags(connections, indexer, &merged)?;
+    Ok(merged)
+}
@@ -1,10 +1,17 @@
+mod anonymize_user;
 mod archive_comments;
 mod archive_events;
 mod archive_ratings;
+mod change_email;
 mod change_user_role;
+mod check_links;
 mod create_event;
 mod create_place;
 mod create_rating;
+mod merge_tags;
+mod place_edit_suggestions;
+mod place_ownership;
+mod reject_place_clearances;
 mod reset_password;
 mod review_places;
 mod update_event;
@@ -12,9 +19,11 @@ mod update_place;
 
 pub mod prelude {
     pub use super::{
-        archive_comments::*, archive_events::*, archive_ratings::*, change_user_role::*,
-        create_event::*, create_place::*, create_rating::*, reset_password::*, review_places::*,
-        update_event::*, update_place::*,
+        anonymize_user::*, archive_comments::*, archive_events::*, archive_ratings::*,
+        change_email::*, change_user_role::*, check_links::*, create_event::*, create_place::*,
+        create_rating::*, merge_tags::*, place_edit_suggestions::*, place_ownership::*,
+        reject_place_clearances::*, reset_password::*, review_places::*, update_event::*,
+        update_place::*,
     };
 }
 
@@ -0,0 +1,134 @@
+use super::*;
+use crate::core::error::Error;
+use diesel::connection::Connection;
+use ofdb_core::{gateways::notify::NotificationGateway, user::RolePermissions};
+
+pub fn accept_place_edit_suggestion(
+    connections: &sqlite::Connections,
+    indexer: &mut dyn PlaceIndexer,
+    notify: &dyn NotificationGateway,
+    permissions: &RolePermissions,
+    reviewer: usecases::SuggestionReviewer,
+    id: &str,
+) -> Result<Place> {
+    // Store the suggestion as a new revision
+    let (place, ratings) = {
+        let connection = connections.exclusive()?;
+        let mut rollback_err: Option<Error> = None;
+        connection
+            .transaction::<_, diesel::result::Error, _>(|| {
+                usecases::accept_place_edit_suggestion(&*connection, permissions, reviewer, id)
+                    .and_then(|storable| usecases::store_updated_place(&*connection, storable))
+                    .map_err(|err| {
+                        warn!("Failed to accept suggestion {}: {}", id, err);
+                        rollback_err = Some(err);
+                        diesel::result::Error::RollbackTransaction
+                    })
+            })
+            .map_err(|err| rollback_err.unwrap_or_else(|| Error::from(RepoError::from(err))))
+    }?;
+
+    // Reindex updated place
+    // TODO: Move to a separate task/thread that doesn't delay this request
+    if let Err(err) = usecases::reindex_place(indexer, &place, ReviewStatus::Created, &ratings)
+        .and_then(|_| indexer.flush_index())
+    {
+        error!("Failed to reindex updated place {}: {}", place.id, err);
+    }
+
+    // Send subscription e-mails
+    // TODO: Move to a separate task/thread that doesn't delay this request
+    if let Err(err) = super::update_place::notify_place_updated(connections, notify, &place) {
+        error!(
+            "Failed to send notifications for updated place {}: {}",
+            place.id, err
+        );
+    }
+
+    Ok(place)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::prelude::*;
+
+    #[test]
+    fn should_store_accepted_suggestion_as_new_revision() {
+        let mut fixture = BackendFixture::new();
+        fixture.create_user(
+            usecases::NewUser {
+                email: "scout@some.org".to_string(),
+                password: "secret1".to_string(),
+            },
+            Some(Role::Scout),
+        );
+        let place_id = fixture.create_place(0.into(), None);
+        let (place, _) = fixture.try_get_place(&place_id).unwrap();
+
+        let mut update_place = usecases::UpdatePlace::from(place.clone());
+        update_place.version = place.revision.next().into();
+        update_place.telephone = Some("0123".into());
+        let suggestion = usecases::suggest_place_edit(
+            &*fixture.db_connections.exclusive().unwrap(),
+            place.id.clone(),
+            update_place,
+            None,
+        )
+        .unwrap();
+        let pending = usecases::load_pending_place_edit_suggestions(
+            &*fixture.db_connections.shared().unwrap(),
+            &fixture.permissions,
+            usecases::SuggestionReviewer::User("scout@some.org"),
+            None,
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(vec![suggestion.clone()], pending);
+
+        let updated_place = flows::accept_place_edit_suggestion(
+            &fixture.db_connections,
+            fixture.search_engine.get_mut(),
+            &fixture.notify,
+            &fixture.permissions,
+            usecases::SuggestionReviewer::User("scout@some.org"),
+            suggestion.id.as_str(),
+        )
+        .unwrap();
+        assert_eq!(place.revision.next(), updated_place.revision);
+        assert_eq!(
+            Some("0123"),
+            updated_place
+                .contact
+                .as_ref()
+                .and_then(|c| c.phone.as_deref())
+        );
+        let (place, _) = fixture.try_get_place(&place_id).unwrap();
+        assert_eq!(updated_place.revision, place.revision);
+
+        // No longer pending
+        assert!(flows::accept_place_edit_suggestion(
+            &fixture.db_connections,
+            fixture.search_engine.get_mut(),
+            &fixture.notify,
+            &fixture.permissions,
+            usecases::SuggestionReviewer::User("scout@some.org"),
+            suggestion.id.as_str(),
+        )
+        .is_err());
+        let suggestion = fixture
+            .db_connections
+            .shared()
+            .unwrap()
+            .get_place_edit_suggestion(suggestion.id.as_str())
+            .unwrap();
+        assert_eq!(SuggestionStatus::Accepted, suggestion.status);
+        assert_eq!(
+            Some("scout@some.org"),
+            suggestion
+                .reviewed
+                .as_ref()
+                .and_then(|log| log.activity.by.as_ref())
+                .map(|email| email.as_str())
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use super::*;
+use crate::core::error::Error;
+use diesel::connection::Connection;
+use ofdb_core::gateways::notify::NotificationGateway;
+
+pub fn request_place_ownership(
+    connections: &sqlite::Connections,
+    notify: &dyn NotificationGateway,
+    user_email: &str,
+    place_id: &str,
+) -> Result<EmailNonce> {
+    let mut rollback_err: Option<Error> = None;
+    let connection = connections.exclusive()?;
+    let (email_nonce, place) = connection
+        .transaction::<_, diesel::result::Error, _>(|| {
+            usecases::request_place_ownership(&*connection, user_email, place_id).map_err(|err| {
+                warn!(
+                    "Failed to request ownership of place {} for user '{}': {}",
+                    place_id, user_email, err
+                );
+                rollback_err = Some(err);
+                diesel::result::Error::RollbackTransaction
+            })
+        })
+        .map_err(|err| rollback_err.unwrap_or_else(|| Error::from(RepoError::from(err))))?;
+    // The confirmation is sent to the contact of the place
+    if let Some(contact_email) = place.contact.as_ref().and_then(|c| c.email.as_ref()) {
+        notify.place_ownership_requested(&email_nonce, contact_email, &place);
+    }
+    Ok(email_nonce)
+}
+
+pub fn confirm_place_ownership_with_email_nonce(
+    connections: &sqlite::Connections,
+    email_nonce: EmailNonce,
+) -> Result<PlaceOwnership> {
+    let connection = connections.exclusive()?;
+
+    // The token should be consumed only once, even if the
+    // following transaction for assigning the owner fails!
+    let mut rollback_err: Option<Error> = None;
+    let token = connection
+        .transaction::<_, diesel::result::Error, _>(|| {
+            usecases::consume_user_token(&*connection, &email_nonce).map_err(|err| {
+                warn!(
+                    "Missing or invalid token to claim a place for user '{}': {}",
+                    email_nonce.email, err
+                );
+                rollback_err = Some(err);
+                diesel::result::Error::RollbackTransaction
+            })
+        })
+        .map_err(|err| rollback_err.unwrap_or_else(|| Error::from(RepoError::from(err))))?;
+
+    // The consumed nonce must match the request parameters
+    debug_assert!(token.email_nonce == email_nonce);
+
+    let mut rollback_err: Option<Error> = None;
+    let ownership = connection
+        .transaction::<_, diesel::result::Error, _>(|| {
+            usecases::confirm_place_ownership_with_user_token(&*connection, token).map_err(|err| {
+                warn!(
+                    "Failed to assign user '{}' as owner of a place: {}",
+                    email_nonce.email, err
+                );
+                rollback_err = Some(err);
+                diesel::result::Error::RollbackTransaction
+            })
+        })
+        .map_err(|err| rollback_err.unwrap_or_else(|| Error::from(RepoError::from(err))))?;
+    Ok(ownership)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::prelude::*;
+
+    #[test]
+    fn should_lock_fields_after_claiming_a_place() {
+        let mut fixture = BackendFixture::new();
+        fixture.create_user(
+            usecases::NewUser {
+                email: "owner@some.org".to_string(),
+                password: "secret1".to_string(),
+            },
+            None,
+        );
+        let mut new_place = usecases::NewPlace::from(NewPlace::from(1));
+        new_place.email = Some("contact@some.org".into());
+        let place = flows::create_place(
+            &fixture.db_connections,
+            fixture.search_engine.get_mut(),
+            &fixture.notify,
+            new_place,
+            None,
+            None,
+        )
+        .unwrap();
+
+        // The e-mail address of the user must be confirmed
+        assert!(super::request_place_ownership(
+            &fixture.db_connections,
+            &fixture.notify,
+            "owner@some.org",
+            place.id.as_str(),
+        )
+        .is_err());
+        let mut user = fixture.try_get_user("owner@some.org").unwrap();
+        user.email_confirmed = true;
+        fixture
+            .db_connections
+            .exclusive()
+            .unwrap()
+            .update_user(&user)
+            .unwrap();
+
+        let email_nonce = super::request_place_ownership(
+            &fixture.db_connections,
+            &fixture.notify,
+            "owner@some.org",
+            place.id.as_str(),
+        )
+        .unwrap();
+
+        // The token could not be used for resetting the password
+        assert!(flows::reset_password_with_email_nonce(
+            &fixture.db_connections,
+            email_nonce.clone(),
+            "secret2".parse::<Password>().unwrap()
+        )
+        .is_err());
+
+        let email_nonce = super::request_place_ownership(
+            &fixture.db_connections,
+            &fixture.notify,
+            "owner@some.org",
+            place.id.as_str(),
+        )
+        .unwrap();
+        let ownership =
+            super::confirm_place_ownership_with_email_nonce(&fixture.db_connections, email_nonce)
+                .unwrap();
+        assert_eq!(place.id, ownership.place_id);
+        assert_eq!("owner@some.org", ownership.owner.as_str());
+        assert!(ownership.locked_fields.is_empty());
+
+        usecases::lock_place_fields(
+            &*fixture.db_connections.exclusive().unwrap(),
+            "owner@some.org",
+            place.id.as_str(),
+            &[PlaceField::Title],
+        )
+        .unwrap();
+
+        let mut update_place = usecases::UpdatePlace::from(place.clone());
+        update_place.version = place.revision.next().into();
+        update_place.title = "Changed by someone else".into();
+        assert!(matches!(
+            flows::update_place(
+                &fixture.db_connections,
+                fixture.search_engine.get_mut(),
+                &fixture.notify,
+                place.id.clone(),
+                update_place.clone(),
+                None,
+                None,
+            ),
+            Err(AppError::Business(Error::Parameter(
+                ParameterError::LockedField(PlaceField::Title)
+            )))
+        ));
+        let place = flows::update_place(
+            &fixture.db_connections,
+            fixture.search_engine.get_mut(),
+            &fixture.notify,
+            place.id.clone(),
+            update_place,
+            Some("owner@some.org"),
+            None,
+        )
+        .unwrap();
+        assert_eq!("Changed by someone else", place.title);
+    }
+}
@@ -0,0 +1,36 @@
+use super::*;
+use crate::core::error::Error;
+use diesel::connection::Connection;
+use ofdb_core::gateways::notify::NotificationGateway;
+
+pub fn reject_place_clearances(
+    connections: &sqlite::Connections,
+    notify: &dyn NotificationGateway,
+    org: &Organization,
+    rejections: &[RejectionForPlace],
+) -> Result<usize> {
+    let mut rollback_err: Option<Error> = None;
+    let connection = connections.exclusive()?;
+    let rejected_places = connection
+        .transaction::<_, diesel::result::Error, _>(|| {
+            usecases::clearance::place::reject_pending_clearances(&*connection, org, rejections)
+                .map_err(|err| {
+                    warn!(
+                        "Failed to reject {} pending clearance(s) for places: {}",
+                        rejections.len(),
+                        err
+                    );
+                    rollback_err = Some(err);
+                    diesel::result::Error::RollbackTransaction
+                })
+        })
+        .map_err(|err| rollback_err.unwrap_or_else(|| Error::from(RepoError::from(err))))?;
+    // The editors of the rejected revisions are informed about the reason
+    for rejected_place in &rejected_places {
+        let usecases::clearance::place::RejectedPlace { place, reason } = rejected_place;
+        if let Some(email) = &place.created.by {
+            notify.place_clearance_rejected(email.as_str(), place, &org.name, reason);
+        }
+    }
+    Ok(rejected_places.len())
+}
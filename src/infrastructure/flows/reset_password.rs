@@ -56,6 +56,26 @@ pub fn reset_password_with_email_nonce(
     // The consumed nonce must match the request parameters
     debug_assert!(token.email_nonce == email_nonce);
 
+    // Tokens for changing the e-mail address have been sent
+    // to the new address and must not be used for resetting
+    // the password
+    if token.new_email.is_some() {
+        warn!(
+            "Rejecting token for changing the e-mail to reset password for user '{}'",
+            email_nonce.email
+        );
+        return Err(Error::Parameter(ParameterError::TokenInvalid).into());
+    }
+    // The same applies to tokens for claiming a place that
+    // have been sent to the contact of the place
+    if token.place_id.is_some() {
+        warn!(
+            "Rejecting token for claiming a place to reset password for user '{}'",
+            email_nonce.email
+        );
+        return Err(Error::Parameter(ParameterError::TokenInvalid).into());
+    }
+
     // Verify and update the user entity
     let mut rollback_err: Option<Error> = None;
     connection
@@ -15,6 +15,7 @@ pub mod prelude {
     };
 
     use crate::ports::web::tests::DummyNotifyGW;
+    pub use ofdb_core::user::RolePermissions;
 
     use std::cell::RefCell;
 
@@ -24,6 +25,7 @@ pub mod prelude {
         pub db_connections: sqlite::Connections,
         pub search_engine: RefCell<tantivy::SearchEngine>,
         pub notify: DummyNotifyGW,
+        pub permissions: RolePermissions,
     }
 
     impl BackendFixture {
@@ -35,6 +37,7 @@ pub mod prelude {
                 db_connections,
                 search_engine: RefCell::new(search_engine),
                 notify: DummyNotifyGW,
+                permissions: RolePermissions::default(),
             }
         }
 
@@ -92,6 +95,7 @@ pub mod prelude {
                 &self.db_connections,
                 &mut *self.search_engine.borrow_mut(),
                 rate_entry,
+                RatingAuthor::AnonymousSession("test".into()),
             )
             .unwrap()
         }
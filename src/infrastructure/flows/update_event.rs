@@ -12,8 +12,10 @@ pub fn update_event(
     new_event: usecases::NewEvent,
 ) -> Result<Event> {
     // Create and add new event
-    let event = {
+    let (event, previous) = {
         let connection = connections.exclusive()?;
+        // Needed to detect newly matching saved searches
+        let previous = connection.get_event(id.as_str()).ok();
         let mut prepare_err = None;
         connection
             .transaction::<_, diesel::result::Error, _>(|| {
@@ -30,7 +32,7 @@ pub fn update_event(
                                 diesel::result::Error::RollbackTransaction
                             },
                         )?;
-                        Ok(event)
+                        Ok((event, previous))
                     }
                     Err(err) => {
                         prepare_err = Some(err);
@@ -61,6 +63,17 @@ pub fn update_event(
             event.id, err
         );
     }
+    if let Err(err) = super::create_event::notify_saved_search_alerts_for_event(
+        connections,
+        notify,
+        &event,
+        previous.as_ref(),
+    ) {
+        error!(
+            "Failed to send saved search alerts for updated event {}: {}",
+            event.id, err
+        );
+    }
 
     Ok(event)
 }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::infrastructure::IMAGE_BASE_URL;
 use diesel::connection::Connection;
 use ofdb_core::gateways::notify::NotificationGateway;
 
@@ -12,18 +13,30 @@ pub fn update_place(
     created_by_org: Option<&Organization>,
 ) -> Result<Place> {
     // Update existing entry
-    let (place, ratings) = {
+    let (place, ratings, previous) = {
         let connection = connections.exclusive()?;
+        // Needed to detect newly matching saved searches
+        let previous = connection
+            .get_place(id.as_str())
+            .map(|(place, _)| place)
+            .ok();
         let mut prepare_err = None;
         connection
             .transaction::<_, diesel::result::Error, _>(|| {
-                match usecases::prepare_updated_place(
-                    &*connection,
-                    id,
-                    update_place,
-                    created_by_email,
-                    created_by_org,
-                ) {
+                // Uploaded images must exist
+                let image = match update_place.image_url {
+                    Some(ref url) => usecases::check_image_url(&*connection, &IMAGE_BASE_URL, url),
+                    None => Ok(()),
+                };
+                match image.and_then(|()| {
+                    usecases::prepare_updated_place(
+                        &*connection,
+                        id,
+                        update_place,
+                        created_by_email,
+                        created_by_org,
+                    )
+                }) {
                     Ok(storable) => {
                         let (place, ratings) =
                             usecases::store_updated_place(&*connection, storable).map_err(
@@ -32,7 +45,7 @@ pub fn update_place(
                                     diesel::result::Error::RollbackTransaction
                                 },
                             )?;
-                        Ok((place, ratings))
+                        Ok((place, ratings, previous))
                     }
                     Err(err) => {
                         prepare_err = Some(err);
@@ -65,19 +78,39 @@ pub fn update_place(
             place.id, err
         );
     }
+    if let Err(err) = super::create_place::notify_saved_search_alerts_for_place(
+        connections,
+        notify,
+        &place,
+        previous.as_ref(),
+    ) {
+        error!(
+            "Failed to send saved search alerts for updated place {}: {}",
+            place.id, err
+        );
+    }
 
     Ok(place)
 }
 
-fn notify_place_updated(
+pub(super) fn notify_place_updated(
     connections: &sqlite::Connections,
     notify: &dyn NotificationGateway,
     place: &Place,
 ) -> Result<()> {
     let (email_addresses, all_categories) = {
         let connection = connections.shared()?;
-        let email_addresses =
+        let mut email_addresses =
             usecases::email_addresses_by_coordinate(&*connection, place.location.pos)?;
+        // The owner is informed about every modification made by others
+        if let Some(ownership) = connection.get_place_ownership(place.id.as_str())? {
+            let owner = String::from(ownership.owner);
+            if place.created.by.as_ref().map(|e| e.as_str()) != Some(owner.as_str())
+                && !email_addresses.contains(&owner)
+            {
+                email_addresses.push(owner);
+            }
+        }
         let all_categories = connection.all_categories()?;
         (email_addresses, all_categories)
     };
@@ -2,10 +2,14 @@ pub mod db;
 pub mod error;
 pub mod flows;
 
+use ofdb_core::gateways::image::ImageStorageGateway;
 use ofdb_entities::email::*;
-use ofdb_gateways::{mailgun::*, opencage::*, sendmail::*};
+use ofdb_gateways::{file_storage::*, mailgun::*, opencage::*, s3::*, sendmail::*};
 use std::env;
 
+const DEFAULT_IMAGE_DIR: &str = "images";
+const DEFAULT_IMAGE_BASE_URL: &str = "https://api.ofdb.io/v0/images";
+
 lazy_static! {
 
     pub static ref GEO_CODING_GW: OpenCage = {
@@ -48,6 +52,42 @@ lazy_static! {
             None
         }
     };
+
+    /// The public URL under which the images API is reachable
+    pub static ref IMAGE_BASE_URL: String = env::var("IMAGE_BASE_URL")
+        .unwrap_or_else(|_| DEFAULT_IMAGE_BASE_URL.to_string());
+}
+
+/// Creates the configured image storage. The configuration is
+/// validated at startup instead of failing on the first upload.
+pub fn image_storage_from_env() -> anyhow::Result<Box<dyn ImageStorageGateway + Send + Sync>> {
+    let endpoint = env::var("IMAGE_S3_ENDPOINT");
+    let bucket = env::var("IMAGE_S3_BUCKET");
+    let access_key = env::var("IMAGE_S3_ACCESS_KEY");
+    let secret_key = env::var("IMAGE_S3_SECRET_KEY");
+
+    if let (Ok(endpoint), Ok(bucket), Ok(access_key), Ok(secret_key)) =
+        (endpoint, bucket, access_key, secret_key)
+    {
+        let config = S3Config {
+            endpoint,
+            region: env::var("IMAGE_S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+            bucket,
+            access_key,
+            secret_key,
+        };
+        info!(
+            "Storing images in S3 bucket '{}' at {}",
+            config.bucket, config.endpoint
+        );
+        let storage = S3ImageStorage::new(config)
+            .map_err(|err| anyhow::anyhow!("Invalid S3 image storage configuration: {}", err))?;
+        Ok(Box::new(storage))
+    } else {
+        let dir = env::var("IMAGE_DIR").unwrap_or_else(|_| DEFAULT_IMAGE_DIR.to_string());
+        info!("Storing images in directory '{}'", dir);
+        Ok(Box::new(FileImageStorage::new(dir)))
+    }
 }
 
 #[cfg(test)]
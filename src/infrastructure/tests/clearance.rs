@@ -918,3 +918,281 @@ fn should_do_nothing_when_clearing_places_without_pending_clearances() -> flows:
 
     Ok(())
 }
+
+#[test]
+fn should_keep_the_last_cleared_revision_when_rejecting_places_with_revert() -> flows::Result<()> {
+    let fixture = PlaceClearanceFixture::new();
+    let org = fixture.organization_with_add_remove_clearance_tag;
+    let tag = &org.moderated_tags.first().unwrap().label;
+    let old_place = fixture.confirmed_place;
+    let place_id = &old_place.id;
+    let mut backend = fixture.backend;
+
+    let update_title = |backend: &mut flows::BackendFixture, place: &Place, title: &str| {
+        let mut update_place = usecases::UpdatePlace::from(place.clone());
+        update_place.title = title.into();
+        update_place.version = place.revision.next().into();
+        flows::update_place(
+            &backend.db_connections,
+            backend.search_engine.get_mut(),
+            &backend.notify,
+            place.id.clone(),
+            update_place,
+            None,
+            None,
+        )
+    };
+    let count_pending_clearances = |backend: &flows::BackendFixture| {
+        usecases::clearance::place::count_pending_clearances(
+            &*backend.db_connections.shared().unwrap(),
+            &org,
+        )
+        .unwrap()
+    };
+    let load_cleared_title = |backend: &flows::BackendFixture| {
+        usecases::load_places(
+            &*backend.db_connections.shared().unwrap(),
+            &[place_id.as_str()],
+            Some(tag.as_str()),
+        )
+        .unwrap()
+        .first()
+        .unwrap()
+        .0
+        .title
+        .clone()
+    };
+
+    let new_place = update_title(&mut backend, &old_place, "new title")?;
+    assert_eq!(1, count_pending_clearances(&backend));
+
+    // A reason is required
+    assert!(flows::reject_place_clearances(
+        &backend.db_connections,
+        &backend.notify,
+        &org,
+        &[RejectionForPlace {
+            place_id: place_id.clone(),
+            rejected_revision: None,
+            reason: " ".into(),
+            revert: true,
+        }],
+    )
+    .is_err());
+
+    // The last cleared revision cannot be rejected
+    assert!(flows::reject_place_clearances(
+        &backend.db_connections,
+        &backend.notify,
+        &org,
+        &[RejectionForPlace {
+            place_id: place_id.clone(),
+            rejected_revision: Some(old_place.revision),
+            reason: "reason".into(),
+            revert: false,
+        }],
+    )
+    .is_err());
+
+    // Rejecting without reverting keeps the clearance pending
+    assert_eq!(
+        1,
+        flows::reject_place_clearances(
+            &backend.db_connections,
+            &backend.notify,
+            &org,
+            &[RejectionForPlace {
+                place_id: place_id.clone(),
+                rejected_revision: Some(new_place.revision),
+                reason: "Please add a source".into(),
+                revert: false,
+            }],
+        )?
+    );
+    assert_eq!(1, count_pending_clearances(&backend));
+
+    // Rejecting with reverting resolves the pending clearance
+    assert_eq!(
+        1,
+        flows::reject_place_clearances(
+            &backend.db_connections,
+            &backend.notify,
+            &org,
+            &[RejectionForPlace {
+                place_id: place_id.clone(),
+                rejected_revision: None,
+                reason: "Wrong title".into(),
+                revert: true,
+            }],
+        )?
+    );
+    assert_eq!(0, count_pending_clearances(&backend));
+    assert_eq!(old_place.title, load_cleared_title(&backend));
+
+    let audit_log = backend.db_connections.shared()?.query_audit_log(
+        &AuditLogQuery {
+            entity_id: Some(place_id.as_str()),
+            action: Some(AuditAction::PlaceRejectClearance),
+            ..Default::default()
+        },
+        &Default::default(),
+    )?;
+    assert_eq!(2, audit_log.len());
+    assert_eq!(Some(org.id.to_string()), audit_log[0].log.context);
+    assert!(audit_log[0]
+        .details
+        .as_ref()
+        .unwrap()
+        .ends_with("Wrong title"));
+
+    // Modifying the place again requires a new clearance
+    // of all changes since the last cleared revision
+    let newer_place = update_title(&mut backend, &new_place, "newer title")?;
+    let pending_clearances = usecases::clearance::place::list_pending_clearances(
+        &*backend.db_connections.shared()?,
+        &org,
+        &Default::default(),
+    )?;
+    assert_eq!(1, pending_clearances.len());
+    assert_eq!(
+        Some(old_place.revision),
+        pending_clearances.first().unwrap().last_cleared_revision
+    );
+    // The reopened clearance is pending since the newer revision
+    assert_eq!(
+        newer_place.created.at,
+        pending_clearances.first().unwrap().created_at
+    );
+    assert_eq!(old_place.title, load_cleared_title(&backend));
+
+    // Clearing the newer revision finally resolves the pending clearance
+    assert_eq!(
+        1,
+        usecases::clearance::place::update_pending_clearances(
+            &*backend.db_connections.exclusive()?,
+            &org,
+            &[ClearanceForPlace {
+                place_id: place_id.clone(),
+                cleared_revision: Some(newer_place.revision),
+            }],
+        )?
+    );
+    assert_eq!(0, count_pending_clearances(&backend));
+    assert_eq!(newer_place.title, load_cleared_title(&backend));
+
+    Ok(())
+}
+
+#[test]
+fn should_return_the_last_cleared_state_when_querying_cleared_events() -> flows::Result<()> {
+    let mut fixture = PlaceClearanceFixture::new();
+    let org = fixture.organization_with_add_remove_clearance_tag;
+    let tag = &org.moderated_tags.first().unwrap().label;
+
+    let new_event = usecases::NewEvent {
+        title: "created_event".into(),
+        start: chrono::Utc::now().naive_utc().timestamp(),
+        tags: Some(vec![tag.clone()]),
+        created_by: Some(fixture.user_email.to_string()),
+        ..Default::default()
+    };
+    let created_event = flows::create_event(
+        &fixture.backend.db_connections,
+        fixture.backend.search_engine.get_mut(),
+        &fixture.backend.notify,
+        None,
+        new_event.clone(),
+    )?;
+    let pending_clearances = usecases::clearance::event::list_pending_clearances(
+        &*fixture.backend.db_connections.shared()?,
+        &org,
+        &Default::default(),
+    )?;
+    assert_eq!(1, pending_clearances.len());
+    assert_eq!(created_event.id, pending_clearances[0].event_id);
+    // Not yet cleared (and invisible)
+    assert!(pending_clearances[0].last_cleared_event.is_none());
+
+    let cleared_query = usecases::EventQuery {
+        org_tag: Some(tag.clone()),
+        ..Default::default()
+    };
+    assert!(usecases::query_events(
+        &*fixture.backend.db_connections.shared()?,
+        &*fixture.backend.search_engine.borrow(),
+        cleared_query.clone(),
+    )?
+    .is_empty());
+
+    // Clear the newly created event
+    assert_eq!(
+        1,
+        usecases::clearance::event::update_pending_clearances(
+            &*fixture.backend.db_connections.exclusive()?,
+            &org,
+            &[ClearanceForEvent {
+                event_id: created_event.id.clone(),
+            }],
+        )?
+    );
+    assert_eq!(
+        0,
+        usecases::clearance::event::count_pending_clearances(
+            &*fixture.backend.db_connections.shared()?,
+            &org,
+        )?
+    );
+
+    // Update the event twice
+    for title in &["updated_event", "updated_event_again"] {
+        flows::update_event(
+            &fixture.backend.db_connections,
+            fixture.backend.search_engine.get_mut(),
+            &fixture.backend.notify,
+            None,
+            created_event.id.clone(),
+            usecases::NewEvent {
+                title: (*title).to_string(),
+                ..new_event.clone()
+            },
+        )?;
+    }
+    let pending_clearances = usecases::clearance::event::list_pending_clearances(
+        &*fixture.backend.db_connections.shared()?,
+        &org,
+        &Default::default(),
+    )?;
+    assert_eq!(1, pending_clearances.len());
+    assert_eq!(
+        Some("created_event"),
+        pending_clearances[0]
+            .last_cleared_event
+            .as_ref()
+            .map(|e| e.title.as_str())
+    );
+
+    // Query uncleared
+    let uncleared_events = usecases::query_events(
+        &*fixture.backend.db_connections.shared()?,
+        &*fixture.backend.search_engine.borrow(),
+        usecases::EventQuery {
+            tags: Some(vec![tag.clone()]),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(1, uncleared_events.len());
+    assert_eq!("updated_event_again", uncleared_events[0].title);
+
+    // Query cleared
+    let cleared_events = usecases::query_events(
+        &*fixture.backend.db_connections.shared()?,
+        &*fixture.backend.search_engine.borrow(),
+        cleared_query,
+    )?;
+    assert_eq!(1, cleared_events.len());
+    assert_eq!(created_event.id, cleared_events[0].id);
+    assert_eq!("created_event", cleared_events[0].title);
+    assert!(cleared_events[0].tags.contains(tag));
+
+    Ok(())
+}
@@ -45,5 +45,9 @@ fn default_search_request<'a>() -> usecases::SearchRequest<'a> {
         ids: vec![],
         status: vec![],
         text: None,
+        link_status: None,
+        lang: None,
+        sort: None,
+        cursor: None,
     }
 }
@@ -146,3 +146,88 @@ fn should_find_places_by_tags() -> flows::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn should_find_places_by_stemmed_and_folded_text() -> flows::Result<()> {
+    let fixture = flows::BackendFixture::new();
+
+    let garden = flows::create_place(
+        &fixture.db_connections,
+        &mut *fixture.search_engine.borrow_mut(),
+        &fixture.notify,
+        usecases::NewPlace {
+            title: "Interkultureller Garten".into(),
+            description: "Ein Garten für die ganze Nachbarschaft".into(),
+            ..default_new_place()
+        },
+        None,
+        None,
+    )
+    .unwrap();
+
+    let shop = flows::create_place(
+        &fixture.db_connections,
+        &mut *fixture.search_engine.borrow_mut(),
+        &fixture.notify,
+        usecases::NewPlace {
+            title: "Bioladen".into(),
+            description: "Der Bioladen in der Altstadt".into(),
+            ..default_new_place()
+        },
+        None,
+        None,
+    )
+    .unwrap();
+
+    let community_garden = flows::create_place(
+        &fixture.db_connections,
+        &mut *fixture.search_engine.borrow_mut(),
+        &fixture.notify,
+        usecases::NewPlace {
+            title: "Community gardens".into(),
+            description: "The gardens of the neighbourhood".into(),
+            ..default_new_place()
+        },
+        None,
+        None,
+    )
+    .unwrap();
+
+    let search_ids = |text, lang| -> flows::Result<Vec<Id>> {
+        Ok(usecases::search(
+            &*fixture.db_connections.shared()?,
+            &*fixture.search_engine.borrow(),
+            usecases::SearchRequest {
+                text: Some(text),
+                lang,
+                ..default_search_request()
+            },
+            100,
+        )?
+        .0
+        .into_iter()
+        .map(|p| p.id.into())
+        .collect())
+    };
+
+    // Folding
+    assert_eq!(vec![shop.id.clone()], search_ids("Bioläden", None)?);
+    // Stemming
+    assert_eq!(vec![garden.id.clone()], search_ids("Gärten", None)?);
+    assert_eq!(
+        vec![garden.id],
+        search_ids("Gärten", Some(Language::German))?
+    );
+    assert_eq!(
+        vec![community_garden.id.clone()],
+        search_ids("gardening", None)?
+    );
+    assert_eq!(
+        vec![community_garden.id],
+        search_ids("gardening", Some(Language::English))?
+    );
+    // Only the fields of the requested language are searched
+    assert!(search_ids("gardening", Some(Language::German))?.is_empty());
+
+    Ok(())
+}
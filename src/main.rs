@@ -25,7 +25,7 @@ mod core;
 pub(crate) mod infrastructure;
 mod ports;
 
-fn main() {
+fn main() -> anyhow::Result<()> {
     env_logger::init();
-    ports::cli::run();
+    ports::cli::run()
 }
@@ -1,19 +1,26 @@
 use crate::{
+    adapters::json,
     core::prelude::*,
     infrastructure::{
         db::{sqlite, tantivy},
-        GEO_CODING_GW,
+        flows::prelude as flows,
+        image_storage_from_env, GEO_CODING_GW,
     },
     ports::web,
 };
 
 use clap::{crate_authors, App, Arg};
 use dotenv::dotenv;
-use ofdb_core::gateways::geocode::GeoCodingGateway;
-use std::{env, path::Path};
+use ofdb_core::{gateways::geocode::GeoCodingGateway, user::RolePermissions};
+use ofdb_gateways::link_checker::HttpLinkChecker;
+use std::{env, fs::File, path::Path, thread, time::Duration};
 
 const DEFAULT_DB_URL: &str = "openfair.db";
 const DB_CONNECTION_POOL_SIZE: u32 = 10;
+const LINK_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+const LINK_CHECK_POLL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+// Links are checked at least once a year
+const MAX_LINK_CHECK_INTERVAL_HOURS: u64 = 24 * 366;
 
 embed_migrations!();
 
@@ -39,8 +46,30 @@ fn update_event_locations<D: Db>(db: &mut D) -> Result<()> {
     Ok(())
 }
 
+fn spawn_link_checker(connections: sqlite::Connections, recheck_interval: Duration) {
+    thread::spawn(move || {
+        let checker = HttpLinkChecker::new(LINK_CHECK_TIMEOUT);
+        loop {
+            match flows::check_links(&connections, &checker, recheck_interval) {
+                Ok(count) => {
+                    if count > 0 {
+                        info!("Checked {} links", count);
+                    }
+                }
+                Err(err) => error!("Failed to check links: {}", err),
+            }
+            thread::sleep(LINK_CHECK_POLL_INTERVAL);
+        }
+    });
+}
+
+fn load_role_permissions(path: &Path) -> anyhow::Result<RolePermissions> {
+    let file = File::open(path)?;
+    json::role_permissions(serde_json::from_reader(file)?)
+}
+
 #[allow(deprecated)]
-pub fn run() {
+pub fn run() -> anyhow::Result<()> {
     dotenv().ok();
     let matches = App::new("openFairDB")
         .version(env!("CARGO_PKG_VERSION"))
@@ -62,6 +91,23 @@ pub fn run() {
                 .long("enable-cors")
                 .help("Allow requests from any origin"),
         )
+        .arg(
+            Arg::with_name("shared-captcha-store")
+                .long("shared-captcha-store")
+                .help("Store captcha challenges in the database to share them between multiple instances"),
+        )
+        .arg(
+            Arg::with_name("role-permissions")
+                .long("role-permissions")
+                .value_name("ROLE_PERMISSIONS_FILE")
+                .help("JSON file that maps roles to permissions, e.g. {\"scout\":[\"place.review\"]}"),
+        )
+        .arg(
+            Arg::with_name("link-check-interval")
+                .long("link-check-interval")
+                .value_name("LINK_CHECK_INTERVAL_HOURS")
+                .help("Periodically check the links of places and events in the background"),
+        )
         .arg(
             Arg::with_name("fix-event-address-location")
                 .long("fix-event-address-location")
@@ -90,6 +136,34 @@ pub fn run() {
     info!("Initializing Tantivy full-text search engine");
     let search_engine = tantivy::SearchEngine::init_with_path(idx_path).unwrap();
 
+    let role_permissions = matches
+        .value_of("role-permissions")
+        .map(ToString::to_string)
+        .or_else(|| env::var("ROLE_PERMISSIONS_FILE").ok())
+        .map(|path| {
+            info!("Loading role permissions from '{}'", path);
+            load_role_permissions(Path::new(&path))
+                .map_err(|err| anyhow::anyhow!("Invalid role permissions in '{}': {}", path, err))
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    let image_storage = image_storage_from_env()?;
+
+    let link_check_interval = matches
+        .value_of("link-check-interval")
+        .map(ToString::to_string)
+        .or_else(|| env::var("LINK_CHECK_INTERVAL_HOURS").ok())
+        .map(|hours| {
+            hours
+                .parse::<u64>()
+                .ok()
+                .filter(|hours| (1..=MAX_LINK_CHECK_INTERVAL_HOURS).contains(hours))
+                .map(|hours| Duration::from_secs(hours * 60 * 60))
+                .ok_or_else(|| anyhow::anyhow!("Invalid link check interval in hours: {}", hours))
+        })
+        .transpose()?;
+
     #[allow(clippy::match_single_binding)]
     match matches.subcommand() {
         _ => {
@@ -97,11 +171,22 @@ pub fn run() {
                 info!("Updating all event locations...");
                 update_event_locations(&mut *connections.exclusive().unwrap()).unwrap();
             }
+            if let Some(interval) = link_check_interval {
+                info!(
+                    "Checking links every {} hours",
+                    interval.as_secs() / (60 * 60)
+                );
+                spawn_link_checker(connections.clone(), interval);
+            }
             web::run(
                 connections,
                 search_engine,
                 matches.is_present("enable-cors"),
+                matches.is_present("shared-captcha-store"),
+                role_permissions,
+                image_storage.into(),
             );
         }
     }
+    Ok(())
 }
@@ -0,0 +1,101 @@
+use super::{super::guards::*, Result};
+use crate::{
+    adapters::json,
+    core::{prelude::*, usecases},
+    infrastructure::db::sqlite,
+};
+use ofdb_core::user::RolePermissions;
+use rocket::{self, State};
+use rocket_contrib::json::Json;
+
+const AUDIT_LOG_DEFAULT_LIMIT: u64 = 100;
+
+const AUDIT_LOG_MAX_LIMIT: u64 = 1000;
+
+#[get("/audit?<since>&<actor>&<entity>&<action>&<offset>&<limit>")]
+#[allow(clippy::too_many_arguments)]
+pub fn get_audit_log(
+    db: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    account: Account,
+    since: Option<i64>, // in seconds
+    actor: Option<String>,
+    entity: Option<String>,
+    action: Option<String>,
+    offset: Option<u64>,
+    limit: Option<u64>,
+) -> Result<Vec<json::AuditLogEntry>> {
+    let action = action
+        .map(|action| action.parse::<AuditAction>())
+        .transpose()
+        .map_err(|_| Error::Parameter(ParameterError::AuditAction))?;
+    let limit = limit.unwrap_or(AUDIT_LOG_DEFAULT_LIMIT);
+    if limit > AUDIT_LOG_MAX_LIMIT {
+        return Err(Error::Parameter(ParameterError::InvalidLimit).into());
+    }
+    // Conversion from seconds (external) to milliseconds (internal)
+    let query = AuditLogQuery {
+        since: since.map(TimestampMs::from_seconds),
+        actor_email: actor.as_deref(),
+        entity_id: entity.as_deref(),
+        action,
+    };
+    let pagination = Pagination {
+        offset,
+        limit: Some(limit),
+    };
+    let entries = usecases::audit_log(
+        &*db.shared()?,
+        &permissions,
+        account.email(),
+        &query,
+        &pagination,
+    )?;
+    Ok(Json(entries.into_iter().map(Into::into).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::web::api::tests::prelude::*;
+
+    #[test]
+    fn query_audit_log_of_changed_user_regions() {
+        let (client, db) = setup();
+        register_user_with_role(&db, "scout@example.com", Role::Scout);
+        register_user_with_role(&db, "admin@example.com", Role::Admin);
+
+        // Only admins are permitted to read the audit log
+        assert_eq!(Status::Ok, login(&client, "scout@example.com", "secret"));
+        let res = client.get("/audit").dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        assert_eq!(Status::Ok, login(&client, "admin@example.com", "secret"));
+        let res = client
+            .put("/users/scout@example.com/regions")
+            .header(ContentType::JSON)
+            .body(r#"[{"sw":{"lat":40.0,"lng":0.0},"ne":{"lat":50.0,"lng":10.0}}]"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        let mut res = client
+            .get("/audit?actor=admin%40example.com&action=user.change_regions")
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let entries: Vec<json::AuditLogEntry> = serde_json::from_str(&body).unwrap();
+        assert_eq!(1, entries.len());
+        assert_eq!(Some("admin@example.com"), entries[0].by.as_deref());
+        assert_eq!("user.change_regions", entries[0].action);
+        assert_eq!("user", entries[0].entity_kind);
+        assert_eq!("scout@example.com", entries[0].entity_id);
+
+        let mut res = client.get("/audit?entity=other%40example.com").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        assert_eq!("[]", body);
+
+        let res = client.get("/audit?action=unknown").dispatch();
+        assert_eq!(res.status(), Status::BadRequest);
+    }
+}
@@ -1,6 +1,11 @@
-use super::super::guards::{COOKIE_CAPTCHA_KEY, MAX_CAPTCHA_TTL};
+use super::super::{
+    guards::{COOKIE_CAPTCHA_KEY, COOKIE_CAPTCHA_SESSION_KEY, MAX_CAPTCHA_TTL},
+    sqlite,
+};
+use crate::core::prelude::{Captcha, CaptchaKind, CaptchaRepo, Nonce, RepoError, Timestamp};
 use ::captcha::{gen, Difficulty};
 use chrono::prelude::*;
+use rand::Rng;
 use rocket::{
     data::Data,
     http::{ContentType, Cookie, Cookies, RawStr, Status},
@@ -11,46 +16,189 @@ use std::{
     collections::HashMap,
     io::Read,
     sync::{Mutex, MutexGuard},
+    time::Duration,
 };
-use uuid::Uuid;
 
-pub struct CaptchaCache(Mutex<HashMap<Uuid, Option<String>>>);
+type RepoResult<T> = std::result::Result<T, RepoError>;
+
+/// The time span in which a prepared challenge must be
+/// requested and answered.
+pub const MAX_CAPTCHA_CHALLENGE_TTL: Duration = Duration::from_secs(600);
+
+/// Volatile storage for captchas that is only suitable
+/// for running a single instance.
+#[derive(Default)]
+pub struct InMemoryCaptchaRepo(Mutex<HashMap<Nonce, Captcha>>);
+
+impl InMemoryCaptchaRepo {
+    fn lock(&self) -> MutexGuard<HashMap<Nonce, Captcha>> {
+        match self.0.lock() {
+            Ok(guard) => guard,
+            Err(poison_err) => {
+                log::error!("A poisoned mutex guard for the InMemoryCaptchaRepo was found.");
+                poison_err.into_inner()
+            }
+        }
+    }
+}
+
+impl CaptchaRepo for InMemoryCaptchaRepo {
+    fn create_captcha(&self, nonce: &Nonce, captcha: &Captcha) -> RepoResult<()> {
+        self.lock().insert(*nonce, captcha.clone());
+        Ok(())
+    }
+
+    fn update_captcha(&self, nonce: &Nonce, captcha: &Captcha) -> RepoResult<()> {
+        let mut captchas = self.lock();
+        let existing = captchas.get_mut(nonce).ok_or(RepoError::NotFound)?;
+        *existing = captcha.clone();
+        Ok(())
+    }
+
+    fn load_captcha(&self, nonce: &Nonce) -> RepoResult<Captcha> {
+        self.lock().get(nonce).cloned().ok_or(RepoError::NotFound)
+    }
+
+    fn consume_captcha(&self, nonce: &Nonce) -> RepoResult<Captcha> {
+        self.lock().remove(nonce).ok_or(RepoError::NotFound)
+    }
+
+    fn delete_expired_captchas(&self, created_before: Timestamp) -> RepoResult<usize> {
+        let mut captchas = self.lock();
+        let len_before = captchas.len();
+        captchas.retain(|_, captcha| !captcha.is_expired(created_before));
+        Ok(len_before - captchas.len())
+    }
+}
+
+enum CaptchaStore {
+    InMemory(InMemoryCaptchaRepo),
+    // Shared between multiple instances that use the same database
+    Database(sqlite::Connections),
+}
+
+pub struct CaptchaCache {
+    store: CaptchaStore,
+    ttl: Duration,
+}
 
 impl CaptchaCache {
     pub fn new() -> Self {
-        Self(Mutex::new(HashMap::new()))
+        Self {
+            store: CaptchaStore::InMemory(Default::default()),
+            ttl: MAX_CAPTCHA_CHALLENGE_TTL,
+        }
     }
-    pub fn prepare(&self) -> Uuid {
-        let uuid = Uuid::new_v4();
-        self.lock().insert(uuid, None);
-        uuid
+
+    pub fn with_database(connections: sqlite::Connections) -> Self {
+        Self {
+            store: CaptchaStore::Database(connections),
+            ttl: MAX_CAPTCHA_CHALLENGE_TTL,
+        }
     }
-    pub fn is_prepared(&self, uuid: &Uuid) -> bool {
-        self.lock().get(uuid) == Some(&None)
+
+    fn with_repo<T>(&self, f: impl FnOnce(&dyn CaptchaRepo) -> RepoResult<T>) -> RepoResult<T> {
+        match &self.store {
+            CaptchaStore::InMemory(repo) => f(repo),
+            CaptchaStore::Database(connections) => {
+                let connection = connections.exclusive()?;
+                f(&*connection)
+            }
+        }
     }
-    pub fn activate(&self, uuid: Uuid, answer: String) {
-        self.lock().insert(uuid, Some(answer));
+
+    fn created_before(&self) -> Timestamp {
+        Timestamp::from(Utc::now() - chrono::Duration::from_std(self.ttl).unwrap())
     }
-    pub fn verify(&self, uuid: Uuid, answer: String) -> bool {
-        let is_valid = self.lock().get(&uuid) == Some(&Some(answer));
-        self.lock().remove(&uuid);
-        is_valid
+
+    pub fn prepare(&self) -> RepoResult<Nonce> {
+        let created_before = self.created_before();
+        self.with_repo(|repo| {
+            // do housekeeping
+            let expired_count = repo.delete_expired_captchas(created_before)?;
+            if expired_count > 0 {
+                log::debug!("Deleted {} expired captcha(s)", expired_count);
+            }
+            let nonce = Nonce::new();
+            repo.create_captcha(&nonce, &Captcha::prepare())?;
+            Ok(nonce)
+        })
     }
-    fn lock(&self) -> MutexGuard<HashMap<Uuid, Option<String>>> {
-        match self.0.lock() {
-            Ok(guard) => guard,
-            Err(poison_err) => {
-                log::error!("A poisoned mutex guard for the CaptchaCache was found.");
-                poison_err.into_inner()
+
+    /// Store the expected answer for a prepared captcha.
+    ///
+    /// Returns `false` if the captcha is unknown, has expired,
+    /// or if a challenge has already been generated for it.
+    pub fn activate(&self, nonce: &Nonce, kind: CaptchaKind, answer: String) -> RepoResult<bool> {
+        let created_before = self.created_before();
+        self.with_repo(|repo| {
+            let mut captcha = match repo.load_captcha(nonce) {
+                Ok(captcha) => captcha,
+                Err(RepoError::NotFound) => return Ok(false),
+                Err(err) => return Err(err),
+            };
+            if !captcha.is_prepared() || captcha.is_expired(created_before) {
+                return Ok(false);
+            }
+            captcha.challenge = Some((kind, answer));
+            repo.update_captcha(nonce, &captcha)?;
+            Ok(true)
+        })
+    }
+
+    pub fn verify(&self, nonce: &Nonce, answer: &str) -> bool {
+        let created_before = self.created_before();
+        match self.with_repo(|repo| repo.consume_captcha(nonce)) {
+            Ok(captcha) => !captcha.is_expired(created_before) && captcha.is_valid_answer(answer),
+            Err(RepoError::NotFound) => false,
+            Err(err) => {
+                log::warn!("Failed to verify captcha {}: {}", nonce, err);
+                false
             }
         }
     }
 }
 
+fn gen_arithmetic_challenge() -> (String, String) {
+    let mut rng = rand::thread_rng();
+    let a: u8 = rng.gen_range(1, 10);
+    let b: u8 = rng.gen_range(1, 10);
+    if rng.gen() {
+        (format!("What is {} plus {}?", a, b), (a + b).to_string())
+    } else {
+        // Avoid negative results
+        let (a, b) = (a.max(b), a.min(b));
+        (format!("What is {} minus {}?", a, b), (a - b).to_string())
+    }
+}
+
+fn parse_token(token: &RawStr) -> Result<Nonce, Status> {
+    token.as_str().parse().map_err(|_| Status::BadRequest)
+}
+
+fn activate_challenge(
+    captcha_cache: &CaptchaCache,
+    nonce: &Nonce,
+    kind: CaptchaKind,
+    answer: String,
+) -> Result<(), Status> {
+    match captcha_cache.activate(nonce, kind, answer) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(Status::BadRequest),
+        Err(err) => {
+            log::error!("Failed to store captcha challenge: {}", err);
+            Err(Status::InternalServerError)
+        }
+    }
+}
+
 #[post("/captcha", rank = 2)]
 pub fn post_captcha(captcha_cache: State<CaptchaCache>) -> Result<String, Status> {
-    let uuid = captcha_cache.prepare();
-    Ok(uuid.to_simple().to_string())
+    let nonce = captcha_cache.prepare().map_err(|err| {
+        log::error!("Failed to prepare captcha: {}", err);
+        Status::InternalServerError
+    })?;
+    Ok(nonce.to_string())
 }
 
 #[get("/captcha/<token>")]
@@ -58,17 +206,25 @@ pub fn get_captcha(
     captcha_cache: State<CaptchaCache>,
     token: &RawStr,
 ) -> Result<Content<Vec<u8>>, Status> {
-    let uuid: Uuid = token.as_str().parse().map_err(|_| Status::BadRequest)?;
-    if !captcha_cache.is_prepared(&uuid) {
-        return Err(Status::BadRequest);
-    }
+    let nonce = parse_token(token)?;
     let captcha = gen(Difficulty::Easy);
     let answer = captcha.chars_as_string();
-    captcha_cache.activate(uuid, answer);
+    activate_challenge(&captcha_cache, &nonce, CaptchaKind::Image, answer)?;
     let png = captcha.as_png().ok_or(Status::InternalServerError)?;
     Ok(Content(ContentType::PNG, png))
 }
 
+#[get("/captcha/<token>/text")]
+pub fn get_captcha_text(
+    captcha_cache: State<CaptchaCache>,
+    token: &RawStr,
+) -> Result<Content<String>, Status> {
+    let nonce = parse_token(token)?;
+    let (question, answer) = gen_arithmetic_challenge();
+    activate_challenge(&captcha_cache, &nonce, CaptchaKind::Arithmetic, answer)?;
+    Ok(Content(ContentType::Plain, question))
+}
+
 #[post("/captcha/<token>/verify", format = "plain", data = "<data>")]
 pub fn post_captcha_verify(
     mut cookies: Cookies,
@@ -81,8 +237,8 @@ pub fn post_captcha_verify(
         .take(36)
         .read_to_string(&mut answer)
         .map_err(|_| Status::BadRequest)?;
-    let token: Uuid = token.as_str().parse().map_err(|_| Status::BadRequest)?;
-    if captcha_cache.verify(token, answer) {
+    let nonce = parse_token(token)?;
+    if captcha_cache.verify(&nonce, &answer) {
         let now = Utc::now().to_string();
         let expires = time::now() + time::Duration::from_std(MAX_CAPTCHA_TTL).unwrap();
         cookies.add_private(
@@ -91,6 +247,15 @@ pub fn post_captcha_verify(
                 .same_site(rocket::http::SameSite::None)
                 .finish(),
         );
+        // The session outlives the captcha to recognize
+        // anonymous visitors who solve another captcha
+        if cookies.get_private(COOKIE_CAPTCHA_SESSION_KEY).is_none() {
+            cookies.add_private(
+                Cookie::build(COOKIE_CAPTCHA_SESSION_KEY, nonce.to_string())
+                    .same_site(rocket::http::SameSite::None)
+                    .finish(),
+            );
+        }
         Ok(())
     } else {
         Err(Status::BadRequest)
@@ -104,6 +269,16 @@ pub mod tests {
     use std::str::FromStr;
     use uuid::Uuid;
 
+    fn expected_answer(client: &Client, token_str: &str) -> Option<String> {
+        let cache: State<CaptchaCache> = State::from(client.rocket()).unwrap();
+        let nonce = Nonce::from_str(token_str).unwrap();
+        cache
+            .with_repo(|repo| repo.load_captcha(&nonce))
+            .ok()
+            .and_then(|captcha| captcha.challenge)
+            .map(|(_, answer)| answer)
+    }
+
     #[test]
     fn request_new_captcha_challenge() {
         let (client, _) = setup();
@@ -122,6 +297,17 @@ pub mod tests {
         assert_eq!(res.status(), Status::Ok);
     }
 
+    #[test]
+    fn request_captcha_image_only_once() {
+        let (client, _) = setup();
+        let mut res = client.post("/captcha").dispatch();
+        let token = res.body().and_then(|b| b.into_string()).unwrap();
+        let res = client.get(format!("/captcha/{}", token)).dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let res = client.get(format!("/captcha/{}/text", token)).dispatch();
+        assert_eq!(res.status(), Status::BadRequest);
+    }
+
     #[test]
     fn request_invalid_captcha_image() {
         let (client, _) = setup();
@@ -148,9 +334,7 @@ pub mod tests {
         let mut res = client.post("/captcha").dispatch();
         let token_str = res.body().and_then(|b| b.into_string()).unwrap();
         let _ = client.get(format!("/captcha/{}", token_str)).dispatch();
-        let cache: State<CaptchaCache> = State::from(client.rocket()).unwrap();
-        let uuid = Uuid::from_str(&token_str).unwrap();
-        let answer = cache.lock().get(&uuid).unwrap().clone().unwrap();
+        let answer = expected_answer(&client, &token_str).unwrap();
         let res = client
             .post(format!("/captcha/{}/verify", token_str))
             .header(ContentType::Plain)
@@ -158,17 +342,75 @@ pub mod tests {
             .dispatch();
         assert_eq!(res.status(), Status::Ok);
         assert!(cookie_from_response(&res, COOKIE_CAPTCHA_KEY).is_some());
-        let cache: State<CaptchaCache> = State::from(client.rocket()).unwrap();
-        assert!(cache.lock().get(&uuid).is_none());
+        assert!(expected_answer(&client, &token_str).is_none());
+    }
+
+    #[test]
+    fn verify_valid_arithmetic_captcha_answer() {
+        let (client, _) = setup();
+        let mut res = client.post("/captcha").dispatch();
+        let token_str = res.body().and_then(|b| b.into_string()).unwrap();
+        let mut res = client
+            .get(format!("/captcha/{}/text", token_str))
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let question = res.body().and_then(|b| b.into_string()).unwrap();
+        assert!(question.starts_with("What is "));
+        let answer = expected_answer(&client, &token_str).unwrap();
+        assert!(answer.parse::<u8>().is_ok());
+        let res = client
+            .post(format!("/captcha/{}/verify", token_str))
+            .header(ContentType::Plain)
+            .body(answer)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        assert!(cookie_from_response(&res, COOKIE_CAPTCHA_KEY).is_some());
+    }
+
+    #[test]
+    fn evict_expired_captchas() {
+        let repo = InMemoryCaptchaRepo::default();
+        let expired = Nonce::new();
+        repo.create_captcha(
+            &expired,
+            &Captcha {
+                created_at: Timestamp::from_seconds(100),
+                challenge: None,
+            },
+        )
+        .unwrap();
+        let valid = Nonce::new();
+        repo.create_captcha(&valid, &Captcha::prepare()).unwrap();
+        assert_eq!(
+            1,
+            repo.delete_expired_captchas(Timestamp::from_seconds(101))
+                .unwrap()
+        );
+        assert!(repo.load_captcha(&expired).is_err());
+        assert!(repo.load_captcha(&valid).is_ok());
+    }
+
+    #[test]
+    fn share_captchas_in_database() {
+        let (_, connections) = setup();
+        let cache1 = CaptchaCache::with_database(connections.clone());
+        let cache2 = CaptchaCache::with_database(connections);
+        let nonce = cache1.prepare().unwrap();
+        assert!(cache2
+            .activate(&nonce, CaptchaKind::Arithmetic, "3".into())
+            .unwrap());
+        assert!(!cache1
+            .activate(&nonce, CaptchaKind::Arithmetic, "4".into())
+            .unwrap());
+        assert!(cache1.verify(&nonce, "3"));
+        assert!(!cache2.verify(&nonce, "3"));
     }
 
     pub fn get_valid_captcha_cookie(client: &Client) -> Option<Cookie<'static>> {
         let mut res = client.post("/captcha").dispatch();
         let token_str = res.body().and_then(|b| b.into_string()).unwrap();
         let _ = client.get(format!("/captcha/{}", token_str)).dispatch();
-        let cache: State<CaptchaCache> = State::from(client.rocket()).unwrap();
-        let uuid = Uuid::from_str(&token_str).unwrap();
-        let answer = cache.lock().get(&uuid).unwrap().clone().unwrap();
+        let answer = expected_answer(client, &token_str).unwrap();
         let res = client
             .post(format!("/captcha/{}/verify", token_str))
             .header(ContentType::Plain)
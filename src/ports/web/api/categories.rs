@@ -0,0 +1,159 @@
+use super::*;
+
+fn new_category(from: json::NewCategory) -> usecases::NewCategory {
+    let json::NewCategory {
+        tag,
+        names,
+        icon,
+        color,
+    } = from;
+    usecases::NewCategory {
+        tag,
+        names,
+        icon,
+        color,
+    }
+}
+
+// Cached map tiles contain the categories of places and
+// need to be recreated after categories have been modified.
+
+#[post("/categories", format = "application/json", data = "<data>")]
+pub fn post_category(
+    db: sqlite::Connections,
+    search_engine: tantivy::SearchEngine,
+    permissions: State<RolePermissions>,
+    account: Account,
+    data: Json<json::NewCategory>,
+) -> Result<json::Category> {
+    let category = usecases::create_category(
+        &*db.exclusive()?,
+        &permissions,
+        account.email(),
+        new_category(data.into_inner()),
+    )?;
+    search_engine.invalidate_all_tiles();
+    Ok(Json(category.into()))
+}
+
+#[put("/categories/<id>", format = "application/json", data = "<data>")]
+pub fn put_category(
+    db: sqlite::Connections,
+    search_engine: tantivy::SearchEngine,
+    permissions: State<RolePermissions>,
+    account: Account,
+    id: String,
+    data: Json<json::NewCategory>,
+) -> Result<json::Category> {
+    let category = usecases::update_category(
+        &*db.exclusive()?,
+        &permissions,
+        account.email(),
+        &id,
+        new_category(data.into_inner()),
+    )?;
+    search_engine.invalidate_all_tiles();
+    Ok(Json(category.into()))
+}
+
+#[delete("/categories/<id>")]
+pub fn delete_category(
+    db: sqlite::Connections,
+    search_engine: tantivy::SearchEngine,
+    permissions: State<RolePermissions>,
+    account: Account,
+    id: String,
+) -> Result<()> {
+    usecases::delete_category(&*db.exclusive()?, &permissions, account.email(), &id)?;
+    search_engine.invalidate_all_tiles();
+    Ok(Json(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::web::api::tests::prelude::*;
+
+    #[test]
+    fn manage_categories_and_search_by_category() {
+        let (client, db, mut search_engine, _) = setup2();
+        let place = Place::build()
+            .id("repair")
+            .title("Repair cafe")
+            .tags(vec!["repair"])
+            .finish();
+        db.exclusive()
+            .unwrap()
+            .create_or_update_place(place.clone())
+            .unwrap();
+        search_engine
+            .add_or_update_place(&place, ReviewStatus::Created, &AvgRatings::default())
+            .unwrap();
+        search_engine.flush_index().unwrap();
+        let new_category = r##"{"tag":"repair","names":{"en":"Repair","de":"Reparatur"},"icon":"wrench","color":"#ff8800"}"##;
+
+        let res = client
+            .post("/categories")
+            .header(ContentType::JSON)
+            .body(new_category)
+            .dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        login_admin(&client, &db, "admin@example.com");
+        let mut res = client
+            .post("/categories")
+            .header(ContentType::JSON)
+            .body(new_category)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let category: json::Category = serde_json::from_str(&body).unwrap();
+        assert_eq!("#repair", category.name);
+        assert_eq!("Reparatur", category.names["de"]);
+
+        let mut res = client.get("/categories").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let categories: Vec<json::Category> = serde_json::from_str(&body).unwrap();
+        assert_eq!(4, categories.len());
+
+        let mut res = client
+            .get(format!(
+                "/search?bbox=-10,-10,10,10&categories={}",
+                category.id
+            ))
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let response: json::SearchResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(1, response.visible.len());
+        assert_eq!(vec![category.id.clone()], response.visible[0].categories);
+        assert!(response.visible[0].tags.is_empty());
+
+        let res = client
+            .put(format!("/categories/{}", Category::ID_EVENT))
+            .header(ContentType::JSON)
+            .body(r#"{"tag":"meetup","names":{"en":"Meetup"}}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::BadRequest);
+        let res = client
+            .put(format!("/categories/{}", Category::ID_EVENT))
+            .header(ContentType::JSON)
+            .body(r#"{"tag":"event","names":{"en":"Event","de":"Veranstaltung"}}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        let res = client
+            .delete(format!("/categories/{}", Category::ID_NON_PROFIT))
+            .dispatch();
+        assert_eq!(res.status(), Status::BadRequest);
+        let res = client
+            .delete(format!("/categories/{}", category.id))
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let res = client
+            .delete(format!("/categories/{}", category.id))
+            .dispatch();
+        assert_eq!(res.status(), Status::NotFound);
+    }
+}
@@ -32,10 +32,15 @@ pub fn get_entry(
     let results = {
         let db = db.shared()?;
         let places = usecases::load_places(&*db, &ids, org_tag.as_ref().map(String::as_str))?;
+        let all_categories = db.all_categories()?;
         let mut results = Vec::with_capacity(places.len());
         for (place, _) in places.into_iter() {
             let r = db.load_ratings_of_place(place.id.as_ref())?;
-            results.push(json::entry_from_place_with_ratings(place, r));
+            results.push(json::entry_from_place_with_ratings(
+                place,
+                r,
+                &all_categories,
+            ));
         }
         results
     };
@@ -109,17 +114,24 @@ pub fn get_entries_recently_changed(
     let results = {
         let db = db.shared()?;
         let entries = db.recently_changed_places(&params, &pagination)?;
+        let all_categories = db.all_categories()?;
         if with_ratings.unwrap_or(false) {
             let mut results = Vec::with_capacity(entries.len());
             for (place, _, _) in entries.into_iter() {
                 let r = db.load_ratings_of_place(place.id.as_ref())?;
-                results.push(json::entry_from_place_with_ratings(place, r));
+                results.push(json::entry_from_place_with_ratings(
+                    place,
+                    r,
+                    &all_categories,
+                ));
             }
             results
         } else {
             entries
                 .into_iter()
-                .map(|(place, _, _)| json::entry_from_place_with_ratings(place, vec![]))
+                .map(|(place, _, _)| {
+                    json::entry_from_place_with_ratings(place, vec![], &all_categories)
+                })
                 .collect()
         }
     };
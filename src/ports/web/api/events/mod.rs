@@ -7,7 +7,7 @@ use crate::{
     },
     infrastructure::{flows::prelude as flows, GEO_CODING_GW},
 };
-use ofdb_core::gateways::geocode::GeoCodingGateway;
+use ofdb_core::{gateways::geocode::GeoCodingGateway, user::RolePermissions};
 
 use rocket::{
     http::{RawStr, Status as HttpStatus},
@@ -209,6 +209,12 @@ impl<'q> FromQuery<'q> for usecases::EventQuery {
             .map(|i| i.value.url_decode_lossy())
             .find(|v| !v.is_empty());
 
+        let org_tag = query
+            .clone()
+            .filter(|i| i.key == "org_tag")
+            .map(|i| i.value.url_decode_lossy())
+            .find(|v| !v.is_empty());
+
         drop(query); // silence clippy warning
         Ok(usecases::EventQuery {
             bbox,
@@ -218,6 +224,7 @@ impl<'q> FromQuery<'q> for usecases::EventQuery {
             start_min,
             tags,
             text,
+            org_tag,
         })
     }
 }
@@ -306,6 +313,7 @@ pub fn get_events_chronologically(
 pub fn csv_export(
     connections: sqlite::Connections,
     search_engine: tantivy::SearchEngine,
+    permissions: State<RolePermissions>,
     auth: Auth,
     query: usecases::EventQuery,
 ) -> result::Result<Content<String>, AppError> {
@@ -317,7 +325,7 @@ pub fn csv_export(
         vec![]
     };
 
-    let user = auth.user_with_min_role(&*db, Role::Scout)?;
+    let user = auth.user_with_permission(&*db, &permissions, Permission::EventExport)?;
 
     let limit = if let Some(limit) = query.limit {
         // Limited
@@ -337,6 +345,7 @@ pub fn csv_export(
     let events = events.into_iter().map(|e| {
         usecases::export_event(
             e,
+            &permissions,
             user.role,
             moderated_tags
                 .iter()
@@ -363,6 +372,7 @@ pub fn post_events_archive(
     auth: Auth,
     db: sqlite::Connections,
     mut search_engine: tantivy::SearchEngine,
+    permissions: State<RolePermissions>,
     ids: String,
 ) -> StatusResult {
     let ids = util::split_ids(&ids);
@@ -371,8 +381,13 @@ pub fn post_events_archive(
     }
     let archived_by_email = {
         let db = db.shared()?;
-        // Only scouts and admins are entitled to review events
-        auth.user_with_min_role(&*db, Role::Scout)?.email
+        usecases::authorize_user_for_archiving_events(
+            &*db,
+            &permissions,
+            auth.account_email()?,
+            &ids,
+        )?
+        .email
     };
     let update_count = flows::archive_events(&db, &mut search_engine, &ids, &archived_by_email)?;
     if update_count < ids.len() {
@@ -386,6 +401,70 @@ pub fn post_events_archive(
     Ok(HttpStatus::NoContent)
 }
 
+#[get("/events/clearance/count")]
+pub fn count_pending_clearances(db: sqlite::Connections, auth: Auth) -> Result<json::ResultCount> {
+    let db = db.shared()?;
+    let count =
+        usecases::clearance::event::count_pending_clearances(&*db, &auth.organization(&*db)?)?;
+    Ok(Json(json::ResultCount { count }))
+}
+
+#[get("/events/clearance?<offset>&<limit>")]
+pub fn list_pending_clearances(
+    db: sqlite::Connections,
+    auth: Auth,
+    offset: Option<u64>,
+    limit: Option<u64>,
+) -> Result<Vec<json::PendingClearanceForEvent>> {
+    let pagination = Pagination { offset, limit };
+    let db = db.shared()?;
+    let org = auth.organization(&*db)?;
+    let pending_clearances =
+        usecases::clearance::event::list_pending_clearances(&*db, &org, &pagination)?;
+    // Release the database connection asap
+    drop(db);
+
+    let moderated_tags = org.moderated_tags;
+    Ok(Json(
+        pending_clearances
+            .into_iter()
+            .map(|mut pending_clearance| {
+                pending_clearance.last_cleared_event =
+                    pending_clearance.last_cleared_event.map(|e| {
+                        usecases::filter_event(
+                            e,
+                            moderated_tags
+                                .iter()
+                                .map(|moderated_tag| moderated_tag.label.as_str()),
+                        )
+                    });
+                pending_clearance.into()
+            })
+            .collect(),
+    ))
+}
+
+#[post("/events/clearance", data = "<clearances>")]
+pub fn update_pending_clearances(
+    db: sqlite::Connections,
+    auth: Auth,
+    clearances: Json<Vec<json::ClearanceForEvent>>,
+) -> Result<json::ResultCount> {
+    let clearances: Vec<_> = clearances
+        .into_inner()
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let count = usecases::clearance::event::update_pending_clearances(
+        &*db.exclusive()?,
+        &auth.organization(&*db.shared()?)?,
+        &clearances,
+    )?;
+    Ok(Json(json::ResultCount {
+        count: count as u64,
+    }))
+}
+
 #[delete("/events/<_id>", rank = 2)]
 pub fn delete_event(mut _db: sqlite::Connections, _id: &RawStr) -> HttpStatus {
     HttpStatus::Unauthorized
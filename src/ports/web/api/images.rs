@@ -0,0 +1,132 @@
+use super::*;
+use crate::{infrastructure::IMAGE_BASE_URL, ports::web::image_storage::ImageStorage};
+use rocket::data::Data;
+use std::io::Read;
+
+/// Uploads that exceed this size are rejected before processing
+const MAX_UPLOAD_SIZE: u64 = 10 * 1024 * 1024;
+
+#[post("/images?<license>&<attribution>", data = "<data>")]
+pub fn post_image(
+    db: sqlite::Connections,
+    storage: ImageStorage,
+    auth: Auth,
+    license: String,
+    attribution: Option<String>,
+    data: Data,
+) -> Result<json::Image> {
+    let org = auth.organization(&*db.shared()?).ok();
+    let email = auth.account_email().ok();
+    if org.is_none() && email.is_none() {
+        return Err(Error::Parameter(ParameterError::Unauthorized).into());
+    }
+    let mut buf = Vec::new();
+    data.open()
+        .take(MAX_UPLOAD_SIZE + 1)
+        .read_to_end(&mut buf)?;
+    if buf.len() as u64 > MAX_UPLOAD_SIZE {
+        return Err(Error::Parameter(ParameterError::ImageTooLarge).into());
+    }
+    // Processing and storing the image must not block other writers
+    let image = usecases::store_image(
+        &*storage,
+        &buf,
+        usecases::NewImage {
+            license,
+            attribution,
+        },
+        email,
+    )?;
+    usecases::add_stored_image(&*db.exclusive()?, &*storage, &image)?;
+    Ok(Json(json::image(image, &IMAGE_BASE_URL)))
+}
+
+#[get("/images/<id>/metadata")]
+pub fn get_image_metadata(db: sqlite::Connections, id: String) -> Result<json::Image> {
+    let image = db.shared()?.get_image(&id)?;
+    Ok(Json(json::image(image, &IMAGE_BASE_URL)))
+}
+
+#[get("/images/<id>")]
+pub fn get_image(
+    db: sqlite::Connections,
+    storage: ImageStorage,
+    id: String,
+) -> result::Result<Content<Vec<u8>>, AppError> {
+    load_image(&db, &*storage, &id, ImageVariant::Original)
+}
+
+#[get("/images/<id>/thumbnail")]
+pub fn get_image_thumbnail(
+    db: sqlite::Connections,
+    storage: ImageStorage,
+    id: String,
+) -> result::Result<Content<Vec<u8>>, AppError> {
+    load_image(&db, &*storage, &id, ImageVariant::Thumbnail)
+}
+
+fn load_image(
+    db: &sqlite::Connections,
+    storage: &dyn ofdb_core::gateways::image::ImageStorageGateway,
+    id: &str,
+    variant: ImageVariant,
+) -> result::Result<Content<Vec<u8>>, AppError> {
+    let (image, data) = usecases::load_image(&*db.shared()?, storage, id, variant)?;
+    let content_type = match image.format {
+        ImageFormat::Jpeg => ContentType::JPEG,
+        ImageFormat::Png => ContentType::PNG,
+    };
+    Ok(Content(content_type, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::web::{api::tests::prelude::*, tests::register_user};
+
+    // 1x1 transparent GIF
+    const GIF: &[u8] = &[
+        0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xff, 0xff, 0xff, 0x21, 0xf9, 0x04, 0x01, 0x00, 0x00, 0x00, 0x00, 0x2c, 0x00, 0x00,
+        0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x02, 0x02, 0x44, 0x01, 0x00, 0x3b,
+    ];
+
+    #[test]
+    fn upload_image() {
+        let (client, db) = setup();
+        let res = client
+            .post("/images?license=CC0-1.0")
+            .header(ContentType::GIF)
+            .body(GIF)
+            .dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        register_user(&db, "user@example.com", "secret", true);
+        assert_eq!(Status::Ok, login(&client, "user@example.com", "secret"));
+        let res = client
+            .post("/images?license=CC-BY-4.0")
+            .header(ContentType::GIF)
+            .body(GIF)
+            .dispatch();
+        assert_eq!(res.status(), Status::BadRequest);
+        let mut res = client
+            .post("/images?license=CC-BY-4.0&attribution=Jane%20Doe")
+            .header(ContentType::GIF)
+            .body(GIF)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let image: json::Image = serde_json::from_str(&body).unwrap();
+        assert_eq!(Some("Jane Doe"), image.attribution.as_deref());
+        assert_eq!(format!("{}/{}", *IMAGE_BASE_URL, image.id), image.url);
+
+        let mut res = client
+            .get(format!("/images/{}/thumbnail", image.id))
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        assert_eq!(res.content_type(), Some(ContentType::PNG));
+        assert!(res.body_bytes().unwrap().starts_with(b"\x89PNG"));
+        let res = client.get("/images/unknown").dispatch();
+        assert_eq!(res.status(), Status::NotFound);
+    }
+}
@@ -0,0 +1,122 @@
+use super::{super::guards::*, Result};
+use crate::{
+    adapters::json,
+    core::{prelude::*, usecases},
+    infrastructure::db::sqlite,
+};
+use ofdb_core::user::RolePermissions;
+use rocket::{self, State};
+use rocket_contrib::json::Json;
+
+const LINK_REPORT_DEFAULT_LIMIT: u64 = 100;
+
+const LINK_REPORT_MAX_LIMIT: u64 = 1000;
+
+#[get("/links/report?<status>&<kind>&<offset>&<limit>")]
+#[allow(clippy::too_many_arguments)]
+pub fn get_link_report(
+    db: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    account: Account,
+    status: Option<String>,
+    kind: Option<String>,
+    offset: Option<u64>,
+    limit: Option<u64>,
+) -> Result<Vec<json::LinkCheck>> {
+    let status = status
+        .map(|status| status.parse::<LinkStatus>())
+        .transpose()
+        .map_err(|_| Error::Parameter(ParameterError::LinkStatus))?;
+    let owner_kind = kind
+        .map(|kind| kind.parse::<LinkOwnerKind>())
+        .transpose()
+        .map_err(|_| Error::Parameter(ParameterError::LinkOwnerKind))?;
+    let limit = limit.unwrap_or(LINK_REPORT_DEFAULT_LIMIT);
+    if limit > LINK_REPORT_MAX_LIMIT {
+        return Err(Error::Parameter(ParameterError::InvalidLimit).into());
+    }
+    let query = LinkCheckQuery { owner_kind, status };
+    let pagination = Pagination {
+        offset,
+        limit: Some(limit),
+    };
+    let checks = usecases::link_check_report(
+        &*db.shared()?,
+        &permissions,
+        account.email(),
+        &query,
+        &pagination,
+    )?;
+    Ok(Json(checks.into_iter().map(Into::into).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::web::{api::tests::prelude::*, tests::register_user};
+
+    #[test]
+    fn report_broken_links() {
+        let (client, db) = setup();
+        register_user(&db, "user@example.com", "secret", true);
+        register_user_with_role(&db, "admin@example.com", Role::Admin);
+        {
+            let db = db.exclusive().unwrap();
+            let outcome = |status, http_status| LinkCheckOutcome {
+                status,
+                http_status,
+                redirect_url: None,
+            };
+            db.save_link_checks(&[
+                LinkCheck {
+                    owner: LinkOwner {
+                        kind: LinkOwnerKind::Place,
+                        id: "p1".into(),
+                    },
+                    url: "https://foo.org/".parse().unwrap(),
+                    outcome: outcome(LinkStatus::Broken, Some(404)),
+                    checked_at: TimestampMs::now(),
+                },
+                LinkCheck {
+                    owner: LinkOwner {
+                        kind: LinkOwnerKind::Event,
+                        id: "e1".into(),
+                    },
+                    url: "https://bar.org/".parse().unwrap(),
+                    outcome: outcome(LinkStatus::Ok, Some(200)),
+                    checked_at: TimestampMs::now(),
+                },
+            ])
+            .unwrap();
+        }
+
+        let res = client.get("/links/report").dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        // Only admins are permitted to read the report
+        assert_eq!(Status::Ok, login(&client, "user@example.com", "secret"));
+        let res = client.get("/links/report").dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        assert_eq!(Status::Ok, login(&client, "admin@example.com", "secret"));
+        let mut res = client.get("/links/report?status=broken").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let checks: Vec<json::LinkCheck> = serde_json::from_str(&body).unwrap();
+        assert_eq!(1, checks.len());
+        assert_eq!("place", checks[0].owner_kind);
+        assert_eq!("p1", checks[0].owner_id);
+        assert_eq!("https://foo.org/", checks[0].url);
+        assert_eq!(Some(404), checks[0].http_status);
+
+        let mut res = client.get("/links/report?kind=event").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let checks: Vec<json::LinkCheck> = serde_json::from_str(&body).unwrap();
+        assert_eq!(1, checks.len());
+        assert_eq!("ok", checks[0].status);
+
+        let res = client.get("/links/report?status=dead").dispatch();
+        assert_eq!(res.status(), Status::BadRequest);
+    }
+}
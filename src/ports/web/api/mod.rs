@@ -13,6 +13,7 @@ use crate::{
     },
     ports::web::{jwt, notify::*},
 };
+use ofdb_core::user::RolePermissions;
 use rocket::{
     self,
     http::{ContentType, Cookie, Cookies, Status},
@@ -23,15 +24,24 @@ use rocket::{
 use rocket_contrib::json::Json;
 use std::result;
 
+mod audit;
 pub mod captcha;
+mod categories;
 mod count;
 mod entries;
 pub mod events;
+mod images;
+mod links;
 mod places;
 mod ratings;
+mod saved_searches;
 mod search;
+mod stats;
+mod tags;
+mod taxonomy;
 #[cfg(test)]
 pub mod tests;
+mod tiles;
 mod users;
 
 type Result<T> = result::Result<Json<T>, AppError>;
@@ -45,6 +55,10 @@ pub fn routes() -> Vec<Route> {
         subscribe_to_bbox,
         get_bbox_subscriptions,
         unsubscribe_all_bboxes,
+        saved_searches::post_saved_search,
+        saved_searches::get_saved_searches,
+        saved_searches::put_saved_search,
+        saved_searches::delete_saved_search,
         entries::get_entry,
         entries::get_entries_recently_changed,
         entries::get_entries_most_popular_tags,
@@ -65,18 +79,45 @@ pub fn routes() -> Vec<Route> {
         events::delete_event,
         events::delete_event_with_token,
         events::csv_export,
+        events::count_pending_clearances,
+        events::list_pending_clearances,
+        events::update_pending_clearances,
         users::post_request_password_reset,
         users::post_reset_password,
+        users::post_change_password,
+        users::post_request_email_change,
+        users::post_change_email,
         users::post_user,
         ratings::post_rating,
         ratings::load_rating,
+        ratings::post_rating_flag,
+        ratings::post_rating_comment,
+        ratings::get_flagged_ratings,
         users::get_user,
         users::get_current_user,
+        users::get_current_user_export,
         users::delete_user,
+        users::get_anonymized_users,
+        users::get_user_regions,
+        users::put_user_regions,
+        audit::get_audit_log,
+        stats::get_stats,
+        tiles::get_places_tile,
         get_categories,
         get_category,
+        categories::post_category,
+        categories::put_category,
+        categories::delete_category,
         get_tags,
+        tags::get_tag,
+        tags::put_tag,
+        tags::post_tags_merge,
+        taxonomy::get_taxonomy,
+        taxonomy::put_taxonomy,
+        taxonomy::get_taxonomy_suggest,
         search::get_search,
+        search::get_search_clusters,
+        search::get_search_suggest,
         get_duplicates,
         search::post_search_duplicates,
         count::get_count_entries,
@@ -87,8 +128,24 @@ pub fn routes() -> Vec<Route> {
         places::count_pending_clearances,
         places::list_pending_clearances,
         places::update_pending_clearances,
+        places::reject_pending_clearances,
+        places::post_ownership_request,
+        places::post_ownership_confirmation,
+        places::get_ownership,
+        places::put_locked_fields,
+        places::post_suggestion,
+        places::get_place_suggestions,
+        places::get_suggestions,
+        places::post_suggestion_accept,
+        places::post_suggestion_decline,
+        images::post_image,
+        images::get_image,
+        images::get_image_thumbnail,
+        images::get_image_metadata,
+        links::get_link_report,
         captcha::post_captcha,
         captcha::get_captcha,
+        captcha::get_captcha_text,
         captcha::post_captcha_verify,
     ]
 }
@@ -113,6 +170,7 @@ pub fn get_place(
 #[get("/places/<id>/history/<revision>")]
 pub fn get_place_history_revision(
     db: sqlite::Connections,
+    permissions: State<RolePermissions>,
     auth: Auth,
     id: String,
     revision: RevisionValue,
@@ -121,8 +179,12 @@ pub fn get_place_history_revision(
         let db = db.shared()?;
 
         // The history contains e-mail addresses of registered users
-        // is only permitted for scouts and admins or organizations!
-        if auth.user_with_min_role(&*db, Role::Scout).is_err() {
+        // is only permitted for users with the corresponding permission
+        // or organizations!
+        if auth
+            .user_with_permission(&*db, &permissions, Permission::PlaceReadHistory)
+            .is_err()
+        {
             auth.organization(&*db)?;
         }
 
@@ -134,6 +196,7 @@ pub fn get_place_history_revision(
 #[get("/places/<id>/history", rank = 2)]
 pub fn get_place_history(
     db: sqlite::Connections,
+    permissions: State<RolePermissions>,
     auth: Auth,
     id: String,
 ) -> Result<json::PlaceHistory> {
@@ -141,8 +204,12 @@ pub fn get_place_history(
         let db = db.shared()?;
 
         // The history contains e-mail addresses of registered users
-        // is only permitted for scouts and admins or for organizations!
-        if auth.user_with_min_role(&*db, Role::Scout).is_err() {
+        // is only permitted for users with the corresponding permission
+        // or for organizations!
+        if auth
+            .user_with_permission(&*db, &permissions, Permission::PlaceReadHistory)
+            .is_err()
+        {
             auth.organization(&*db)?;
         }
 
@@ -156,6 +223,7 @@ pub fn post_places_review(
     auth: Auth,
     db: sqlite::Connections,
     mut search_engine: tantivy::SearchEngine,
+    permissions: State<RolePermissions>,
     ids: String,
     review: Json<json::Review>,
 ) -> Result<()> {
@@ -163,18 +231,25 @@ pub fn post_places_review(
     if ids.is_empty() {
         return Err(Error::Parameter(ParameterError::EmptyIdList).into());
     }
+    let json::Review { status, comment } = review.into_inner();
+    let status = status.into();
     let reviewer_email = {
         let db = db.shared()?;
-        // Only scouts and admins are entitled to review places
-        auth.user_with_min_role(&*db, Role::Scout)?.email
+        usecases::authorize_user_for_reviewing_places(
+            &*db,
+            &permissions,
+            auth.account_email()?,
+            &ids,
+            status,
+        )?
+        .email
     };
-    let json::Review { status, comment } = review.into_inner();
     // TODO: Record context information
     let context = None;
     let review = usecases::Review {
         context,
         reviewer_email: reviewer_email.into(),
-        status: status.into(),
+        status,
         comment,
     };
     let update_count = flows::review_places(&db, &mut search_engine, &ids, review)?;
@@ -318,13 +393,7 @@ fn get_bbox_subscriptions(
     let email = account.email();
     let user_subscriptions = usecases::get_bbox_subscriptions(&*db.shared()?, &email)?
         .into_iter()
-        .map(|s| json::BboxSubscription {
-            id: s.id.into(),
-            south_west_lat: s.bbox.southwest().lat().to_deg(),
-            south_west_lng: s.bbox.southwest().lng().to_deg(),
-            north_east_lat: s.bbox.northeast().lat().to_deg(),
-            north_east_lng: s.bbox.northeast().lng().to_deg(),
-        })
+        .map(Into::into)
         .collect();
     Ok(Json(user_subscriptions))
 }
@@ -368,6 +437,7 @@ fn get_category(connections: sqlite::Connections, ids: String) -> Result<Vec<jso
 fn entries_csv_export(
     connections: sqlite::Connections,
     search_engine: tantivy::SearchEngine,
+    permissions: State<RolePermissions>,
     auth: Auth,
     query: Form<search::SearchQuery>,
 ) -> result::Result<Content<String>, AppError> {
@@ -378,7 +448,7 @@ fn entries_csv_export(
         _ => vec![],
     };
 
-    let user = auth.user_with_min_role(&*db, Role::Scout)?;
+    let user = auth.user_with_permission(&*db, &permissions, Permission::PlaceExport)?;
 
     let (req, limit) = search::parse_search_query(&query)?;
     let limit = if let Some(limit) = limit {
@@ -401,15 +471,12 @@ fn entries_csv_export(
                     ..
                 } = indexed_entry;
                 if let Ok((mut place, _)) = db.get_place(id) {
-                    let (tags, categories) = Category::split_from_tags(place.tags);
+                    let (tags, categories) =
+                        Category::split_from_tags_with(&all_categories, place.tags);
                     place.tags = tags;
-                    let categories = all_categories
-                        .iter()
-                        .filter(|c1| categories.iter().any(|c2| c1.id == c2.id))
-                        .cloned()
-                        .collect::<Vec<Category>>();
                     let place = usecases::export_place(
                         place,
+                        &permissions,
                         user.role,
                         moderated_tags
                             .iter()
@@ -455,9 +522,10 @@ impl<'r> Responder<'r> for AppError {
                         ParameterError::EmailNotConfirmed => {
                             <Status>::new(403, "EmailNotConfirmed")
                         }
-                        ParameterError::Forbidden | ParameterError::ModeratedTag => {
-                            Status::Forbidden
-                        }
+                        ParameterError::Forbidden
+                        | ParameterError::ModeratedTag
+                        | ParameterError::LockedField(_) => Status::Forbidden,
+                        ParameterError::ImageTooLarge => Status::PayloadTooLarge,
                         _ => Status::BadRequest,
                     });
                 }
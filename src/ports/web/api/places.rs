@@ -47,3 +47,346 @@ pub fn update_pending_clearances(
         count: count as u64,
     }))
 }
+
+#[post("/places/clearance/reject", data = "<rejections>")]
+pub fn reject_pending_clearances(
+    db: sqlite::Connections,
+    notify: Notify,
+    auth: Auth,
+    rejections: Json<Vec<json::RejectionForPlace>>,
+) -> Result<json::ResultCount> {
+    let rejections: Vec<_> = rejections
+        .into_inner()
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let org = auth.organization(&*db.shared()?)?;
+    let count = flows::reject_place_clearances(&db, &*notify, &org, &rejections)?;
+    Ok(Json(json::ResultCount {
+        count: count as u64,
+    }))
+}
+
+#[post("/places/<id>/ownership")]
+pub fn post_ownership_request(
+    db: sqlite::Connections,
+    notify: Notify,
+    account: Account,
+    id: String,
+) -> Result<()> {
+    flows::request_place_ownership(&db, &*notify, account.email(), &id)?;
+    Ok(Json(()))
+}
+
+#[post(
+    "/places/ownership/confirm",
+    format = "application/json",
+    data = "<data>"
+)]
+pub fn post_ownership_confirmation(
+    db: sqlite::Connections,
+    data: Json<json::ConfirmPlaceOwnership>,
+) -> Result<json::PlaceOwnership> {
+    let req = data.into_inner();
+    let email_nonce = EmailNonce::decode_from_str(&req.token)?;
+    let ownership = flows::confirm_place_ownership_with_email_nonce(&db, email_nonce)?;
+    Ok(Json(ownership.into()))
+}
+
+#[get("/places/<id>/ownership")]
+pub fn get_ownership(db: sqlite::Connections, id: String) -> Result<json::PlaceOwnership> {
+    let ownership = db
+        .shared()?
+        .get_place_ownership(&id)?
+        .ok_or(RepoError::NotFound)?;
+    Ok(Json(ownership.into()))
+}
+
+#[put(
+    "/places/<id>/ownership/locked-fields",
+    format = "application/json",
+    data = "<fields>"
+)]
+pub fn put_locked_fields(
+    db: sqlite::Connections,
+    account: Account,
+    id: String,
+    fields: Json<Vec<String>>,
+) -> Result<()> {
+    let fields = fields
+        .into_inner()
+        .iter()
+        .map(|f| f.parse::<PlaceField>())
+        .collect::<result::Result<Vec<_>, _>>()
+        .map_err(|_| Error::Parameter(ParameterError::PlaceField))?;
+    usecases::lock_place_fields(&*db.exclusive()?, account.email(), &id, &fields)?;
+    Ok(Json(()))
+}
+
+// Organizations are identified by their API token and
+// take precedence over logged in users
+fn suggestion_reviewer<T>(
+    db: &sqlite::Connections,
+    auth: &Auth,
+    f: impl FnOnce(usecases::SuggestionReviewer) -> result::Result<T, AppError>,
+) -> result::Result<T, AppError> {
+    let org = auth.organization(&*db.shared()?).ok();
+    if let Some(org) = &org {
+        return f(usecases::SuggestionReviewer::Organization(org));
+    }
+    f(usecases::SuggestionReviewer::User(auth.account_email()?))
+}
+
+#[post(
+    "/places/<id>/suggestions",
+    format = "application/json",
+    data = "<data>"
+)]
+pub fn post_suggestion(
+    db: sqlite::Connections,
+    auth: Auth,
+    id: String,
+    data: Json<json::UpdatePlace>,
+) -> Result<String> {
+    let org = auth.organization(&*db.shared()?).ok();
+    if org.is_none() && auth.account_email().is_err() {
+        auth.has_captcha()?;
+    }
+    let suggestion = usecases::suggest_place_edit(
+        &*db.exclusive()?,
+        id.into(),
+        data.into_inner().into(),
+        auth.account_email().ok(),
+    )?;
+    Ok(Json(suggestion.id.into()))
+}
+
+#[get("/places/<id>/suggestions")]
+pub fn get_place_suggestions(
+    db: sqlite::Connections,
+    auth: Auth,
+    permissions: State<RolePermissions>,
+    id: String,
+) -> Result<Vec<json::PlaceEditSuggestion>> {
+    let suggestions = suggestion_reviewer(&db, &auth, |reviewer| {
+        Ok(usecases::load_pending_place_edit_suggestions(
+            &*db.shared()?,
+            &permissions,
+            reviewer,
+            Some(&id),
+            &Default::default(),
+        )?)
+    })?;
+    Ok(Json(suggestions.into_iter().map(Into::into).collect()))
+}
+
+#[get("/places/suggestions?<offset>&<limit>")]
+pub fn get_suggestions(
+    db: sqlite::Connections,
+    auth: Auth,
+    permissions: State<RolePermissions>,
+    offset: Option<u64>,
+    limit: Option<u64>,
+) -> Result<Vec<json::PlaceEditSuggestion>> {
+    let pagination = Pagination { offset, limit };
+    let suggestions = suggestion_reviewer(&db, &auth, |reviewer| {
+        Ok(usecases::load_pending_place_edit_suggestions(
+            &*db.shared()?,
+            &permissions,
+            reviewer,
+            None,
+            &pagination,
+        )?)
+    })?;
+    Ok(Json(suggestions.into_iter().map(Into::into).collect()))
+}
+
+#[post("/places/suggestions/<id>/accept")]
+pub fn post_suggestion_accept(
+    db: sqlite::Connections,
+    mut search_engine: tantivy::SearchEngine,
+    notify: Notify,
+    auth: Auth,
+    permissions: State<RolePermissions>,
+    id: String,
+) -> Result<String> {
+    let place = suggestion_reviewer(&db, &auth, |reviewer| {
+        flows::accept_place_edit_suggestion(
+            &db,
+            &mut search_engine,
+            &*notify,
+            &permissions,
+            reviewer,
+            &id,
+        )
+    })?;
+    Ok(Json(place.id.into()))
+}
+
+#[post(
+    "/places/suggestions/<id>/decline",
+    format = "application/json",
+    data = "<data>"
+)]
+pub fn post_suggestion_decline(
+    db: sqlite::Connections,
+    auth: Auth,
+    permissions: State<RolePermissions>,
+    id: String,
+    data: Json<json::DeclineSuggestion>,
+) -> Result<()> {
+    let json::DeclineSuggestion { reason } = data.into_inner();
+    suggestion_reviewer(&db, &auth, |reviewer| {
+        Ok(usecases::decline_place_edit_suggestion(
+            &*db.exclusive()?,
+            &permissions,
+            reviewer,
+            &id,
+            reason,
+        )?)
+    })?;
+    Ok(Json(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::web::{api::tests::prelude::*, tests::register_user};
+
+    #[test]
+    fn claim_place_and_lock_fields() {
+        let (client, db, mut search_engine, notify) = setup2();
+        register_user(&db, "owner@example.com", "secret", true);
+        let new_place = usecases::NewPlace {
+            title: "foo".into(),
+            description: "bar".into(),
+            email: Some("contact@example.com".into()),
+            ..default_new_entry()
+        };
+        let place_id = flows::create_place(&db, &mut search_engine, &notify, new_place, None, None)
+            .unwrap()
+            .id;
+
+        let res = client
+            .post(format!("/places/{}/ownership", place_id))
+            .dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+        let res = client
+            .get(format!("/places/{}/ownership", place_id))
+            .dispatch();
+        assert_eq!(res.status(), Status::NotFound);
+
+        // User sends the request
+        assert_eq!(Status::Ok, login(&client, "owner@example.com", "secret"));
+        let res = client
+            .post(format!("/places/{}/ownership", place_id))
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        // The contact of the place gets an email with the token
+        let token = db
+            .shared()
+            .unwrap()
+            .get_user_token_by_email("owner@example.com")
+            .unwrap();
+        assert_eq!(Some(place_id.as_str()), token.place_id.as_deref());
+        let token = token.email_nonce.encode_to_string();
+        let res = client
+            .post("/places/ownership/confirm")
+            .header(ContentType::JSON)
+            .body(format!("{{\"token\":\"{}\"}}", token))
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        let res = client
+            .put(format!("/places/{}/ownership/locked-fields", place_id))
+            .header(ContentType::JSON)
+            .body(r#"["unknown"]"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::BadRequest);
+        let res = client
+            .put(format!("/places/{}/ownership/locked-fields", place_id))
+            .header(ContentType::JSON)
+            .body(r#"["title","opening_hours"]"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        let mut res = client
+            .get(format!("/places/{}/ownership", place_id))
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let ownership: json::PlaceOwnership = serde_json::from_str(&body).unwrap();
+        assert_eq!(place_id.as_str(), ownership.place_id);
+        assert_eq!(vec!["title", "opening_hours"], ownership.locked_fields);
+        // The owner is not disclosed
+        assert!(!body.contains("owner@example.com"));
+    }
+
+    #[test]
+    fn suggest_edit_of_moderated_place() {
+        let (client, db, _, _) = setup2();
+        db.exclusive()
+            .unwrap()
+            .create_or_update_place(Place::build().id("foo").tags(vec!["a"]).finish())
+            .unwrap();
+        db.exclusive()
+            .unwrap()
+            .create_org(Organization {
+                id: "a".into(),
+                name: "a".into(),
+                moderated_tags: vec!["a".into()],
+                api_token: "a".into(),
+            })
+            .unwrap();
+        register_user(&db, "user@example.com", "secret", true);
+        assert_eq!(Status::Ok, login(&client, "user@example.com", "secret"));
+        let body = r#"{"version":1,"title":"foo","description":"bar","lat":0.0,"lng":0.0,"categories":[],"tags":[]}"#;
+
+        // Removing the moderated tag directly is not allowed
+        let res = client
+            .put("/entries/foo")
+            .header(ContentType::JSON)
+            .body(body)
+            .dispatch();
+        assert_eq!(res.status(), Status::Forbidden);
+
+        let mut res = client
+            .post("/places/foo/suggestions")
+            .header(ContentType::JSON)
+            .body(body)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let suggestion_id: String = serde_json::from_str(&body).unwrap();
+
+        // Regular users are not allowed to review suggestions
+        let res = client
+            .post(format!("/places/suggestions/{}/accept", suggestion_id))
+            .dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+        let res = client.post("/logout").header(ContentType::JSON).dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        let mut res = client
+            .get("/places/suggestions")
+            .header(rocket::http::Header::new("Authorization", "Bearer a"))
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let suggestions: Vec<json::PlaceEditSuggestion> = serde_json::from_str(&body).unwrap();
+        assert_eq!(1, suggestions.len());
+        assert_eq!(suggestion_id, suggestions[0].id);
+        assert_eq!("pending", suggestions[0].status);
+
+        let res = client
+            .post(format!("/places/suggestions/{}/accept", suggestion_id))
+            .header(rocket::http::Header::new("Authorization", "Bearer a"))
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let (place, _) = db.shared().unwrap().get_place("foo").unwrap();
+        assert_eq!(Revision::from(1), place.revision);
+        assert!(place.tags.is_empty());
+        assert_eq!("bar", place.description);
+    }
+}
@@ -4,15 +4,83 @@ use crate::{core::util, infrastructure::flows::prelude as flows};
 
 #[post("/ratings", format = "application/json", data = "<data>")]
 pub fn post_rating(
+    auth: Auth,
     connections: sqlite::Connections,
     mut search_engine: tantivy::SearchEngine,
     data: Json<usecases::NewPlaceRating>,
 ) -> Result<()> {
-    let _ = flows::create_rating(&connections, &mut search_engine, data.into_inner())?;
+    let author = if let Ok(email) = auth.account_email() {
+        RatingAuthor::User(email.into())
+    } else {
+        RatingAuthor::AnonymousSession(auth.anonymous_session()?.to_owned())
+    };
+    let _ = flows::create_rating(&connections, &mut search_engine, data.into_inner(), author)?;
     Ok(Json(()))
 }
 
-#[get("/ratings/<ids>")]
+#[post("/ratings/<id>/flag", format = "application/json", data = "<data>")]
+pub fn post_rating_flag(
+    auth: Auth,
+    db: sqlite::Connections,
+    id: String,
+    data: Json<json::NewRatingFlag>,
+) -> Result<()> {
+    let flagged_by = auth.account_email().ok();
+    if flagged_by.is_none() {
+        auth.has_captcha()?;
+    }
+    let json::NewRatingFlag { comment } = data.into_inner();
+    usecases::flag_rating(&*db.exclusive()?, &id, flagged_by, comment)?;
+    Ok(Json(()))
+}
+
+#[post("/ratings/<id>/comments", format = "application/json", data = "<data>")]
+pub fn post_rating_comment(
+    auth: Auth,
+    db: sqlite::Connections,
+    id: String,
+    data: Json<json::NewRatingComment>,
+) -> Result<json::Comment> {
+    let db = db.exclusive()?;
+    let author = if let Ok(email) = auth.account_email() {
+        CommentAuthor::User(email.into())
+    } else {
+        CommentAuthor::Organization(auth.organization(&*db)?.id)
+    };
+    let json::NewRatingComment {
+        text,
+        reply_to,
+        response,
+    } = data.into_inner();
+    let new_comment = usecases::NewRatingComment {
+        text,
+        reply_to,
+        is_response: response,
+    };
+    let comment = usecases::comment_rating(&*db, &id, author, new_comment)?;
+    Ok(Json(comment.into()))
+}
+
+#[get("/ratings/flagged?<offset>&<limit>")]
+pub fn get_flagged_ratings(
+    db: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    account: Account,
+    offset: Option<u64>,
+    limit: Option<u64>,
+) -> Result<Vec<json::FlaggedRating>> {
+    let pagination = Pagination { offset, limit };
+    let flagged_ratings =
+        usecases::flagged_ratings(&*db.shared()?, &permissions, account.email(), &pagination)?;
+    Ok(Json(
+        flagged_ratings
+            .into_iter()
+            .map(json::flagged_rating)
+            .collect(),
+    ))
+}
+
+#[get("/ratings/<ids>", rank = 2)]
 pub fn load_rating(db: sqlite::Connections, ids: String) -> Result<Vec<json::Rating>> {
     // TODO: RESTful API
     //   - Only lookup and return a single entity
@@ -25,14 +93,7 @@ pub fn load_rating(db: sqlite::Connections, ids: String) -> Result<Vec<json::Rat
     let result = ratings_with_comments
         .into_iter()
         .map(|(r, cs)| {
-            let comments = cs
-                .into_iter()
-                .map(|c| json::Comment {
-                    id: c.id.clone().into(),
-                    created: c.created_at.into_seconds(),
-                    text: c.text,
-                })
-                .collect();
+            let comments = cs.into_iter().map(Into::into).collect();
             json::Rating {
                 id: r.id.into(),
                 created: r.created_at.into_seconds(),
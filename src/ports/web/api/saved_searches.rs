@@ -0,0 +1,145 @@
+use super::*;
+use std::convert::TryInto;
+
+fn new_saved_search(
+    from: json::NewSavedSearch,
+) -> result::Result<usecases::NewSavedSearch, AppError> {
+    let json::NewSavedSearch {
+        name,
+        bbox,
+        categories,
+        tags,
+        text,
+        org_tag,
+        alert,
+    } = from;
+    let bbox = bbox
+        .try_into()
+        .map_err(|_| Error::Parameter(ParameterError::Bbox))?;
+    Ok(usecases::NewSavedSearch {
+        name,
+        bbox,
+        categories,
+        hash_tags: tags,
+        text,
+        org_tag,
+        alert,
+    })
+}
+
+#[post("/saved-searches", format = "application/json", data = "<data>")]
+pub fn post_saved_search(
+    db: sqlite::Connections,
+    account: Account,
+    data: Json<json::NewSavedSearch>,
+) -> Result<json::SavedSearch> {
+    let new = new_saved_search(data.into_inner())?;
+    let saved_search = usecases::create_saved_search(&*db.exclusive()?, account.email(), new)?;
+    Ok(Json(saved_search.into()))
+}
+
+#[get("/saved-searches")]
+pub fn get_saved_searches(
+    db: sqlite::Connections,
+    account: Account,
+) -> Result<Vec<json::SavedSearch>> {
+    let saved_searches = usecases::get_saved_searches(&*db.shared()?, account.email())?;
+    Ok(Json(saved_searches.into_iter().map(Into::into).collect()))
+}
+
+#[put("/saved-searches/<id>", format = "application/json", data = "<data>")]
+pub fn put_saved_search(
+    db: sqlite::Connections,
+    account: Account,
+    id: String,
+    data: Json<json::NewSavedSearch>,
+) -> Result<json::SavedSearch> {
+    let new = new_saved_search(data.into_inner())?;
+    let saved_search = usecases::update_saved_search(&*db.exclusive()?, account.email(), &id, new)?;
+    Ok(Json(saved_search.into()))
+}
+
+#[delete("/saved-searches/<id>")]
+pub fn delete_saved_search(db: sqlite::Connections, account: Account, id: String) -> Result<()> {
+    usecases::delete_saved_search(&*db.exclusive()?, account.email(), &id)?;
+    Ok(Json(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::web::{api::tests::prelude::*, tests::register_user};
+
+    #[test]
+    fn create_update_and_delete_saved_searches() {
+        let (client, db) = setup();
+        register_user(&db, "foo@example.com", "secret", true);
+        register_user(&db, "bar@example.com", "secret", true);
+        let search = r##"{"name":"Organic food","bbox":{"sw":{"lat":40.0,"lng":0.0},"ne":{"lat":50.0,"lng":10.0}},"tags":["#Organic"],"text":"food","alert":true}"##;
+
+        let res = client
+            .post("/saved-searches")
+            .header(ContentType::JSON)
+            .body(search)
+            .dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        assert_eq!(Status::Ok, login(&client, "foo@example.com", "secret"));
+        let mut res = client
+            .post("/saved-searches")
+            .header(ContentType::JSON)
+            .body(search)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let saved: json::SavedSearch = serde_json::from_str(&body).unwrap();
+        assert_eq!("Organic food", saved.name);
+        assert_eq!(vec!["organic".to_string()], saved.tags);
+        assert!(saved.alert);
+
+        let res = client
+            .post("/saved-searches")
+            .header(ContentType::JSON)
+            .body(
+                r#"{"name":"","bbox":{"sw":{"lat":40.0,"lng":0.0},"ne":{"lat":50.0,"lng":10.0}}}"#,
+            )
+            .dispatch();
+        assert_eq!(res.status(), Status::BadRequest);
+
+        assert_eq!(Status::Ok, login(&client, "bar@example.com", "secret"));
+        let mut res = client.get("/saved-searches").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        assert_eq!("[]", body);
+        let res = client
+            .delete(format!("/saved-searches/{}", saved.id))
+            .dispatch();
+        assert_eq!(res.status(), Status::Forbidden);
+
+        assert_eq!(Status::Ok, login(&client, "foo@example.com", "secret"));
+        let mut res = client
+            .put(format!("/saved-searches/{}", saved.id))
+            .header(ContentType::JSON)
+            .body(search.replace("\"alert\":true", "\"alert\":false"))
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let updated: json::SavedSearch = serde_json::from_str(&body).unwrap();
+        assert!(!updated.alert);
+        assert_eq!(saved.created_at, updated.created_at);
+
+        let mut res = client.get("/saved-searches").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let saved_searches: Vec<json::SavedSearch> = serde_json::from_str(&body).unwrap();
+        assert_eq!(1, saved_searches.len());
+
+        let res = client
+            .delete(format!("/saved-searches/{}", saved.id))
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let mut res = client.get("/saved-searches").dispatch();
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        assert_eq!("[]", body);
+    }
+}
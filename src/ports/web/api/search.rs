@@ -24,7 +24,12 @@ pub struct SearchQuery {
     tags: Option<String>,
     text: Option<String>,
     status: Option<String>,
+    link_status: Option<String>,
+    lang: Option<String>,
+    sort: Option<String>,
+    cursor: Option<String>,
     limit: Option<usize>,
+    facets: Option<bool>,
 }
 
 pub fn parse_search_query(
@@ -38,7 +43,12 @@ pub fn parse_search_query(
         tags,
         text,
         status,
+        link_status,
+        lang,
+        sort,
+        cursor,
         limit,
+        facets: _,
     } = query;
 
     let bbox = bbox
@@ -80,6 +90,26 @@ pub fn parse_search_query(
         })
         .collect();
 
+    let link_status = link_status
+        .as_deref()
+        .map(str::parse::<LinkStatus>)
+        .transpose()
+        .map_err(|_| Error::Parameter(ParameterError::LinkStatus))?;
+
+    let lang = lang
+        .as_deref()
+        .map(str::parse::<Language>)
+        .transpose()
+        .map_err(|_| Error::Parameter(ParameterError::Language))?;
+
+    let sort = sort
+        .as_deref()
+        .map(str::parse::<usecases::SearchSort>)
+        .transpose()
+        .map_err(Error::Parameter)?;
+
+    let cursor = cursor.as_deref();
+
     Ok((
         usecases::SearchRequest {
             bbox,
@@ -89,6 +119,10 @@ pub fn parse_search_query(
             hash_tags,
             text,
             status,
+            link_status,
+            lang,
+            sort,
+            cursor,
         },
         *limit,
     ))
@@ -132,22 +166,102 @@ pub fn get_search(
         DEFAULT_RESULT_LIMIT
     };
 
-    let (visible, invisible) =
-        usecases::search(&*connections.shared()?, &search_engine, req, limit)?;
+    let db = connections.shared()?;
+    let facets = if query.facets.unwrap_or(false) {
+        let facets = usecases::search_facets(&*db, &search_engine, req.clone())?;
+        Some(facets.into())
+    } else {
+        None
+    };
+    let (visible, invisible, next_cursor) = if req.sort.is_some() || req.cursor.is_some() {
+        let usecases::SearchPage {
+            visible,
+            invisible,
+            next_cursor,
+        } = usecases::search_sorted(&*db, &search_engine, req, limit)?;
+        (visible, invisible, next_cursor)
+    } else {
+        let (visible, invisible) = usecases::search(&*db, &search_engine, req, limit)?;
+        (visible, invisible, None)
+    };
+
+    let all_categories = db.all_categories()?;
 
-    let visible: Vec<json::PlaceSearchResult> = visible.into_iter().map(Into::into).collect();
+    let visible: Vec<_> = visible
+        .into_iter()
+        .map(|p| json::place_search_result(p, &all_categories))
+        .collect();
 
-    let invisible: Vec<json::PlaceSearchResult> = invisible.into_iter().map(Into::into).collect();
+    let invisible: Vec<_> = invisible
+        .into_iter()
+        .map(|p| json::place_search_result(p, &all_categories))
+        .collect();
 
-    Ok(Json(json::SearchResponse { visible, invisible }))
+    Ok(Json(json::SearchResponse {
+        visible,
+        invisible,
+        facets,
+        next_cursor,
+    }))
+}
+
+// The zoom level is passed separately, all other parameters
+// are the same as for searching.
+#[get("/search/clusters?<zoom>&<query..>")]
+pub fn get_search_clusters(
+    connections: sqlite::Connections,
+    search_engine: tantivy::SearchEngine,
+    zoom: u8,
+    query: Form<SearchQuery>,
+) -> Result<Vec<json::PlaceCluster>> {
+    let query = query.into_inner();
+    let (req, _) = parse_search_query(&query)?;
+    let db = connections.shared()?;
+    let clusters = usecases::search_clusters(&*db, &search_engine, req, zoom)?;
+    Ok(Json(clusters.into_iter().map(Into::into).collect()))
+}
+
+const DEFAULT_SUGGESTION_LIMIT: usize = 10;
+const MAX_SUGGESTION_LIMIT: usize = 50;
+
+#[get("/search/suggest?<q>&<bbox>&<limit>")]
+pub fn get_search_suggest(
+    search_engine: tantivy::SearchEngine,
+    q: String,
+    bbox: String,
+    limit: Option<usize>,
+) -> Result<Vec<json::Suggestion>> {
+    let bbox = bbox
+        .parse::<geo::MapBbox>()
+        .map_err(|_| ParameterError::Bbox)
+        .map_err(Error::Parameter)
+        .map_err(AppError::Business)?;
+    let limit = match limit {
+        Some(0) => {
+            return Err(AppError::Business(Error::Parameter(
+                ParameterError::InvalidLimit,
+            )));
+        }
+        Some(limit) => limit.min(MAX_SUGGESTION_LIMIT),
+        None => DEFAULT_SUGGESTION_LIMIT,
+    };
+    let suggestions = usecases::suggest(&search_engine, bbox, &q, limit)?;
+    Ok(Json(suggestions.into_iter().map(Into::into).collect()))
 }
 
 #[post("/search/duplicates", data = "<body>")]
 pub fn post_search_duplicates(
+    db: sqlite::Connections,
     search_engine: tantivy::SearchEngine,
     body: Json<ofdb_boundary::NewPlace>,
 ) -> Result<Vec<json::PlaceSearchResult>> {
     let new_place = usecases::NewPlace::from(body.into_inner());
     let duplicate_places = usecases::search_duplicates(&search_engine, &new_place)?;
-    Ok(Json(duplicate_places.into_iter().map(Into::into).collect()))
+    let all_categories = db.shared()?.all_categories()?;
+    Ok(Json(
+        duplicate_places
+            .into_iter()
+            .map(|p| json::place_search_result(p, &all_categories))
+            .collect(),
+    ))
 }
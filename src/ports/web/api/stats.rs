@@ -0,0 +1,68 @@
+use super::{super::guards::*, Result};
+use crate::{
+    adapters::json,
+    core::{prelude::*, usecases},
+    infrastructure::db::sqlite,
+};
+use ofdb_core::user::RolePermissions;
+use rocket::{self, State};
+use rocket_contrib::json::Json;
+
+#[get("/stats")]
+pub fn get_stats(
+    db: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    account: Account,
+) -> Result<json::Statistics> {
+    let stats = usecases::statistics(&*db.shared()?, &permissions, account.email())?;
+    Ok(Json(stats.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::web::api::tests::prelude::*;
+
+    #[test]
+    fn get_statistics_as_admin() {
+        let (client, db, mut search_engine, notify) = setup2();
+        let mut new_place = usecases::NewPlace {
+            title: "foo".into(),
+            description: "bar".into(),
+            country: Some("DE".into()),
+            state: Some("Berlin".into()),
+            categories: vec![Category::ID_NON_PROFIT.into()],
+            tags: vec!["bio".into()],
+            ..default_new_entry()
+        };
+        flows::create_place(
+            &db,
+            &mut search_engine,
+            &notify,
+            new_place.clone(),
+            None,
+            None,
+        )
+        .unwrap();
+        new_place.tags.push("fair".into());
+        flows::create_place(&db, &mut search_engine, &notify, new_place, None, None).unwrap();
+
+        let res = client.get("/stats").dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        login_admin(&client, &db, "admin@example.com");
+        let mut res = client.get("/stats").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let stats: json::Statistics = serde_json::from_str(&body).unwrap();
+        assert_eq!(1, stats.places_created_per_week.len());
+        assert_eq!(2, stats.places_created_per_week[0].count);
+        assert!(stats.places_updated_per_week.is_empty());
+        assert_eq!(1, stats.users_registered_per_week.len());
+        assert_eq!("non-profit", stats.places_by_category[0].key);
+        assert_eq!(2, stats.places_by_category[0].count);
+        assert_eq!("bio", stats.places_by_tag[0].key);
+        assert_eq!(2, stats.places_by_tag[0].count);
+        assert_eq!("DE/Berlin", stats.places_by_state[0].key);
+    }
+}
@@ -0,0 +1,180 @@
+use super::*;
+
+fn tag_details(from: usecases::TagDetails) -> json::TagDetails {
+    let usecases::TagDetails {
+        id,
+        description,
+        synonyms,
+    } = from;
+    json::TagDetails {
+        id,
+        description,
+        synonyms,
+    }
+}
+
+#[get("/tags/<tag>")]
+pub fn get_tag(db: sqlite::Connections, tag: String) -> Result<json::TagDetails> {
+    let details = usecases::get_tag_details(&*db.shared()?, &tag)?;
+    Ok(Json(tag_details(details)))
+}
+
+#[put("/tags/<tag>", format = "application/json", data = "<data>")]
+pub fn put_tag(
+    db: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    account: Account,
+    tag: String,
+    data: Json<json::UpdateTag>,
+) -> Result<json::TagDetails> {
+    let json::UpdateTag {
+        description,
+        synonyms,
+    } = data.into_inner();
+    let update = usecases::UpdateTag {
+        description,
+        synonyms,
+    };
+    let details = usecases::update_tag(
+        &*db.exclusive()?,
+        &permissions,
+        account.email(),
+        &tag,
+        update,
+    )?;
+    Ok(Json(tag_details(details)))
+}
+
+#[post("/tags/merge", format = "application/json", data = "<data>")]
+pub fn post_tags_merge(
+    db: sqlite::Connections,
+    mut search_engine: tantivy::SearchEngine,
+    permissions: State<RolePermissions>,
+    account: Account,
+    data: Json<json::MergeTags>,
+) -> Result<json::MergedTags> {
+    let json::MergeTags { tags, into } = data.into_inner();
+    let usecases::MergedTags { places, events } = flows::merge_tags(
+        &db,
+        &mut search_engine,
+        &permissions,
+        account.email(),
+        &tags,
+        &into,
+    )?;
+    Ok(Json(json::MergedTags {
+        places: places.into_iter().map(|p| p.id.into()).collect(),
+        events: events.into_iter().map(|e| e.id.into()).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::web::{api::tests::prelude::*, tests::register_user};
+
+    #[test]
+    fn merge_tags_and_describe_tag() {
+        let (client, db) = setup();
+        register_user_with_role(&db, "admin@example.com", Role::Admin);
+        register_user(&db, "user@example.com", "secret", true);
+        let place = Place::build()
+            .id("place")
+            .title("Repair")
+            .tags(vec!["repair-cafe"])
+            .finish();
+        db.exclusive()
+            .unwrap()
+            .create_or_update_place(place)
+            .unwrap();
+        db.exclusive()
+            .unwrap()
+            .create_tag_if_it_does_not_exist(&Tag {
+                id: "repair-cafe".into(),
+            })
+            .unwrap();
+        let merge = r#"{"tags":["repair-cafe","reparaturcafe"],"into":"repaircafe"}"#;
+
+        assert_eq!(Status::Ok, login(&client, "user@example.com", "secret"));
+        let res = client
+            .post("/tags/merge")
+            .header(ContentType::JSON)
+            .body(merge)
+            .dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        assert_eq!(Status::Ok, login(&client, "admin@example.com", "secret"));
+        let mut res = client
+            .post("/tags/merge")
+            .header(ContentType::JSON)
+            .body(merge)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let merged: json::MergedTags = serde_json::from_str(&body).unwrap();
+        assert_eq!(vec!["place".to_string()], merged.places);
+        let (place, _) = db.shared().unwrap().get_place("place").unwrap();
+        assert_eq!(vec!["repaircafe".to_string()], place.tags);
+        assert!(!db
+            .shared()
+            .unwrap()
+            .all_tags()
+            .unwrap()
+            .iter()
+            .any(|t| t.id == "repair-cafe"));
+
+        let res = client
+            .put("/tags/repaircafe")
+            .header(ContentType::JSON)
+            .body(r#"{"description":"Fixing things together","synonyms":["repair-cafe","reparaturcafe"]}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let mut res = client.get("/tags/RepairCafe").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let details: json::TagDetails = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            Some("Fixing things together"),
+            details.description.as_deref()
+        );
+        assert_eq!(2, details.synonyms.len());
+
+        let res = client.get("/tags/unknown").dispatch();
+        assert_eq!(res.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn search_by_merged_tag() {
+        let (client, db, mut search_engine, _) = setup2();
+        let place = Place::build()
+            .id("place")
+            .title("Repair")
+            .tags(vec!["repair-cafe"])
+            .finish();
+        db.exclusive()
+            .unwrap()
+            .create_or_update_place(place.clone())
+            .unwrap();
+        search_engine
+            .add_or_update_place(&place, ReviewStatus::Created, &AvgRatings::default())
+            .unwrap();
+        search_engine.flush_index().unwrap();
+
+        login_admin(&client, &db, "admin@example.com");
+        let res = client
+            .post("/tags/merge")
+            .header(ContentType::JSON)
+            .body(r#"{"tags":["repair-cafe"],"into":"repaircafe"}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        for query in &["tags=repair-cafe", "text=%23repair-cafe", "tags=repaircafe"] {
+            let mut res = client
+                .get(format!("/search?bbox=-10,-10,10,10&{}", query))
+                .dispatch();
+            assert_eq!(res.status(), Status::Ok);
+            let body = res.body().and_then(|b| b.into_string()).unwrap();
+            assert!(body.contains("\"id\":\"place\""), "{}", query);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use super::*;
+
+const DEFAULT_SUGGESTION_LIMIT: usize = 10;
+const MAX_SUGGESTION_LIMIT: usize = 50;
+
+fn taxonomy_node(from: usecases::TaxonomyNode) -> json::TaxonomyNode {
+    let usecases::TaxonomyNode {
+        tag,
+        description,
+        children,
+    } = from;
+    json::TaxonomyNode {
+        tag,
+        description,
+        children: children.into_iter().map(taxonomy_node).collect(),
+    }
+}
+
+fn new_taxonomy_node(from: json::NewTaxonomyNode) -> usecases::NewTaxonomyNode {
+    let json::NewTaxonomyNode { tag, children } = from;
+    usecases::NewTaxonomyNode {
+        tag,
+        children: children.into_iter().map(new_taxonomy_node).collect(),
+    }
+}
+
+#[get("/taxonomy")]
+pub fn get_taxonomy(db: sqlite::Connections) -> Result<Vec<json::TaxonomyNode>> {
+    let taxonomy = usecases::get_taxonomy(&*db.shared()?)?;
+    Ok(Json(taxonomy.into_iter().map(taxonomy_node).collect()))
+}
+
+#[put("/taxonomy", format = "application/json", data = "<data>")]
+pub fn put_taxonomy(
+    db: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    account: Account,
+    data: Json<Vec<json::NewTaxonomyNode>>,
+) -> Result<Vec<json::TaxonomyNode>> {
+    let nodes = data
+        .into_inner()
+        .into_iter()
+        .map(new_taxonomy_node)
+        .collect();
+    let taxonomy =
+        usecases::replace_taxonomy(&*db.exclusive()?, &permissions, account.email(), nodes)?;
+    Ok(Json(taxonomy.into_iter().map(taxonomy_node).collect()))
+}
+
+#[get("/taxonomy/suggest?<q>&<limit>")]
+pub fn get_taxonomy_suggest(
+    db: sqlite::Connections,
+    q: String,
+    limit: Option<usize>,
+) -> Result<Vec<String>> {
+    let limit = match limit {
+        Some(0) => {
+            return Err(AppError::Business(Error::Parameter(
+                ParameterError::InvalidLimit,
+            )));
+        }
+        Some(limit) => limit.min(MAX_SUGGESTION_LIMIT),
+        None => DEFAULT_SUGGESTION_LIMIT,
+    };
+    let tags = usecases::suggest_curated_tags(&*db.shared()?, &q, limit)?;
+    Ok(Json(tags))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::web::api::tests::prelude::*;
+
+    #[test]
+    fn replace_taxonomy_and_search_by_parent_tag() {
+        let (client, db, mut search_engine, _) = setup2();
+        let place = Place::build()
+            .id("coop")
+            .title("Food coop")
+            .tags(vec!["food-coop"])
+            .finish();
+        db.exclusive()
+            .unwrap()
+            .create_or_update_place(place.clone())
+            .unwrap();
+        search_engine
+            .add_or_update_place(&place, ReviewStatus::Created, &AvgRatings::default())
+            .unwrap();
+        search_engine.flush_index().unwrap();
+        let taxonomy =
+            r#"[{"tag":"food","children":[{"tag":"food-coop"},{"tag":"organic-shop"}]}]"#;
+
+        let res = client
+            .put("/taxonomy")
+            .header(ContentType::JSON)
+            .body(taxonomy)
+            .dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        login_admin(&client, &db, "admin@example.com");
+        let res = client
+            .put("/taxonomy")
+            .header(ContentType::JSON)
+            .body(taxonomy)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        let mut res = client.get("/taxonomy").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let taxonomy: Vec<json::TaxonomyNode> = serde_json::from_str(&body).unwrap();
+        assert_eq!(1, taxonomy.len());
+        assert_eq!("food", taxonomy[0].tag);
+        assert_eq!(2, taxonomy[0].children.len());
+
+        let mut res = client.get("/taxonomy/suggest?q=foo").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        assert_eq!(r#"["food","food-coop"]"#, body);
+
+        let mut res = client
+            .get("/search?bbox=-10,-10,10,10&tags=food")
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        assert!(body.contains("\"id\":\"coop\""));
+    }
+}
@@ -11,6 +11,7 @@ pub mod prelude {
         },
     };
     use crate::{
+        core::{prelude::Role, usecases},
         infrastructure::db::{sqlite, tantivy},
         ports::web::{self, api},
     };
@@ -31,6 +32,58 @@ pub mod prelude {
         (client, connections, search_engine, DummyNotifyGW {})
     }
 
+    pub fn login(client: &Client, email: &str, password: &str) -> Status {
+        client
+            .post("/login")
+            .header(ContentType::JSON)
+            .body(format!(
+                "{{\"email\":\"{}\",\"password\":\"{}\"}}",
+                email, password
+            ))
+            .dispatch()
+            .status()
+    }
+
+    /// Register a confirmed user with the password "secret"
+    pub fn register_user_with_role(db: &sqlite::Connections, email: &str, role: Role) {
+        web::tests::register_user(db, email, "secret", true);
+        let db = db.exclusive().unwrap();
+        let mut user = db.get_user_by_email(email).unwrap();
+        user.role = role;
+        db.update_user(&user).unwrap();
+    }
+
+    pub fn login_admin(client: &Client, db: &sqlite::Connections, email: &str) {
+        register_user_with_role(db, email, Role::Admin);
+        assert_eq!(Status::Ok, login(client, email, "secret"));
+    }
+
+    pub fn default_new_entry() -> usecases::NewPlace {
+        usecases::NewPlace {
+            title: Default::default(),
+            description: Default::default(),
+            categories: Default::default(),
+            contact_name: None,
+            email: None,
+            telephone: None,
+            lat: Default::default(),
+            lng: Default::default(),
+            street: None,
+            zip: None,
+            city: None,
+            country: None,
+            state: None,
+            tags: Default::default(),
+            homepage: None,
+            opening_hours: None,
+            founded_on: None,
+            license: "CC0-1.0".into(),
+            image_url: None,
+            image_link_url: None,
+            custom_links: vec![],
+        }
+    }
+
     pub fn test_json(r: &Response) {
         assert_eq!(
             r.headers().get("Content-Type").collect::<Vec<_>>()[0],
@@ -186,6 +239,7 @@ fn get_one_entry() {
             comment: "bla".into(),
             source: Some("blabla".into()),
         },
+        RatingAuthor::AnonymousSession("test".into()),
     )
     .unwrap();
     let req = client.get("/entries/get_one_entry_test");
@@ -204,7 +258,7 @@ fn get_one_entry() {
     assert!(body_str.contains(&format!(r#""ratings":["{}"]"#, rating.id)));
     assert_eq!(
         entries[0],
-        json::entry_from_place_with_ratings(e, vec![rating])
+        json::entry_from_place_with_ratings(e, vec![rating], &Category::built_in())
     );
 }
 
@@ -239,36 +293,12 @@ fn get_multiple_places() {
     assert_eq!(entries.len(), 2);
     assert!(entries
         .iter()
-        .any(|x| *x == json::entry_from_place_with_ratings(one.clone(), vec![])));
+        .any(|x| *x
+            == json::entry_from_place_with_ratings(one.clone(), vec![], &Category::built_in())));
     assert!(entries
         .iter()
-        .any(|x| *x == json::entry_from_place_with_ratings(two.clone(), vec![])));
-}
-
-fn default_new_entry() -> usecases::NewPlace {
-    usecases::NewPlace {
-        title: Default::default(),
-        description: Default::default(),
-        categories: Default::default(),
-        contact_name: None,
-        email: None,
-        telephone: None,
-        lat: Default::default(),
-        lng: Default::default(),
-        street: None,
-        zip: None,
-        city: None,
-        country: None,
-        state: None,
-        tags: Default::default(),
-        homepage: None,
-        opening_hours: None,
-        founded_on: None,
-        license: "CC0-1.0".into(),
-        image_url: None,
-        image_link_url: None,
-        custom_links: vec![],
-    }
+        .any(|x| *x
+            == json::entry_from_place_with_ratings(two.clone(), vec![], &Category::built_in())));
 }
 
 fn new_entry_with_category(category: &str, lat: f64, lng: f64) -> usecases::NewPlace {
@@ -484,6 +514,289 @@ fn search_partial_text() {
     assert!(body_str.contains(&format!("\"{}\"", place_ids[3])));
 }
 
+#[test]
+fn search_with_facets() {
+    let entry = |category: &str, tags: &[&str], pos: f64| usecases::NewPlace {
+        categories: vec![category.to_string()],
+        tags: tags.iter().map(|t| (*t).to_string()).collect(),
+        lat: pos,
+        lng: pos,
+        ..default_new_entry()
+    };
+    let entries = vec![
+        entry(Category::ID_NON_PROFIT, &["repair"], 1.0),
+        entry(Category::ID_NON_PROFIT, &["repair", "food"], 2.0),
+        entry(Category::ID_COMMERCIAL, &["food"], 3.0),
+        entry(Category::ID_COMMERCIAL, &["food"], 20.0),
+    ];
+    let (client, connections, mut search_engine, notify) = setup2();
+    for e in entries {
+        flows::create_place(&connections, &mut search_engine, &notify, e, None, None).unwrap();
+    }
+
+    let mut response = client.get("/search?bbox=-10,-10,10,10").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body_str = response.body().and_then(|b| b.into_string()).unwrap();
+    assert!(!body_str.contains("facets"));
+
+    let mut response = client
+        .get("/search?bbox=-10,-10,10,10&facets=true&limit=1")
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    test_json(&response);
+    let body_str = response.body().and_then(|b| b.into_string()).unwrap();
+    let facets = serde_json::from_str::<serde_json::Value>(&body_str).unwrap()["facets"].clone();
+    assert_eq!(
+        facets,
+        serde_json::json!({
+            "total_count": 3,
+            "categories": [
+                { "key": Category::ID_NON_PROFIT, "count": 2 },
+                { "key": Category::ID_COMMERCIAL, "count": 1 },
+            ],
+            "tags": [
+                { "key": "food", "count": 2 },
+                { "key": "repair", "count": 2 },
+            ],
+            "status": [{ "status": "created", "count": 3 }],
+            "ratings": [{ "rating": 0, "count": 3 }],
+        })
+    );
+}
+
+#[test]
+fn search_sorted_with_cursor() {
+    let entries = vec![
+        new_entry_with_text("Cherry", "bla", 3.0, 3.0),
+        new_entry_with_text("apple", "bla", 1.0, 1.0),
+        new_entry_with_text("Banana", "bla", 2.0, 2.0),
+    ];
+    let (client, connections, mut search_engine, notify) = setup2();
+    let place_ids: Vec<_> = entries
+        .into_iter()
+        .map(|e| {
+            flows::create_place(&connections, &mut search_engine, &notify, e, None, None)
+                .unwrap()
+                .id
+                .to_string()
+        })
+        .collect();
+
+    let search_page = |query: String| {
+        let mut response = client.get(format!("/search?{}", query)).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let body_str = response.body().and_then(|b| b.into_string()).unwrap();
+        let res: json::SearchResponse = serde_json::from_str(&body_str).unwrap();
+        (
+            res.visible.into_iter().map(|p| p.id).collect::<Vec<_>>(),
+            res.next_cursor,
+        )
+    };
+
+    let mut ids = vec![];
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut query = "bbox=-10,-10,10,10&sort=alphabetical&limit=1".to_string();
+        if let Some(cursor) = cursor {
+            query += &format!("&cursor={}", cursor);
+        }
+        let (page, next_cursor) = search_page(query);
+        assert_eq!(1, page.len());
+        ids.extend(page);
+        cursor = next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(
+        vec![&place_ids[1], &place_ids[2], &place_ids[0]],
+        ids.iter().collect::<Vec<_>>()
+    );
+
+    let (mut ids, next_cursor) =
+        search_page("bbox=-10,-10,10,10&sort=recently_changed&limit=2".to_string());
+    assert_eq!(2, ids.len());
+    let (page, next_cursor) = search_page(format!(
+        "bbox=-10,-10,10,10&sort=recently_changed&limit=2&cursor={}",
+        next_cursor.unwrap()
+    ));
+    assert!(next_cursor.is_none());
+    ids.extend(page);
+    ids.sort();
+    let mut sorted_place_ids = place_ids.clone();
+    sorted_place_ids.sort();
+    assert_eq!(sorted_place_ids, ids);
+
+    let (ids, next_cursor) = search_page("bbox=-10,-10,10,10&sort=distance".to_string());
+    assert_eq!(
+        vec![&place_ids[1], &place_ids[2], &place_ids[0]],
+        ids.iter().collect::<Vec<_>>()
+    );
+    assert!(next_cursor.is_none());
+
+    let response = client
+        .get("/search?bbox=-10,-10,10,10&sort=newest")
+        .dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    let response = client
+        .get("/search?bbox=-10,-10,10,10&sort=distance&cursor=alphabetical:apple:foo")
+        .dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[test]
+fn search_clusters() {
+    let entry = |category: &str, pos: f64| usecases::NewPlace {
+        categories: vec![category.to_string()],
+        lat: pos,
+        lng: pos,
+        ..default_new_entry()
+    };
+    let entries = vec![
+        entry(Category::ID_NON_PROFIT, 1.0),
+        entry(Category::ID_NON_PROFIT, 1.2),
+        entry(Category::ID_COMMERCIAL, 1.3),
+        entry(Category::ID_COMMERCIAL, 5.0),
+    ];
+    let (client, connections, mut search_engine, notify) = setup2();
+    for e in entries {
+        flows::create_place(&connections, &mut search_engine, &notify, e, None, None).unwrap();
+    }
+
+    let search_clusters = |query: &str| {
+        let mut response = client
+            .get(format!("/search/clusters?bbox=-10,-10,10,10&{}", query))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        test_json(&response);
+        let body_str = response.body().and_then(|b| b.into_string()).unwrap();
+        serde_json::from_str::<Vec<json::PlaceCluster>>(&body_str).unwrap()
+    };
+
+    let clusters = search_clusters("zoom=6");
+    assert_eq!(2, clusters.len());
+    assert_eq!(3, clusters[0].count);
+    assert!((clusters[0].lat - 3.5 / 3.0).abs() < 1e-6);
+    assert!((clusters[0].lng - 3.5 / 3.0).abs() < 1e-6);
+    assert_eq!(
+        Some(Category::ID_NON_PROFIT),
+        clusters[0].category.as_deref()
+    );
+    assert_eq!(1, clusters[1].count);
+    assert!((clusters[1].lat - 5.0).abs() < 1e-6);
+    assert_eq!(
+        Some(Category::ID_COMMERCIAL),
+        clusters[1].category.as_deref()
+    );
+
+    let clusters = search_clusters("zoom=0");
+    assert_eq!(1, clusters.len());
+    assert_eq!(4, clusters[0].count);
+
+    let clusters = search_clusters(&format!("zoom=6&categories={}", Category::ID_COMMERCIAL));
+    assert_eq!(2, clusters.len());
+    assert!(clusters.iter().all(|c| c.count == 1));
+
+    let response = client
+        .get("/search/clusters?bbox=-10,-10,10,10&zoom=23")
+        .dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[test]
+fn search_with_misspelled_text() {
+    let entries = vec![
+        new_entry_with_text("Bioladen Sonnenschein", "bla", 1.0, 1.0),
+        new_entry_with_text("Weltladen", "blub", 2.0, 2.0),
+    ];
+    let (client, connections, mut search_engine, notify) = setup2();
+    let place_ids: Vec<_> = entries
+        .into_iter()
+        .map(|e| {
+            flows::create_place(&connections, &mut search_engine, &notify, e, None, None)
+                .unwrap()
+                .id
+                .to_string()
+        })
+        .collect();
+
+    for text in &["Bioldaen", "sonnenschien", "Sonnenshine"] {
+        let mut response = client
+            .get(format!("/search?bbox=-10,-10,10,10&text={}", text))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let body_str = response.body().and_then(|b| b.into_string()).unwrap();
+        assert!(body_str.contains(&format!("\"{}\"", place_ids[0])));
+        assert!(!body_str.contains(&format!("\"{}\"", place_ids[1])));
+    }
+
+    // Short words must match exactly
+    let mut response = client
+        .get("/search?bbox=-10,-10,10,10&text=blau")
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body_str = response.body().and_then(|b| b.into_string()).unwrap();
+    assert!(!body_str.contains(&format!("\"{}\"", place_ids[0])));
+    assert!(!body_str.contains(&format!("\"{}\"", place_ids[1])));
+}
+
+#[test]
+fn suggest_titles_and_tags() {
+    let entries = vec![
+        usecases::NewPlace {
+            title: "Bioladen Sonnenschein".into(),
+            tags: vec!["bio".to_string()],
+            ..default_new_entry()
+        },
+        usecases::NewPlace {
+            title: "Weltladen".into(),
+            tags: vec!["bio".to_string(), "fairtrade".to_string()],
+            ..default_new_entry()
+        },
+        usecases::NewPlace {
+            title: "Repair Café".into(),
+            tags: vec!["repair".to_string()],
+            ..default_new_entry()
+        },
+        usecases::NewPlace {
+            title: "Biohof".into(),
+            lat: 20.0,
+            lng: 20.0,
+            ..default_new_entry()
+        },
+    ];
+    let (client, connections, mut search_engine, notify) = setup2();
+    for e in entries {
+        flows::create_place(&connections, &mut search_engine, &notify, e, None, None).unwrap();
+    }
+
+    let mut response = client
+        .get("/search/suggest?q=Bio&bbox=-10,-10,10,10")
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    test_json(&response);
+    let body_str = response.body().and_then(|b| b.into_string()).unwrap();
+    assert_eq!(
+        body_str,
+        r#"[{"text":"bio","kind":"tag","count":2},{"text":"Bioladen Sonnenschein","kind":"title","count":1}]"#
+    );
+
+    let mut response = client
+        .get("/search/suggest?q=rep&bbox=-10,-10,10,10&limit=1")
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body_str = response.body().and_then(|b| b.into_string()).unwrap();
+    assert_eq!(
+        body_str,
+        r#"[{"text":"Repair Café","kind":"title","count":1}]"#
+    );
+
+    let response = client
+        .get("/search/suggest?q=bio&bbox=-10,-10,10,10&limit=0")
+        .dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
 #[test]
 fn search_with_text_terms_inclusive_exclusive() {
     let entries = vec![
@@ -1133,21 +1446,178 @@ fn create_rating() {
             .create_or_update_place(e)
             .unwrap();
     }
-    let req = client.post("/ratings")
+    let body = r#"{"value": 1,"context":"fairness","entry":"foo","comment":"test", "title":"idontcare", "source":"source..."}"#;
+
+    // Anonymous ratings require a captcha
+    let response = client
+        .post("/ratings")
         .header(ContentType::JSON)
-        .body(r#"{"value": 1,"context":"fairness","entry":"foo","comment":"test", "title":"idontcare", "source":"source..."}"#);
+        .body(body)
+        .dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    let cookie = get_captcha_cookie(&client).unwrap();
+    let req = client
+        .post("/ratings")
+        .header(ContentType::JSON)
+        .cookie(cookie)
+        .body(body);
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
-    assert_eq!(
-        connections
-            .shared()
-            .unwrap()
-            .load_ratings_of_place("foo")
-            .unwrap()[0]
-            .value,
-        RatingValue::from(1)
-    );
+    let ratings = connections
+        .shared()
+        .unwrap()
+        .load_ratings_of_place("foo")
+        .unwrap();
+    assert_eq!(ratings[0].value, RatingValue::from(1));
+    assert!(matches!(
+        ratings[0].author,
+        Some(RatingAuthor::AnonymousSession(_))
+    ));
     test_json(&response);
+
+    // Only one rating per session and context
+    let cookie = get_captcha_cookie(&client).unwrap();
+    let response = client
+        .post("/ratings")
+        .header(ContentType::JSON)
+        .cookie(cookie)
+        .body(body)
+        .dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[test]
+fn flag_rating() {
+    let (client, connections, mut search_engine, _) = setup2();
+    connections
+        .exclusive()
+        .unwrap()
+        .create_or_update_place(Place::build().id("foo").finish())
+        .unwrap();
+    let (rating_id, _) = flows::create_rating(
+        &connections,
+        &mut search_engine,
+        usecases::NewPlaceRating {
+            context: ofdb_boundary::RatingContext::Humanity,
+            value: ofdb_boundary::RatingValue::from(2),
+            user: None,
+            title: "title".into(),
+            entry: "foo".into(),
+            comment: "bla".into(),
+            source: None,
+        },
+        RatingAuthor::AnonymousSession("test".into()),
+    )
+    .unwrap();
+    register_user_with_role(&connections, "scout@example.com", Role::Scout);
+
+    let cookie = get_captcha_cookie(&client).unwrap();
+    let response = client
+        .post(format!("/ratings/{}/flag", rating_id))
+        .header(ContentType::JSON)
+        .cookie(cookie)
+        .body(r#"{"comment":"spam"}"#)
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let response = client
+        .post("/ratings/unknown/flag")
+        .header(ContentType::JSON)
+        .body(r#"{}"#)
+        .dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    // Only scouts may review flagged ratings
+    let response = client.get("/ratings/flagged").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(Status::Ok, login(&client, "scout@example.com", "secret"));
+    let mut response = client.get("/ratings/flagged").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body_str = response.body().and_then(|b| b.into_string()).unwrap();
+    let flagged: Vec<json::FlaggedRating> = serde_json::from_str(&body_str).unwrap();
+    assert_eq!(1, flagged.len());
+    assert_eq!(rating_id, flagged[0].rating.id);
+    assert_eq!(Some("spam"), flagged[0].flags[0].comment.as_deref());
+}
+
+#[test]
+fn comment_rating() {
+    let (client, connections, mut search_engine, _) = setup2();
+    connections
+        .exclusive()
+        .unwrap()
+        .create_or_update_place(Place::build().id("foo").tags(vec!["a"]).finish())
+        .unwrap();
+    connections
+        .exclusive()
+        .unwrap()
+        .create_org(Organization {
+            id: "a".into(),
+            name: "a".into(),
+            moderated_tags: vec!["a".into()],
+            api_token: "a".into(),
+        })
+        .unwrap();
+    let (rating_id, comment_id) = flows::create_rating(
+        &connections,
+        &mut search_engine,
+        usecases::NewPlaceRating {
+            context: ofdb_boundary::RatingContext::Humanity,
+            value: ofdb_boundary::RatingValue::from(2),
+            user: None,
+            title: "title".into(),
+            entry: "foo".into(),
+            comment: "bla".into(),
+            source: None,
+        },
+        RatingAuthor::AnonymousSession("test".into()),
+    )
+    .unwrap();
+
+    // Anonymous comments are not allowed
+    let response = client
+        .post(format!("/ratings/{}/comments", rating_id))
+        .header(ContentType::JSON)
+        .body(r#"{"text":"anonymous"}"#)
+        .dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    crate::ports::web::tests::register_user(&connections, "user@example.com", "secret", true);
+    assert_eq!(Status::Ok, login(&client, "user@example.com", "secret"));
+    let mut response = client
+        .post(format!("/ratings/{}/comments", rating_id))
+        .header(ContentType::JSON)
+        .body(format!(r#"{{"text":"reply","reply_to":"{}"}}"#, comment_id))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body_str = response.body().and_then(|b| b.into_string()).unwrap();
+    let reply: json::Comment = serde_json::from_str(&body_str).unwrap();
+    assert_eq!(Some(comment_id.as_str()), reply.reply_to.as_deref());
+    assert!(!reply.response);
+
+    // Only the owners of a place are permitted to respond
+    let response = client
+        .post(format!("/ratings/{}/comments", rating_id))
+        .header(ContentType::JSON)
+        .body(r#"{"text":"response","response":true}"#)
+        .dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    let response = client.post("/logout").header(ContentType::JSON).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let response = client
+        .post(format!("/ratings/{}/comments", rating_id))
+        .header(ContentType::JSON)
+        .header(rocket::http::Header::new("Authorization", "Bearer a"))
+        .body(r#"{"text":"response","response":true}"#)
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let mut response = client.get(format!("/ratings/{}", rating_id)).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body_str = response.body().and_then(|b| b.into_string()).unwrap();
+    let ratings: Vec<json::Rating> = serde_json::from_str(&body_str).unwrap();
+    assert_eq!(3, ratings[0].comments.len());
+    assert_eq!(1, ratings[0].comments.iter().filter(|c| c.response).count());
 }
 
 #[test]
@@ -1171,6 +1641,7 @@ fn get_one_rating() {
             comment: "bla".into(),
             source: Some("blabla".into()),
         },
+        RatingAuthor::AnonymousSession("test".into()),
     )
     .unwrap();
     let rid = connections
@@ -1218,6 +1689,7 @@ fn ratings_with_and_without_source() {
             comment: "bla".into(),
             source: Some("blabla blabla".into()),
         },
+        RatingAuthor::AnonymousSession("test".into()),
     )
     .unwrap();
     flows::create_rating(
@@ -1232,6 +1704,7 @@ fn ratings_with_and_without_source() {
             comment: "bla".into(),
             source: Some("blabla blabla".into()),
         },
+        RatingAuthor::AnonymousSession("test".into()),
     )
     .unwrap();
 
@@ -1739,6 +2212,7 @@ fn entries_export_csv() {
             value: RatingValue::from(2),
             context: diversity,
             source: None,
+            author: None,
         })
         .unwrap();
     db.exclusive()
@@ -1752,6 +2226,7 @@ fn entries_export_csv() {
             value: RatingValue::from(1),
             context: diversity,
             source: None,
+            author: None,
         })
         .unwrap();
 
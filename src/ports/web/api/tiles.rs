@@ -0,0 +1,96 @@
+use super::*;
+use ofdb_core::tile::TileCoord;
+use std::{io::Cursor, sync::Arc};
+
+// Cached tiles are shared and must not be copied for every response
+pub struct TileBytes(Arc<Vec<u8>>);
+
+impl AsRef<[u8]> for TileBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<'r> Responder<'r> for TileBytes {
+    fn respond_to(self, _: &rocket::Request) -> result::Result<Response<'r>, Status> {
+        Response::build().sized_body(Cursor::new(self)).ok()
+    }
+}
+
+#[get("/tiles/<z>/<x>/<y>")]
+pub fn get_places_tile(
+    db: sqlite::Connections,
+    search_engine: tantivy::SearchEngine,
+    z: u8,
+    x: u32,
+    y: String,
+) -> result::Result<Content<TileBytes>, AppError> {
+    let tile = y
+        .strip_suffix(".mvt")
+        .and_then(|y| y.parse().ok())
+        .and_then(|y| TileCoord::new(z, x, y))
+        .ok_or(Error::Parameter(ParameterError::Tile))?;
+    let bytes = search_engine.get_or_create_tile(tile, || {
+        let places = usecases::load_tile_places(&search_engine, tile)?;
+        let all_categories = db.shared()?.all_categories()?;
+        Ok::<_, AppError>(adapters::mvt::encode_places_tile(
+            tile,
+            &places,
+            &all_categories,
+        ))
+    })?;
+    let (top, sub) = adapters::mvt::CONTENT_TYPE;
+    Ok(Content(ContentType::new(top, sub), TileBytes(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::web::api::tests::prelude::*;
+
+    #[test]
+    fn get_tile_with_places() {
+        let (client, db, mut search_engine, notify) = setup2();
+        let new_place = usecases::NewPlace {
+            title: "foo".into(),
+            description: "bar".into(),
+            lat: 1.0,
+            lng: 1.0,
+            categories: vec![Category::ID_NON_PROFIT.into()],
+            ..default_new_entry()
+        };
+        let place =
+            flows::create_place(&db, &mut search_engine, &notify, new_place, None, None).unwrap();
+
+        let mut response = client.get("/tiles/0/0/0.mvt").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(
+            Some("application/vnd.mapbox-vector-tile".to_string()),
+            response.content_type().map(|c| c.to_string())
+        );
+        let body = response.body_bytes().unwrap();
+        let id = place.id.as_str().as_bytes();
+        assert!(body.windows(id.len()).any(|w| w == id));
+        let mut response = client.get("/tiles/1/1/0.mvt").dispatch();
+        let body = response.body_bytes().unwrap();
+        assert!(body.windows(id.len()).any(|w| w == id));
+
+        // The cached tile is invalidated after the place has been moved
+        let mut moved_place = place.clone();
+        moved_place.location.pos = MapPoint::from_lat_lng_deg(-60.0, -120.0);
+        usecases::reindex_place(&search_engine, &moved_place, ReviewStatus::Created, &[]).unwrap();
+        search_engine.flush_index().unwrap();
+        let mut response = client.get("/tiles/1/1/0.mvt").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let body = response.body_bytes().unwrap();
+        assert!(!body.windows(id.len()).any(|w| w == id));
+        let mut response = client.get("/tiles/1/0/1.mvt").dispatch();
+        let body = response.body_bytes().unwrap();
+        assert!(body.windows(id.len()).any(|w| w == id));
+
+        for path in &["/tiles/0/1/0.mvt", "/tiles/1/0/0.pbf", "/tiles/23/0/0.mvt"] {
+            let response = client.get(*path).dispatch();
+            assert_eq!(response.status(), Status::BadRequest);
+        }
+    }
+}
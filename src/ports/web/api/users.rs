@@ -1,4 +1,5 @@
 use super::*;
+use std::convert::TryInto;
 
 #[post("/users", format = "application/json", data = "<u>")]
 pub fn post_user(db: sqlite::Connections, n: Notify, u: Json<usecases::NewUser>) -> Result<()> {
@@ -42,9 +43,132 @@ pub fn post_reset_password(
     Ok(Json(()))
 }
 
-#[delete("/users/<email>")]
-pub fn delete_user(db: sqlite::Connections, account: Account, email: String) -> Result<()> {
-    usecases::delete_user(&*db.exclusive()?, account.email(), &email)?;
+#[post("/users/change-password", format = "application/json", data = "<data>")]
+pub fn post_change_password(
+    connections: sqlite::Connections,
+    account: Account,
+    data: Json<json::ChangePassword>,
+) -> Result<()> {
+    let json::ChangePassword {
+        old_password,
+        new_password,
+    } = data.into_inner();
+    let new_password = new_password.parse::<Password>()?;
+    usecases::change_password(
+        &*connections.exclusive()?,
+        account.email(),
+        &old_password,
+        new_password,
+    )?;
+    Ok(Json(()))
+}
+
+#[post(
+    "/users/change-email-request",
+    format = "application/json",
+    data = "<data>"
+)]
+pub fn post_request_email_change(
+    connections: sqlite::Connections,
+    notify: Notify,
+    account: Account,
+    data: Json<json::RequestEmailChange>,
+) -> Result<()> {
+    let json::RequestEmailChange {
+        new_email,
+        password,
+    } = data.into_inner();
+    flows::change_email_request(
+        &connections,
+        &*notify,
+        account.email(),
+        &password,
+        &new_email,
+    )?;
+    Ok(Json(()))
+}
+
+#[post("/users/change-email", format = "application/json", data = "<data>")]
+pub fn post_change_email(
+    connections: sqlite::Connections,
+    notify: Notify,
+    data: Json<json::ConfirmEmailChange>,
+) -> Result<json::User> {
+    let req = data.into_inner();
+    let email_nonce = EmailNonce::decode_from_str(&req.token)?;
+    let user = flows::change_email_with_email_nonce(&connections, &*notify, email_nonce)?;
+    Ok(Json(user.into()))
+}
+
+#[get("/users/current/export", format = "application/json")]
+pub fn get_current_user_export(
+    db: sqlite::Connections,
+    account: Account,
+) -> Result<json::UserExport> {
+    let export = usecases::export_user(&*db.shared()?, account.email())?;
+    Ok(Json(export.into()))
+}
+
+#[delete("/users/<email>?<anonymize>")]
+pub fn delete_user(
+    connections: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    account: Account,
+    email: String,
+    anonymize: Option<bool>,
+) -> Result<()> {
+    if anonymize.unwrap_or(false) {
+        // Keep all authored activities under a pseudonym
+        flows::anonymize_user(&connections, &permissions, account.email(), &email)?;
+    } else {
+        usecases::delete_user(&*connections.exclusive()?, account.email(), &email)?;
+    }
+    Ok(Json(()))
+}
+
+#[get("/users/anonymized", format = "application/json")]
+pub fn get_anonymized_users(
+    db: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    account: Account,
+) -> Result<Vec<json::AnonymizedUser>> {
+    let anonymized_users =
+        usecases::anonymized_users(&*db.shared()?, &permissions, account.email())?;
+    Ok(Json(anonymized_users.into_iter().map(Into::into).collect()))
+}
+
+#[get("/users/<email>/regions", format = "application/json")]
+pub fn get_user_regions(
+    db: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    account: Account,
+    email: String,
+) -> Result<Vec<json::MapBbox>> {
+    let regions = usecases::user_regions(&*db.shared()?, &permissions, account.email(), &email)?;
+    Ok(Json(regions.into_iter().map(Into::into).collect()))
+}
+
+#[put("/users/<email>/regions", format = "application/json", data = "<data>")]
+pub fn put_user_regions(
+    db: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    account: Account,
+    email: String,
+    data: Json<Vec<json::MapBbox>>,
+) -> Result<()> {
+    let regions = data
+        .into_inner()
+        .into_iter()
+        .map(TryInto::try_into)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|_| Error::Parameter(ParameterError::Bbox))?;
+    usecases::change_user_regions(
+        &*db.exclusive()?,
+        &permissions,
+        account.email(),
+        &email,
+        &regions,
+    )?;
     Ok(Json(()))
 }
 
@@ -119,6 +243,274 @@ mod tests {
         assert_eq!(res.status(), Status::Ok);
     }
 
+    #[test]
+    fn change_password() {
+        let (client, db) = setup();
+        register_user(&db, "user@example.com", "secret", true);
+
+        // Without login
+        let res = client
+            .post("/users/change-password")
+            .header(ContentType::JSON)
+            .body(r#"{"old_password":"secret","new_password":"12345678"}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        assert_eq!(Status::Ok, login(&client, "user@example.com", "secret"));
+
+        // Wrong old password
+        let res = client
+            .post("/users/change-password")
+            .header(ContentType::JSON)
+            .body(r#"{"old_password":"wrong","new_password":"12345678"}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        let res = client
+            .post("/users/change-password")
+            .header(ContentType::JSON)
+            .body(r#"{"old_password":"secret","new_password":"12345678"}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        assert_eq!(
+            Status::Unauthorized,
+            login(&client, "user@example.com", "secret")
+        );
+        assert_eq!(Status::Ok, login(&client, "user@example.com", "12345678"));
+    }
+
+    #[test]
+    fn change_email() {
+        let (client, db) = setup();
+        register_user(&db, "old@example.com", "secret", true);
+        assert_eq!(Status::Ok, login(&client, "old@example.com", "secret"));
+
+        // User sends the request
+        let res = client
+            .post("/users/change-email-request")
+            .header(ContentType::JSON)
+            .body(r#"{"new_email":"new@example.com","password":"secret"}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        // User gets an email with the corresponding token
+        // at the new address
+        let token = db
+            .shared()
+            .unwrap()
+            .get_user_token_by_email("old@example.com")
+            .unwrap();
+        assert_eq!(Some("new@example.com"), token.new_email.as_deref());
+        let token = token.email_nonce.encode_to_string();
+
+        // The token could not be used to reset the password
+        let res = client
+            .post("/users/reset-password")
+            .header(ContentType::JSON)
+            .body(format!(
+                "{{\"token\":\"{}\",\"new_password\":\"12345678\"}}",
+                token
+            ))
+            .dispatch();
+        assert_eq!(res.status(), Status::BadRequest);
+        assert_eq!(
+            Status::Unauthorized,
+            login(&client, "old@example.com", "12345678")
+        );
+
+        // Request a new token, because the previous one has been consumed
+        let res = client
+            .post("/users/change-email-request")
+            .header(ContentType::JSON)
+            .body(r#"{"new_email":"new@example.com","password":"secret"}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let token = db
+            .shared()
+            .unwrap()
+            .get_user_token_by_email("old@example.com")
+            .unwrap()
+            .email_nonce
+            .encode_to_string();
+
+        // User confirms the new address
+        let mut res = client
+            .post("/users/change-email")
+            .header(ContentType::JSON)
+            .body(format!("{{\"token\":\"{}\"}}", token))
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let user: json::User = serde_json::from_str(&body).unwrap();
+        assert_eq!("new@example.com", user.email);
+
+        assert_eq!(
+            Status::Unauthorized,
+            login(&client, "old@example.com", "secret")
+        );
+        assert_eq!(Status::Ok, login(&client, "new@example.com", "secret"));
+    }
+
+    #[test]
+    fn export_current_user() {
+        let (client, db) = setup();
+        register_user(&db, "user@example.com", "secret", true);
+
+        // Before login
+        let res = client
+            .get("/users/current/export")
+            .header(ContentType::JSON)
+            .dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        assert_eq!(Status::Ok, login(&client, "user@example.com", "secret"));
+        let res = client
+            .post("/subscribe-to-bbox")
+            .header(ContentType::JSON)
+            .body("[{\"lat\":-10.0,\"lng\":-10.0},{\"lat\":10.0,\"lng\":10.0}]")
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let res = client
+            .post("/entries")
+            .header(ContentType::JSON)
+            .body(r#"{"title":"foo","description":"blablabla","lat":0.0,"lng":0.0,"categories":["x"],"license":"CC0-1.0","tags":[]}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        let mut res = client
+            .get("/users/current/export")
+            .header(ContentType::JSON)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let export: json::UserExport = serde_json::from_str(&body).unwrap();
+        assert_eq!("user@example.com", export.user.email);
+        assert_eq!(1, export.place_revisions.len());
+        assert_eq!("foo", export.place_revisions[0].1.title);
+        assert!(export.ratings.is_empty());
+        assert_eq!(1, export.bbox_subscriptions.len());
+        assert!(export.user_tokens.is_empty());
+    }
+
+    #[test]
+    fn delete_user_anonymized() {
+        let (client, db) = setup();
+        register_user(&db, "user@example.com", "secret", true);
+        register_user_with_role(&db, "admin@example.com", Role::Admin);
+
+        assert_eq!(Status::Ok, login(&client, "user@example.com", "secret"));
+        let mut res = client
+            .post("/entries")
+            .header(ContentType::JSON)
+            .body(r#"{"title":"foo","description":"blablabla","lat":0.0,"lng":0.0,"categories":["x"],"license":"CC0-1.0","tags":[]}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let place_id: String = serde_json::from_str(&body).unwrap();
+
+        // Regular users are not allowed to see the report
+        let res = client
+            .get("/users/anonymized")
+            .header(ContentType::JSON)
+            .dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        let res = client
+            .delete("/users/user@example.com?anonymize=true")
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        assert_eq!(
+            Status::Unauthorized,
+            login(&client, "user@example.com", "secret")
+        );
+
+        // The place and its history are still available
+        let (place, _) = db.shared().unwrap().get_place(&place_id).unwrap();
+        let pseudonym = place.created.by.unwrap();
+        assert!(pseudonym.as_str().starts_with("anonymous-"));
+
+        assert_eq!(Status::Ok, login(&client, "admin@example.com", "secret"));
+        let mut res = client
+            .get("/users/anonymized")
+            .header(ContentType::JSON)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let report: Vec<json::AnonymizedUser> = serde_json::from_str(&body).unwrap();
+        assert_eq!(1, report.len());
+        assert_eq!(pseudonym.as_str(), report[0].pseudonym);
+        assert_eq!(1, report[0].place_revisions);
+    }
+
+    #[test]
+    fn restrict_scouts_to_their_regions() {
+        let (client, db) = setup();
+        register_user_with_role(&db, "scout@example.com", Role::Scout);
+        register_user_with_role(&db, "admin@example.com", Role::Admin);
+        let region = r#"[{"sw":{"lat":40.0,"lng":0.0},"ne":{"lat":50.0,"lng":10.0}}]"#;
+
+        assert_eq!(Status::Ok, login(&client, "scout@example.com", "secret"));
+        let mut res = client
+            .post("/entries")
+            .header(ContentType::JSON)
+            .body(r#"{"title":"foo","description":"blablabla","lat":0.0,"lng":0.0,"categories":["x"],"license":"CC0-1.0","tags":[]}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let place_id: String = serde_json::from_str(&body).unwrap();
+
+        // Scouts are not allowed to change their own regions
+        let res = client
+            .put("/users/scout@example.com/regions")
+            .header(ContentType::JSON)
+            .body(region)
+            .dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        assert_eq!(Status::Ok, login(&client, "admin@example.com", "secret"));
+        let res = client
+            .put("/users/scout@example.com/regions")
+            .header(ContentType::JSON)
+            .body(region)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        assert_eq!(Status::Ok, login(&client, "scout@example.com", "secret"));
+        let mut res = client
+            .get("/users/scout@example.com/regions")
+            .header(ContentType::JSON)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body = res.body().and_then(|b| b.into_string()).unwrap();
+        let regions: Vec<json::MapBbox> = serde_json::from_str(&body).unwrap();
+        assert_eq!(1, regions.len());
+
+        // The place is located outside of the region
+        let res = client
+            .post(format!("/places/{}/review", place_id))
+            .header(ContentType::JSON)
+            .body(r#"{"status":"archived"}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Forbidden);
+
+        assert_eq!(Status::Ok, login(&client, "admin@example.com", "secret"));
+        let res = client
+            .put("/users/scout@example.com/regions")
+            .header(ContentType::JSON)
+            .body("[]")
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+
+        assert_eq!(Status::Ok, login(&client, "scout@example.com", "secret"));
+        let res = client
+            .post(format!("/places/{}/review", place_id))
+            .header(ContentType::JSON)
+            .body(r#"{"status":"archived"}"#)
+            .dispatch();
+        assert_eq!(res.status(), Status::Ok);
+    }
+
     #[test]
     fn current_user() {
         let (client, db) = setup();
@@ -0,0 +1,160 @@
+use super::super::guards::*;
+use super::view;
+use crate::{
+    core::{prelude::*, usecases},
+    infrastructure::{error::AppError, flows::prelude::*},
+    ports::web::{notify::*, sqlite::Connections},
+};
+use maud::Markup;
+use rocket::{
+    self,
+    http::{Cookie, Cookies, RawStr},
+    request::{FlashMessage, Form},
+    response::{Flash, Redirect},
+};
+
+#[get("/account")]
+pub fn get_account(account: Account, flash: Option<FlashMessage>) -> Markup {
+    view::account(account.email(), flash)
+}
+
+#[derive(FromForm)]
+pub struct ChangePassword {
+    old_password: String,
+    new_password: String,
+    new_password_repeated: String,
+}
+
+#[post("/users/actions/change-password", data = "<data>")]
+pub fn post_change_password(
+    db: Connections,
+    account: Account,
+    data: Form<ChangePassword>,
+) -> std::result::Result<Flash<Redirect>, Flash<Redirect>> {
+    let req = data.into_inner();
+
+    if req.new_password != req.new_password_repeated {
+        return Err(Flash::error(
+            Redirect::to(uri!(get_account)),
+            "Your passwords do not match.",
+        ));
+    }
+    let new_password = req.new_password.parse::<Password>().map_err(|_| {
+        Flash::error(
+            Redirect::to(uri!(get_account)),
+            "Your new password is not allowed.",
+        )
+    })?;
+    let db = db.exclusive().map_err(|_| {
+        Flash::error(
+            Redirect::to(uri!(get_account)),
+            "We are so sorry! An internal server error has occurred. Please try again later.",
+        )
+    })?;
+    match usecases::change_password(&*db, account.email(), &req.old_password, new_password) {
+        Err(Error::Parameter(ParameterError::Credentials)) => Err(Flash::error(
+            Redirect::to(uri!(get_account)),
+            "Your current password is wrong.",
+        )),
+        Err(_) => Err(Flash::error(
+            Redirect::to(uri!(get_account)),
+            "Failed to change your password.",
+        )),
+        Ok(_) => Ok(Flash::success(
+            Redirect::to(uri!(get_account)),
+            "Your password has been changed.",
+        )),
+    }
+}
+
+#[derive(FromForm)]
+pub struct ChangeEmailRequest {
+    new_email: String,
+    password: String,
+}
+
+#[post("/users/actions/change-email-request", data = "<data>")]
+pub fn post_change_email_request(
+    db: Connections,
+    notify: Notify,
+    account: Account,
+    data: Form<ChangeEmailRequest>,
+) -> std::result::Result<Flash<Redirect>, Flash<Redirect>> {
+    let ChangeEmailRequest {
+        new_email,
+        password,
+    } = data.into_inner();
+    match change_email_request(&db, &*notify, account.email(), &password, &new_email) {
+        Err(AppError::Business(Error::Parameter(ParameterError::Credentials))) => Err(
+            Flash::error(Redirect::to(uri!(get_account)), "Your password is wrong."),
+        ),
+        Err(AppError::Business(Error::Parameter(ParameterError::Email))) => Err(Flash::error(
+            Redirect::to(uri!(get_account)),
+            "Your new email address is invalid.",
+        )),
+        Err(AppError::Business(Error::Parameter(ParameterError::UserExists))) => Err(Flash::error(
+            Redirect::to(uri!(get_account)),
+            "This email address is already in use.",
+        )),
+        Err(_) => Err(Flash::error(
+            Redirect::to(uri!(get_account)),
+            "Failed to request an email change.",
+        )),
+        Ok(_) => Ok(Flash::success(
+            Redirect::to(uri!(get_account)),
+            "Please look into the inbox of your new email address to confirm the change.",
+        )),
+    }
+}
+
+#[get("/change-email/confirm/<token>")]
+pub fn get_change_email_confirmation(
+    db: Connections,
+    notify: Notify,
+    token: &RawStr,
+    mut cookies: Cookies,
+) -> std::result::Result<Flash<Redirect>, Flash<Redirect>> {
+    let email_nonce = EmailNonce::decode_from_str(token.as_str()).map_err(|_| {
+        Flash::error(
+            Redirect::to(uri!(super::get_index)),
+            "Changing your email address is not possible (invalid token).",
+        )
+    })?;
+    match change_email_with_email_nonce(&db, &*notify, email_nonce) {
+        Ok(_) => {
+            // The login cookie still contains the previous address
+            cookies.remove_private(Cookie::named(COOKIE_EMAIL_KEY));
+            Ok(Flash::success(
+                Redirect::to(uri!(super::login::get_login)),
+                "Your email address has been changed. Please login with your new email address.",
+            ))
+        }
+        Err(_) => Err(Flash::error(
+            Redirect::to(uri!(super::get_index)),
+            "We are sorry but seems to be something wrong.",
+        )),
+    }
+}
+
+#[get("/places/claim/confirm/<token>")]
+pub fn get_place_ownership_confirmation(
+    db: Connections,
+    token: &RawStr,
+) -> std::result::Result<Flash<Redirect>, Flash<Redirect>> {
+    let email_nonce = EmailNonce::decode_from_str(token.as_str()).map_err(|_| {
+        Flash::error(
+            Redirect::to(uri!(super::get_index)),
+            "Confirming the owner of the entry is not possible (invalid token).",
+        )
+    })?;
+    match confirm_place_ownership_with_email_nonce(&db, email_nonce) {
+        Ok(ownership) => Ok(Flash::success(
+            Redirect::to(uri!(super::get_entry: ownership.place_id.as_str())),
+            "The owner of this entry has been confirmed.",
+        )),
+        Err(_) => Err(Flash::error(
+            Redirect::to(uri!(super::get_index)),
+            "We are sorry but seems to be something wrong.",
+        )),
+    }
+}
@@ -9,6 +9,7 @@ use crate::{
 };
 use maud::Markup;
 use num_traits::FromPrimitive;
+use ofdb_core::user::RolePermissions;
 use rocket::{
     self,
     http::{ContentType, RawStr},
@@ -17,9 +18,10 @@ use rocket::{
         content::{Content, Css, Html, JavaScript},
         Flash, Redirect,
     },
-    Route,
+    Route, State,
 };
 
+mod account;
 mod login;
 mod password;
 mod register;
@@ -74,11 +76,16 @@ pub fn get_search(search_engine: SearchEngine, q: &RawStr, limit: Option<usize>)
 }
 
 #[get("/search-users?<email>")]
-pub fn get_search_users(pool: sqlite::Connections, email: &RawStr, auth: Auth) -> Result<Markup> {
+pub fn get_search_users(
+    pool: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    email: &RawStr,
+    auth: Auth,
+) -> Result<Markup> {
     let email = email.url_decode()?;
     {
         let db = pool.shared()?;
-        let admin = auth.user_with_min_role(&*db, Role::Admin)?;
+        let admin = auth.user_with_permission(&*db, &permissions, Permission::UserChangeRole)?;
         let users: Vec<_> = db.try_get_user_by_email(&email)?.into_iter().collect();
         Ok(view::user_search_result(&admin.email, &users))
     }
@@ -93,6 +100,7 @@ pub struct ChangeUserRoleAction {
 #[post("/change-user-role", data = "<data>")]
 pub fn post_change_user_role(
     db: sqlite::Connections,
+    permissions: State<RolePermissions>,
     account: Account,
     data: Form<ChangeUserRoleAction>,
 ) -> std::result::Result<Redirect, Flash<Redirect>> {
@@ -102,7 +110,7 @@ pub fn post_change_user_role(
             Redirect::to(uri!(get_search_users:d.email)),
             "Failed to change user role: invalid role.",
         )),
-        Some(role) => match change_user_role(&db, account.email(), &d.email, role) {
+        Some(role) => match change_user_role(&db, &permissions, account.email(), &d.email, role) {
             Err(_) => Err(Flash::error(
                 Redirect::to(uri!(get_search_users:d.email)),
                 "Failed to change user role.",
@@ -123,15 +131,25 @@ pub fn get_main_css() -> Css<&'static str> {
 }
 
 #[get("/places/<id>/history")]
-pub fn get_place_history(db: sqlite::Connections, id: &RawStr, account: Account) -> Result<Markup> {
+pub fn get_place_history(
+    db: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    id: &RawStr,
+    account: Account,
+) -> Result<Markup> {
     let db = db.shared()?;
     let user = db
         .try_get_user_by_email(account.email())?
         .ok_or(Error::Parameter(ParameterError::Unauthorized))?;
     let place_history = {
         // The history contains e-mail addresses of registered users
-        // and is only permitted for scouts and admins!
-        usecases::authorize_user_by_email(&*db, &account.email(), Role::Scout)?;
+        // and is only permitted for users with the corresponding permission!
+        usecases::authorize_user_by_permission(
+            &*db,
+            &permissions,
+            account.email(),
+            Permission::PlaceReadHistory,
+        )?;
 
         db.get_place_history(&id, None)?
     };
@@ -139,11 +157,29 @@ pub fn get_place_history(db: sqlite::Connections, id: &RawStr, account: Account)
 }
 
 #[get("/places/<id>/review")]
-pub fn get_place_review(db: sqlite::Connections, id: &RawStr, account: Account) -> Result<Markup> {
+pub fn get_place_review(
+    db: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    id: &RawStr,
+    account: Account,
+) -> Result<Markup> {
     let db = db.shared()?;
-    // Only scouts and admins are entitled to review places
-    let reviewer_email =
-        usecases::authorize_user_by_email(&*db, &account.email(), Role::Scout)?.email;
+    let reviewer_email = usecases::authorize_user_by_permission(
+        &*db,
+        &permissions,
+        account.email(),
+        Permission::PlaceReview,
+    )
+    .or_else(|_| {
+        usecases::authorize_user_by_permission(
+            &*db,
+            &permissions,
+            account.email(),
+            Permission::PlaceArchive,
+        )
+    })?
+    .email;
+    usecases::authorize_user_regions_for_places(&*db, &reviewer_email, &[id.as_str()])?;
     let (place, review_status) = db.get_place(&id)?;
     Ok(view::place_review(&reviewer_email, &place, review_status))
 }
@@ -158,36 +194,47 @@ pub struct Review {
 pub fn post_place_review(
     db: sqlite::Connections,
     search_engine: SearchEngine,
+    permissions: State<RolePermissions>,
     id: &RawStr,
     review: Form<Review>,
     account: Account,
 ) -> std::result::Result<Redirect, Flash<Redirect>> {
     let Review { status, comment } = review.into_inner();
     let id = id.as_str();
-    review_place(&db, account.email(), status, comment, id, search_engine)
-        .map(|_| Redirect::to(uri!(get_entry: id)))
-        .map_err(|_| {
-            Flash::error(
-                Redirect::to(uri!(get_place_review: id)),
-                "Failed to archive the place.",
-            )
-        })
+    review_place(
+        &db,
+        &permissions,
+        account.email(),
+        status,
+        comment,
+        id,
+        search_engine,
+    )
+    .map(|_| Redirect::to(uri!(get_entry: id)))
+    .map_err(|_| {
+        Flash::error(
+            Redirect::to(uri!(get_place_review: id)),
+            "Failed to archive the place.",
+        )
+    })
 }
 
 fn review_place(
     db: &sqlite::Connections,
+    permissions: &RolePermissions,
     email: &str,
     status: i16,
     comment: String,
     id: &str,
     mut search_engine: SearchEngine,
 ) -> Result<()> {
+    let status = ReviewStatus::try_from(status)
+        .ok_or_else(|| Error::Parameter(ParameterError::RatingContext(status.to_string())))?;
     let reviewer_email = {
         let db = db.shared()?;
-        usecases::authorize_user_by_email(&*db, email, Role::Scout)?.email
+        usecases::authorize_user_for_reviewing_places(&*db, permissions, email, &[id], status)?
+            .email
     };
-    let status = ReviewStatus::try_from(status)
-        .ok_or_else(|| Error::Parameter(ParameterError::RatingContext(status.to_string())))?;
     // TODO: Record context information
     let context = None;
     let review = usecases::Review {
@@ -257,13 +304,18 @@ pub fn post_archive_event(
     account: Account,
     pool: sqlite::Connections,
     mut search_engine: SearchEngine,
+    permissions: State<RolePermissions>,
     id: &RawStr,
 ) -> std::result::Result<Redirect, Flash<Redirect>> {
     let archived_by_email = pool
         .shared()
         .and_then(|db| {
-            // Only scouts and admins are entitled to review events
-            let user = usecases::authorize_user_by_email(&*db, &account.email(), Role::Scout)?;
+            let user = usecases::authorize_user_for_archiving_events(
+                &*db,
+                &permissions,
+                account.email(),
+                &[id.as_str()],
+            )?;
             Ok(user.email)
         })
         .map_err(|_| {
@@ -311,8 +363,14 @@ pub fn get_events_chronologically(
     Ok(view::events(email, &events))
 }
 
+const DASHBOARD_AUDIT_LOG_LIMIT: u64 = 50;
+
 #[get("/dashboard")]
-pub fn get_dashboard(db: sqlite::Connections, account: Account) -> Result<Markup> {
+pub fn get_dashboard(
+    db: sqlite::Connections,
+    permissions: State<RolePermissions>,
+    account: Account,
+) -> Result<Markup> {
     let db = db.shared()?;
     let tag_count = db.count_tags()?;
     let place_count = db.count_places()?;
@@ -321,13 +379,45 @@ pub fn get_dashboard(db: sqlite::Connections, account: Account) -> Result<Markup
     let user = db
         .try_get_user_by_email(account.email())?
         .ok_or(Error::Parameter(ParameterError::Unauthorized))?;
-    if user.role == Role::Admin {
+    let is_granted = |permission| permissions.is_granted(user.role, permission);
+    if is_granted(Permission::UserAnonymize)
+        || is_granted(Permission::AuditRead)
+        || is_granted(Permission::StatsRead)
+    {
+        let anonymized_users = if is_granted(Permission::UserAnonymize) {
+            usecases::anonymized_users(&*db, &permissions, &user.email)?
+        } else {
+            vec![]
+        };
+        let audit_log = if is_granted(Permission::AuditRead) {
+            let pagination = Pagination {
+                offset: None,
+                limit: Some(DASHBOARD_AUDIT_LOG_LIMIT),
+            };
+            usecases::audit_log(
+                &*db,
+                &permissions,
+                &user.email,
+                &Default::default(),
+                &pagination,
+            )?
+        } else {
+            vec![]
+        };
+        let statistics = if is_granted(Permission::StatsRead) {
+            Some(usecases::statistics(&*db, &permissions, &user.email)?)
+        } else {
+            None
+        };
         return Ok(view::dashboard(view::DashBoardPresenter {
             user,
             place_count,
             event_count,
             tag_count,
             user_count,
+            anonymized_users,
+            audit_log,
+            statistics,
         }));
     }
     Err(Error::Parameter(ParameterError::Unauthorized).into())
@@ -343,12 +433,13 @@ pub struct ArchiveAction {
 pub fn post_comments_archive(
     account: Account,
     db: sqlite::Connections,
+    permissions: State<RolePermissions>,
     data: Form<ArchiveAction>,
 ) -> std::result::Result<Redirect, Flash<Redirect>> {
     //TODO: dry out
     let d = data.into_inner();
     let ids: Vec<_> = d.ids.split(',').filter(|id| !id.is_empty()).collect();
-    match archive_comments(&db, account.email(), &ids) {
+    match archive_comments(&db, &permissions, account.email(), &ids) {
         Err(_) => Err(Flash::error(
             Redirect::to(uri!(get_entry:d.place_id)),
             "Failed to achive the comment.",
@@ -362,11 +453,12 @@ pub fn post_ratings_archive(
     account: Account,
     db: sqlite::Connections,
     mut search_engine: SearchEngine,
+    permissions: State<RolePermissions>,
     data: Form<ArchiveAction>,
 ) -> std::result::Result<Redirect, Flash<Redirect>> {
     let d = data.into_inner();
     let ids: Vec<_> = d.ids.split(',').filter(|id| !id.is_empty()).collect();
-    match archive_ratings(&db, &mut search_engine, account.email(), &ids) {
+    match archive_ratings(&db, &mut search_engine, &permissions, account.email(), &ids) {
         Err(_) => Err(Flash::error(
             Redirect::to(uri!(get_entry:d.place_id)),
             "Failed to archive the rating.",
@@ -407,5 +499,10 @@ pub fn routes() -> Vec<Route> {
         password::get_reset_password,
         password::post_reset_password_request,
         password::post_reset_password,
+        account::get_account,
+        account::post_change_password,
+        account::post_change_email_request,
+        account::get_change_email_confirmation,
+        account::get_place_ownership_confirmation,
     ]
 }
@@ -349,7 +349,13 @@ mod entry {
             value: 1.into(),
             entry: e_id.clone().into(),
         };
-        let (r_id, c_id) = flows::prelude::create_rating(db, search, r).unwrap();
+        let (r_id, c_id) = flows::prelude::create_rating(
+            db,
+            search,
+            r,
+            RatingAuthor::AnonymousSession("test".into()),
+        )
+        .unwrap();
         (e_id.into(), r_id, c_id)
     }
 
@@ -633,3 +639,89 @@ mod pw_reset {
         assert_eq!(h.value, "/");
     }
 }
+
+mod account {
+    use super::*;
+
+    fn location(res: &rocket::local::LocalResponse) -> String {
+        res.headers()
+            .iter()
+            .find(|h| h.name.as_str() == "Location")
+            .unwrap()
+            .value
+            .to_string()
+    }
+
+    #[test]
+    fn change_password() {
+        let (client, db, _) = setup();
+        create_user(&db, "user", Role::User);
+
+        let res = client.get("/account").dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        login_user(&client, "user");
+        let mut res = client.get("/account").dispatch();
+        assert_eq!(res.status(), Status::Ok);
+        let body_str = res.body().and_then(|b| b.into_string()).unwrap();
+        assert!(body_str.contains("action=\"/users/actions/change-password\""));
+        assert!(body_str.contains("action=\"/users/actions/change-email-request\""));
+        assert!(body_str.contains("href=\"/api/users/current/export\""));
+
+        let res = client
+            .post("/users/actions/change-password")
+            .header(ContentType::Form)
+            .body("old_password=wrong&new_password=12345678&new_password_repeated=12345678")
+            .dispatch();
+        assert_eq!(res.status(), Status::SeeOther);
+        assert!(get_user(&db, "user").password.verify("secret"));
+
+        let res = client
+            .post("/users/actions/change-password")
+            .header(ContentType::Form)
+            .body("old_password=secret&new_password=12345678&new_password_repeated=12345678")
+            .dispatch();
+        assert_eq!(res.status(), Status::SeeOther);
+        assert_eq!("/account", location(&res));
+        assert!(get_user(&db, "user").password.verify("12345678"));
+    }
+
+    #[test]
+    fn change_email() {
+        let (client, db, _) = setup();
+        create_user(&db, "old", Role::User);
+        login_user(&client, "old");
+
+        let res = client
+            .post("/users/actions/change-email-request")
+            .header(ContentType::Form)
+            .body("new_email=new%40example.com&password=secret")
+            .dispatch();
+        assert_eq!(res.status(), Status::SeeOther);
+
+        let token = db
+            .shared()
+            .unwrap()
+            .get_user_token_by_email("old@example.com")
+            .unwrap()
+            .email_nonce
+            .encode_to_string();
+        let res = client
+            .get(format!("/change-email/confirm/{}", token))
+            .dispatch();
+        assert_eq!(res.status(), Status::SeeOther);
+        assert_eq!("/login", location(&res));
+
+        // The user has been logged out
+        let res = client.get("/account").dispatch();
+        assert_eq!(res.status(), Status::Unauthorized);
+
+        assert!(db
+            .shared()
+            .unwrap()
+            .try_get_user_by_email("old@example.com")
+            .unwrap()
+            .is_none());
+        assert!(get_user(&db, "new").email_confirmed);
+    }
+}
@@ -0,0 +1,67 @@
+use super::page::*;
+use maud::{html, Markup};
+use rocket::request::FlashMessage;
+
+pub fn account(email: &str, flash: Option<FlashMessage>) -> Markup {
+    page(
+        "Account",
+        Some(email),
+        flash,
+        None,
+        html! {
+          h2 { "Account" }
+          h3 { "Change password" }
+          form class="change-pw" action="/users/actions/change-password" method="POST" {
+              fieldset{
+                label{
+                    "Current password:"
+                    br;
+                    input type="password" name="old_password" placeholder="current password";
+                }
+                br;
+                label{
+                    "New password:"
+                    br;
+                    input type="password" name="new_password" placeholder="new password";
+                }
+                br;
+                label{
+                    "New password (repeated):"
+                    br;
+                    input type="password" name="new_password_repeated" placeholder="repeat new password";
+                }
+                br;
+                input type="submit" value="change password";
+              }
+          }
+          h3 { "Change email address" }
+          p {
+            "We will send you a link to your new email address."
+            br;
+            "Your email address will only be changed after you followed this link."
+          }
+          form class="change-email" action="/users/actions/change-email-request" method="POST" {
+              fieldset{
+                label {
+                    "New eMail:"
+                    br;
+                    input type="email" name="new_email" placeholder="new eMail address";
+                }
+                br;
+                label{
+                    "Password:"
+                    br;
+                    input type="password" name="password" placeholder="Password";
+                }
+                br;
+                input type="submit" value="change email";
+              }
+          }
+          h3 { "Export my data" }
+          p {
+            "Download all data that is linked to your account: "
+            a href="/api/users/current/export" download="openfairdb-account.json" { "export (JSON)" }
+          }
+        },
+    )
+}
@@ -1,13 +1,19 @@
 use super::page;
-use crate::core::entities::*;
+use crate::core::{entities::*, usecases};
 use maud::{html, Markup};
 
+const CHART_HEIGHT: u64 = 100;
+const CHART_BAR_WIDTH: u64 = 6;
+
 pub struct DashBoardPresenter {
     pub user: User,
     pub place_count: usize,
     pub event_count: usize,
     pub tag_count: usize,
     pub user_count: usize,
+    pub anonymized_users: Vec<AnonymizedUser>,
+    pub audit_log: Vec<AuditLogEntry>,
+    pub statistics: Option<usecases::Statistics>,
 }
 
 pub fn dashboard(data: DashBoardPresenter) -> Markup {
@@ -37,9 +43,153 @@ pub fn dashboard(data: DashBoardPresenter) -> Markup {
                         td {(data.tag_count)}
                     }
                 }
+                @if let Some(ref stats) = data.statistics {
+                    (statistics(stats))
+                }
                 h3 { "User Management" }
                 (super::search_users_form())
+                h3 { "Deleted Users" }
+                @if data.anonymized_users.is_empty() {
+                    p { "No user accounts have been deleted yet." }
+                } @else {
+                    table class="anonymized-users" {
+                        tr {
+                            th { "Pseudonym" }
+                            th { "Deleted at" }
+                            th { "Place revisions" }
+                            th { "Place reviews" }
+                            th { "Ratings" }
+                            th { "Comments" }
+                            th { "Events" }
+                        }
+                        @for u in &data.anonymized_users {
+                            tr {
+                                td { (u.pseudonym) }
+                                td { (u.anonymized_at) }
+                                td { (u.place_revisions) }
+                                td { (u.place_reviews) }
+                                td { (u.place_ratings) }
+                                td { (u.place_rating_comments) }
+                                td { (u.events) }
+                            }
+                        }
+                    }
+                }
+                h3 { "Audit Log" }
+                @if data.audit_log.is_empty() {
+                    p { "No moderation actions have been recorded yet." }
+                } @else {
+                    table class="audit-log" {
+                        tr {
+                            th { "Time" }
+                            th { "User" }
+                            th { "Action" }
+                            th { "Entity" }
+                            th { "Details" }
+                            th { "Comment" }
+                        }
+                        @for e in &data.audit_log {
+                            tr {
+                                td { (e.log.activity.at) }
+                                td {
+                                    @if let Some(ref by) = e.log.activity.by {
+                                        (by)
+                                    }
+                                }
+                                td { (e.action) }
+                                td { (e.entity.kind) ": " (e.entity.id) }
+                                td {
+                                    @if let Some(ref details) = e.details {
+                                        (details)
+                                    }
+                                }
+                                td {
+                                    @if let Some(ref comment) = e.log.comment {
+                                        (comment)
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
             }
         },
     )
 }
+
+fn statistics(stats: &usecases::Statistics) -> Markup {
+    html! {
+        h3 { "Statistics over Time" }
+        (period_chart("Places created per week", &stats.places_created_per_week))
+        (period_chart("Places updated per week", &stats.places_updated_per_week))
+        (period_chart("Events per month", &stats.events_per_month))
+        (period_chart("New users per week", &stats.users_registered_per_week))
+        (period_chart("Ratings per week", &stats.ratings_per_week))
+        h3 { "Pending Clearances" }
+        @if stats.pending_clearances.is_empty() {
+            p { "No organization requires clearances." }
+        } @else {
+            table class="pending-clearances" {
+                tr {
+                    th { "Organization" }
+                    th { "Places" }
+                    th { "Events" }
+                }
+                @for c in &stats.pending_clearances {
+                    tr {
+                        td { (c.org_name) }
+                        td { (c.places) }
+                        td { (c.events) }
+                    }
+                }
+            }
+        }
+        h3 { "Places by Category, Tag and Region" }
+        div class="place-counts" {
+            (place_counts("Category", &stats.places_by_category))
+            (place_counts("Top tags", &stats.places_by_tag))
+            (place_counts("Country", &stats.places_by_country))
+            (place_counts("State", &stats.places_by_state))
+        }
+    }
+}
+
+fn period_chart(title: &str, counts: &[usecases::PeriodCount]) -> Markup {
+    let max = counts.iter().map(|c| c.count).max().unwrap_or(0).max(1);
+    let width = counts.len() as u64 * CHART_BAR_WIDTH;
+    html! {
+        h4 { (title) }
+        @if counts.is_empty() {
+            p { "No data available." }
+        } @else {
+            svg class="chart" width=(width) height=(CHART_HEIGHT) viewBox=(format!("0 0 {} {}", width, CHART_HEIGHT)) {
+                @for (i, c) in counts.iter().enumerate() {
+                    @let height = c.count * CHART_HEIGHT / max;
+                    rect x=(i as u64 * CHART_BAR_WIDTH) y=(CHART_HEIGHT - height) width=(CHART_BAR_WIDTH - 1) height=(height) {
+                        title { (c.period_start) ": " (c.count) }
+                    }
+                }
+            }
+            p class="chart-range" {
+                (counts[0].period_start) " – " (counts[counts.len() - 1].period_start)
+            }
+        }
+    }
+}
+
+fn place_counts(title: &str, counts: &[usecases::PlaceCount]) -> Markup {
+    html! {
+        table {
+            tr {
+                th { (title) }
+                th { "Places" }
+            }
+            @for c in counts {
+                tr {
+                    td { (c.key) }
+                    td { (c.count) }
+                }
+            }
+        }
+    }
+}
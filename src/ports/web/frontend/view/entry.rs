@@ -141,7 +141,13 @@ fn rating(place_id: &str, archive: bool, r: &Rating, comments: &[Comment]) -> Ma
       }
       ul {
           @for c in comments {
-              li {
+              li id=(format!("comment-{}", c.id)) {
+                  @if c.is_response {
+                      strong { "Response of the owner" }
+                  }
+                  @if let Some(ref parent_id) = c.reply_to {
+                      p { a href=(format!("#comment-{}", parent_id)) { "in reply to" } }
+                  }
                   p { (c.text) }
                   @if archive {
                     form action = "/comments/actions/archive" method = "POST" {
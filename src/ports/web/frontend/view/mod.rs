@@ -8,6 +8,7 @@ const LEAFLET_JS_URL: &str = "https://cdnjs.cloudflare.com/ajax/libs/leaflet/1.4
 const LEAFLET_JS_SHA512 : &str="sha512-QVftwZFqvtRNi0ZyCtsznlKSWOStnDORoefr1enyq5mVL4tmKB3S/EnC3rRJcxCPavG10IcrVGSmPh6Qw5lwrg==";
 const MAP_JS_URL: &str = "/map.js";
 
+mod account;
 mod dashboard;
 mod entry;
 mod event;
@@ -17,6 +18,7 @@ mod password;
 mod place;
 mod register;
 
+pub use account::*;
 pub use dashboard::*;
 pub use entry::*;
 pub use event::*;
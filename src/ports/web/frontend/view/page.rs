@@ -50,6 +50,7 @@ fn header(email: Option<&str>) -> Markup {
             a href="/events" { "events" }
             @if email.is_some() {
                 a href="/dashboard" { "dashboard" }
+                a href="/account" { "account" }
                 form class="logout" action="/logout" method ="POST" {
                     input type="submit" value="logout";
                 }
@@ -3,6 +3,7 @@ use crate::{
     ports::web::jwt,
 };
 use chrono::prelude::*;
+use ofdb_core::user::RolePermissions;
 use rocket::{
     self,
     http::Status,
@@ -13,6 +14,7 @@ use std::time::Duration;
 
 pub const COOKIE_EMAIL_KEY: &str = "ofdb-user-email";
 pub const COOKIE_CAPTCHA_KEY: &str = "ofdb-captcha";
+pub const COOKIE_CAPTCHA_SESSION_KEY: &str = "ofdb-captcha-session";
 pub const MAX_CAPTCHA_TTL: Duration = Duration::from_secs(120);
 
 type Result<T> = std::result::Result<T, AppError>;
@@ -31,6 +33,7 @@ pub struct Auth {
     bearer_tokens: Vec<String>,
     account_email: Option<String>,
     has_captcha: bool,
+    captcha_session: Option<String>,
 }
 
 impl Auth {
@@ -60,6 +63,17 @@ impl Auth {
         }
     }
 
+    /// Anonymous visitors are identified by the session that
+    /// has been started when solving their first captcha.
+    pub fn anonymous_session(&self) -> Result<&str> {
+        self.has_captcha()?;
+        self.captcha_session
+            .as_deref()
+            .ok_or(AppError::Business(Error::Parameter(
+                ParameterError::Unauthorized,
+            )))
+    }
+
     pub fn organization<R: OrganizationRepo>(&self, db: &R) -> Result<Organization> {
         Ok(usecases::authorize_organization_by_possible_api_tokens(
             db,
@@ -67,11 +81,17 @@ impl Auth {
         )?)
     }
 
-    pub fn user_with_min_role<D: Db>(&self, db: &D, min_required_role: Role) -> Result<User> {
-        Ok(usecases::authorize_user_by_email(
+    pub fn user_with_permission<D: Db>(
+        &self,
+        db: &D,
+        permissions: &RolePermissions,
+        permission: Permission,
+    ) -> Result<User> {
+        Ok(usecases::authorize_user_by_permission(
             db,
+            permissions,
             self.account_email()?,
-            min_required_role,
+            permission,
         )?)
     }
 
@@ -102,6 +122,13 @@ impl Auth {
             .next()
     }
 
+    fn captcha_session_from_cookie(request: &Request) -> Option<String> {
+        request
+            .cookies()
+            .get_private(COOKIE_CAPTCHA_SESSION_KEY)
+            .map(|cookie| cookie.value().to_owned())
+    }
+
     fn captcha_from_cookie(request: &Request) -> bool {
         request
             .cookies()
@@ -127,11 +154,13 @@ impl<'a, 'r> FromRequest<'a, 'r> for Auth {
         }
 
         let has_captcha = Self::captcha_from_cookie(request);
+        let captcha_session = Self::captcha_session_from_cookie(request);
 
         let auth = Self {
             bearer_tokens,
             account_email,
             has_captcha,
+            captcha_session,
         };
 
         Outcome::Success(auth)
@@ -0,0 +1,34 @@
+use core::ops::Deref;
+use ofdb_core::gateways::image::ImageStorageGateway;
+use rocket::{
+    request::{self, FromRequest},
+    Outcome, Request, State,
+};
+use std::sync::Arc;
+
+/// The image storage is created and validated at startup
+/// and managed as shared state of the web server.
+#[derive(Clone)]
+pub struct ImageStorage(Arc<dyn ImageStorageGateway + Send + Sync>);
+
+impl From<Box<dyn ImageStorageGateway + Send + Sync>> for ImageStorage {
+    fn from(from: Box<dyn ImageStorageGateway + Send + Sync>) -> Self {
+        Self(from.into())
+    }
+}
+
+impl Deref for ImageStorage {
+    type Target = dyn ImageStorageGateway;
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for ImageStorage {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, ()> {
+        let storage = request.guard::<State<ImageStorage>>()?;
+        Outcome::Success(storage.clone())
+    }
+}
@@ -1,12 +1,8 @@
 use crate::{
-    core::{
-        db::{EventIndexer, PlaceIndexer},
-        prelude::*,
-        usecases,
-    },
+    core::{db::EventIndexer, prelude::*, usecases},
     infrastructure::error::AppError,
 };
-use ofdb_core::rating::Rated;
+use ofdb_core::{rating::Rated, user::RolePermissions};
 use rocket::{config::Config, Rocket, Route};
 use rocket_contrib::json::Json;
 use std::result;
@@ -15,6 +11,7 @@ pub mod api;
 #[cfg(feature = "frontend")]
 mod frontend;
 mod guards;
+pub mod image_storage;
 pub mod jwt;
 #[cfg(test)]
 mod mockdb;
@@ -28,22 +25,25 @@ type Result<T> = result::Result<Json<T>, AppError>;
 
 fn index_all_places<D: PlaceRepo + RatingRepository>(
     db: &D,
-    indexer: &mut dyn PlaceIndexer,
+    search_engine: &mut tantivy::SearchEngine,
 ) -> Result<()> {
     // TODO: Split into chunks with fixed size instead of
     // loading all places at once!
     let places = db.all_places()?;
     for (place, status) in places {
         let ratings = db.load_ratings_of_place(place.id.as_ref())?;
-        if let Err(err) =
-            indexer.add_or_update_place(&place, status, &place.avg_ratings(&ratings[..]))
-        {
+        if let Err(err) = search_engine.add_or_update_place_in_bulk(
+            &place,
+            status,
+            &place.avg_ratings(&ratings[..]),
+        ) {
             error!("Failed to index place {:?}: {}", place, err);
         }
     }
-    if let Err(err) = indexer.flush_index() {
+    if let Err(err) = search_engine.flush_index() {
         error!("Failed to build place index: {}", err);
     }
+    search_engine.invalidate_all_tiles();
     Ok(Json(()))
 }
 
@@ -68,6 +68,9 @@ fn index_all_events_chronologically<D: EventGateway>(
 pub(crate) fn rocket_instance(
     connections: sqlite::Connections,
     mut search_engine: tantivy::SearchEngine,
+    captcha_cache: api::captcha::CaptchaCache,
+    role_permissions: RolePermissions,
+    image_storage: image_storage::ImageStorage,
     mounts: Vec<(&str, Vec<Route>)>,
     cfg: Option<Config>,
 ) -> Rocket {
@@ -86,12 +89,13 @@ pub(crate) fn rocket_instance(
         Some(cfg) => rocket::custom(cfg),
         None => rocket::ignite(),
     };
-    let captcha_cache = api::captcha::CaptchaCache::new();
     let jwt_state = jwt::JwtState::new();
     let mut instance = r
         .manage(connections)
         .manage(search_engine)
         .manage(captcha_cache)
+        .manage(role_permissions)
+        .manage(image_storage)
         .manage(jwt_state);
 
     for (m, r) in mounts {
@@ -114,17 +118,42 @@ pub fn run(
     connections: sqlite::Connections,
     search_engine: tantivy::SearchEngine,
     enable_cors: bool,
+    shared_captcha_store: bool,
+    role_permissions: RolePermissions,
+    image_storage: image_storage::ImageStorage,
 ) {
+    let captcha_cache = if shared_captcha_store {
+        api::captcha::CaptchaCache::with_database(connections.clone())
+    } else {
+        api::captcha::CaptchaCache::new()
+    };
     if enable_cors {
         let cors = rocket_cors::CorsOptions {
             ..Default::default()
         }
         .to_cors()
         .unwrap();
-        rocket_instance(connections, search_engine, mounts(), None)
-            .attach(cors)
-            .launch();
+        rocket_instance(
+            connections,
+            search_engine,
+            captcha_cache,
+            role_permissions,
+            image_storage,
+            mounts(),
+            None,
+        )
+        .attach(cors)
+        .launch();
     } else {
-        rocket_instance(connections, search_engine, mounts(), None).launch();
+        rocket_instance(
+            connections,
+            search_engine,
+            captcha_cache,
+            role_permissions,
+            image_storage,
+            mounts(),
+            None,
+        )
+        .launch();
     }
 }
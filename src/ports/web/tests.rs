@@ -2,6 +2,8 @@ use crate::{
     core::{prelude::*, usecases},
     infrastructure::db::{sqlite, tantivy},
 };
+use ofdb_core::{gateways::image::ImageStorageGateway, user::RolePermissions};
+use ofdb_gateways::file_storage::FileImageStorage;
 use rocket::{
     config::{Config, Environment},
     local::Client,
@@ -38,6 +40,9 @@ pub fn setup(
     let rocket = super::rocket_instance(
         connections.clone(),
         search_engine.clone(),
+        super::api::captcha::CaptchaCache::new(),
+        RolePermissions::default(),
+        test_image_storage(),
         mounts,
         Some(cfg),
     );
@@ -45,6 +50,13 @@ pub fn setup(
     (client, connections, search_engine)
 }
 
+fn test_image_storage() -> super::image_storage::ImageStorage {
+    let storage: Box<dyn ImageStorageGateway + Send + Sync> = Box::new(FileImageStorage::new(
+        std::env::temp_dir().join("openfairdb-test-images"),
+    ));
+    storage.into()
+}
+
 pub fn register_user(pool: &sqlite::Connections, email: &str, pw: &str, confirmed: bool) {
     let db = pool.exclusive().unwrap();
     usecases::create_new_user(
@@ -72,8 +84,14 @@ impl ofdb_core::gateways::notify::NotificationGateway for DummyNotifyGW {
     fn place_updated(&self, _: &[String], _: &Place, _: Vec<Category>) {}
     fn event_created(&self, _: &[String], _: &Event) {}
     fn event_updated(&self, _: &[String], _: &Event) {}
+    fn place_matches_saved_search(&self, _: &str, _: &str, _: &Place, _: Vec<Category>) {}
+    fn event_matches_saved_search(&self, _: &str, _: &str, _: &Event) {}
     fn user_registered_kvm(&self, _: &User) {}
     fn user_registered_ofdb(&self, _: &User) {}
     fn user_registered(&self, _: &User, _: &str) {}
     fn user_reset_password_requested(&self, _: &EmailNonce) {}
+    fn user_email_change_requested(&self, _: &EmailNonce, _: &str) {}
+    fn user_email_changed(&self, _: &str, _: &str) {}
+    fn place_clearance_rejected(&self, _: &str, _: &Place, _: &str, _: &str) {}
+    fn place_ownership_requested(&self, _: &EmailNonce, _: &str, _: &Place) {}
 }